@@ -6,6 +6,8 @@ use clap::Parser as ClapParser;
 #[cfg(feature = "chain-spec-pruning")]
 use serde_json::Value;
 use std::{io::Write, path::PathBuf};
+#[cfg(feature = "chain-spec-pruning")]
+use subxt::utils::to_hex;
 use subxt_codegen::fetch_metadata::Url;
 
 mod fetch;
@@ -102,7 +104,7 @@ pub async fn run(opts: Opts, output: &mut impl Write) -> color_eyre::Result<()>
     #[cfg(feature = "chain-spec-pruning")]
     if opts.state_root_hash {
         let state_root_hash = compute_state_root_hash(&spec)?;
-        let state_root_hash = format!("0x{}", hex::encode(state_root_hash));
+        let state_root_hash = to_hex(state_root_hash);
 
         if let Some(genesis) = spec.get_mut("genesis") {
             let object = genesis.as_object_mut().ok_or_else(|| {
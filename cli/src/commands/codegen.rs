@@ -5,13 +5,16 @@
 use crate::utils::{validate_url_security, FileOrUrl};
 use clap::Parser as ClapParser;
 use codec::Decode;
-use color_eyre::eyre::eyre;
+use color_eyre::eyre::{bail, eyre};
+use quote::ToTokens;
 use scale_typegen_description::scale_typegen::typegen::{
     settings::substitutes::path_segments,
     validation::{registry_contains_type_path, similar_type_paths_in_registry},
 };
-use subxt_codegen::CodegenBuilder;
+use std::path::{Path, PathBuf};
+use subxt_codegen::{is_absolute_path, CodegenBuilder};
 use subxt_metadata::Metadata;
+use syn::ext::IdentExt;
 
 /// Generate runtime API client code from metadata.
 ///
@@ -45,9 +48,10 @@ pub struct Opts {
     /// Example `--substitute-type sp_runtime::MultiAddress<A,B>=subxt::utils::Static<::sp_runtime::MultiAddress<A,B>>`
     #[clap(long = "substitute-type", value_parser = substitute_type_parser)]
     substitute_types: Vec<(String, String)>,
-    /// The `subxt` crate access path in the generated code.
+    /// The `subxt` crate access path in the generated code. This must be an
+    /// absolute path (ie start with `::` or `crate`).
     /// Defaults to `::subxt::ext::subxt_core`.
-    #[clap(long = "crate")]
+    #[clap(long = "crate", alias = "crate-path")]
     crate_path: Option<String>,
     /// Do not generate documentation for the runtime API code.
     ///
@@ -61,17 +65,30 @@ pub struct Opts {
     runtime_types_only: bool,
     /// Do not provide default trait derivations for the generated types.
     ///
+    /// Warning: This is not recommended, and is highly likely to break some part of the
+    /// generated interface. Expect compile errors.
+    ///
     /// Defaults to `false` (default trait derivations are provided).
     #[clap(long)]
     no_default_derives: bool,
     /// Do not provide default substitutions for the generated types.
     ///
+    /// Warning: This is not recommended, and is highly likely to break some part of the
+    /// generated interface. Expect compile errors.
+    ///
     /// Defaults to `false` (default substitutions are provided).
-    #[clap(long)]
+    #[clap(long, alias = "no-default-substitutes")]
     no_default_substitutions: bool,
     /// Allow insecure URLs e.g. URLs starting with ws:// or http:// without SSL encryption
     #[clap(long, short)]
     allow_insecure: bool,
+    /// Write the generated code into this directory instead of to stdout, splitting it into
+    /// a `mod.rs` file and one file for each pallet (and for the runtime types).
+    ///
+    /// The directory can then be used as a module, for example with `mod generated;` if
+    /// the directory is called `generated`.
+    #[clap(long, value_parser)]
+    out_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -158,6 +175,7 @@ pub async fn run(opts: Opts, output: &mut impl std::io::Write) -> color_eyre::Re
         opts.runtime_types_only,
         opts.no_default_derives,
         opts.no_default_substitutions,
+        opts.out_dir.as_deref(),
         output,
     )?;
     Ok(())
@@ -185,14 +203,23 @@ fn codegen(
     runtime_types_only: bool,
     no_default_derives: bool,
     no_default_substitutions: bool,
+    out_dir: Option<&Path>,
     output: &mut impl std::io::Write,
 ) -> color_eyre::Result<()> {
     let mut codegen = CodegenBuilder::new();
 
     // Use the provided crate path:
     if let Some(crate_path) = crate_path {
-        let crate_path =
+        let crate_path: syn::Path =
             syn::parse_str(&crate_path).map_err(|e| eyre!("Cannot parse crate path: {e}"))?;
+        if !is_absolute_path(&crate_path) {
+            return Err(eyre!(
+                "The crate path must be absolute (ie start with `::` or `crate`), but got `{}`",
+                quote::ToTokens::to_token_stream(&crate_path)
+                    .to_string()
+                    .replace(' ', "")
+            ));
+        }
         codegen.set_subxt_crate_path(crate_path);
     }
 
@@ -269,10 +296,87 @@ fn codegen(
         .generate(metadata)
         .map_err(|e| eyre!("Cannot generate code: {e}"))?;
 
-    writeln!(output, "{code}")?;
+    match out_dir {
+        Some(out_dir) => {
+            let item_mod =
+                syn::parse2(code).map_err(|e| eyre!("Cannot parse the generated code: {e}"))?;
+            write_to_dir(item_mod, out_dir)?
+        }
+        None => writeln!(output, "{code}")?,
+    }
+    Ok(())
+}
+
+/// Write the generated module into `out_dir`, as a `mod.rs` file. Each inline module in it
+/// is moved into its own file, and the same is done for any modules nested inside those.
+fn write_to_dir(item_mod: syn::ItemMod, out_dir: &Path) -> color_eyre::Result<()> {
+    let Some((_, items)) = item_mod.content else {
+        bail!("The generated code should be an inline module");
+    };
+
+    std::fs::create_dir_all(out_dir)?;
+
+    // The attributes on the generated module apply to the whole of `mod.rs`.
+    let attrs = item_mod.attrs.into_iter().map(into_inner_attr).collect();
+    write_module(attrs, items, out_dir, "mod.rs")
+}
+
+/// Write some module items to `file_name` in `dir`. Each inline module among the items is
+/// written to `<name>.rs` in the same directory, or to `<name>/mod.rs` if it contains inline
+/// modules of its own, which are then written alongside it in the same way.
+fn write_module(
+    attrs: Vec<syn::Attribute>,
+    items: Vec<syn::Item>,
+    dir: &Path,
+    file_name: &str,
+) -> color_eyre::Result<()> {
+    let mut file = syn::File {
+        shebang: None,
+        attrs,
+        items: Vec::with_capacity(items.len()),
+    };
+
+    for item in items {
+        let syn::Item::Mod(mut sub_mod) = item else {
+            file.items.push(item);
+            continue;
+        };
+        let Some((_, sub_items)) = sub_mod.content.take() else {
+            file.items.push(syn::Item::Mod(sub_mod));
+            continue;
+        };
+
+        // Inner attributes move into the new file, and outer ones stay on the declaration.
+        let (inner_attrs, outer_attrs) = sub_mod
+            .attrs
+            .into_iter()
+            .partition(|attr| matches!(attr.style, syn::AttrStyle::Inner(_)));
+        sub_mod.attrs = outer_attrs;
+        sub_mod.semi = Some(Default::default());
+
+        let name = sub_mod.ident.unraw().to_string();
+        let has_inline_mods = sub_items
+            .iter()
+            .any(|item| matches!(item, syn::Item::Mod(m) if m.content.is_some()));
+        if has_inline_mods {
+            let sub_dir = dir.join(&name);
+            std::fs::create_dir_all(&sub_dir)?;
+            write_module(inner_attrs, sub_items, &sub_dir, "mod.rs")?;
+        } else {
+            write_module(inner_attrs, sub_items, dir, &format!("{name}.rs"))?;
+        }
+        file.items.push(syn::Item::Mod(sub_mod));
+    }
+
+    std::fs::write(dir.join(file_name), file.to_token_stream().to_string())?;
     Ok(())
 }
 
+fn into_inner_attr(mut attr: syn::Attribute) -> syn::Attribute {
+    attr.style = syn::AttrStyle::Inner(Default::default());
+    attr
+}
+
 /// Validates that the type path is part of the metadata.
 fn validate_path_with_metadata(path: &syn::Path, metadata: &Metadata) -> color_eyre::Result<()> {
     fn pretty_path(path: &syn::Path) -> String {
@@ -367,4 +471,106 @@ mod tests {
             assert!(output.unwrap_err().to_string().contains(valid_type));
         }
     }
+
+    #[tokio::test]
+    async fn codegen_passthrough_flags() {
+        let output = run("--crate-path ::my_subxt --no-default-derives --no-default-substitutes")
+            .await
+            .unwrap()
+            .replace(' ', "");
+        assert!(output.contains("::my_subxt::"));
+        assert!(!output.contains("::subxt::ext::subxt_core"));
+
+        // The original flag names continue to work:
+        let output = run("--crate ::my_subxt --no-default-substitutions")
+            .await
+            .unwrap()
+            .replace(' ', "");
+        assert!(output.contains("::my_subxt::"));
+
+        // Paths starting with `crate` are absolute too:
+        let output = run("--crate-path crate::my_subxt")
+            .await
+            .unwrap()
+            .replace(' ', "");
+        assert!(output.contains("crate::my_subxt::"));
+
+        // Relative crate paths would panic in the builder, so are rejected up front:
+        let err = run("--crate-path my_subxt").await.unwrap_err();
+        assert!(err.to_string().contains("must be absolute"));
+    }
+
+    #[tokio::test]
+    async fn out_dir_splits_modules_into_files() {
+        use syn::ext::IdentExt;
+
+        let test_dir = std::env::temp_dir().join(format!("subxt_codegen_{}", std::process::id()));
+        let out_dir = test_dir.join("api");
+        let output = run(&format!("--out-dir {}", out_dir.display()))
+            .await
+            .unwrap();
+        // Nothing is written to stdout:
+        assert!(output.is_empty());
+
+        // Every module declared in mod.rs has a corresponding file, and so on for the
+        // modules declared in those files, and there are no other files.
+        let mut expected_files = vec![out_dir.join("mod.rs")];
+        let mut to_check = vec![(out_dir.join("mod.rs"), out_dir.clone())];
+        let mut top_level_mods = vec![];
+        while let Some((path, dir)) = to_check.pop() {
+            let file = syn::parse_file(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            for item in &file.items {
+                let syn::Item::Mod(m) = item else {
+                    continue;
+                };
+                assert!(
+                    m.content.is_none(),
+                    "`mod {}` should be in its own file",
+                    m.ident
+                );
+                if path == out_dir.join("mod.rs") {
+                    top_level_mods.push(m.ident.to_string());
+                }
+                let name = m.ident.unraw().to_string();
+                let (mod_path, mod_dir) = if dir.join(&name).is_dir() {
+                    (dir.join(&name).join("mod.rs"), dir.join(&name))
+                } else {
+                    (dir.join(format!("{name}.rs")), dir.clone())
+                };
+                expected_files.push(mod_path.clone());
+                to_check.push((mod_path, mod_dir));
+            }
+        }
+        assert!(top_level_mods.contains(&"system".to_string()));
+        assert!(top_level_mods.contains(&"runtime_types".to_string()));
+        // Nested modules are split out too:
+        assert!(out_dir.join("runtime_types").join("mod.rs").is_file());
+
+        let mut files = vec![];
+        let mut dirs = vec![out_dir.clone()];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else {
+                    files.push(path);
+                }
+            }
+        }
+        files.sort();
+        expected_files.sort();
+        assert_eq!(files, expected_files);
+
+        // The generated files compile when declared as a module:
+        let main_rs = test_dir.join("main.rs");
+        std::fs::write(
+            &main_rs,
+            "#[path = \"api/mod.rs\"]\nmod api;\n\nfn main() {}\n",
+        )
+        .unwrap();
+        trybuild::TestCases::new().pass(&main_rs);
+
+        std::fs::remove_dir_all(&test_dir).unwrap();
+    }
 }
@@ -5,14 +5,15 @@ use frame_metadata::RuntimeMetadataPrefixed;
 use std::collections::HashMap;
 use std::hash::Hash;
 
-use crate::utils::{validate_url_security, FileOrUrl};
+use crate::utils::{format_scale_value, validate_url_security, FileOrUrl};
 use color_eyre::owo_colors::OwoColorize;
 
 use scale_info::form::PortableForm;
 use scale_info::Variant;
 
 use subxt_metadata::{
-    ConstantMetadata, Metadata, PalletMetadata, RuntimeApiMetadata, StorageEntryMetadata,
+    ChangedConstant, ConstantMetadata, Metadata, PalletMetadata, RuntimeApiMetadata,
+    StorageEntryMetadata,
 };
 
 /// Explore the differences between two nodes
@@ -21,6 +22,7 @@ use subxt_metadata::{
 /// ```
 /// subxt diff ./artifacts/polkadot_metadata_small.scale ./artifacts/polkadot_metadata_tiny.scale
 /// subxt diff ./artifacts/polkadot_metadata_small.scale wss://rpc.polkadot.io:443
+/// subxt diff --show-constant-values ./old_metadata.scale ./new_metadata.scale
 /// ```
 #[derive(Debug, Args)]
 #[command(author, version, about, long_about = None)]
@@ -32,6 +34,9 @@ pub struct Opts {
     /// Allow insecure URLs e.g. URLs starting with ws:// or http:// without SSL encryption
     #[clap(long, short)]
     allow_insecure: bool,
+    /// Also decode and show the old and new values of any constants whose values have changed
+    #[clap(long)]
+    show_constant_values: bool,
 }
 
 pub async fn run(opts: Opts, output: &mut impl std::io::Write) -> color_eyre::Result<()> {
@@ -40,9 +45,28 @@ pub async fn run(opts: Opts, output: &mut impl std::io::Write) -> color_eyre::Re
 
     let (entry_1_metadata, entry_2_metadata) = get_metadata(&opts).await?;
 
-    let metadata_diff = MetadataDiff::construct(&entry_1_metadata, &entry_2_metadata);
+    write_diff(
+        &entry_1_metadata,
+        &entry_2_metadata,
+        opts.show_constant_values,
+        output,
+    )
+}
 
-    if metadata_diff.is_empty() {
+fn write_diff(
+    entry_1_metadata: &Metadata,
+    entry_2_metadata: &Metadata,
+    show_constant_values: bool,
+    output: &mut impl std::io::Write,
+) -> color_eyre::Result<()> {
+    let metadata_diff = MetadataDiff::construct(entry_1_metadata, entry_2_metadata);
+    let changed_constants = if show_constant_values {
+        entry_1_metadata.changed_constants(entry_2_metadata)
+    } else {
+        Vec::new()
+    };
+
+    if metadata_diff.is_empty() && changed_constants.is_empty() {
         writeln!(output, "No difference in metadata found.")?;
         return Ok(());
     }
@@ -126,8 +150,8 @@ pub async fn run(opts: Opts, output: &mut impl std::io::Write) -> color_eyre::Re
                                     let storage_diff = StorageEntryDiff::construct(
                                         from,
                                         to,
-                                        &entry_1_metadata,
-                                        &entry_2_metadata,
+                                        entry_1_metadata,
+                                        entry_2_metadata,
                                     );
 
                                     writeln!(
@@ -165,9 +189,67 @@ pub async fn run(opts: Opts, output: &mut impl std::io::Write) -> color_eyre::Re
             }
         }
     }
+
+    if !changed_constants.is_empty() {
+        writeln!(output, "Constant Values:")?;
+        for changed in changed_constants {
+            let name = format!("{}::{}", changed.pallet, changed.name);
+            match ConstantValueDiff::construct(&changed, entry_1_metadata, entry_2_metadata)? {
+                ConstantValueDiff::TypeChanged => writeln!(
+                    output,
+                    "{}",
+                    format!("    ~ {name} (Changed: type; values not compared)").yellow()
+                )?,
+                ConstantValueDiff::ValueChanged { from, to } => {
+                    writeln!(output, "{}", format!("    ~ {name}").yellow())?;
+                    writeln!(output, "{}", format!("        - {from}").red())?;
+                    writeln!(output, "{}", format!("        + {to}").green())?;
+                }
+            }
+        }
+    }
     Ok(())
 }
 
+/// How the value of a constant differs between two metadatas.
+enum ConstantValueDiff {
+    /// The type of the constant changed, so its values can't be meaningfully compared.
+    TypeChanged,
+    /// The type is the same but the value changed. Both values are decoded and formatted.
+    ValueChanged { from: String, to: String },
+}
+
+impl ConstantValueDiff {
+    fn construct(
+        changed: &ChangedConstant,
+        metadata_1: &Metadata,
+        metadata_2: &Metadata,
+    ) -> color_eyre::Result<Self> {
+        let constant_ty = |metadata: &Metadata| {
+            metadata
+                .pallet_by_name(&changed.pallet)
+                .and_then(|p| p.constant_by_name(&changed.name))
+                .map(|c| c.ty())
+                .expect("constant is in metadata; qed")
+        };
+        let ty_1 = constant_ty(metadata_1);
+        let ty_2 = constant_ty(metadata_2);
+
+        if metadata_1.type_hash(ty_1) != metadata_2.type_hash(ty_2) {
+            return Ok(ConstantValueDiff::TypeChanged);
+        }
+
+        let from =
+            scale_value::scale::decode_as_type(&mut &*changed.old_value, ty_1, metadata_1.types())?;
+        let to =
+            scale_value::scale::decode_as_type(&mut &*changed.new_value, ty_2, metadata_2.types())?;
+        Ok(ConstantValueDiff::ValueChanged {
+            from: format_scale_value(&from),
+            to: format_scale_value(&to),
+        })
+    }
+}
+
 struct MetadataDiff<'a> {
     pallets: Vec<Diff<PalletMetadata<'a>>>,
     runtime_apis: Vec<Diff<RuntimeApiMetadata<'a>>>,
@@ -441,7 +523,78 @@ fn diff<T, C: PartialEq, I: Hash + PartialEq + Eq + Ord>(
 
 #[cfg(test)]
 mod test {
-    use crate::commands::diff::{diff, Diff};
+    use crate::commands::diff::{diff, write_diff, Diff};
+    use codec::{Decode, Encode};
+    use frame_metadata::{v15, RuntimeMetadata, RuntimeMetadataPrefixed};
+    use scale_info::{TypeDef, TypeDefPrimitive};
+    use subxt_metadata::Metadata;
+
+    /// Load our fixture metadata, modifying it first.
+    fn fixture_metadata(modify: impl FnOnce(&mut v15::RuntimeMetadataV15)) -> Metadata {
+        let bytes = std::fs::read("../artifacts/polkadot_metadata_small.scale").unwrap();
+        let mut metadata = RuntimeMetadataPrefixed::decode(&mut &*bytes).unwrap();
+        let RuntimeMetadata::V15(inner) = &mut metadata.1 else {
+            panic!("fixture metadata should be V15");
+        };
+        modify(inner);
+        metadata.try_into().unwrap()
+    }
+
+    fn constant<'a>(
+        metadata: &'a mut v15::RuntimeMetadataV15,
+        pallet: &str,
+        name: &str,
+    ) -> &'a mut v15::PalletConstantMetadata<scale_info::form::PortableForm> {
+        metadata
+            .pallets
+            .iter_mut()
+            .find(|p| p.name == pallet)
+            .and_then(|p| p.constants.iter_mut().find(|c| c.name == name))
+            .expect("constant should exist in fixture metadata")
+    }
+
+    fn write_diff_to_string(old: &Metadata, new: &Metadata, show_constant_values: bool) -> String {
+        let mut output = Vec::new();
+        write_diff(old, new, show_constant_values, &mut output).unwrap();
+        let output = strip_ansi_escapes::strip(output);
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn changed_constant_values_are_shown() {
+        let mut old_deposit = 0;
+        let old = fixture_metadata(|m| {
+            let value = &constant(m, "Balances", "ExistentialDeposit").value;
+            old_deposit = u128::decode(&mut &**value).unwrap();
+        });
+        let new_deposit = old_deposit / 2;
+        let new = fixture_metadata(|m| {
+            constant(m, "Balances", "ExistentialDeposit").value = new_deposit.encode();
+
+            // Change the type of another constant to a u32:
+            let u32_id = m
+                .types
+                .types
+                .iter()
+                .find(|ty| matches!(ty.ty.type_def, TypeDef::Primitive(TypeDefPrimitive::U32)))
+                .expect("fixture metadata should contain a u32")
+                .id;
+            let ss58_prefix = constant(m, "System", "SS58Prefix");
+            ss58_prefix.ty = u32_id.into();
+            ss58_prefix.value = 42u32.encode();
+        });
+
+        // Without the flag, a change in value alone isn't noticed:
+        let output = write_diff_to_string(&old, &new, false);
+        assert!(!output.contains("Constant Values:"));
+        assert!(!output.contains("ExistentialDeposit"));
+
+        let output = write_diff_to_string(&old, &new, true);
+        assert!(output.contains(&format!(
+            "Constant Values:\n    ~ Balances::ExistentialDeposit\n        - {old_deposit}\n        + {new_deposit}\n"
+        )));
+        assert!(output.contains("    ~ System::SS58Prefix (Changed: type; values not compared)\n"));
+    }
 
     #[test]
     fn test_diff_fn() {
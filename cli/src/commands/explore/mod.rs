@@ -440,6 +440,33 @@ pub mod tests {
         assert_eq_start!(output, start);
     }
 
+    #[tokio::test]
+    async fn storage_keys_are_validated() {
+        // Multisig.Multisigs is keyed by an account and a call hash, so two keys are expected:
+        let err = run_against_file("pallet Multisig storage Multisigs --execute (1, 2, 3)")
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("expects 2 keys, but 3 were provided"));
+
+        let err = run_against_file("pallet Multisig storage Multisigs --execute 1")
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("expects 2 keys"));
+    }
+
+    #[tokio::test]
+    async fn storage_entries_can_be_fetched_by_ss58_address() {
+        // The Polkadot treasury account:
+        let output = run("--url wss://rpc.polkadot.io:443 pallet System storage Account --execute 13UVJyLnbVp9RBZYFwFGyDvVd1y27Tt8tkntv6Q7JVPhFsTB")
+            .await
+            .unwrap()
+            .strip_ansi();
+        assert!(output.contains("The value of the storage entry is:"));
+        assert!(output.contains("free"));
+    }
+
     #[tokio::test]
     async fn insecure_urls_get_denied() {
         // Connection should work fine:
@@ -8,7 +8,7 @@ use scale_value::{Composite, ValueDef};
 use std::str::FromStr;
 
 use subxt::tx;
-use subxt::utils::H256;
+use subxt::utils::{to_hex, H256};
 use subxt::{
     config::SubstrateConfig,
     metadata::{types::PalletMetadata, Metadata},
@@ -106,7 +106,7 @@ pub fn explore_calls(
     let offline_client = mocked_offline_client(metadata.clone());
     let payload = tx::dynamic(pallet_name, call_name, value_as_composite);
     let unsigned_extrinsic = offline_client.tx().create_unsigned(&payload)?;
-    let hex_bytes = format!("0x{}", hex::encode(unsigned_extrinsic.encoded()));
+    let hex_bytes = to_hex(unsigned_extrinsic.encoded());
     writedoc! {output, "
     Encoded call data:
         {hex_bytes}
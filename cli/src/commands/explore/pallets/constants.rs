@@ -4,7 +4,9 @@ use indoc::{formatdoc, writedoc};
 use scale_typegen_description::type_description;
 use subxt::metadata::{types::PalletMetadata, Metadata};
 
-use crate::utils::{first_paragraph_of_docs, format_scale_value, Indent, SyntaxHighlight};
+use crate::utils::{
+    first_paragraph_of_docs, format_scale_value_with_type_names, Indent, SyntaxHighlight,
+};
 
 #[derive(Debug, Clone, Args)]
 pub struct ConstantsSubcommand {
@@ -66,7 +68,8 @@ pub fn explore_constants(
     // value
     let value =
         scale_value::scale::decode_as_type(&mut constant.value(), constant.ty(), metadata.types())?;
-    let value = format_scale_value(&value).indent(4);
+    let value =
+        format_scale_value_with_type_names(&value, constant.ty(), metadata.types()).indent(4);
 
     writedoc!(
         output,
@@ -5,21 +5,18 @@ use color_eyre::{
 };
 use indoc::{formatdoc, writedoc};
 use scale_typegen_description::type_description;
-use scale_value::Value;
+use scale_value::{Composite, Value, ValueDef};
 use std::fmt::Write;
 use std::write;
 
-use subxt::{
-    ext::scale_encode::EncodeAsType,
-    metadata::{
-        types::{PalletMetadata, StorageEntryType, StorageMetadata},
-        Metadata,
-    },
+use subxt::metadata::{
+    types::{PalletMetadata, StorageEntryType, StorageMetadata},
+    Metadata,
 };
 
 use crate::utils::{
-    create_client, first_paragraph_of_docs, parse_string_into_scale_value, type_example, FileOrUrl,
-    Indent, SyntaxHighlight,
+    create_client, first_paragraph_of_docs, format_scale_value_with_type_names,
+    parse_string_into_scale_value, type_example, FileOrUrl, Indent, SyntaxHighlight,
 };
 
 #[derive(Debug, Clone, Args)]
@@ -80,11 +77,13 @@ pub async fn explore_storage(
         );
     };
 
-    let (return_ty_id, key_ty_id) = match storage.entry_type() {
-        StorageEntryType::Plain(value) => (*value, None),
+    let (return_ty_id, key_ty_id, num_keys) = match storage.entry_type() {
+        StorageEntryType::Plain(value) => (*value, None, 0),
         StorageEntryType::Map {
-            value_ty, key_ty, ..
-        } => (*value_ty, Some(*key_ty)),
+            value_ty,
+            key_ty,
+            hashers,
+        } => (*value_ty, Some(*key_ty), hashers.len()),
     };
 
     let key_value_placeholder = "<KEY_VALUE>".blue();
@@ -160,7 +159,7 @@ pub async fn explore_storage(
             return Ok(());
         }
         (true, None) => vec![],
-        (false, Some(type_id)) => {
+        (false, Some(_)) => {
             let value = parse_string_into_scale_value(trailing_args)?;
             let value_str = value.indent(4);
             writedoc! {output, "
@@ -169,9 +168,7 @@ pub async fn explore_storage(
             {value_str}
             "}?;
 
-            let key_bytes = value.encode_as_type(type_id, metadata.types())?;
-            let bytes_composite = Value::from_bytes(key_bytes);
-            vec![bytes_composite]
+            split_into_keys(value, num_keys)?
         }
     };
 
@@ -189,7 +186,11 @@ pub async fn explore_storage(
     let decoded_value_thunk =
         decoded_value_thunk_or_none.ok_or(eyre!("Value not found in storage."))?;
 
-    let value = decoded_value_thunk.to_value()?.to_string().highlight();
+    let value = format_scale_value_with_type_names(
+        &decoded_value_thunk.to_value()?,
+        return_ty_id,
+        metadata.types(),
+    );
     writedoc! {output, "
 
     The value of the storage entry is:
@@ -199,6 +200,25 @@ pub async fn explore_storage(
     Ok(())
 }
 
+/// Storage maps with more than one hasher expect a tuple containing one value per key. Split
+/// such a tuple up so that each key can be hashed with the right hasher.
+fn split_into_keys(value: Value, num_keys: usize) -> color_eyre::Result<Vec<Value>> {
+    if num_keys == 1 {
+        return Ok(vec![value]);
+    }
+
+    let ValueDef::Composite(Composite::Unnamed(keys)) = value.value else {
+        bail!("This storage entry expects {num_keys} keys, provided as a tuple like (key1, key2).");
+    };
+    if keys.len() != num_keys {
+        bail!(
+            "This storage entry expects {num_keys} keys, but {} were provided.",
+            keys.len()
+        );
+    }
+    Ok(keys)
+}
+
 fn storage_entries_string(storage_metadata: &StorageMetadata, pallet_name: &str) -> String {
     let storage_entry_placeholder = "<STORAGE_ENTRY>".blue();
     if storage_metadata.entries().is_empty() {
@@ -1,6 +1,7 @@
 use crate::utils::{
     create_client, fields_composite_example, fields_description, first_paragraph_of_docs,
-    parse_string_into_scale_value, FileOrUrl, Indent, SyntaxHighlight,
+    format_scale_value_with_type_names, parse_string_into_scale_value, FileOrUrl, Indent,
+    SyntaxHighlight,
 };
 
 use color_eyre::{
@@ -173,10 +174,12 @@ pub async fn run<'a>(
         .runtime_api()
         .at_latest()
         .await?
-        .call(method_call)
+        .call_with_dispatch_error(method_call)
         .await?;
 
-    let output_value = output_value.to_value()?.to_string().highlight();
+    let output_value = output_value.to_value()?;
+    let output_value =
+        format_scale_value_with_type_names(&output_value, output_value.context, metadata.types());
     writedoc! {output, "
 
     Returned value:
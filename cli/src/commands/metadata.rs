@@ -8,6 +8,7 @@ use codec::{Decode, Encode};
 use color_eyre::eyre::{self, bail};
 use frame_metadata::{v15::RuntimeMetadataV15, RuntimeMetadata, RuntimeMetadataPrefixed};
 use std::{io::Write, path::PathBuf};
+use subxt::utils::to_hex;
 use subxt_metadata::Metadata;
 
 /// Download metadata from a substrate node, for use with `subxt` codegen.
@@ -88,7 +89,7 @@ pub async fn run(opts: Opts, output: &mut impl Write) -> color_eyre::Result<()>
             Ok(())
         }
         "hex" => {
-            let hex_data = format!("0x{}", hex::encode(metadata.encode()));
+            let hex_data = to_hex(metadata.encode());
             write!(output, "{hex_data}")?;
             Ok(())
         }
@@ -6,14 +6,15 @@ use clap::Args;
 use color_eyre::eyre::{bail, eyre};
 use color_eyre::owo_colors::OwoColorize;
 use heck::ToUpperCamelCase;
-use scale_info::PortableRegistry;
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
 use scale_typegen_description::{format_type_description, type_description};
 use std::fmt::Display;
 use std::str::FromStr;
 use std::{fs, io::Read, path::PathBuf};
+use subxt::utils::to_hex;
 use subxt::{OnlineClient, PolkadotConfig};
 
-use scale_value::Value;
+use scale_value::{stringify::custom_parsers, Composite, Value, ValueDef};
 use subxt_codegen::fetch_metadata::{fetch_metadata_from_url, MetadataVersion, Url};
 
 /// The source of the metadata.
@@ -178,6 +179,137 @@ pub fn format_scale_value<T>(value: &Value<T>) -> String {
     scale_typegen_description::format_type_description(&value.to_string()).highlight()
 }
 
+/// Like [`format_scale_value`], but uses the type registry to also name the structs that
+/// make up the value, for example `AccountInfo { nonce: 0, .. }` rather than `{ nonce: 0, .. }`.
+/// Byte sequences are shown as hex.
+pub fn format_scale_value_with_type_names<T>(
+    value: &Value<T>,
+    type_id: u32,
+    types: &PortableRegistry,
+) -> String {
+    let value = scale_value_with_type_names(value, type_id, types);
+    scale_typegen_description::format_type_description(&value).highlight()
+}
+
+fn scale_value_with_type_names<T>(
+    value: &Value<T>,
+    type_id: u32,
+    types: &PortableRegistry,
+) -> String {
+    let Some(ty) = types.resolve(type_id) else {
+        return value.to_string();
+    };
+
+    match (&ty.type_def, &value.value) {
+        (TypeDef::Composite(composite), ValueDef::Composite(fields)) => {
+            let field_tys = composite.fields.iter().map(|f| f.ty.id);
+            let Some(fields) = composite_with_type_names(fields, field_tys, types) else {
+                return value.to_string();
+            };
+            format!("{}{fields}", ty.path.ident().unwrap_or_default())
+        }
+        (TypeDef::Variant(variant), ValueDef::Variant(value_variant)) => {
+            let Some(variant_ty) = variant
+                .variants
+                .iter()
+                .find(|v| v.name == value_variant.name)
+            else {
+                return value.to_string();
+            };
+            let field_tys = variant_ty.fields.iter().map(|f| f.ty.id);
+            let Some(fields) = composite_with_type_names(&value_variant.values, field_tys, types)
+            else {
+                return value.to_string();
+            };
+            format!("{}{fields}", value_variant.name)
+        }
+        (TypeDef::Sequence(seq), ValueDef::Composite(values)) => {
+            sequence_with_type_names(values, seq.type_param.id, types)
+        }
+        (TypeDef::Array(arr), ValueDef::Composite(values)) => {
+            sequence_with_type_names(values, arr.type_param.id, types)
+        }
+        (TypeDef::Tuple(tuple), ValueDef::Composite(values)) => {
+            if tuple.fields.len() != values.len() {
+                return value.to_string();
+            }
+            let values = tuple
+                .fields
+                .iter()
+                .zip(values.values())
+                .map(|(ty, v)| scale_value_with_type_names(v, ty.id, types))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("({values})")
+        }
+        (TypeDef::Compact(compact), _) => {
+            scale_value_with_type_names(value, compact.type_param.id, types)
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Returns `None` if the number of fields doesn't match the number of field types.
+fn composite_with_type_names<T>(
+    fields: &Composite<T>,
+    field_tys: impl ExactSizeIterator<Item = u32>,
+    types: &PortableRegistry,
+) -> Option<String> {
+    if fields.len() != field_tys.len() {
+        return None;
+    }
+
+    let formatted = match fields {
+        Composite::Named(fields) if !fields.is_empty() => {
+            let fields = fields
+                .iter()
+                .zip(field_tys)
+                .map(|((name, v), ty)| {
+                    format!("{name}: {}", scale_value_with_type_names(v, ty, types))
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(" {{ {fields} }}")
+        }
+        Composite::Unnamed(values) if !values.is_empty() => {
+            let values = values
+                .iter()
+                .zip(field_tys)
+                .map(|(v, ty)| scale_value_with_type_names(v, ty, types))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("({values})")
+        }
+        _ => String::new(),
+    };
+    Some(formatted)
+}
+
+fn sequence_with_type_names<T>(
+    values: &Composite<T>,
+    item_ty: u32,
+    types: &PortableRegistry,
+) -> String {
+    let is_u8 = matches!(
+        types.resolve(item_ty).map(|ty| &ty.type_def),
+        Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+    );
+    let bytes: Option<Vec<u8>> = values
+        .values()
+        .map(|v| v.as_u128().and_then(|n| u8::try_from(n).ok()))
+        .collect();
+    if let (true, Some(bytes)) = (is_u8, bytes) {
+        return to_hex(bytes);
+    }
+
+    let values = values
+        .values()
+        .map(|v| scale_value_with_type_names(v, item_ty, types))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("[{values}]")
+}
+
 pub fn type_example(type_id: u32, types: &PortableRegistry) -> Value {
     scale_typegen_description::scale_value_from_seed(type_id, types, time_based_seed()).expect("")
 }
@@ -223,12 +355,21 @@ pub async fn create_client(
     Ok(client)
 }
 
+/// Parses a string into a [`Value`]. Besides the usual syntax, hex strings like `0x1234` are
+/// parsed into bytes and SS58 addresses are parsed into the bytes of the account they encode.
 pub fn parse_string_into_scale_value(str: &str) -> color_eyre::Result<Value> {
-    let value = scale_value::stringify::from_str(str).0.map_err(|err| {
+    let (value, rest) = scale_value::stringify::from_str_custom()
+        .add_custom_parser(custom_parsers::parse_hex)
+        .add_custom_parser(custom_parsers::parse_ss58)
+        .parse(str);
+    let value = value.map_err(|err| {
         eyre!(
             "scale_value::stringify::from_str led to a ParseError.\n\ntried parsing: \"{str}\"\n\n{err}",
         )
     })?;
+    if !rest.trim().is_empty() {
+        bail!("Unexpected input after the value: \"{}\"", rest.trim());
+    }
     Ok(value)
 }
 
@@ -325,7 +466,12 @@ pub fn validate_url_security(url: Option<&Url>, allow_insecure: bool) -> color_e
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::{FileOrUrl, PathOrStdIn};
+    use crate::utils::{scale_value_with_type_names, FileOrUrl, PathOrStdIn};
+    use scale_info::{
+        build::{Fields, Variants},
+        Path, PortableRegistry, Type, TypeInfo,
+    };
+    use scale_value::Value;
     use std::str::FromStr;
 
     #[test]
@@ -368,4 +514,75 @@ mod tests {
             })
         ));
     }
+
+    struct Address;
+    impl TypeInfo for Address {
+        type Identity = Self;
+        fn type_info() -> Type {
+            Type::builder()
+                .path(Path::new("Address", module_path!()))
+                .variant(Variants::new().variant("Id", |v| {
+                    v.index(0)
+                        .fields(Fields::unnamed().field(|f| f.ty::<[u8; 4]>()))
+                }))
+        }
+    }
+
+    struct Transfer;
+    impl TypeInfo for Transfer {
+        type Identity = Self;
+        fn type_info() -> Type {
+            Type::builder()
+                .path(Path::new("Transfer", module_path!()))
+                .composite(
+                    Fields::named()
+                        .field(|f| f.ty::<Address>().name("dest"))
+                        .field(|f| f.ty::<u128>().name("value")),
+                )
+        }
+    }
+
+    #[test]
+    fn values_are_formatted_with_type_names() {
+        let mut registry = scale_info::Registry::new();
+        let type_id = registry
+            .register_type(&scale_info::meta_type::<Transfer>())
+            .id;
+        let types: PortableRegistry = registry.into();
+
+        let value = Value::named_composite([
+            (
+                "dest",
+                Value::unnamed_variant(
+                    "Id",
+                    [Value::unnamed_composite([
+                        Value::u128(1),
+                        Value::u128(2),
+                        Value::u128(3),
+                        Value::u128(4),
+                    ])],
+                ),
+            ),
+            ("value", Value::u128(1000)),
+        ]);
+
+        assert_eq!(
+            scale_value_with_type_names(&value, type_id, &types),
+            "Transfer { dest: Id(0x01020304), value: 1000 }"
+        );
+
+        // Values which don't line up with the type are still shown:
+        let value = Value::u128(1000);
+        assert_eq!(
+            scale_value_with_type_names(&value, type_id, &types),
+            value.to_string()
+        );
+
+        // Including when the number of fields doesn't match:
+        let value = Value::named_composite([("value", Value::u128(1000))]);
+        assert_eq!(
+            scale_value_with_type_names(&value, type_id, &types),
+            value.to_string()
+        );
+    }
 }
@@ -2,10 +2,10 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
-use super::CodegenError;
-use heck::{ToSnakeCase as _, ToUpperCamelCase as _};
+use super::{CodegenError, MethodAttributes, Renames};
+use heck::ToUpperCamelCase as _;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{format_ident, quote};
+use quote::quote;
 use scale_typegen::typegen::ir::ToTokensWithSettings;
 use scale_typegen::{typegen::ir::type_ir::CompositeIRKind, TypeGenerator};
 use subxt_metadata::PalletMetadata;
@@ -17,10 +17,14 @@ use subxt_metadata::PalletMetadata;
 ///
 /// - `type_gen` - [`scale_typegen::TypeGenerator`] that contains settings and all types from the runtime metadata.
 /// - `pallet` - Pallet metadata from which the calls are generated.
+/// - `renames` - Overrides for the names of the generated call functions.
+/// - `method_attributes` - Additional attributes to place on the generated call functions.
 /// - `crate_path` - The crate path under which the `subxt-core` crate is located, e.g. `::subxt::ext::subxt_core` when using subxt as a dependency.
 pub fn generate_calls(
     type_gen: &TypeGenerator,
     pallet: &PalletMetadata,
+    renames: &Renames,
+    method_attributes: &MethodAttributes,
     crate_path: &syn::Path,
 ) -> Result<TokenStream2, CodegenError> {
     // Early return if the pallet has no calls.
@@ -34,6 +38,10 @@ pub fn generate_calls(
         |name| name.to_upper_camel_case().into(),
         "Call",
     )?;
+    // Boxed args are boxed using the same `alloc` crate as the rest of the generated types,
+    // so that they work in `no_std` crates too.
+    let alloc_crate_path = &type_gen.settings().alloc_crate_path;
+
     let (call_structs, call_fns): (Vec<_>, Vec<_>) = variant_names_and_struct_defs
         .into_iter()
         .map(|var| {
@@ -44,7 +52,7 @@ pub fn generate_calls(
                         // Note: fn_arg_type this is relative the type path of the type alias when prefixed with `types::`, e.g. `set_max_code_size::New`
                         let fn_arg_type = field.type_path.to_token_stream(type_gen.settings());
                         let call_arg = if field.is_boxed {
-                            quote! { #name: #crate_path::alloc::boxed::Box::new(#name) }
+                            quote! { #name: #alloc_crate_path::boxed::Box::new(#name) }
                         } else {
                             quote! { #name }
                         };
@@ -66,7 +74,8 @@ pub fn generate_calls(
                     call_name.to_string(),
                 ));
             };
-            let fn_name = format_ident!("{}", var.variant_name.to_snake_case());
+            let fn_name = renames.call_fn_name(pallet_name, &var.variant_name);
+            let fn_attrs = method_attributes.for_call(pallet_name, &var.variant_name);
             // Propagate the documentation just to `TransactionApi` methods, while
             // draining the documentation of inner call structures.
             let docs = &var.composite.docs;
@@ -89,6 +98,7 @@ pub fn generate_calls(
 
             let client_fn = quote! {
                 #docs
+                #( #fn_attrs )*
                 pub fn #fn_name(
                     &self,
                     #( #call_fn_args, )*
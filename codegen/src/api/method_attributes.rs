@@ -0,0 +1,123 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::error::CodegenError;
+use std::collections::HashMap;
+use subxt_metadata::Metadata;
+use syn::Attribute;
+
+/// Additional attributes to place on the functions that we generate for calls,
+/// storage entries and runtime API methods.
+#[derive(Debug, Clone, Default)]
+pub struct MethodAttributes {
+    calls: HashMap<(String, String), Vec<Attribute>>,
+    storage_entries: HashMap<(String, String), Vec<Attribute>>,
+    runtime_api_methods: HashMap<(String, String), Vec<Attribute>>,
+}
+
+impl MethodAttributes {
+    /// Add attributes to the function generated for some call in some pallet.
+    pub fn add_for_call(
+        &mut self,
+        pallet: String,
+        call: String,
+        attributes: impl IntoIterator<Item = Attribute>,
+    ) {
+        self.calls
+            .entry((pallet, call))
+            .or_default()
+            .extend(attributes);
+    }
+
+    /// Add attributes to each of the functions generated for some storage entry in some pallet.
+    pub fn add_for_storage_entry(
+        &mut self,
+        pallet: String,
+        entry: String,
+        attributes: impl IntoIterator<Item = Attribute>,
+    ) {
+        self.storage_entries
+            .entry((pallet, entry))
+            .or_default()
+            .extend(attributes);
+    }
+
+    /// Add attributes to the function generated for some method in some runtime API trait.
+    pub fn add_for_runtime_api_method(
+        &mut self,
+        runtime_api: String,
+        method: String,
+        attributes: impl IntoIterator<Item = Attribute>,
+    ) {
+        self.runtime_api_methods
+            .entry((runtime_api, method))
+            .or_default()
+            .extend(attributes);
+    }
+
+    /// The attributes to place on the function generated for the given call.
+    pub fn for_call(&self, pallet: &str, call: &str) -> &[Attribute] {
+        lookup(&self.calls, pallet, call)
+    }
+
+    /// The attributes to place on the functions generated for the given storage entry.
+    pub fn for_storage_entry(&self, pallet: &str, entry: &str) -> &[Attribute] {
+        lookup(&self.storage_entries, pallet, entry)
+    }
+
+    /// The attributes to place on the function generated for the given runtime API method.
+    pub fn for_runtime_api_method(&self, runtime_api: &str, method: &str) -> &[Attribute] {
+        lookup(&self.runtime_api_methods, runtime_api, method)
+    }
+
+    /// Check that every call, storage entry and runtime API method
+    /// given attributes exists in the metadata.
+    pub fn validate(&self, metadata: &Metadata) -> Result<(), CodegenError> {
+        for (pallet, call) in self.calls.keys() {
+            let exists = metadata
+                .pallet_by_name(pallet)
+                .and_then(|p| p.call_variant_by_name(call))
+                .is_some();
+            if !exists {
+                return Err(CodegenError::InvalidMethodAttributes(format!(
+                    "call {pallet}.{call}"
+                )));
+            }
+        }
+        for (pallet, entry) in self.storage_entries.keys() {
+            let exists = metadata
+                .pallet_by_name(pallet)
+                .and_then(|p| p.storage())
+                .and_then(|s| s.entry_by_name(entry))
+                .is_some();
+            if !exists {
+                return Err(CodegenError::InvalidMethodAttributes(format!(
+                    "storage entry {pallet}.{entry}"
+                )));
+            }
+        }
+        for (runtime_api, method) in self.runtime_api_methods.keys() {
+            let exists = metadata
+                .runtime_api_trait_by_name(runtime_api)
+                .and_then(|t| t.method_by_name(method))
+                .is_some();
+            if !exists {
+                return Err(CodegenError::InvalidMethodAttributes(format!(
+                    "runtime API method {runtime_api}.{method}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn lookup<'a>(
+    map: &'a HashMap<(String, String), Vec<Attribute>>,
+    outer: &str,
+    inner: &str,
+) -> &'a [Attribute] {
+    map.get(&(outer.to_string(), inner.to_string()))
+        .map(|attrs| attrs.as_slice())
+        .unwrap_or_default()
+}
@@ -9,6 +9,8 @@ mod constants;
 mod custom_values;
 mod errors;
 mod events;
+mod method_attributes;
+mod renames;
 mod runtime_apis;
 mod storage;
 
@@ -21,16 +23,26 @@ use subxt_metadata::Metadata;
 use syn::{parse_quote, Ident};
 
 use crate::error::CodegenError;
+use crate::report::TypeMappingReport;
 use crate::subxt_type_gen_settings;
 use crate::{api::custom_values::generate_custom_values, ir};
 
+pub use method_attributes::MethodAttributes;
+pub use renames::Renames;
+
 use heck::{ToSnakeCase as _, ToUpperCamelCase};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
+use std::collections::BTreeMap;
 
 /// Create the API for interacting with a Substrate runtime.
 pub struct RuntimeGenerator {
     metadata: Metadata,
+    renames: Renames,
+    method_attributes: MethodAttributes,
+    codec_attributes: bool,
+    value_conversions: bool,
+    precompute_storage_prefixes: bool,
 }
 
 impl RuntimeGenerator {
@@ -46,7 +58,133 @@ impl RuntimeGenerator {
     /// Supported versions: v14 and v15.
     pub fn new(mut metadata: Metadata) -> Self {
         scale_typegen::utils::ensure_unique_type_paths(metadata.types_mut());
-        RuntimeGenerator { metadata }
+        RuntimeGenerator {
+            metadata,
+            renames: Renames::default(),
+            method_attributes: MethodAttributes::default(),
+            codec_attributes: true,
+            value_conversions: false,
+            precompute_storage_prefixes: false,
+        }
+    }
+
+    /// Override the identifiers generated for some pallets and calls.
+    pub fn with_renames(mut self, renames: Renames) -> Self {
+        self.renames = renames;
+        self
+    }
+
+    /// Add attributes to the functions generated for some calls, storage entries
+    /// and runtime API methods.
+    pub fn with_method_attributes(mut self, method_attributes: MethodAttributes) -> Self {
+        self.method_attributes = method_attributes;
+        self
+    }
+
+    /// Whether to add `#[codec(..)]` attributes (eg `#[codec(compact)]`) to the fields and
+    /// variants of generated types. These must be disabled if the generated types do not
+    /// derive `parity-scale-codec`'s `Encode` and `Decode`. Enabled by default.
+    pub fn with_codec_attributes(mut self, codec_attributes: bool) -> Self {
+        self.codec_attributes = codec_attributes;
+        self
+    }
+
+    /// Whether to implement `ValueConversion` for the generated types, allowing them to be
+    /// converted to and from dynamic `scale_value::Value`s. Disabled by default.
+    pub fn with_value_conversions(mut self, value_conversions: bool) -> Self {
+        self.value_conversions = value_conversions;
+        self
+    }
+
+    /// Whether to generate a `const` holding the precomputed storage prefix of each storage
+    /// entry, alongside the functions which build storage addresses. Disabled by default.
+    pub fn with_precomputed_storage_prefixes(mut self, precompute_storage_prefixes: bool) -> Self {
+        self.precompute_storage_prefixes = precompute_storage_prefixes;
+        self
+    }
+
+    /// Report the path to the Rust type that each type in the metadata is generated as.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_substitutes` - Provide custom type substitutes.
+    /// * `crate_path` - Path to the `subxt` crate.
+    /// * `no_std` - True if `alloc` types should be referred to via `::alloc`.
+    pub fn type_mapping_report(
+        &self,
+        type_substitutes: scale_typegen::TypeSubstitutes,
+        crate_path: &syn::Path,
+        no_std: bool,
+    ) -> Result<TypeMappingReport, CodegenError> {
+        // Derives have no bearing on type paths, so we don't bother providing them here.
+        let settings = subxt_type_gen_settings(
+            scale_typegen::DerivesRegistry::new(),
+            type_substitutes,
+            crate_path,
+            false,
+            no_std,
+            self.codec_attributes,
+        );
+        let type_gen = TypeGenerator::new(self.metadata.types(), &settings);
+
+        let mut paths: BTreeMap<u32, String> = BTreeMap::new();
+        for ty in &self.metadata.types().types {
+            let path = type_gen
+                .resolve_type_path(ty.id)?
+                .to_token_stream(type_gen.settings())
+                .to_string();
+            paths.insert(ty.id, path.split_whitespace().collect());
+        }
+        Ok(TypeMappingReport::new(paths))
+    }
+
+    /// Implement `ValueConversion` for each of the generated composite and variant types,
+    /// if value conversions were asked for. Generic types are skipped, since they don't line
+    /// up with a single type in the metadata, as are substituted types, which we don't own.
+    fn generate_value_conversions(
+        &self,
+        type_gen: &TypeGenerator,
+        crate_path: &syn::Path,
+    ) -> Result<TokenStream2, CodegenError> {
+        if !self.value_conversions {
+            return Ok(quote!());
+        }
+
+        let types_mod_prefix = format!("{} ::", type_gen.types_mod_ident());
+        let mut impls = Vec::new();
+        for ty in &self.metadata.types().types {
+            let is_composite_or_variant = matches!(
+                ty.ty.type_def,
+                scale_info::TypeDef::Composite(_) | scale_info::TypeDef::Variant(_)
+            );
+            if !is_composite_or_variant
+                || !ty.ty.type_params.is_empty()
+                || ty.ty.path.segments.is_empty()
+            {
+                continue;
+            }
+
+            let path = type_gen
+                .resolve_type_path(ty.id)?
+                .to_token_stream(type_gen.settings());
+            if !path.to_string().starts_with(&types_mod_prefix) {
+                continue;
+            }
+
+            let type_id = ty.id;
+            let type_hash = self
+                .metadata
+                .type_hash(ty.id)
+                .expect("type is in the registry; qed");
+            impls.push(quote! {
+                impl #crate_path::dynamic::ValueConversion for #path {
+                    const TYPE_ID: u32 = #type_id;
+                    const TYPE_HASH: [u8; 32] = [ #(#type_hash,)* ];
+                }
+            });
+        }
+
+        Ok(quote! { #( #impls )* })
     }
 
     /// Generate the API for interacting with a Substrate runtime.
@@ -58,6 +196,7 @@ impl RuntimeGenerator {
     /// * `type_substitutes` - Provide custom type substitutes.
     /// * `crate_path` - Path to the `subxt` crate.
     /// * `should_gen_docs` - True if the generated API contains the documentation from the metadata.
+    /// * `no_std` - True if `alloc` types should be referred to via `::alloc`, for use in `no_std` crates.
     pub fn generate_runtime_types(
         &self,
         item_mod: syn::ItemMod,
@@ -65,17 +204,25 @@ impl RuntimeGenerator {
         type_substitutes: scale_typegen::TypeSubstitutes,
         crate_path: syn::Path,
         should_gen_docs: bool,
+        no_std: bool,
     ) -> Result<TokenStream2, CodegenError> {
         let item_mod_attrs = item_mod.attrs.clone();
         let item_mod_ir = ir::ItemMod::try_from(item_mod)?;
 
-        let settings =
-            subxt_type_gen_settings(derives, type_substitutes, &crate_path, should_gen_docs);
+        let settings = subxt_type_gen_settings(
+            derives,
+            type_substitutes,
+            &crate_path,
+            should_gen_docs,
+            no_std,
+            self.codec_attributes,
+        );
 
         let type_gen = TypeGenerator::new(self.metadata.types(), &settings);
         let types_mod = type_gen
             .generate_types_mod()?
             .to_token_stream(type_gen.settings());
+        let value_conversions = self.generate_value_conversions(&type_gen, &crate_path)?;
         let mod_ident = &item_mod_ir.ident;
         let rust_items = item_mod_ir.rust_items();
 
@@ -96,6 +243,123 @@ impl RuntimeGenerator {
                 }
 
                 #types_mod
+
+                #value_conversions
+            }
+        })
+    }
+
+    /// Generate the outer `Call`, `Event` and `Error` enums and the types that they need, without
+    /// generating the rest of the Subxt interface (ie the per-pallet calls, storage and so on).
+    ///
+    /// # Arguments
+    ///
+    /// * `item_mod` - The module declaration for which the API is implemented.
+    /// * `derives` - Provide custom derives for the generated types.
+    /// * `type_substitutes` - Provide custom type substitutes.
+    /// * `crate_path` - Path to the `subxt` crate.
+    /// * `should_gen_docs` - True if the generated API contains the documentation from the metadata.
+    /// * `no_std` - True if `alloc` types should be referred to via `::alloc`, for use in `no_std` crates.
+    pub fn generate_root_enums(
+        &self,
+        item_mod: syn::ItemMod,
+        derives: scale_typegen::DerivesRegistry,
+        type_substitutes: scale_typegen::TypeSubstitutes,
+        crate_path: syn::Path,
+        should_gen_docs: bool,
+        no_std: bool,
+    ) -> Result<TokenStream2, CodegenError> {
+        let item_mod_attrs = item_mod.attrs.clone();
+        let item_mod_ir = ir::ItemMod::try_from(item_mod)?;
+
+        let settings = subxt_type_gen_settings(
+            derives,
+            type_substitutes,
+            &crate_path,
+            should_gen_docs,
+            no_std,
+            self.codec_attributes,
+        );
+
+        let type_gen = TypeGenerator::new(self.metadata.types(), &settings);
+        let types_mod = type_gen
+            .generate_types_mod()?
+            .to_token_stream(type_gen.settings());
+        let value_conversions = self.generate_value_conversions(&type_gen, &crate_path)?;
+        let types_mod_ident = type_gen.types_mod_ident();
+
+        // The outer enums are built from every pallet's calls, events and errors,
+        // so we validate against all of the pallets, but none of the runtime APIs.
+        let pallet_names: Vec<_> = self
+            .metadata
+            .pallets()
+            .map(|pallet| pallet.name())
+            .collect();
+        let pallet_names_len = pallet_names.len();
+        let metadata_hash = self
+            .metadata
+            .hasher()
+            .only_these_pallets(&pallet_names)
+            .only_these_runtime_apis::<&str>(&[])
+            .hash();
+
+        let call_path = type_gen
+            .resolve_type_path(self.metadata.outer_enums().call_enum_ty())?
+            .to_token_stream(type_gen.settings());
+        let event_path = type_gen
+            .resolve_type_path(self.metadata.outer_enums().event_enum_ty())?
+            .to_token_stream(type_gen.settings());
+        let error_path = type_gen
+            .resolve_type_path(self.metadata.outer_enums().error_enum_ty())?
+            .to_token_stream(type_gen.settings());
+
+        let mod_ident = &item_mod_ir.ident;
+        let rust_items = item_mod_ir.rust_items();
+
+        Ok(quote! {
+            #( #item_mod_attrs )*
+            #[allow(dead_code, unused_imports, non_camel_case_types)]
+            #[allow(clippy::all)]
+            #[allow(rustdoc::broken_intra_doc_links)]
+            pub mod #mod_ident {
+                // Preserve any Rust items that were previously defined in the adorned module.
+                #( #rust_items ) *
+
+                // Make it easy to access the root items via `root_mod` at different levels
+                // without reaching out of this module.
+                #[allow(unused_imports)]
+                mod root_mod {
+                    pub use super::*;
+                }
+
+                // Identify the pallets composing the static metadata by name.
+                pub static PALLETS: [&str; #pallet_names_len] = [ #(#pallet_names,)* ];
+
+                /// The error type returned when there is a runtime issue.
+                pub type DispatchError = #types_mod_ident::sp_runtime::DispatchError;
+
+                /// The outer event enum.
+                pub type Event = #event_path;
+
+                /// The outer extrinsic enum.
+                pub type Call = #call_path;
+
+                /// The outer error enum represents the DispatchError's Module variant.
+                pub type Error = #error_path;
+
+                /// check whether the metadata provided is aligned with this statically generated code.
+                pub fn is_codegen_valid_for(metadata: &#crate_path::Metadata) -> bool {
+                    let runtime_metadata_hash = metadata
+                        .hasher()
+                        .only_these_pallets(&PALLETS)
+                        .only_these_runtime_apis::<&str>(&[])
+                        .hash();
+                    runtime_metadata_hash == [ #(#metadata_hash,)* ]
+                }
+
+                #types_mod
+
+                #value_conversions
             }
         })
     }
@@ -109,6 +373,7 @@ impl RuntimeGenerator {
     /// * `type_substitutes` - Provide custom type substitutes.
     /// * `crate_path` - Path to the `subxt` crate.
     /// * `should_gen_docs` - True if the generated API contains the documentation from the metadata.
+    /// * `no_std` - True if `alloc` types should be referred to via `::alloc`, for use in `no_std` crates.
     pub fn generate_runtime(
         &self,
         item_mod: syn::ItemMod,
@@ -116,27 +381,33 @@ impl RuntimeGenerator {
         type_substitutes: scale_typegen::TypeSubstitutes,
         crate_path: syn::Path,
         should_gen_docs: bool,
+        no_std: bool,
     ) -> Result<TokenStream2, CodegenError> {
         let item_mod_attrs = item_mod.attrs.clone();
         let item_mod_ir = ir::ItemMod::try_from(item_mod)?;
 
-        let settings =
-            subxt_type_gen_settings(derives, type_substitutes, &crate_path, should_gen_docs);
+        self.renames.validate(&self.metadata)?;
+        self.method_attributes.validate(&self.metadata)?;
+
+        let settings = subxt_type_gen_settings(
+            derives,
+            type_substitutes,
+            &crate_path,
+            should_gen_docs,
+            no_std,
+            self.codec_attributes,
+        );
 
         let type_gen = TypeGenerator::new(self.metadata.types(), &settings);
         let types_mod = type_gen
             .generate_types_mod()?
             .to_token_stream(type_gen.settings());
+        let value_conversions = self.generate_value_conversions(&type_gen, &crate_path)?;
         let types_mod_ident = type_gen.types_mod_ident();
         let pallets_with_mod_names = self
             .metadata
             .pallets()
-            .map(|pallet| {
-                (
-                    pallet,
-                    format_ident!("{}", pallet.name().to_string().to_snake_case()),
-                )
-            })
+            .map(|pallet| (pallet, self.renames.pallet_mod_name(pallet.name())))
             .collect::<Vec<_>>();
 
         // Pallet names and their length are used to create PALLETS array.
@@ -161,11 +432,23 @@ impl RuntimeGenerator {
         let modules = pallets_with_mod_names
             .iter()
             .map(|(pallet, mod_name)| {
-                let calls = calls::generate_calls(&type_gen, pallet, &crate_path)?;
+                let calls = calls::generate_calls(
+                    &type_gen,
+                    pallet,
+                    &self.renames,
+                    &self.method_attributes,
+                    &crate_path,
+                )?;
 
                 let event = events::generate_events(&type_gen, pallet, &crate_path)?;
 
-                let storage_mod = storage::generate_storage(&type_gen, pallet, &crate_path)?;
+                let storage_mod = storage::generate_storage(
+                    &type_gen,
+                    pallet,
+                    &self.method_attributes,
+                    &crate_path,
+                    self.precompute_storage_prefixes,
+                )?;
 
                 let constants_mod = constants::generate_constants(&type_gen, pallet, &crate_path)?;
 
@@ -213,6 +496,7 @@ impl RuntimeGenerator {
             &self.metadata,
             &type_gen,
             types_mod_ident,
+            &self.method_attributes,
             &crate_path,
         )?;
 
@@ -328,6 +612,8 @@ impl RuntimeGenerator {
 
                 #( #modules )*
                 #types_mod
+
+                #value_conversions
             }
         })
     }
@@ -0,0 +1,94 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::error::CodegenError;
+use heck::ToSnakeCase as _;
+use quote::format_ident;
+use std::collections::{HashMap, HashSet};
+use subxt_metadata::Metadata;
+use syn::Ident;
+
+/// Overrides for the identifiers that we generate for pallets and calls. The names
+/// in the metadata are still used when encoding calls and so on.
+#[derive(Debug, Clone, Default)]
+pub struct Renames {
+    pallets: HashMap<String, Ident>,
+    calls: HashMap<(String, String), Ident>,
+}
+
+impl Renames {
+    /// Use the given identifier for the module generated for some pallet.
+    pub fn rename_pallet(&mut self, pallet: String, ident: Ident) {
+        self.pallets.insert(pallet, ident);
+    }
+
+    /// Use the given identifier for the function generated for some call in some pallet.
+    pub fn rename_call(&mut self, pallet: String, call: String, ident: Ident) {
+        self.calls.insert((pallet, call), ident);
+    }
+
+    /// The identifier to use for the module generated for the given pallet.
+    pub fn pallet_mod_name(&self, pallet: &str) -> Ident {
+        self.pallets
+            .get(pallet)
+            .cloned()
+            .unwrap_or_else(|| format_ident!("{}", pallet.to_snake_case()))
+    }
+
+    /// The identifier to use for the function generated for the given call.
+    pub fn call_fn_name(&self, pallet: &str, call: &str) -> Ident {
+        self.calls
+            .get(&(pallet.to_string(), call.to_string()))
+            .cloned()
+            .unwrap_or_else(|| format_ident!("{}", call.to_snake_case()))
+    }
+
+    /// Check that every pallet and call renamed exists in the metadata, and
+    /// that renaming them doesn't lead to any duplicate identifiers.
+    pub fn validate(&self, metadata: &Metadata) -> Result<(), CodegenError> {
+        for pallet in self.pallets.keys() {
+            if metadata.pallet_by_name(pallet).is_none() {
+                return Err(CodegenError::InvalidRename(
+                    format!("pallet {pallet}"),
+                    "it does not exist in the metadata".to_string(),
+                ));
+            }
+        }
+        for (pallet, call) in self.calls.keys() {
+            let call_exists = metadata
+                .pallet_by_name(pallet)
+                .and_then(|p| p.call_variant_by_name(call))
+                .is_some();
+            if !call_exists {
+                return Err(CodegenError::InvalidRename(
+                    format!("call {pallet}.{call}"),
+                    "it does not exist in the metadata".to_string(),
+                ));
+            }
+        }
+
+        let mut pallet_mod_names = HashSet::new();
+        for pallet in metadata.pallets() {
+            let mod_name = self.pallet_mod_name(pallet.name());
+            if !pallet_mod_names.insert(mod_name.to_string()) {
+                return Err(CodegenError::InvalidRename(
+                    format!("pallet {}", pallet.name()),
+                    format!("the identifier `{mod_name}` is used by more than one pallet"),
+                ));
+            }
+
+            let mut call_fn_names = HashSet::new();
+            for call in pallet.call_variants().unwrap_or_default() {
+                let fn_name = self.call_fn_name(pallet.name(), &call.name);
+                if !call_fn_names.insert(fn_name.to_string()) {
+                    return Err(CodegenError::InvalidRename(
+                        format!("call {}.{}", pallet.name(), call.name),
+                        format!("the identifier `{fn_name}` is used by more than one call"),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -14,12 +14,14 @@ use subxt_metadata::{Metadata, RuntimeApiMetadata};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
 
+use super::MethodAttributes;
 use crate::CodegenError;
 
 /// Generates runtime functions for the given API metadata.
 fn generate_runtime_api(
     api: RuntimeApiMetadata,
     type_gen: &TypeGenerator,
+    method_attributes: &MethodAttributes,
     crate_path: &syn::Path,
 ) -> Result<(TokenStream2, TokenStream2), CodegenError> {
     // Trait name must remain as is (upper case) to identify the runtime call.
@@ -133,8 +135,10 @@ fn generate_runtime_api(
                 ))
             };
 
+            let fn_attrs = method_attributes.for_runtime_api_method(trait_name_str, method_name_str);
             let method = quote!(
                 #docs
+                #( #fn_attrs )*
                 pub fn #method_name(&self, #( #fn_params, )* ) -> #crate_path::runtime_api::payload::StaticPayload<types::#struct_name, types::#method_name::output::Output> {
                     #crate_path::runtime_api::payload::StaticPayload::new_static(
                         #trait_name_str,
@@ -190,11 +194,12 @@ pub fn generate_runtime_apis(
     metadata: &Metadata,
     type_gen: &TypeGenerator,
     types_mod_ident: &syn::Ident,
+    method_attributes: &MethodAttributes,
     crate_path: &syn::Path,
 ) -> Result<TokenStream2, CodegenError> {
     let runtime_fns: Vec<_> = metadata
         .runtime_api_traits()
-        .map(|api| generate_runtime_api(api, type_gen, crate_path))
+        .map(|api| generate_runtime_api(api, type_gen, method_attributes, crate_path))
         .collect::<Result<_, _>>()?;
 
     let runtime_apis_def = runtime_fns.iter().map(|(apis, _)| apis);
@@ -2,16 +2,17 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
-use heck::{ToSnakeCase as _, ToUpperCamelCase};
+use heck::{ToShoutySnakeCase as _, ToSnakeCase as _, ToUpperCamelCase};
 use proc_macro2::{Ident, TokenStream as TokenStream2, TokenStream};
 use quote::{format_ident, quote};
 use scale_info::TypeDef;
 use scale_typegen::{typegen::type_path::TypePath, TypeGenerator};
 use subxt_metadata::{
-    PalletMetadata, StorageEntryMetadata, StorageEntryModifier, StorageEntryType, StorageHasher,
+    twox_128, PalletMetadata, StorageEntryMetadata, StorageEntryModifier, StorageEntryType,
+    StorageHasher,
 };
 
-use super::CodegenError;
+use super::{CodegenError, MethodAttributes};
 
 use scale_typegen::typegen::ir::ToTokensWithSettings;
 
@@ -22,11 +23,15 @@ use scale_typegen::typegen::ir::ToTokensWithSettings;
 ///
 /// - `type_gen` - [`scale_typegen::TypeGenerator`] that contains settings and all types from the runtime metadata.
 /// - `pallet` - Pallet metadata from which the storage items are generated.
+/// - `method_attributes` - Additional attributes to place on the generated storage functions.
 /// - `crate_path` - The crate path under which the `subxt-core` crate is located, e.g. `::subxt::ext::subxt_core` when using subxt as a dependency.
+/// - `precompute_prefixes` - Whether to also generate a `const` holding the storage prefix of each entry.
 pub fn generate_storage(
     type_gen: &TypeGenerator,
     pallet: &PalletMetadata,
+    method_attributes: &MethodAttributes,
     crate_path: &syn::Path,
+    precompute_prefixes: bool,
 ) -> Result<TokenStream2, CodegenError> {
     let Some(storage) = pallet.storage() else {
         return Ok(quote!());
@@ -35,10 +40,22 @@ pub fn generate_storage(
     let (storage_fns, alias_modules): (Vec<TokenStream2>, Vec<TokenStream2>) = storage
         .entries()
         .iter()
-        .map(|entry| generate_storage_entry_fns(type_gen, pallet, entry, crate_path))
+        .map(|entry| {
+            let fn_attrs = method_attributes.for_storage_entry(pallet.name(), entry.name());
+            generate_storage_entry_fns(type_gen, pallet, entry, fn_attrs, crate_path)
+        })
         .collect::<Result<Vec<_>, CodegenError>>()?
         .into_iter()
         .unzip();
+    let storage_prefixes: Vec<TokenStream2> = if precompute_prefixes {
+        storage
+            .entries()
+            .iter()
+            .map(|entry| generate_storage_prefix_const(type_gen, pallet, entry))
+            .collect()
+    } else {
+        Vec::new()
+    };
     let types_mod_ident = type_gen.types_mod_ident();
 
     Ok(quote! {
@@ -54,6 +71,8 @@ pub fn generate_storage(
             pub struct StorageApi;
 
             impl StorageApi {
+                #( #storage_prefixes )*
+
                 #( #storage_fns )*
             }
         }
@@ -65,6 +84,7 @@ fn generate_storage_entry_fns(
     type_gen: &TypeGenerator,
     pallet: &PalletMetadata,
     storage_entry: &StorageEntryMetadata,
+    fn_attrs: &[syn::Attribute],
     crate_path: &syn::Path,
 ) -> Result<(TokenStream2, TokenStream2), CodegenError> {
     let snake_case_name = storage_entry.name().to_snake_case();
@@ -255,6 +275,7 @@ fn generate_storage_entry_fns(
 
         quote!(
             #docs
+            #( #fn_attrs )*
             pub fn #fn_name(
                 &self,
                 #(#key_args,)*
@@ -300,6 +321,43 @@ fn generate_storage_entry_fns(
     ))
 }
 
+/// Returns a `const` containing the storage prefix of the entry, ie `twox_128(pallet_name) ++ twox_128(entry_name)`.
+/// This is the same prefix that is computed at runtime when building the storage address.
+fn generate_storage_prefix_const(
+    type_gen: &TypeGenerator,
+    pallet: &PalletMetadata,
+    storage_entry: &StorageEntryMetadata,
+) -> TokenStream2 {
+    let const_name = format_ident!(
+        "{}_STORAGE_PREFIX",
+        storage_entry.name().to_shouty_snake_case()
+    );
+    let prefix = storage_prefix(pallet.name(), storage_entry.name());
+
+    let docs = format!(
+        "The storage prefix of `{}::{}`, which all of its storage keys begin with.",
+        pallet.name(),
+        storage_entry.name()
+    );
+    let docs = type_gen
+        .settings()
+        .should_gen_docs
+        .then_some(quote! { #[doc = #docs] })
+        .unwrap_or_default();
+
+    quote! {
+        #docs
+        pub const #const_name: [::core::primitive::u8; 32] = [#(#prefix,)*];
+    }
+}
+
+fn storage_prefix(pallet_name: &str, entry_name: &str) -> [u8; 32] {
+    let mut prefix = [0u8; 32];
+    prefix[..16].copy_from_slice(&twox_128(pallet_name.as_bytes()));
+    prefix[16..].copy_from_slice(&twox_128(entry_name.as_bytes()));
+    prefix
+}
+
 fn primitive_type_alias(
     type_path: &TypePath,
     settings: &scale_typegen::TypeGeneratorSettings,
@@ -419,6 +477,7 @@ mod tests {
                 Default::default(),
                 syn::parse_str("::subxt_path").unwrap(),
                 false,
+                false,
             )
             .expect("should be able to generate runtime");
         let generated_str = generated.to_string();
@@ -451,4 +510,35 @@ mod tests {
             assert!(generated_str.contains(&expected_alias_module.to_string()));
         }
     }
+
+    #[test]
+    fn precomputed_storage_prefixes() {
+        let metadata = metadata_with_storage_entries([("account", meta_type::<u32>())]);
+
+        let generate = |precompute_prefixes: bool| {
+            RuntimeGenerator::new(metadata.clone())
+                .with_precomputed_storage_prefixes(precompute_prefixes)
+                .generate_runtime(
+                    syn::parse_quote!(
+                        pub mod api {}
+                    ),
+                    Default::default(),
+                    Default::default(),
+                    syn::parse_str("::subxt_path").unwrap(),
+                    false,
+                    false,
+                )
+                .expect("should be able to generate runtime")
+                .to_string()
+        };
+
+        let mut expected_prefix = sp_crypto_hashing::twox_128(b"Pallet1").to_vec();
+        expected_prefix.extend(sp_crypto_hashing::twox_128(b"account"));
+        let expected_const = quote!(
+            pub const ACCOUNT_STORAGE_PREFIX: [::core::primitive::u8; 32] = [#(#expected_prefix,)*];
+        );
+
+        assert!(generate(true).contains(&expected_const.to_string()));
+        assert!(!generate(false).contains("ACCOUNT_STORAGE_PREFIX"));
+    }
 }
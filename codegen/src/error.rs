@@ -54,6 +54,22 @@ pub enum CodegenError {
         /// Number of hashers
         hasher_count: usize,
     },
+    /// The same type was substituted more than once, with different types.
+    #[error("Type {path} cannot be substituted with {second}, because it has already been substituted with {first}")]
+    ConflictingTypeSubstitute {
+        /// The path of the type being substituted.
+        path: String,
+        /// The type it was first substituted with.
+        first: String,
+        /// The conflicting type it was then substituted with.
+        second: String,
+    },
+    /// A pallet or call could not be given the identifier requested for it.
+    #[error("Cannot rename {0}: {1}")]
+    InvalidRename(String, String),
+    /// Attributes were given for a call, storage entry or runtime API method that doesn't exist.
+    #[error("Cannot add attributes to {0}: it does not exist in the metadata")]
+    InvalidMethodAttributes(String),
     /// Cannot generate types.
     #[error("Type Generation failed: {0}")]
     TypeGeneration(#[from] TypegenError),
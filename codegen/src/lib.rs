@@ -12,6 +12,7 @@
 mod api;
 pub mod error;
 mod ir;
+mod report;
 
 // These should probably be in a separate crate; they are used by the
 // macro and CLI tool, so they only live here because this is a common
@@ -23,7 +24,7 @@ pub mod fetch_metadata;
 #[cfg(feature = "web")]
 use getrandom as _;
 
-use api::RuntimeGenerator;
+use api::{MethodAttributes, Renames, RuntimeGenerator};
 use proc_macro2::TokenStream as TokenStream2;
 use scale_typegen::typegen::settings::AllocCratePath;
 use scale_typegen::{
@@ -35,6 +36,7 @@ use syn::parse_quote;
 
 // Part of the public interface, so expose:
 pub use error::CodegenError;
+pub use report::TypeMappingReport;
 pub use subxt_metadata::Metadata;
 pub use syn;
 
@@ -62,13 +64,21 @@ pub use syn;
 pub struct CodegenBuilder {
     crate_path: syn::Path,
     use_default_derives: bool,
+    use_codec_derives: bool,
     use_default_substitutions: bool,
     generate_docs: bool,
     runtime_types_only: bool,
+    root_enums_only: bool,
+    no_std: bool,
+    value_conversions: bool,
+    precompute_storage_prefixes: bool,
     item_mod: syn::ItemMod,
     extra_global_derives: Vec<syn::Path>,
     extra_global_attributes: Vec<syn::Attribute>,
-    type_substitutes: HashMap<syn::Path, syn::Path>,
+    type_substitutes: Vec<(syn::Path, syn::Path)>,
+    conflicting_type_substitute: Option<(syn::Path, syn::Path, syn::Path)>,
+    renames: Renames,
+    method_attributes: MethodAttributes,
     derives_for_type: HashMap<syn::TypePath, Vec<syn::Path>>,
     attributes_for_type: HashMap<syn::TypePath, Vec<syn::Attribute>>,
     derives_for_type_recursive: HashMap<syn::TypePath, Vec<syn::Path>>,
@@ -80,15 +90,23 @@ impl Default for CodegenBuilder {
         CodegenBuilder {
             crate_path: syn::parse_quote!(::subxt::ext::subxt_core),
             use_default_derives: true,
+            use_codec_derives: true,
             use_default_substitutions: true,
             generate_docs: true,
             runtime_types_only: false,
+            root_enums_only: false,
+            no_std: false,
+            value_conversions: false,
+            precompute_storage_prefixes: false,
             item_mod: syn::parse_quote!(
                 pub mod api {}
             ),
             extra_global_derives: Vec::new(),
             extra_global_attributes: Vec::new(),
-            type_substitutes: HashMap::new(),
+            type_substitutes: Vec::new(),
+            conflicting_type_substitute: None,
+            renames: Renames::default(),
+            method_attributes: MethodAttributes::default(),
             derives_for_type: HashMap::new(),
             attributes_for_type: HashMap::new(),
             derives_for_type_recursive: HashMap::new(),
@@ -113,6 +131,22 @@ impl CodegenBuilder {
         self.use_default_derives = false;
     }
 
+    /// Omit the `parity-scale-codec` `Encode` and `Decode` derives (and the `#[codec(..)]`
+    /// attributes that go with them) from the generated types. This is useful for consumers
+    /// which only want plain data types to display values which were decoded elsewhere, and
+    /// would rather not depend on `parity-scale-codec`. Any `Compact` types are referred to via
+    /// the re-export in `subxt_core::utils`, and `CompactAs` is not derived.
+    ///
+    /// # Warning
+    ///
+    /// The generated types can **no longer be SCALE encoded or decoded** via `parity-scale-codec`,
+    /// and so can't be used to build transactions, query storage or decode anything from a node
+    /// in the usual way. Much of the Subxt specific interface relies on these derives, so this is
+    /// best paired with [`CodegenBuilder::runtime_types_only()`]; expect compile errors otherwise.
+    pub fn without_codec_derives(&mut self) {
+        self.use_codec_derives = false;
+    }
+
     /// Disable the default type substitutions that are applied to the generated
     /// code.
     ///
@@ -137,6 +171,44 @@ impl CodegenBuilder {
         self.runtime_types_only = true;
     }
 
+    /// Only generate the outer `Call`, `Event` and `Error` enums (and the types that they rely on),
+    /// along with an `is_codegen_valid_for` function to check them against some metadata. This is
+    /// useful if you only need to decode blocks, and don't need the rest of the Subxt specific
+    /// interface for building transactions, accessing storage and so on.
+    ///
+    /// If [`CodegenBuilder::runtime_types_only()`] is also set, that takes precedence.
+    pub fn root_enums_only(&mut self) {
+        self.root_enums_only = true;
+    }
+
+    /// Generate code that can be used from a `#![no_std]` crate. When this is enabled, types
+    /// like `Vec`, `String` and `Box` are referred to via `::alloc`, so the crate that the code
+    /// is generated into must declare `extern crate alloc`. The generated code only relies on
+    /// `subxt_core`, which doesn't need `std`; if you depend on `subxt_core` directly rather than
+    /// via `subxt`, use [`CodegenBuilder::set_subxt_crate_path()`] to point at it.
+    pub fn no_std(&mut self, no_std: bool) {
+        self.no_std = no_std;
+    }
+
+    /// Implement `ValueConversion` for the generated runtime types, so that they can be converted
+    /// to and from dynamic `scale_value::Value`s via their `EncodeAsType` and `DecodeAsType` impls.
+    /// Each type records the ID and hash of the metadata type it was generated from, and so
+    /// conversions will fail if given metadata that is incompatible with the generated code.
+    ///
+    /// Generic types, and any types which are substituted for others, don't get an implementation.
+    pub fn generate_value_conversions(&mut self) {
+        self.value_conversions = true;
+    }
+
+    /// Generate a `const [u8; 32]` for each storage entry, holding its storage prefix (ie
+    /// `twox_128(pallet_name) ++ twox_128(entry_name)`). The prefix is fixed for a given
+    /// metadata, so this allows raw storage keys to be built without hashing at runtime.
+    /// The constants are named after the entry, eg `ACCOUNT_STORAGE_PREFIX`, and live on the
+    /// `StorageApi` of each pallet.
+    pub fn precompute_storage_prefixes(&mut self) {
+        self.precompute_storage_prefixes = true;
+    }
+
     /// Set the additional derives that will be applied to all types. By default,
     /// a set of derives required for Subxt are automatically added for all types.
     ///
@@ -211,8 +283,126 @@ impl CodegenBuilder {
     ///
     /// The substituted type will need to implement the relevant traits to be compatible with the
     /// original, and it will need to SCALE encode and SCALE decode in a compatible way.
+    ///
+    /// Substituting the same type path more than once with different types is a mistake, and
+    /// will lead to [`CodegenError::ConflictingTypeSubstitute`] being returned when generating
+    /// the interface. Substituting a type that is substituted by default is fine, and overrides
+    /// the default substitution.
     pub fn set_type_substitute(&mut self, ty: syn::Path, with: syn::Path) {
-        self.type_substitutes.insert(ty, with);
+        match self.type_substitutes.iter().find(|(from, _)| *from == ty) {
+            Some((_, existing)) if *existing != with => {
+                if self.conflicting_type_substitute.is_none() {
+                    self.conflicting_type_substitute = Some((ty, existing.clone(), with));
+                }
+            }
+            Some(_) => {}
+            None => self.type_substitutes.push((ty, with)),
+        }
+    }
+
+    /// List every type substitution that will be applied when generating the interface, in the
+    /// form `(path, substitute)`. This includes the default substitutions (unless they have been
+    /// disabled via [`CodegenBuilder::disable_default_substitutes()`]), taking into account any
+    /// that have been overridden via [`CodegenBuilder::set_type_substitute()`].
+    pub fn type_substitutes(&self) -> Vec<(syn::Path, syn::Path)> {
+        let mut substitutes = if self.use_default_substitutions {
+            default_substitute_paths(&self.crate_path)
+        } else {
+            Vec::new()
+        };
+
+        for (from, with) in &self.type_substitutes {
+            match substitutes.iter_mut().find(|(f, _)| f == from) {
+                Some((_, existing)) => *existing = with.clone(),
+                None => substitutes.push((from.clone(), with.clone())),
+            }
+        }
+        substitutes
+    }
+
+    /// By default, the module generated for each pallet is named after the pallet, converted to
+    /// snake case. Use this to provide a different identifier for the module generated for the
+    /// pallet with the given name (for instance, to avoid an awkward or colliding identifier). The
+    /// name of the pallet in the metadata is still used when encoding calls, storage keys and so on.
+    ///
+    /// Generating the interface will fail if the pallet doesn't exist, or if the new identifier
+    /// collides with the one generated for another pallet.
+    pub fn rename_pallet(&mut self, pallet: impl Into<String>, new_ident: syn::Ident) {
+        self.renames.rename_pallet(pallet.into(), new_ident);
+    }
+
+    /// By default, the function generated to construct each call is named after the call, converted
+    /// to snake case. Use this to provide a different identifier for the function generated for the
+    /// given call in the given pallet. The name of the call in the metadata is still used when encoding it.
+    ///
+    /// Generating the interface will fail if the call doesn't exist, or if the new identifier
+    /// collides with the one generated for another call in the same pallet.
+    pub fn rename_call(
+        &mut self,
+        pallet: impl Into<String>,
+        call: impl Into<String>,
+        new_ident: syn::Ident,
+    ) {
+        self.renames
+            .rename_call(pallet.into(), call.into(), new_ident);
+    }
+
+    /// Add attributes (for instance `#[tracing::instrument]`) to the function generated to
+    /// construct the given call in the given pallet.
+    ///
+    /// Generating the interface will fail if the call doesn't exist.
+    ///
+    /// # Warning
+    ///
+    /// Invalid attributes can very easily lead to compile errors in the generated code.
+    pub fn add_attributes_for_call(
+        &mut self,
+        pallet: impl Into<String>,
+        call: impl Into<String>,
+        attributes: impl IntoIterator<Item = syn::Attribute>,
+    ) {
+        self.method_attributes
+            .add_for_call(pallet.into(), call.into(), attributes);
+    }
+
+    /// Add attributes to each of the functions generated to build addresses for the given
+    /// storage entry in the given pallet (ie both the function to fetch a value and any
+    /// functions to iterate over values).
+    ///
+    /// Generating the interface will fail if the storage entry doesn't exist.
+    ///
+    /// # Warning
+    ///
+    /// Invalid attributes can very easily lead to compile errors in the generated code.
+    pub fn add_attributes_for_storage_entry(
+        &mut self,
+        pallet: impl Into<String>,
+        entry: impl Into<String>,
+        attributes: impl IntoIterator<Item = syn::Attribute>,
+    ) {
+        self.method_attributes
+            .add_for_storage_entry(pallet.into(), entry.into(), attributes);
+    }
+
+    /// Add attributes to the function generated to call the given method
+    /// of the given runtime API trait.
+    ///
+    /// Generating the interface will fail if the runtime API method doesn't exist.
+    ///
+    /// # Warning
+    ///
+    /// Invalid attributes can very easily lead to compile errors in the generated code.
+    pub fn add_attributes_for_runtime_api_method(
+        &mut self,
+        runtime_api: impl Into<String>,
+        method: impl Into<String>,
+        attributes: impl IntoIterator<Item = syn::Attribute>,
+    ) {
+        self.method_attributes.add_for_runtime_api_method(
+            runtime_api.into(),
+            method.into(),
+            attributes,
+        );
     }
 
     /// By default, all of the code is generated inside a module `pub mod api {}`. We decorate
@@ -227,9 +417,9 @@ impl CodegenBuilder {
     ///
     /// # Panics
     ///
-    /// Panics if the path provided is not an absolute path.
+    /// Panics if the path provided is not an absolute path (see [`is_absolute_path()`]).
     pub fn set_subxt_crate_path(&mut self, crate_path: syn::Path) {
-        if absolute_path(crate_path.clone()).is_err() {
+        if !is_absolute_path(&crate_path) {
             // Throw an error here, because otherwise we end up with a harder to comprehend error when
             // substitute types don't begin with an absolute path.
             panic!("The provided crate path must be an absolute path, ie prefixed with '::' or 'crate'");
@@ -241,10 +431,41 @@ impl CodegenBuilder {
     /// If the `subxt` crate is not available as a top level dependency, use `generate` and provide
     /// a valid path to the `subxt¦ crate.
     pub fn generate(self, metadata: Metadata) -> Result<TokenStream2, CodegenError> {
+        self.generate_inner(metadata, false)
+            .map(|(token_stream, _)| token_stream)
+    }
+
+    /// Generate an interface as with [`CodegenBuilder::generate()`], and alongside it, return a
+    /// [`TypeMappingReport`] describing which Rust type each type ID in the metadata was generated
+    /// as (or substituted with). This is useful when debugging surprising codegen output.
+    pub fn generate_with_type_mapping_report(
+        self,
+        metadata: Metadata,
+    ) -> Result<(TokenStream2, TypeMappingReport), CodegenError> {
+        let (token_stream, report) = self.generate_inner(metadata, true)?;
+        Ok((
+            token_stream,
+            report.expect("report is generated when requested; qed"),
+        ))
+    }
+
+    fn generate_inner(
+        self,
+        metadata: Metadata,
+        with_type_mapping_report: bool,
+    ) -> Result<(TokenStream2, Option<TypeMappingReport>), CodegenError> {
+        if let Some((path, first, second)) = self.conflicting_type_substitute {
+            return Err(CodegenError::ConflictingTypeSubstitute {
+                path: quote::quote!(#path).to_string(),
+                first: quote::quote!(#first).to_string(),
+                second: quote::quote!(#second).to_string(),
+            });
+        }
+
         let crate_path = self.crate_path;
 
         let mut derives_registry: DerivesRegistry = if self.use_default_derives {
-            default_derives(&crate_path)
+            default_derives(&crate_path, self.use_codec_derives)
         } else {
             DerivesRegistry::new()
         };
@@ -265,31 +486,62 @@ impl CodegenBuilder {
             derives_registry.add_attributes_for(ty, attributes, true);
         }
 
-        let mut type_substitutes: TypeSubstitutes = if self.use_default_substitutions {
-            default_substitutes(&crate_path)
-        } else {
-            TypeSubstitutes::new()
+        let build_type_substitutes = || -> Result<TypeSubstitutes, CodegenError> {
+            let mut type_substitutes: TypeSubstitutes = if self.use_default_substitutions {
+                default_substitutes(&crate_path)
+            } else {
+                TypeSubstitutes::new()
+            };
+
+            for (from, with) in &self.type_substitutes {
+                let abs_path = absolute_path(with.clone()).map_err(TypegenError::from)?;
+                type_substitutes
+                    .insert(from.clone(), abs_path)
+                    .map_err(TypegenError::from)?;
+            }
+            Ok(type_substitutes)
         };
 
-        for (from, with) in self.type_substitutes {
-            let abs_path = absolute_path(with).map_err(TypegenError::from)?;
-            type_substitutes
-                .insert(from, abs_path)
-                .map_err(TypegenError::from)?;
-        }
+        let type_substitutes = build_type_substitutes()?;
+        let generator = RuntimeGenerator::new(metadata)
+            .with_renames(self.renames)
+            .with_method_attributes(self.method_attributes)
+            .with_codec_attributes(self.use_codec_derives)
+            .with_value_conversions(self.value_conversions)
+            .with_precomputed_storage_prefixes(self.precompute_storage_prefixes);
+
+        let report = if with_type_mapping_report {
+            Some(generator.type_mapping_report(
+                build_type_substitutes()?,
+                &crate_path,
+                self.no_std,
+            )?)
+        } else {
+            None
+        };
 
         let item_mod = self.item_mod;
-        let generator = RuntimeGenerator::new(metadata);
         let should_gen_docs = self.generate_docs;
+        let no_std = self.no_std;
 
-        if self.runtime_types_only {
+        let token_stream = if self.runtime_types_only {
             generator.generate_runtime_types(
                 item_mod,
                 derives_registry,
                 type_substitutes,
                 crate_path,
                 should_gen_docs,
-            )
+                no_std,
+            )?
+        } else if self.root_enums_only {
+            generator.generate_root_enums(
+                item_mod,
+                derives_registry,
+                type_substitutes,
+                crate_path,
+                should_gen_docs,
+                no_std,
+            )?
         } else {
             generator.generate_runtime(
                 item_mod,
@@ -297,18 +549,27 @@ impl CodegenBuilder {
                 type_substitutes,
                 crate_path,
                 should_gen_docs,
-            )
-        }
+                no_std,
+            )?
+        };
+
+        Ok((token_stream, report))
     }
 }
 
+/// Is the given path absolute, ie prefixed with `::` or `crate`? Paths given to
+/// [`CodegenBuilder::set_subxt_crate_path()`] must be.
+pub fn is_absolute_path(path: &syn::Path) -> bool {
+    absolute_path(path.clone()).is_ok()
+}
+
 /// The default [`scale_typegen::TypeGeneratorSettings`], subxt is using for generating code.
 /// Useful for emulating subxt's code generation settings from e.g. subxt-explorer.
 pub fn default_subxt_type_gen_settings() -> TypeGeneratorSettings {
     let crate_path: syn::Path = parse_quote!(::subxt::ext::subxt_core);
-    let derives = default_derives(&crate_path);
+    let derives = default_derives(&crate_path, true);
     let substitutes = default_substitutes(&crate_path);
-    subxt_type_gen_settings(derives, substitutes, &crate_path, true)
+    subxt_type_gen_settings(derives, substitutes, &crate_path, true, false, true)
 }
 
 fn subxt_type_gen_settings(
@@ -316,39 +577,61 @@ fn subxt_type_gen_settings(
     substitutes: scale_typegen::TypeSubstitutes,
     crate_path: &syn::Path,
     should_gen_docs: bool,
+    no_std: bool,
+    insert_codec_attributes: bool,
 ) -> TypeGeneratorSettings {
+    let alloc_crate_path: syn::Path = if no_std {
+        parse_quote!(::alloc)
+    } else {
+        parse_quote!(#crate_path::alloc)
+    };
+
+    // Without the codec derives, `CompactAs` can't be derived (it needs the `#[codec(crate = ..)]`
+    // attribute), and `Compact` is referred to via the re-export in `utils` instead.
+    let (compact_type_path, compact_as_type_path) = if insert_codec_attributes {
+        (
+            parse_quote!(#crate_path::ext::codec::Compact),
+            Some(parse_quote!(#crate_path::ext::codec::CompactAs)),
+        )
+    } else {
+        (parse_quote!(#crate_path::utils::Compact), None)
+    };
+
     TypeGeneratorSettings {
         types_mod_ident: parse_quote!(runtime_types),
         should_gen_docs,
         derives,
         substitutes,
         decoded_bits_type_path: Some(parse_quote!(#crate_path::utils::bits::DecodedBits)),
-        compact_as_type_path: Some(parse_quote!(#crate_path::ext::codec::CompactAs)),
-        compact_type_path: Some(parse_quote!(#crate_path::ext::codec::Compact)),
-        insert_codec_attributes: true,
-        alloc_crate_path: AllocCratePath::Custom(parse_quote!(#crate_path::alloc)),
+        compact_as_type_path,
+        compact_type_path: Some(compact_type_path),
+        insert_codec_attributes,
+        alloc_crate_path: AllocCratePath::Custom(alloc_crate_path),
     }
 }
 
-fn default_derives(crate_path: &syn::Path) -> DerivesRegistry {
+fn default_derives(crate_path: &syn::Path, with_codec: bool) -> DerivesRegistry {
     let encode_crate_path = quote::quote! { #crate_path::ext::scale_encode }.to_string();
     let decode_crate_path = quote::quote! { #crate_path::ext::scale_decode }.to_string();
 
-    let derives: [syn::Path; 5] = [
+    let mut derives: Vec<syn::Path> = vec![
         parse_quote!(#crate_path::ext::scale_encode::EncodeAsType),
         parse_quote!(#crate_path::ext::scale_decode::DecodeAsType),
-        parse_quote!(#crate_path::ext::codec::Encode),
-        parse_quote!(#crate_path::ext::codec::Decode),
-        parse_quote!(Debug),
     ];
-
-    let attributes: [syn::Attribute; 4] = [
+    let mut attributes: Vec<syn::Attribute> = vec![
         parse_quote!(#[encode_as_type(crate_path = #encode_crate_path)]),
         parse_quote!(#[decode_as_type(crate_path = #decode_crate_path)]),
-        parse_quote!(#[codec(crate = #crate_path::ext::codec)]),
-        parse_quote!(#[codec(dumb_trait_bound)]),
     ];
 
+    if with_codec {
+        derives.push(parse_quote!(#crate_path::ext::codec::Encode));
+        derives.push(parse_quote!(#crate_path::ext::codec::Decode));
+        attributes.push(parse_quote!(#[codec(crate = #crate_path::ext::codec)]));
+        attributes.push(parse_quote!(#[codec(dumb_trait_bound)]));
+    }
+
+    derives.push(parse_quote!(Debug));
+
     let mut derives_registry = DerivesRegistry::new();
     derives_registry.add_derives_for_all(derives);
     derives_registry.add_attributes_for_all(attributes);
@@ -358,7 +641,22 @@ fn default_derives(crate_path: &syn::Path) -> DerivesRegistry {
 fn default_substitutes(crate_path: &syn::Path) -> TypeSubstitutes {
     let mut type_substitutes = TypeSubstitutes::new();
 
-    let defaults: [(syn::Path, syn::Path); 13] = [
+    let defaults = default_substitute_paths(crate_path)
+        .into_iter()
+        .map(|(from, to)| {
+            (
+                from,
+                absolute_path(to).expect("default substitutes are absolute paths; qed"),
+            )
+        });
+    type_substitutes
+        .extend(defaults)
+        .expect("default substitutes can always be parsed; qed");
+    type_substitutes
+}
+
+fn default_substitute_paths(crate_path: &syn::Path) -> Vec<(syn::Path, syn::Path)> {
+    vec![
         (
             parse_quote!(bitvec::order::Lsb0),
             parse_quote!(#crate_path::utils::bits::Lsb0),
@@ -391,6 +689,10 @@ fn default_substitutes(crate_path: &syn::Path) -> TypeSubstitutes {
             parse_quote!(primitive_types::H512),
             parse_quote!(#crate_path::utils::H512),
         ),
+        (
+            parse_quote!(sp_weights::weight_v2::Weight),
+            parse_quote!(#crate_path::utils::Weight),
+        ),
         (
             parse_quote!(frame_support::traits::misc::WrapperKeepOpaque),
             parse_quote!(#crate_path::utils::WrapperKeepOpaque),
@@ -421,16 +723,176 @@ fn default_substitutes(crate_path: &syn::Path) -> TypeSubstitutes {
             parse_quote!(sp_runtime::generic::unchecked_extrinsic::UncheckedExtrinsic),
             parse_quote!(#crate_path::utils::UncheckedExtrinsic),
         ),
-    ];
+    ]
+}
 
-    let defaults = defaults.into_iter().map(|(from, to)| {
-        (
-            from,
-            absolute_path(to).expect("default substitutes above are absolute paths; qed"),
-        )
-    });
-    type_substitutes
-        .extend(defaults)
-        .expect("default substitutes can always be parsed; qed");
-    type_substitutes
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codec::Decode;
+
+    fn metadata() -> Metadata {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../artifacts/polkadot_metadata_small.scale"
+        );
+        let bytes = std::fs::read(path).unwrap();
+        Metadata::decode(&mut &*bytes).unwrap()
+    }
+
+    #[test]
+    fn conflicting_type_substitutes_error() {
+        let mut codegen = CodegenBuilder::new();
+        codegen.set_type_substitute(
+            parse_quote!(sp_runtime::multiaddress::MultiAddress),
+            parse_quote!(crate::Foo),
+        );
+        // Substituting the same thing again is fine:
+        codegen.set_type_substitute(
+            parse_quote!(sp_runtime::multiaddress::MultiAddress),
+            parse_quote!(crate::Foo),
+        );
+        codegen.set_type_substitute(
+            parse_quote!(sp_runtime::multiaddress::MultiAddress),
+            parse_quote!(crate::Bar),
+        );
+
+        let err = codegen.generate(metadata()).unwrap_err();
+        assert!(matches!(
+            err,
+            CodegenError::ConflictingTypeSubstitute { path, first, second }
+                if path.contains("MultiAddress") && first.contains("Foo") && second.contains("Bar")
+        ));
+    }
+
+    #[test]
+    fn type_substitutes_lists_defaults_and_overrides() {
+        let mut codegen = CodegenBuilder::new();
+        let substitutes = codegen.type_substitutes();
+        let account_id32: syn::Path = parse_quote!(sp_core::crypto::AccountId32);
+        let substitute_for = |substitutes: &[(syn::Path, syn::Path)], path: &syn::Path| {
+            substitutes
+                .iter()
+                .find(|(from, _)| from == path)
+                .map(|(_, to)| to.clone())
+        };
+
+        let num_defaults = default_substitute_paths(&codegen.crate_path).len();
+        assert_eq!(substitutes.len(), num_defaults);
+        assert_eq!(
+            substitute_for(&substitutes, &account_id32),
+            Some(parse_quote!(::subxt::ext::subxt_core::utils::AccountId32))
+        );
+
+        // Overriding a default replaces it, and new substitutes are added to the end:
+        codegen.set_type_substitute(account_id32.clone(), parse_quote!(crate::MyAccountId));
+        codegen.set_type_substitute(parse_quote!(foo::Bar), parse_quote!(crate::Bar));
+        let substitutes = codegen.type_substitutes();
+
+        assert_eq!(substitutes.len(), num_defaults + 1);
+        assert_eq!(
+            substitute_for(&substitutes, &account_id32),
+            Some(parse_quote!(crate::MyAccountId))
+        );
+        assert_eq!(
+            substitutes.last(),
+            Some(&(parse_quote!(foo::Bar), parse_quote!(crate::Bar)))
+        );
+
+        // Without defaults, only our own substitutes are listed:
+        codegen.disable_default_substitutes();
+        assert_eq!(codegen.type_substitutes().len(), 2);
+    }
+
+    #[test]
+    fn pallets_and_calls_can_be_renamed() {
+        let mut codegen = CodegenBuilder::new();
+        codegen.rename_pallet("Balances", parse_quote!(money));
+        codegen.rename_call("System", "remark", parse_quote!(note));
+        let code = codegen.generate(metadata()).unwrap().to_string();
+
+        assert!(code.contains("pub mod money"));
+        assert!(!code.contains("pub mod balances"));
+        assert!(code.contains("pub fn note"));
+        assert!(!code.contains("pub fn remark ("));
+        // The metadata names are still used for encoding:
+        assert!(code.contains("\"Balances\""));
+        assert!(code.contains("\"remark\""));
+    }
+
+    #[test]
+    fn renames_that_collide_error() {
+        let mut codegen = CodegenBuilder::new();
+        codegen.rename_pallet("Balances", parse_quote!(system));
+        let err = codegen.generate(metadata()).unwrap_err();
+        assert!(matches!(err, CodegenError::InvalidRename(..)));
+
+        let mut codegen = CodegenBuilder::new();
+        codegen.rename_pallet("NotAPallet", parse_quote!(foo));
+        let err = codegen.generate(metadata()).unwrap_err();
+        assert!(matches!(err, CodegenError::InvalidRename(..)));
+    }
+
+    #[test]
+    fn attributes_can_be_added_to_methods() {
+        let mut codegen = CodegenBuilder::new();
+        codegen.add_attributes_for_call("System", "remark", [parse_quote!(#[inline])]);
+        codegen.add_attributes_for_storage_entry("System", "Account", [parse_quote!(#[must_use])]);
+        codegen.add_attributes_for_runtime_api_method("Core", "version", [parse_quote!(#[cold])]);
+        let code = codegen
+            .generate(metadata())
+            .unwrap()
+            .to_string()
+            .replace(' ', "");
+
+        assert!(code.contains("#[inline]pubfnremark("));
+        assert!(code.contains("#[must_use]pubfnaccount("));
+        assert!(code.contains("#[must_use]pubfnaccount_iter("));
+        assert!(code.contains("#[cold]pubfnversion("));
+        // Other methods are left alone:
+        assert!(!code.contains("#[inline]pubfnremark_with_event("));
+    }
+
+    #[test]
+    fn attributes_for_unknown_methods_error() {
+        let mut codegen = CodegenBuilder::new();
+        codegen.add_attributes_for_call("System", "not_a_call", [parse_quote!(#[inline])]);
+        let err = codegen.generate(metadata()).unwrap_err();
+        assert!(matches!(err, CodegenError::InvalidMethodAttributes(..)));
+    }
+
+    #[test]
+    fn root_enums_only_omits_pallet_interfaces() {
+        let mut codegen = CodegenBuilder::new();
+        codegen.root_enums_only();
+        let code = codegen.generate(metadata()).unwrap().to_string();
+
+        assert!(code.contains("pub type Call ="));
+        assert!(code.contains("pub type Event ="));
+        assert!(code.contains("pub type Error ="));
+        assert!(code.contains("pub fn is_codegen_valid_for"));
+        assert!(code.contains("pub mod runtime_types"));
+
+        assert!(!code.contains("TransactionApi"));
+        assert!(!code.contains("StorageApi"));
+        assert!(!code.contains("ConstantsApi"));
+    }
+
+    #[test]
+    fn value_conversions_are_generated_when_asked_for() {
+        let mut codegen = CodegenBuilder::new();
+        codegen.runtime_types_only();
+        let code = codegen.generate(metadata()).unwrap().to_string();
+        assert!(!code.contains("ValueConversion"));
+
+        let mut codegen = CodegenBuilder::new();
+        codegen.runtime_types_only();
+        codegen.generate_value_conversions();
+        let code = codegen.generate(metadata()).unwrap().to_string();
+        assert!(code.contains(
+            "impl :: subxt :: ext :: subxt_core :: dynamic :: ValueConversion for runtime_types :: sp_runtime :: DispatchError"
+        ));
+        // Substituted types aren't given an implementation:
+        assert!(!code.contains("ValueConversion for :: subxt"));
+    }
 }
@@ -0,0 +1,97 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use std::collections::BTreeMap;
+
+/// A report describing which Rust type each type in the metadata was generated as. This
+/// is obtained via [`crate::CodegenBuilder::generate_with_type_mapping_report()`], and is
+/// useful for understanding how type substitutions were applied and how types whose paths
+/// collided were renamed during code generation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeMappingReport {
+    paths: BTreeMap<u32, String>,
+}
+
+impl TypeMappingReport {
+    pub(crate) fn new(paths: BTreeMap<u32, String>) -> Self {
+        TypeMappingReport { paths }
+    }
+
+    /// The path to the Rust type that the given type ID was generated as, if the type ID exists.
+    /// Paths to generated types are relative to the generated module, and so begin with
+    /// `runtime_types::`, whereas paths to substituted types are absolute. Whitespace is removed.
+    pub fn path_for_type(&self, type_id: u32) -> Option<&str> {
+        self.paths.get(&type_id).map(|p| p.as_str())
+    }
+
+    /// Iterate over every type ID in the metadata, in ascending order, along
+    /// with the path to the Rust type that it was generated as.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.paths.iter().map(|(id, path)| (*id, path.as_str()))
+    }
+
+    /// The number of types in the report.
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Is the report empty?
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CodegenBuilder, Metadata};
+    use codec::Decode;
+
+    fn metadata() -> Metadata {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../artifacts/polkadot_metadata_full.scale"
+        );
+        let bytes = std::fs::read(path).unwrap();
+        Metadata::decode(&mut &*bytes).unwrap()
+    }
+
+    fn type_id_for_path(metadata: &Metadata, path: &[&str]) -> u32 {
+        metadata
+            .types()
+            .types
+            .iter()
+            .find(|ty| {
+                ty.ty
+                    .path
+                    .segments
+                    .iter()
+                    .map(|s| s.as_str())
+                    .eq(path.iter().copied())
+            })
+            .map(|ty| ty.id)
+            .unwrap()
+    }
+
+    #[test]
+    fn report_contains_generated_and_substituted_paths() {
+        let metadata = metadata();
+        let account_id = type_id_for_path(&metadata, &["sp_core", "crypto", "AccountId32"]);
+        let perbill = type_id_for_path(&metadata, &["sp_arithmetic", "per_things", "Perbill"]);
+        let num_types = metadata.types().types.len();
+
+        let (_, report) = CodegenBuilder::new()
+            .generate_with_type_mapping_report(metadata)
+            .unwrap();
+
+        assert_eq!(report.len(), num_types);
+        assert_eq!(
+            report.path_for_type(account_id),
+            Some("::subxt::ext::subxt_core::utils::AccountId32")
+        );
+        assert_eq!(
+            report.path_for_type(perbill),
+            Some("runtime_types::sp_arithmetic::per_things::Perbill")
+        );
+    }
+}
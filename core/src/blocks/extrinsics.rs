@@ -14,6 +14,7 @@ use codec::{Compact, CompactLen, Decode};
 use scale_decode::DecodeAsType;
 use subxt_metadata::PalletMetadata;
 
+use crate::blocks::AnyStaticExtrinsic;
 pub use crate::blocks::StaticExtrinsic;
 
 /// The body of a block.
@@ -119,6 +120,25 @@ impl<T: Config> Extrinsics<T> {
     pub fn has<E: StaticExtrinsic>(&self) -> Result<bool, Error> {
         Ok(self.find::<E>().next().transpose()?.is_some())
     }
+
+    /// Iterate through the extrinsics using metadata to dynamically decode and skip
+    /// them, and return only those which decode to one of the extrinsics represented by `E`.
+    /// This checks for several extrinsic types in a single pass over the extrinsics.
+    /// If an error occurs, all subsequent iterations return `None`.
+    pub fn find_any<E: AnyStaticExtrinsic>(
+        &self,
+    ) -> impl Iterator<Item = Result<FoundExtrinsic<T, E>, Error>> + '_ {
+        self.iter().filter_map(|res| match res {
+            Err(err) => Some(Err(err)),
+            Ok(details) => match E::decode_any(&details) {
+                // Failed to decode extrinsic:
+                Err(err) => Some(Err(err)),
+                // Not one of the extrinsics we're looking for (skip):
+                Ok(None) => None,
+                Ok(Some(value)) => Some(Ok(FoundExtrinsic { details, value })),
+            },
+        })
+    }
 }
 
 /// A single extrinsic in a block.
@@ -375,17 +395,29 @@ where
         let bytes = &mut self.field_bytes();
         let extrinsic_metadata = self.extrinsic_metadata()?;
 
-        let mut fields = extrinsic_metadata
-            .variant
-            .fields
-            .iter()
-            .map(|f| scale_decode::Field::new(f.ty.id, f.name.as_deref()));
-        let decoded =
-            scale_value::scale::decode_as_fields(bytes, &mut fields, self.metadata.types())?;
+        let decoded = crate::dynamic::decode_fields(
+            bytes,
+            &extrinsic_metadata.variant.fields,
+            self.metadata.types(),
+        )?;
 
         Ok(decoded)
     }
 
+    /// Decode the call fields and convert them into JSON of the form
+    /// `{ "pallet": "Balances", "call": "transfer_allow_death", "fields": { .. } }`. See
+    /// [`crate::utils::json`] for details on how the fields are converted.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn call_to_json(&self) -> Result<serde_json::Value, Error> {
+        let fields = self.field_values()?;
+        Ok(serde_json::json!({
+            "pallet": self.pallet_name()?,
+            "call": self.variant_name()?,
+            "fields": crate::utils::json::composite_to_json(&fields, self.metadata.types()),
+        }))
+    }
+
     /// Attempt to decode these [`ExtrinsicDetails`] into a type representing the extrinsic fields.
     /// Such types are exposed in the codegen as `pallet_name::calls::types::CallName` types.
     pub fn as_extrinsic<E: StaticExtrinsic>(&self) -> Result<Option<E>, Error> {
@@ -529,6 +561,9 @@ mod tests {
             signed: bool,
             name: String,
         },
+        #[allow(unused)]
+        #[codec(index = 3)]
+        OtherCall { value: u32 },
     }
 
     #[allow(unused)]
@@ -554,6 +589,35 @@ mod tests {
         const CALL: &'static str = "TestCall";
     }
 
+    #[allow(unused)]
+    #[derive(Clone, Debug, PartialEq, Eq, scale_decode::DecodeAsType)]
+    struct OtherCallExtrinsic {
+        value: u32,
+    }
+
+    impl StaticExtrinsic for OtherCallExtrinsic {
+        const PALLET: &'static str = "Test";
+        const CALL: &'static str = "OtherCall";
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum AnyTestCall {
+        Test(TestCallExtrinsic),
+        Other(OtherCallExtrinsic),
+    }
+
+    impl AnyStaticExtrinsic for AnyTestCall {
+        fn decode_any<T: Config>(details: &ExtrinsicDetails<T>) -> Result<Option<Self>, Error> {
+            if let Some(ext) = details.as_extrinsic::<TestCallExtrinsic>()? {
+                return Ok(Some(AnyTestCall::Test(ext)));
+            }
+            if let Some(ext) = details.as_extrinsic::<OtherCallExtrinsic>()? {
+                return Ok(Some(AnyTestCall::Other(ext)));
+            }
+            Ok(None)
+        }
+    }
+
     /// Build fake metadata consisting the types needed to represent an extrinsic.
     fn metadata() -> Metadata {
         let pallets = vec![PalletMetadata {
@@ -749,4 +813,58 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn find_any_extrinsic() {
+        let metadata = metadata();
+
+        let encode = |tx| {
+            crate::tx::create_unsigned::<SubstrateConfig, _>(&tx, &metadata)
+                .expect("Valid dynamic parameters are provided")
+                .into_encoded()
+        };
+        let test_call = crate::dynamic::tx(
+            "Test",
+            "TestCall",
+            vec![
+                Value::u128(10),
+                Value::bool(true),
+                Value::string("SomeValue"),
+            ],
+        );
+        let other_call = crate::dynamic::tx("Test", "OtherCall", vec![Value::u128(20)]);
+        let extrinsic_bytes = vec![
+            encode(other_call.clone()),
+            encode(test_call),
+            encode(other_call),
+        ];
+
+        let extrinsics =
+            Extrinsics::<SubstrateConfig>::decode_from(extrinsic_bytes, metadata.clone())
+                .expect("Valid extrinsics");
+
+        let found: Vec<_> = extrinsics
+            .find_any::<AnyTestCall>()
+            .map(|ext| {
+                let ext = ext.expect("can decode extrinsic");
+                (ext.details.index(), ext.value)
+            })
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                (0, AnyTestCall::Other(OtherCallExtrinsic { value: 20 })),
+                (
+                    1,
+                    AnyTestCall::Test(TestCallExtrinsic {
+                        value: 10,
+                        signed: true,
+                        name: "SomeValue".into(),
+                    })
+                ),
+                (2, AnyTestCall::Other(OtherCallExtrinsic { value: 20 })),
+            ]
+        );
+    }
 }
@@ -79,7 +79,7 @@ pub use extrinsic_signed_extensions::{ExtrinsicSignedExtension, ExtrinsicSignedE
 pub use extrinsics::{
     ExtrinsicDetails, ExtrinsicMetadataDetails, Extrinsics, FoundExtrinsic, SignedExtrinsicDetails,
 };
-pub use static_extrinsic::StaticExtrinsic;
+pub use static_extrinsic::{AnyStaticExtrinsic, StaticExtrinsic};
 
 /// Instantiate a new [`Extrinsics`] object, given a vector containing each extrinsic hash (in the
 /// form of bytes) and some metadata that we'll use to decode them.
@@ -2,6 +2,8 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
+use super::ExtrinsicDetails;
+use crate::{config::Config, Error};
 use scale_decode::DecodeAsFields;
 
 /// Trait to uniquely identify the extrinsic's identity from the runtime metadata.
@@ -21,3 +23,12 @@ pub trait StaticExtrinsic: DecodeAsFields {
         Self::PALLET == pallet && Self::CALL == call
     }
 }
+
+/// Implemented by types which represent any one of several [`StaticExtrinsic`]s. This is usually
+/// implemented by generating an enum with `subxt::find_any!`, and is used in
+/// [`super::Extrinsics::find_any()`].
+pub trait AnyStaticExtrinsic: Sized {
+    /// Attempt to decode the given extrinsic into one of the extrinsics that this type
+    /// represents, returning `Ok(None)` if it is not one of them.
+    fn decode_any<T: Config>(details: &ExtrinsicDetails<T>) -> Result<Option<Self>, Error>;
+}
@@ -0,0 +1,266 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Build the "additional signed" bytes of a signer payload from metadata.
+//!
+//! Each signed extension declares an `additional_ty`, which is data that is not
+//! included in the extrinsic itself, but which is included in the payload that
+//! is signed (for instance the genesis hash or the spec version). External
+//! signers need to produce these bytes in the same order that the chain expects
+//! them in, which is the order that the signed extensions are listed in the metadata.
+
+use super::signed_extensions::is_type_empty;
+use crate::client::ClientState;
+use crate::Config;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use codec::Encode;
+use hashbrown::HashMap;
+use scale_info::PortableRegistry;
+use subxt_metadata::SignedExtensionMetadata;
+
+/// A set of known "additional signed" values, keyed by the identifier of the
+/// signed extension that they belong to. Use [`AdditionalSignedValues::encode_for()`]
+/// to turn these into the bytes expected by some chain.
+///
+/// # Example
+///
+/// ```rust
+/// use subxt_core::config::AdditionalSignedValues;
+///
+/// let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_small.scale");
+/// let metadata = subxt_core::metadata::decode_from(&metadata_bytes[..]).unwrap();
+///
+/// let genesis_hash = [0u8; 32];
+/// let additional_signed = AdditionalSignedValues::new()
+///     .with("CheckSpecVersion", 1_002_000u32)
+///     .with("CheckTxVersion", 26u32)
+///     .with("CheckGenesis", genesis_hash)
+///     // An immortal transaction uses the genesis hash as its checkpoint:
+///     .with("CheckMortality", genesis_hash)
+///     .with("CheckMetadataHash", None::<[u8; 32]>)
+///     .encode_for(metadata.extrinsic().signed_extensions(), metadata.types());
+///
+/// assert!(additional_signed.is_complete());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AdditionalSignedValues {
+    values: HashMap<String, Vec<u8>>,
+}
+
+impl AdditionalSignedValues {
+    /// Create an empty set of values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Populate the values that can be derived from the given [`ClientState`]; the
+    /// spec and transaction versions and the genesis hash. The transaction is assumed
+    /// to be immortal (so the genesis hash is used as the [`CheckMortality`] checkpoint),
+    /// and no metadata hash is provided for [`CheckMetadataHash`]. Each of these can be
+    /// overridden by calling [`AdditionalSignedValues::with()`] afterwards.
+    ///
+    /// [`CheckMortality`]: super::signed_extensions::CheckMortality
+    /// [`CheckMetadataHash`]: super::signed_extensions::CheckMetadataHash
+    pub fn from_client_state<T: Config>(client: &ClientState<T>) -> Self {
+        Self::new()
+            .with("CheckSpecVersion", client.runtime_version.spec_version)
+            .with("CheckTxVersion", client.runtime_version.transaction_version)
+            .with("CheckGenesis", client.genesis_hash)
+            .with("CheckMortality", client.genesis_hash)
+            .with("CheckMetadataHash", None::<[u8; 32]>)
+    }
+
+    /// Provide the value for the signed extension with the given identifier. This
+    /// will be SCALE encoded, and should have the shape of the extension's `additional_ty`.
+    pub fn with(self, identifier: impl Into<String>, value: impl Encode) -> Self {
+        self.with_encoded(identifier, value.encode())
+    }
+
+    /// Provide the already SCALE encoded value for the signed extension with the given identifier.
+    pub fn with_encoded(mut self, identifier: impl Into<String>, value: Vec<u8>) -> Self {
+        self.values.insert(identifier.into(), value);
+        self
+    }
+
+    /// Encode the known values in the order given by `signed_extensions`, which is
+    /// expected to come from [`subxt_metadata::ExtrinsicMetadata::signed_extensions()`].
+    ///
+    /// Extensions whose `additional_ty` encodes to nothing don't need a value. Any other
+    /// extension that we don't have a value for is skipped over and reported in
+    /// [`AdditionalSigned::missing()`]; the resulting bytes will not be valid in this case.
+    pub fn encode_for(
+        &self,
+        signed_extensions: &[SignedExtensionMetadata],
+        types: &PortableRegistry,
+    ) -> AdditionalSigned {
+        let mut encoded = Vec::new();
+        let mut missing = Vec::new();
+
+        for ext in signed_extensions {
+            if let Some(value) = self.values.get(ext.identifier()) {
+                encoded.extend_from_slice(value);
+            } else if !is_type_empty(ext.additional_ty(), types) {
+                missing.push(ext.identifier().to_owned());
+            }
+        }
+
+        AdditionalSigned { encoded, missing }
+    }
+}
+
+/// The "additional signed" bytes produced by [`AdditionalSignedValues::encode_for()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdditionalSigned {
+    encoded: Vec<u8>,
+    missing: Vec<String>,
+}
+
+impl AdditionalSigned {
+    /// The encoded bytes, in the order expected by the chain.
+    pub fn encoded(&self) -> &[u8] {
+        &self.encoded
+    }
+
+    /// Consume this, returning the encoded bytes.
+    pub fn into_encoded(self) -> Vec<u8> {
+        self.encoded
+    }
+
+    /// The identifiers of any signed extensions which require additional
+    /// data, but which no value was provided for.
+    pub fn missing(&self) -> &[String] {
+        &self.missing
+    }
+
+    /// Were values provided for every signed extension which needs one?
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::RuntimeVersion;
+    use crate::config::{
+        DefaultExtrinsicParamsBuilder, ExtrinsicParams, ExtrinsicParamsEncoder, PolkadotConfig,
+    };
+    use crate::metadata::Metadata;
+    use crate::utils::H256;
+
+    fn metadata() -> Metadata {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        crate::metadata::decode_from(&bytes[..]).unwrap()
+    }
+
+    fn client_state() -> ClientState<PolkadotConfig> {
+        ClientState {
+            genesis_hash: H256::repeat_byte(1),
+            runtime_version: RuntimeVersion {
+                spec_version: 1_002_000,
+                transaction_version: 26,
+            },
+            metadata: metadata(),
+        }
+    }
+
+    #[test]
+    fn encodes_polkadot_additional_signed_in_order() {
+        let metadata = metadata();
+        let genesis_hash = H256::repeat_byte(1);
+        let checkpoint = H256::repeat_byte(2);
+
+        // Provide the values in a different order from the metadata:
+        let additional_signed = AdditionalSignedValues::new()
+            .with("CheckMetadataHash", None::<[u8; 32]>)
+            .with("CheckMortality", checkpoint)
+            .with("CheckGenesis", genesis_hash)
+            .with("CheckTxVersion", 26u32)
+            .with("CheckSpecVersion", 1_002_000u32)
+            .encode_for(metadata.extrinsic().signed_extensions(), metadata.types());
+
+        let expected = (
+            1_002_000u32,
+            26u32,
+            genesis_hash,
+            checkpoint,
+            None::<[u8; 32]>,
+        )
+            .encode();
+
+        assert!(additional_signed.is_complete());
+        assert_eq!(additional_signed.encoded(), &expected[..]);
+    }
+
+    #[test]
+    fn reports_missing_polkadot_extensions() {
+        let metadata = metadata();
+
+        let additional_signed = AdditionalSignedValues::new()
+            .with("CheckSpecVersion", 1_002_000u32)
+            .with("CheckTxVersion", 26u32)
+            .encode_for(metadata.extrinsic().signed_extensions(), metadata.types());
+
+        assert!(!additional_signed.is_complete());
+        assert_eq!(
+            additional_signed.missing(),
+            &["CheckGenesis", "CheckMortality", "CheckMetadataHash"]
+        );
+    }
+
+    #[test]
+    fn matches_polkadot_extrinsic_params() {
+        let client = client_state();
+        let metadata = &client.metadata;
+
+        let params = <PolkadotConfig as crate::Config>::ExtrinsicParams::new(
+            &client,
+            DefaultExtrinsicParamsBuilder::new().build(),
+        )
+        .unwrap();
+        let mut expected = Vec::new();
+        params.encode_additional_to(&mut expected);
+
+        let additional_signed = AdditionalSignedValues::from_client_state(&client)
+            .encode_for(metadata.extrinsic().signed_extensions(), metadata.types());
+
+        assert!(additional_signed.is_complete());
+        assert_eq!(additional_signed.into_encoded(), expected);
+    }
+
+    #[test]
+    fn mortal_unchecked_uses_checkpoint_hash() {
+        let client = client_state();
+        let checkpoint = H256::repeat_byte(2);
+
+        let params = <PolkadotConfig as crate::Config>::ExtrinsicParams::new(
+            &client,
+            DefaultExtrinsicParamsBuilder::new()
+                .mortal_unchecked(100, checkpoint, 64)
+                .build(),
+        )
+        .unwrap();
+        let mut additional = Vec::new();
+        params.encode_additional_to(&mut additional);
+        let mut extra = Vec::new();
+        params.encode_extra_to(&mut extra);
+
+        // The checkpoint hash, and not the genesis hash, is signed for by `CheckMortality`:
+        let expected = (
+            1_002_000u32,
+            26u32,
+            client.genesis_hash,
+            checkpoint,
+            None::<[u8; 32]>,
+        )
+            .encode();
+        assert_eq!(additional, expected);
+
+        // And the era is encoded into the extra data, starting from the checkpoint block:
+        let era = crate::utils::Era::mortal(64, 100).encode();
+        assert_eq!(&extra[..era.len()], &era[..]);
+    }
+}
@@ -87,6 +87,10 @@ impl<T: Config> DefaultExtrinsicParamsBuilder<T> {
     /// the same block) that it should be mortal from, and the number of blocks (roughly; it'll be
     /// rounded to a power of two) that it will be mortal for.
     ///
+    /// The block hash is used as the `CheckMortality` checkpoint in the signer payload, so this
+    /// can be used to anchor a transaction to some block other than the latest one (for instance,
+    /// a finalized block some way back).
+    ///
     /// Prefer to use [`DefaultExtrinsicParamsBuilder::mortal()`], which ensures that the block hash
     /// and number align.
     pub fn mortal_unchecked(
@@ -8,6 +8,7 @@
 //! default Substrate node implementation, and [`PolkadotConfig`] for a
 //! Polkadot node.
 
+mod additional_signed;
 mod default_extrinsic_params;
 mod extrinsic_params;
 mod refine_params;
@@ -23,6 +24,7 @@ use scale_decode::DecodeAsType;
 use scale_encode::EncodeAsType;
 use serde::{de::DeserializeOwned, Serialize};
 
+pub use additional_signed::{AdditionalSigned, AdditionalSignedValues};
 pub use default_extrinsic_params::{DefaultExtrinsicParams, DefaultExtrinsicParamsBuilder};
 pub use extrinsic_params::{ExtrinsicParams, ExtrinsicParamsEncoder};
 pub use polkadot::{PolkadotConfig, PolkadotExtrinsicParams, PolkadotExtrinsicParamsBuilder};
@@ -59,6 +61,10 @@ pub trait Config: Sized + Send + Sync + 'static {
 
     /// This is used to identify an asset in the `ChargeAssetTxPayment` signed extension.
     type AssetId: Debug + Clone + Encode + DecodeAsType + EncodeAsType + Send;
+
+    /// The SS58 network prefix used when displaying addresses for this chain (for instance,
+    /// 0 for Polkadot or 2 for Kusama). This defaults to the generic Substrate prefix, 42.
+    const SS58_PREFIX: u16 = 42;
 }
 
 /// given some [`Config`], this return the other params needed for its `ExtrinsicParams`.
@@ -122,6 +128,9 @@ pub trait Header: Sized + Encode + Decode {
     /// Return the block number of this header.
     fn number(&self) -> Self::Number;
 
+    /// Return the hash of the parent block.
+    fn parent_hash(&self) -> <Self::Hasher as Hasher>::Output;
+
     /// Hash this header.
     fn hash(&self) -> <Self::Hasher as Hasher>::Output {
         Self::Hasher::hash_of(self)
@@ -143,6 +152,10 @@ cfg_substrate_compat! {
             fn number(&self) -> Self::Number {
                 *self.number()
             }
+
+            fn parent_hash(&self) -> <Self::Hasher as Hasher>::Output {
+                *sp_runtime::traits::Header::parent_hash(self)
+            }
         }
 
         impl<T: sp_runtime::traits::Hash> Hasher for T {
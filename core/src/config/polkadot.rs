@@ -25,6 +25,7 @@ impl Config for PolkadotConfig {
     type Header = <SubstrateConfig as Config>::Header;
     type ExtrinsicParams = PolkadotExtrinsicParams<Self>;
     type AssetId = u32;
+    const SS58_PREFIX: u16 = 0;
 }
 
 /// A struct representing the signed extra and additional parameters required
@@ -543,7 +543,7 @@ const _: () = {
 
 /// Checks to see whether the type being given is empty, ie would require
 /// 0 bytes to encode.
-fn is_type_empty(type_id: u32, types: &scale_info::PortableRegistry) -> bool {
+pub(crate) fn is_type_empty(type_id: u32, types: &scale_info::PortableRegistry) -> bool {
     let Some(ty) = types.resolve(type_id) else {
         // Can't resolve; type may not be empty. Not expected to hit this.
         return false;
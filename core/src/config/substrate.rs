@@ -47,7 +47,7 @@ pub struct BlakeTwo256;
 impl Hasher for BlakeTwo256 {
     type Output = H256;
     fn hash(s: &[u8]) -> Self::Output {
-        sp_crypto_hashing::blake2_256(s).into()
+        crate::utils::hashing::blake2_256(s).into()
     }
 }
 
@@ -77,6 +77,7 @@ impl<N, H> Header for SubstrateHeader<N, H>
 where
     N: Copy + Into<u64> + Into<U256> + TryFrom<U256> + Encode,
     H: Hasher + Encode,
+    H::Output: Clone,
     SubstrateHeader<N, H>: Encode + Decode,
 {
     type Number = N;
@@ -84,6 +85,9 @@ where
     fn number(&self) -> Self::Number {
         self.number
     }
+    fn parent_hash(&self) -> H::Output {
+        self.parent_hash.clone()
+    }
 }
 
 /// Generic header digest. From `sp_runtime::generic::digest`.
@@ -5,9 +5,14 @@
 //! This module provides the entry points to create dynamic
 //! transactions, storage and constant lookups.
 
+use crate::error::{Error, MetadataError};
 use crate::metadata::{DecodeWithMetadata, Metadata};
+use alloc::string::String;
 use alloc::vec::Vec;
+use codec::{Compact, Decode};
 use scale_decode::DecodeAsType;
+use scale_encode::EncodeAsType;
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef};
 pub use scale_value::{At, Value};
 
 /// A [`scale_value::Value`] type endowed with contextual information
@@ -65,12 +70,7 @@ impl DecodedValueThunk {
     }
     /// Decode the SCALE encoded storage entry into a dynamic [`DecodedValue`] type.
     pub fn to_value(&self) -> Result<DecodedValue, scale_decode::Error> {
-        let val = scale_value::scale::decode_as_type(
-            &mut &*self.scale_bytes,
-            self.type_id,
-            self.metadata.types(),
-        )?;
-        Ok(val)
+        decode_value(&mut &*self.scale_bytes, self.type_id, self.metadata.types())
     }
     /// decode the `DecodedValueThunk` into a concrete type.
     pub fn as_type<T: DecodeAsType>(&self) -> Result<T, scale_decode::Error> {
@@ -81,3 +81,349 @@ impl DecodedValueThunk {
         )
     }
 }
+
+/// Convert a [`Value`] into some concrete type, for instance one generated by the `#[subxt]`
+/// macro, given the ID of the type in the metadata that the value represents. This saves
+/// encoding the value to bytes and then decoding it again by hand.
+///
+/// An error is returned if the shape of the value does not line up with the type given by
+/// `type_id`, or if `T` cannot be decoded from a value of that type.
+pub fn value_as_type<T: DecodeAsType, Ctx>(
+    value: &Value<Ctx>,
+    type_id: u32,
+    metadata: &Metadata,
+) -> Result<T, Error> {
+    let bytes = crate::metadata::encode_as(value, type_id, metadata)?;
+    crate::metadata::decode_as(&bytes, type_id, metadata)
+}
+
+/// Implemented for types generated by the `#[subxt]` macro when value conversions are enabled
+/// (see `subxt_codegen::CodegenBuilder::generate_value_conversions()`). Each such type knows
+/// the ID and shape of the type in the metadata that it was generated from, which allows it to
+/// be converted to and from a dynamic [`Value`].
+///
+/// Conversions return [`MetadataError::IncompatibleCodegen`] if the metadata given doesn't
+/// contain a type with the same ID and shape as the one this type was generated from.
+pub trait ValueConversion: EncodeAsType + DecodeAsType + Sized {
+    /// The ID of the type in the metadata that this type was generated from.
+    const TYPE_ID: u32;
+    /// The hash of the type in the metadata that this type was generated from.
+    const TYPE_HASH: [u8; 32];
+
+    /// Convert this into a [`DecodedValue`], using the given metadata.
+    fn to_value(&self, metadata: &Metadata) -> Result<DecodedValue, Error> {
+        let type_id = validate_conversion::<Self>(metadata)?;
+        let bytes = crate::metadata::encode_as(self, type_id, metadata)?;
+        let value = decode_value(&mut &*bytes, type_id, metadata.types())?;
+        Ok(value)
+    }
+
+    /// Convert a [`Value`] into this type, using the given metadata.
+    fn from_value<Ctx>(value: &Value<Ctx>, metadata: &Metadata) -> Result<Self, Error> {
+        let type_id = validate_conversion::<Self>(metadata)?;
+        value_as_type(value, type_id, metadata)
+    }
+}
+
+/// Check that the type that `T` was generated from exists in the metadata, returning its ID.
+fn validate_conversion<T: ValueConversion>(metadata: &Metadata) -> Result<u32, Error> {
+    match metadata.type_hash(T::TYPE_ID) {
+        Some(hash) if hash == T::TYPE_HASH => Ok(T::TYPE_ID),
+        _ => Err(MetadataError::IncompatibleCodegen.into()),
+    }
+}
+
+/// Decode some bytes into a [`DecodedValue`]. Unlike [`scale_value::scale::decode_as_type`],
+/// a failure to decode will return an error which points to the part of the value that could
+/// not be decoded (for instance `data.free`).
+pub(crate) fn decode_value(
+    bytes: &mut &[u8],
+    type_id: u32,
+    types: &PortableRegistry,
+) -> Result<DecodedValue, scale_decode::Error> {
+    let original = *bytes;
+    scale_value::scale::decode_as_type(bytes, type_id, types).map_err(|e| {
+        let mut path = Vec::new();
+        locate_error(&mut &*original, type_id, types, &mut path);
+        with_path(e.into(), path)
+    })
+}
+
+/// Decode some bytes into a [`scale_value::Composite`] given the fields that they represent.
+/// Like [`decode_value()`], errors will point to the part of the value that failed to decode.
+pub(crate) fn decode_fields(
+    bytes: &mut &[u8],
+    fields: &[Field<PortableForm>],
+    types: &PortableRegistry,
+) -> Result<scale_value::Composite<u32>, scale_decode::Error> {
+    let original = *bytes;
+    let mut field_iter = fields
+        .iter()
+        .map(|f| scale_decode::Field::new(f.ty.id, f.name.as_deref()));
+    scale_value::scale::decode_as_fields(bytes, &mut field_iter, types).map_err(|e| {
+        let mut path = Vec::new();
+        locate_fields_error(&mut &*original, fields, types, &mut path);
+        with_path(e.into(), path)
+    })
+}
+
+/// A single step in the path to some part of a value.
+enum Location {
+    Field(String),
+    Index(usize),
+    Variant(String),
+}
+
+fn with_path(mut err: scale_decode::Error, path: Vec<Location>) -> scale_decode::Error {
+    // Context is added to errors from the innermost location outwards.
+    for loc in path.into_iter().rev() {
+        err = match loc {
+            Location::Field(name) => err.at_field(name),
+            Location::Index(idx) => err.at_idx(idx),
+            Location::Variant(name) => err.at_variant(name),
+        };
+    }
+    err
+}
+
+/// Walk through the bytes given the type that they represent, pushing the location of
+/// each part of the type that fails to decode, in order to find where decoding went wrong.
+fn locate_error(
+    bytes: &mut &[u8],
+    type_id: u32,
+    types: &PortableRegistry,
+    path: &mut Vec<Location>,
+) {
+    let Some(ty) = types.resolve(type_id) else {
+        return;
+    };
+
+    match &ty.type_def {
+        TypeDef::Composite(composite) => {
+            locate_fields_error(bytes, &composite.fields, types, path);
+        }
+        TypeDef::Variant(variant) => {
+            let Some((&index, rest)) = bytes.split_first() else {
+                return;
+            };
+            let Some(var) = variant.variants.iter().find(|v| v.index == index) else {
+                return;
+            };
+            *bytes = rest;
+            path.push(Location::Variant(var.name.clone()));
+            locate_fields_error(bytes, &var.fields, types, path);
+        }
+        TypeDef::Sequence(seq) => {
+            let Ok(Compact(len)) = Compact::<u32>::decode(bytes) else {
+                return;
+            };
+            let items = core::iter::repeat(seq.type_param.id).take(len as usize);
+            locate_items_error(bytes, items, types, path);
+        }
+        TypeDef::Array(arr) => {
+            let items = core::iter::repeat(arr.type_param.id).take(arr.len as usize);
+            locate_items_error(bytes, items, types, path);
+        }
+        TypeDef::Tuple(tuple) => {
+            let items = tuple.fields.iter().map(|f| f.id);
+            locate_items_error(bytes, items, types, path);
+        }
+        // Primitives, compacts and bit sequences are the innermost locations.
+        _ => {}
+    }
+}
+
+fn locate_fields_error(
+    bytes: &mut &[u8],
+    fields: &[Field<PortableForm>],
+    types: &PortableRegistry,
+    path: &mut Vec<Location>,
+) {
+    for (idx, field) in fields.iter().enumerate() {
+        if !skip_value(bytes, field.ty.id, types) {
+            path.push(match &field.name {
+                Some(name) => Location::Field(name.clone()),
+                None => Location::Index(idx),
+            });
+            locate_error(bytes, field.ty.id, types, path);
+            return;
+        }
+    }
+}
+
+fn locate_items_error(
+    bytes: &mut &[u8],
+    items: impl Iterator<Item = u32>,
+    types: &PortableRegistry,
+    path: &mut Vec<Location>,
+) {
+    for (idx, type_id) in items.enumerate() {
+        if !skip_value(bytes, type_id, types) {
+            path.push(Location::Index(idx));
+            locate_error(bytes, type_id, types, path);
+            return;
+        }
+    }
+}
+
+/// Move past a value of the given type, returning false and leaving the bytes
+/// untouched if the value could not be decoded.
+fn skip_value(bytes: &mut &[u8], type_id: u32, types: &PortableRegistry) -> bool {
+    let mut cursor = *bytes;
+    if scale_value::scale::decode_as_type(&mut cursor, type_id, types).is_ok() {
+        *bytes = cursor;
+        true
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+    use codec::Encode;
+    use scale_info::TypeInfo;
+
+    #[derive(Encode, TypeInfo)]
+    struct Account {
+        nonce: u32,
+        balances: Balances,
+    }
+
+    #[derive(Encode, TypeInfo)]
+    struct Balances {
+        free: u128,
+        frozen: bool,
+    }
+
+    fn make_type<T: TypeInfo + 'static>() -> (u32, PortableRegistry) {
+        let m = scale_info::MetaType::new::<T>();
+        let mut types = scale_info::Registry::new();
+        let id = types.register_type(&m);
+        let portable_registry: PortableRegistry = types.into();
+
+        (id.id, portable_registry)
+    }
+
+    #[test]
+    fn decode_errors_name_the_field_path() {
+        let (type_id, types) = make_type::<Account>();
+
+        let mut bytes = Account {
+            nonce: 1,
+            balances: Balances {
+                free: 1000,
+                frozen: true,
+            },
+        }
+        .encode();
+        // Make the "frozen" bool invalid:
+        *bytes.last_mut().unwrap() = 5;
+
+        let err = decode_value(&mut &*bytes, type_id, &types).unwrap_err();
+        let path = err.context().path().to_string();
+        assert!(path.contains("balances"), "unexpected path: {path}");
+        assert!(path.contains("frozen"), "unexpected path: {path}");
+        assert!(!path.contains("nonce"), "unexpected path: {path}");
+
+        // Valid bytes still decode fine:
+        *bytes.last_mut().unwrap() = 0;
+        assert!(decode_value(&mut &*bytes, type_id, &types).is_ok());
+    }
+
+    #[test]
+    fn value_as_type_converts_matching_values() {
+        #[derive(Debug, PartialEq, scale_decode::DecodeAsType)]
+        struct AccountInfo {
+            nonce: u32,
+            consumers: u32,
+            providers: u32,
+            sufficients: u32,
+            data: AccountData,
+        }
+
+        #[derive(Debug, PartialEq, scale_decode::DecodeAsType)]
+        struct AccountData {
+            free: u128,
+            reserved: u128,
+            frozen: u128,
+            flags: u128,
+        }
+
+        let bytes = include_bytes!("../../artifacts/polkadot_metadata_full.scale");
+        let metadata = crate::metadata::decode_from(&bytes[..]).unwrap();
+        let account_ty = metadata
+            .pallet_by_name("System")
+            .and_then(|p| p.storage())
+            .and_then(|s| s.entry_by_name("Account"))
+            .map(|e| e.entry_type().value_ty())
+            .unwrap();
+
+        let data = Value::named_composite([
+            ("free", Value::u128(1_000)),
+            ("reserved", Value::u128(200)),
+            ("frozen", Value::u128(30)),
+            ("flags", Value::u128(0)),
+        ]);
+        let value = Value::named_composite([
+            ("nonce", Value::u128(1)),
+            ("consumers", Value::u128(2)),
+            ("providers", Value::u128(3)),
+            ("sufficients", Value::u128(4)),
+            ("data", data),
+        ]);
+
+        let account: AccountInfo = value_as_type(&value, account_ty, &metadata).unwrap();
+        assert_eq!(account.nonce, 1);
+        assert_eq!(account.data.free, 1_000);
+
+        // A value whose shape doesn't match the type can't be converted:
+        let err = value_as_type::<AccountInfo, _>(&Value::u128(1), account_ty, &metadata);
+        assert!(matches!(err, Err(Error::Encode(_))));
+    }
+
+    #[test]
+    fn value_conversion_checks_type_is_compatible() {
+        #[derive(Debug, PartialEq, scale_encode::EncodeAsType, scale_decode::DecodeAsType)]
+        struct Foo {
+            a: u32,
+        }
+
+        impl ValueConversion for Foo {
+            const TYPE_ID: u32 = u32::MAX;
+            const TYPE_HASH: [u8; 32] = [0; 32];
+        }
+
+        let bytes = include_bytes!("../../artifacts/polkadot_metadata_small.scale");
+        let metadata = crate::metadata::decode_from(&bytes[..]).unwrap();
+
+        // No type with the expected ID and shape exists, so we can't convert either way:
+        let err = Foo { a: 1 }.to_value(&metadata);
+        assert!(matches!(
+            err,
+            Err(Error::Metadata(MetadataError::IncompatibleCodegen))
+        ));
+        let err = Foo::from_value(&Value::named_composite([("a", Value::u128(1))]), &metadata);
+        assert!(matches!(
+            err,
+            Err(Error::Metadata(MetadataError::IncompatibleCodegen))
+        ));
+    }
+
+    #[test]
+    fn decode_field_errors_name_the_field_path() {
+        let (account_id, types) = make_type::<Account>();
+        let TypeDef::Composite(account) = &types.resolve(account_id).unwrap().type_def else {
+            panic!("Account should be a composite type");
+        };
+
+        // Too few bytes to decode the balances:
+        let bytes = (1u32, 1000u64).encode();
+
+        let err = decode_fields(&mut &*bytes, &account.fields, &types).unwrap_err();
+        let path = err.context().path().to_string();
+        assert!(path.contains("balances"), "unexpected path: {path}");
+        assert!(path.contains("free"), "unexpected path: {path}");
+    }
+}
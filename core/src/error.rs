@@ -8,6 +8,7 @@ use core::fmt::Display;
 
 use alloc::boxed::Box;
 use alloc::string::String;
+use alloc::vec::Vec;
 use subxt_metadata::StorageHasher;
 
 /// The error emitted when something goes wrong.
@@ -114,6 +115,8 @@ pub enum MetadataError {
     IncompatibleCodegen,
     /// Custom value not found.
     CustomValueNameNotFound(String),
+    /// None of the extrinsic versions supported by the runtime (given here) can be constructed.
+    ExtrinsicVersionNotSupported(Vec<u8>),
 }
 impl Display for MetadataError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -149,6 +152,12 @@ impl Display for MetadataError {
             MetadataError::CustomValueNameNotFound(e) => {
                 write!(f, "Custom value with name {e} not found")
             }
+            MetadataError::ExtrinsicVersionNotSupported(versions) => {
+                write!(
+                    f,
+                    "The runtime supports extrinsic versions {versions:?}, none of which can be constructed"
+                )
+            }
         }
     }
 }
@@ -185,6 +194,13 @@ pub enum StorageAddressError {
         /// The invalid hasher that caused this error.
         hasher: StorageHasher,
     },
+    /// The storage entry is a plain value rather than a map, and so it cannot be iterated over.
+    StorageEntryNotIterable {
+        /// The name of the pallet that the storage entry is in.
+        pallet_name: String,
+        /// The name of the storage entry.
+        entry_name: String,
+    },
 }
 
 impl Display for StorageAddressError {
@@ -214,6 +230,13 @@ impl Display for StorageAddressError {
                 f,
                 "An invalid hasher was used to reconstruct a value with type ID {ty_id} from a hash formed by a {hasher:?} hasher. This is only possible for concat-style hashers or the identity hasher"
             ),
+            StorageAddressError::StorageEntryNotIterable {
+                pallet_name,
+                entry_name,
+            } => write!(
+                f,
+                "Storage entry {pallet_name}.{entry_name} is a plain value and not a map, so it cannot be iterated over"
+            ),
         }
     }
 }
@@ -236,6 +259,8 @@ pub enum ExtrinsicParamsError {
     },
     /// A signed extension in use on some chain was not provided.
     UnknownSignedExtension(String),
+    /// A signed extension that we need was not found in the metadata.
+    SignedExtensionNotFound(String),
     /// Some custom error.
     Custom(Box<dyn CustomError>),
 }
@@ -251,6 +276,10 @@ impl Display for ExtrinsicParamsError {
                 f,
                 "The chain expects a signed extension with the name {e}, but we did not provide one"
             ),
+            ExtrinsicParamsError::SignedExtensionNotFound(e) => write!(
+                f,
+                "The signed extension with the name {e} is needed, but the chain does not use it"
+            ),
             ExtrinsicParamsError::Custom(e) => {
                 write!(f, "Error constructing extrinsic parameters: {e}")
             }
@@ -38,6 +38,7 @@
 //! }
 //! ```
 
+use alloc::collections::BTreeMap;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 use codec::{Compact, Decode, Encode};
@@ -72,6 +73,14 @@ pub trait StaticEvent: DecodeAsFields {
     }
 }
 
+/// Implemented by types which represent any one of several [`StaticEvent`]s. This is usually
+/// implemented by generating an enum with `subxt::find_any!`, and is used in [`Events::find_any()`].
+pub trait AnyStaticEvent: Sized {
+    /// Attempt to decode the given event into one of the events that this type represents,
+    /// returning `Ok(None)` if it is not one of them.
+    fn decode_any<T: Config>(details: &EventDetails<T>) -> Result<Option<Self>, Error>;
+}
+
 /// A collection of events obtained from a block, bundled with the necessary
 /// information needed to decode and iterate over them.
 #[derive_where(Clone)]
@@ -139,7 +148,20 @@ impl<T: Config> Events<T> {
 
     /// Iterate over all of the events, using metadata to dynamically
     /// decode them as we go, and returning the raw bytes and other associated
-    /// details. If an error occurs, all subsequent iterations return `None`.
+    /// details.
+    ///
+    /// An event which can't be decoded (for instance because the metadata doesn't line up
+    /// with the block around a runtime upgrade) is returned as an error. Events aren't
+    /// individually length prefixed, so the length of such an event isn't known. Iteration
+    /// continues past it only if there is exactly one position after it from which all of
+    /// the remaining events decode and use up exactly the remaining bytes, in which case
+    /// that is where the next event is taken to start. Otherwise, all subsequent iterations
+    /// return `None`.
+    ///
+    /// Finding the next event means trying to decode the remaining events from every byte
+    /// after the undecodable one, which in the worst case costs a decode per remaining event
+    /// for every remaining byte. To keep this reasonable for large blocks, we give up looking
+    /// (and stop iterating) after 10,000 event decodes.
     // Dev note: The returned iterator is 'static + Send so that we can box it up and make
     // use of it with our `FilterEvents` stuff.
     pub fn iter(
@@ -166,10 +188,23 @@ impl<T: Config> Events<T> {
                         Some(Ok(event_details))
                     }
                     Err(e) => {
-                        // By setting the position to the "end" of the event bytes,
-                        // the cursor len will become 0 and the iterator will return `None`
-                        // from now on:
-                        pos = event_bytes.len();
+                        match find_next_event_start::<T>(
+                            &metadata,
+                            &event_bytes,
+                            pos,
+                            index + 1,
+                            num_events,
+                        ) {
+                            // Skip over the broken event and carry on from the next one:
+                            Some(next_pos) => {
+                                pos = next_pos;
+                                index += 1;
+                            }
+                            // By setting the position to the "end" of the event bytes,
+                            // the cursor len will become 0 and the iterator will return `None`
+                            // from now on:
+                            None => pos = event_bytes.len(),
+                        }
                         Some(Err(e))
                     }
                 }
@@ -179,7 +214,7 @@ impl<T: Config> Events<T> {
 
     /// Iterate through the events using metadata to dynamically decode and skip
     /// them, and return only those which should decode to the provided `Ev` type.
-    /// If an error occurs, all subsequent iterations return `None`.
+    /// Events which can't be decoded are returned as errors (see [`Events::iter()`]).
     pub fn find<Ev: StaticEvent>(&self) -> impl Iterator<Item = Result<Ev, Error>> + '_ {
         self.iter().filter_map(|ev| {
             ev.and_then(|ev| ev.as_event::<Ev>().map_err(Into::into))
@@ -203,6 +238,161 @@ impl<T: Config> Events<T> {
     pub fn has<Ev: StaticEvent>(&self) -> Result<bool, Error> {
         Ok(self.find::<Ev>().next().transpose()?.is_some())
     }
+
+    /// Iterate through the events using metadata to dynamically decode and skip
+    /// them, and return only those which decode to one of the events represented by `Ev`.
+    /// This checks for several event types in a single pass over the events.
+    /// Events which can't be decoded are returned as errors (see [`Events::iter()`]).
+    pub fn find_any<Ev: AnyStaticEvent>(&self) -> impl Iterator<Item = Result<Ev, Error>> + '_ {
+        self.iter()
+            .filter_map(|ev| ev.and_then(|ev| Ev::decode_any(&ev)).transpose())
+    }
+
+    /// Iterate over the events emitted while initializing the block, ie those in
+    /// [`Phase::Initialization`]. These aren't tied to any extrinsic, and include things
+    /// like session rotations that happen at the start of a block.
+    /// Events which can't be decoded are returned as errors (see [`Events::iter()`]).
+    pub fn initialization_events(
+        &self,
+    ) -> impl Iterator<Item = Result<EventDetails<T>, Error>> + Send + Sync + 'static {
+        self.iter_phase(Phase::Initialization)
+    }
+
+    /// Iterate over the events emitted while finalizing the block, ie those in
+    /// [`Phase::Finalization`]. These aren't tied to any extrinsic.
+    /// Events which can't be decoded are returned as errors (see [`Events::iter()`]).
+    pub fn finalization_events(
+        &self,
+    ) -> impl Iterator<Item = Result<EventDetails<T>, Error>> + Send + Sync + 'static {
+        self.iter_phase(Phase::Finalization)
+    }
+
+    // Iterate over the events in the given phase, passing any errors through.
+    fn iter_phase(
+        &self,
+        phase: Phase,
+    ) -> impl Iterator<Item = Result<EventDetails<T>, Error>> + Send + Sync + 'static {
+        self.iter().filter(move |ev| match ev {
+            Ok(ev) => ev.phase() == phase,
+            Err(_) => true,
+        })
+    }
+
+    /// Decode all of the events and group them by the [`Phase`] in which they were emitted.
+    /// Events emitted while applying an extrinsic are grouped by the index of that extrinsic,
+    /// which lines up with the index given by `ExtrinsicDetails::index()`, so that each
+    /// extrinsic in a block can be paired up with the events that it emitted.
+    pub fn grouped_by_extrinsic(&self) -> Result<GroupedEvents<T>, Error> {
+        let mut grouped = GroupedEvents {
+            initialization: Vec::new(),
+            extrinsics: BTreeMap::new(),
+            finalization: Vec::new(),
+        };
+
+        for ev in self.iter() {
+            let ev = ev?;
+            match ev.phase() {
+                Phase::Initialization => grouped.initialization.push(ev),
+                Phase::Finalization => grouped.finalization.push(ev),
+                Phase::ApplyExtrinsic(idx) => grouped.extrinsics.entry(idx).or_default().push(ev),
+            }
+        }
+
+        Ok(grouped)
+    }
+}
+
+/// The maximum number of event decodes attempted while looking for the start of the event
+/// after an undecodable one. See [`Events::iter()`].
+const MAX_RECOVERY_DECODES: usize = 10_000;
+
+/// Find where the event after some undecodable event at `pos` starts. Since events aren't
+/// length prefixed, we look for positions from which all of the remaining events decode and
+/// use up exactly the remaining bytes, and only trust the answer if there is just one. Gives
+/// up if this takes more than [`MAX_RECOVERY_DECODES`] event decodes.
+fn find_next_event_start<T: Config>(
+    metadata: &Metadata,
+    event_bytes: &Arc<[u8]>,
+    pos: usize,
+    next_index: u32,
+    num_events: u32,
+) -> Option<usize> {
+    let mut budget = MAX_RECOVERY_DECODES;
+    let mut next_pos = None;
+    for start in pos + 1..=event_bytes.len() {
+        let fits = remaining_events_fit::<T>(
+            metadata,
+            event_bytes,
+            start,
+            next_index,
+            num_events,
+            &mut budget,
+        )?;
+        if fits {
+            if next_pos.is_some() {
+                // More than one position fits, so we can't tell which is right.
+                return None;
+            }
+            next_pos = Some(start);
+        }
+    }
+    next_pos
+}
+
+/// Do the events from `index` onwards decode from `pos`, using up exactly the remaining bytes?
+/// Each decode attempt uses up some of the budget, and `None` is returned if it runs out.
+fn remaining_events_fit<T: Config>(
+    metadata: &Metadata,
+    event_bytes: &Arc<[u8]>,
+    mut pos: usize,
+    index: u32,
+    num_events: u32,
+    budget: &mut usize,
+) -> Option<bool> {
+    for index in index..num_events {
+        *budget = budget.checked_sub(1)?;
+        match EventDetails::<T>::decode_from(metadata.clone(), event_bytes.clone(), pos, index) {
+            Ok(event_details) => pos += event_details.bytes().len(),
+            Err(_) => return Some(false),
+        }
+    }
+    Some(pos == event_bytes.len())
+}
+
+/// Events grouped by the [`Phase`] in which they were emitted. This is returned from
+/// [`Events::grouped_by_extrinsic()`].
+#[derive_where(Debug, Clone)]
+pub struct GroupedEvents<T: Config> {
+    initialization: Vec<EventDetails<T>>,
+    extrinsics: BTreeMap<u32, Vec<EventDetails<T>>>,
+    finalization: Vec<EventDetails<T>>,
+}
+
+impl<T: Config> GroupedEvents<T> {
+    /// The events emitted while initializing the block.
+    pub fn initialization(&self) -> &[EventDetails<T>] {
+        &self.initialization
+    }
+
+    /// The events emitted while applying the extrinsic at the given index in the block.
+    /// This is empty if the extrinsic emitted no events or does not exist.
+    pub fn for_extrinsic(&self, extrinsic_index: u32) -> &[EventDetails<T>] {
+        self.extrinsics
+            .get(&extrinsic_index)
+            .map(|evs| &**evs)
+            .unwrap_or_default()
+    }
+
+    /// Iterate over the index of each extrinsic which emitted events, along
+    /// with the events that it emitted, in order of extrinsic index.
+    pub fn extrinsics(&self) -> impl Iterator<Item = (u32, &[EventDetails<T>])> + '_ {
+        self.extrinsics.iter().map(|(idx, evs)| (*idx, &**evs))
+    }
+
+    /// The events emitted while finalizing the block.
+    pub fn finalization(&self) -> &[EventDetails<T>] {
+        &self.finalization
+    }
 }
 
 /// A phase of a block's execution.
@@ -217,7 +407,7 @@ pub enum Phase {
 }
 
 /// The event details.
-#[derive(Debug, Clone)]
+#[derive_where(Debug, Clone)]
 pub struct EventDetails<T: Config> {
     phase: Phase,
     /// The index of the event in the list of events in a given block.
@@ -369,18 +559,29 @@ impl<T: Config> EventDetails<T> {
         let bytes = &mut self.field_bytes();
         let event_metadata = self.event_metadata();
 
-        let mut fields = event_metadata
-            .variant
-            .fields
-            .iter()
-            .map(|f| scale_decode::Field::new(f.ty.id, f.name.as_deref()));
-
-        let decoded =
-            scale_value::scale::decode_as_fields(bytes, &mut fields, self.metadata.types())?;
+        let decoded = crate::dynamic::decode_fields(
+            bytes,
+            &event_metadata.variant.fields,
+            self.metadata.types(),
+        )?;
 
         Ok(decoded)
     }
 
+    /// Decode the event fields and convert them into JSON of the form
+    /// `{ "pallet": "Balances", "event": "Transfer", "fields": { .. } }`. See
+    /// [`crate::utils::json`] for details on how the fields are converted.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn to_json(&self) -> Result<serde_json::Value, Error> {
+        let fields = self.field_values()?;
+        Ok(serde_json::json!({
+            "pallet": self.pallet_name(),
+            "event": self.variant_name(),
+            "fields": crate::utils::json::composite_to_json(&fields, self.metadata.types()),
+        }))
+    }
+
     /// Attempt to decode these [`EventDetails`] into a type representing the event fields.
     /// Such types are exposed in the codegen as `pallet_name::events::EventName` types.
     pub fn as_event<E: StaticEvent>(&self) -> Result<Option<E>, Error> {
@@ -708,6 +909,114 @@ mod tests {
         assert!(event_details.next().is_none());
     }
 
+    #[cfg(feature = "json")]
+    #[test]
+    fn event_to_json() {
+        #[derive(Clone, Debug, PartialEq, Decode, Encode, TypeInfo)]
+        enum Event {
+            Transfer {
+                from: [u8; 4],
+                to: [u8; 4],
+                amount: u128,
+            },
+        }
+
+        let metadata = metadata::<Event>();
+        let event = Event::Transfer {
+            from: [1; 4],
+            to: [2; 4],
+            amount: 1_000,
+        };
+        let events = events::<Event>(
+            metadata,
+            vec![event_record(Phase::ApplyExtrinsic(0), event)],
+        );
+
+        let ev = events.iter().next().unwrap().unwrap();
+        assert_eq!(
+            ev.to_json().unwrap(),
+            serde_json::json!({
+                "pallet": "Test",
+                "event": "Transfer",
+                "fields": {
+                    "from": "0x01010101",
+                    "to": "0x02020202",
+                    "amount": 1000
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn events_grouped_by_extrinsic() {
+        #[derive(Clone, Copy, Debug, PartialEq, Decode, Encode, TypeInfo)]
+        enum Event {
+            A(u8),
+        }
+
+        let metadata = metadata::<Event>();
+        let events = events::<Event>(
+            metadata,
+            vec![
+                event_record(Phase::Initialization, Event::A(1)),
+                event_record(Phase::ApplyExtrinsic(0), Event::A(2)),
+                event_record(Phase::ApplyExtrinsic(2), Event::A(3)),
+                event_record(Phase::ApplyExtrinsic(0), Event::A(4)),
+                event_record(Phase::Finalization, Event::A(5)),
+            ],
+        );
+
+        let grouped = events.grouped_by_extrinsic().unwrap();
+        let indexes = |evs: &[EventDetails<SubstrateConfig>]| -> Vec<u32> {
+            evs.iter().map(|ev| ev.index()).collect()
+        };
+
+        assert_eq!(indexes(grouped.initialization()), vec![0]);
+        assert_eq!(indexes(grouped.for_extrinsic(0)), vec![1, 3]);
+        assert_eq!(indexes(grouped.for_extrinsic(1)), Vec::<u32>::new());
+        assert_eq!(indexes(grouped.for_extrinsic(2)), vec![2]);
+        assert_eq!(indexes(grouped.finalization()), vec![4]);
+        assert_eq!(
+            grouped
+                .extrinsics()
+                .map(|(idx, evs)| (idx, evs.len()))
+                .collect::<Vec<_>>(),
+            vec![(0, 2), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn events_in_initialization_and_finalization_phases() {
+        #[derive(Clone, Copy, Debug, PartialEq, Decode, Encode, TypeInfo)]
+        enum Event {
+            A(u8),
+        }
+
+        let metadata = metadata::<Event>();
+        let events = events::<Event>(
+            metadata,
+            vec![
+                event_record(Phase::Initialization, Event::A(1)),
+                event_record(Phase::ApplyExtrinsic(0), Event::A(2)),
+                event_record(Phase::Initialization, Event::A(3)),
+                event_record(Phase::Finalization, Event::A(4)),
+                event_record(Phase::Finalization, Event::A(5)),
+            ],
+        );
+
+        let initialization: Vec<u32> = events
+            .initialization_events()
+            .map(|ev| ev.unwrap().index())
+            .collect();
+        let finalization: Vec<u32> = events
+            .finalization_events()
+            .map(|ev| ev.unwrap().index())
+            .collect();
+
+        assert_eq!(initialization, vec![0, 2]);
+        assert_eq!(finalization, vec![3, 4]);
+    }
+
     #[test]
     fn dynamically_decode_multiple_events() {
         #[derive(Clone, Copy, Debug, PartialEq, Decode, Encode, TypeInfo)]
@@ -835,6 +1144,86 @@ mod tests {
         assert!(events_iter.next().is_none());
     }
 
+    #[test]
+    fn dynamically_decode_events_after_a_corrupt_event() {
+        #[derive(Clone, Debug, PartialEq, Decode, Encode, TypeInfo)]
+        enum Event {
+            A(u8),
+            B(bool),
+        }
+
+        let metadata = metadata::<Event>();
+
+        let mut event_bytes = vec![];
+        event_record(Phase::Initialization, Event::A(1)).encode_to(&mut event_bytes);
+        // A corrupt event: the phase and variant are fine, but 7 isn't a valid bool:
+        let mut corrupt = event_record(Phase::ApplyExtrinsic(0), Event::B(true)).encode();
+        let bool_idx = Phase::ApplyExtrinsic(0).encoded_size() + 2;
+        corrupt[bool_idx] = 7;
+        event_bytes.extend(corrupt);
+        event_record(Phase::ApplyExtrinsic(1), Event::B(true)).encode_to(&mut event_bytes);
+        event_record(Phase::Finalization, Event::A(2)).encode_to(&mut event_bytes);
+
+        let events = events_raw(metadata, event_bytes, 4);
+        let results: Vec<_> = events.iter().collect();
+        assert_eq!(results.len(), 4);
+
+        // The corrupt event is reported, and the events either side of it still decode:
+        assert!(results[1].is_err());
+        let decoded: Vec<_> = results
+            .into_iter()
+            .filter_map(Result::ok)
+            .map(|ev| {
+                let fields = ev
+                    .field_values()
+                    .unwrap()
+                    .into_values()
+                    .map(|v| v.remove_context())
+                    .collect::<Vec<_>>();
+                (ev.index(), ev.variant_name().to_owned(), fields)
+            })
+            .collect();
+        assert_eq!(
+            decoded,
+            vec![
+                (0, "A".to_owned(), vec![Value::u128(1)]),
+                (2, "B".to_owned(), vec![Value::bool(true)]),
+                (3, "A".to_owned(), vec![Value::u128(2)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn finding_the_event_after_a_corrupt_event_is_bounded() {
+        #[derive(Clone, Debug, PartialEq, Decode, Encode, TypeInfo)]
+        enum Event {
+            A(u8),
+            B(bool),
+        }
+
+        let metadata = metadata::<Event>();
+
+        let mut event_bytes = vec![];
+        event_record(Phase::Initialization, Event::A(1)).encode_to(&mut event_bytes);
+        let mut corrupt = event_record(Phase::ApplyExtrinsic(0), Event::B(true)).encode();
+        let bool_idx = Phase::ApplyExtrinsic(0).encoded_size() + 2;
+        corrupt[bool_idx] = 7;
+        event_bytes.extend(corrupt);
+        // Enough events after the corrupt one that checking every position after it
+        // would take more decodes than we allow:
+        for _ in 0..200 {
+            event_record(Phase::Finalization, Event::A(2)).encode_to(&mut event_bytes);
+        }
+
+        let events = events_raw(metadata, event_bytes, 202);
+        let results: Vec<_> = events.iter().collect();
+
+        // We give up after the corrupt event rather than searching for the next one:
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
+
     #[test]
     fn compact_event_field() {
         #[derive(Clone, Debug, PartialEq, Encode, Decode, TypeInfo)]
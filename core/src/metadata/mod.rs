@@ -15,14 +15,47 @@
 //! let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_small.scale");
 //! let metadata = metadata::decode_from(&metadata_bytes[..]).unwrap();
 //! ```
+//!
+//! # Custom decoding
+//!
+//! The types in the metadata can be used to decode bytes directly into your own types, without
+//! going via [`scale_value::Value`]. Any type implementing [`scale_decode::DecodeAsType`] (which
+//! can be derived) can be decoded using [`decode_as()`], and any type implementing
+//! [`scale_encode::EncodeAsType`] can be encoded using [`encode_as()`]. Named fields are matched
+//! up by name, and numbers can be decoded into any numeric type that is large enough to hold them.
+//!
+//! ```rust
+//! use subxt_core::metadata;
+//!
+//! let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_small.scale");
+//! let metadata = metadata::decode_from(&metadata_bytes[..]).unwrap();
+//!
+//! // Find the type of the values stored in `System.Number`:
+//! let number_ty = metadata
+//!     .pallet_by_name("System")
+//!     .and_then(|p| p.storage())
+//!     .and_then(|s| s.entry_by_name("Number"))
+//!     .map(|e| e.entry_type().value_ty())
+//!     .unwrap();
+//!
+//! // Decode some bytes representing a block number into a u64:
+//! let number: u64 = metadata::decode_as(&[1, 0, 0, 0], number_ty, &metadata).unwrap();
+//! assert_eq!(number, 1);
+//! ```
 
 mod decode_encode_traits;
 mod metadata_type;
+mod type_description;
 
+use crate::error::Error;
+use alloc::vec::Vec;
 use codec::Decode;
 
 pub use decode_encode_traits::{DecodeWithMetadata, EncodeWithMetadata};
 pub use metadata_type::Metadata;
+pub use type_description::{
+    describe_type, FieldDescription, TypeDescription, TypeDescriptionKind, VariantDescription,
+};
 
 /// Attempt to decode some bytes into [`Metadata`], returning an error
 /// if decoding fails.
@@ -32,3 +65,100 @@ pub use metadata_type::Metadata;
 pub fn decode_from(bytes: &[u8]) -> Result<Metadata, codec::Error> {
     Metadata::decode(&mut &*bytes)
 }
+
+/// Decode some bytes into any type implementing [`scale_decode::DecodeAsType`], using the type
+/// with the given ID in the metadata to determine how they should be decoded. Any bytes left
+/// over once the type has been decoded are ignored.
+///
+/// See [the module docs](self) for an example.
+pub fn decode_as<T: scale_decode::DecodeAsType>(
+    bytes: &[u8],
+    type_id: u32,
+    metadata: &Metadata,
+) -> Result<T, Error> {
+    let val = T::decode_with_metadata(&mut &*bytes, type_id, metadata)?;
+    Ok(val)
+}
+
+/// Encode any type implementing [`scale_encode::EncodeAsType`] into bytes, using the type with
+/// the given ID in the metadata to determine the shape that the bytes should have.
+pub fn encode_as<T: scale_encode::EncodeAsType + ?Sized>(
+    value: &T,
+    type_id: u32,
+    metadata: &Metadata,
+) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    value.encode_with_metadata(type_id, metadata, &mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use codec::Encode;
+
+    fn metadata() -> Metadata {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        decode_from(&bytes[..]).unwrap()
+    }
+
+    fn account_info_ty(metadata: &Metadata) -> u32 {
+        metadata
+            .pallet_by_name("System")
+            .and_then(|p| p.storage())
+            .and_then(|s| s.entry_by_name("Account"))
+            .map(|e| e.entry_type().value_ty())
+            .unwrap()
+    }
+
+    #[derive(Encode, scale_decode::DecodeAsType, scale_encode::EncodeAsType, Debug, PartialEq)]
+    struct AccountInfo {
+        nonce: u32,
+        consumers: u32,
+        providers: u32,
+        sufficients: u32,
+        data: AccountData,
+    }
+
+    #[derive(Encode, scale_decode::DecodeAsType, scale_encode::EncodeAsType, Debug, PartialEq)]
+    struct AccountData {
+        free: u128,
+        reserved: u128,
+        frozen: u128,
+        flags: u128,
+    }
+
+    fn account_info() -> AccountInfo {
+        AccountInfo {
+            nonce: 1,
+            consumers: 2,
+            providers: 3,
+            sufficients: 4,
+            data: AccountData {
+                free: 1_000,
+                reserved: 200,
+                frozen: 30,
+                flags: 1 << 127,
+            },
+        }
+    }
+
+    #[test]
+    fn decode_as_custom_struct() {
+        let metadata = metadata();
+        let ty = account_info_ty(&metadata);
+
+        let bytes = account_info().encode();
+        let decoded: AccountInfo = decode_as(&bytes, ty, &metadata).unwrap();
+        assert_eq!(decoded, account_info());
+    }
+
+    #[test]
+    fn encode_as_custom_struct() {
+        let metadata = metadata();
+        let ty = account_info_ty(&metadata);
+
+        let bytes = encode_as(&account_info(), ty, &metadata).unwrap();
+        assert_eq!(bytes, account_info().encode());
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use super::Metadata;
+use crate::error::{Error, MetadataError};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use scale_info::{form::PortableForm, Field, TypeDef, TypeDefPrimitive};
+
+/// A description of the shape of some type in the metadata, including the names of any
+/// fields and variants. This is obtained via [`describe_type()`], and is useful for
+/// rendering dynamically decoded values (ie [`scale_value::Value`]s) along with their types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeDescription {
+    /// The ID of the type in the metadata.
+    pub type_id: u32,
+    /// The path to the type, if it has one (eg `sp_version::RuntimeVersion`).
+    pub path: Option<String>,
+    /// The shape of the type.
+    pub kind: TypeDescriptionKind,
+}
+
+/// The shape of some type described by a [`TypeDescription`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeDescriptionKind {
+    /// A struct with named or unnamed fields.
+    Composite(Vec<FieldDescription>),
+    /// An enum.
+    Variant(Vec<VariantDescription>),
+    /// A variable length sequence of items of the given type.
+    Sequence(Box<TypeDescription>),
+    /// A fixed length array of items of the given type.
+    Array {
+        /// The length of the array.
+        len: u32,
+        /// The type of each item in the array.
+        item: Box<TypeDescription>,
+    },
+    /// A tuple of the given types.
+    Tuple(Vec<TypeDescription>),
+    /// A primitive type.
+    Primitive(TypeDefPrimitive),
+    /// A compact encoded version of the given type.
+    Compact(Box<TypeDescription>),
+    /// A sequence of bits.
+    BitSequence,
+    /// A reference back to a type which contains this one, and has
+    /// already been described further up the tree.
+    Recursive,
+}
+
+/// A description of a field in a composite type or variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDescription {
+    /// The name of the field, if it's named.
+    pub name: Option<String>,
+    /// The name of the field's type as written in the source, if known.
+    pub type_name: Option<String>,
+    /// A description of the field's type.
+    pub ty: TypeDescription,
+}
+
+/// A description of a single variant of an enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantDescription {
+    /// The name of the variant.
+    pub name: String,
+    /// The index of the variant.
+    pub index: u8,
+    /// The fields of the variant.
+    pub fields: Vec<FieldDescription>,
+}
+
+/// Describe the type with the given ID in the metadata. Types which contain themselves are
+/// described once, and then any inner references to them are [`TypeDescriptionKind::Recursive`].
+pub fn describe_type(type_id: u32, metadata: &Metadata) -> Result<TypeDescription, Error> {
+    describe_type_inner(type_id, metadata, &mut Vec::new())
+}
+
+fn describe_type_inner(
+    type_id: u32,
+    metadata: &Metadata,
+    parents: &mut Vec<u32>,
+) -> Result<TypeDescription, Error> {
+    let ty = metadata
+        .types()
+        .resolve(type_id)
+        .ok_or(MetadataError::TypeNotFound(type_id))?;
+    let path = (!ty.path.segments.is_empty()).then(|| ty.path.segments.join("::"));
+
+    if parents.contains(&type_id) {
+        return Ok(TypeDescription {
+            type_id,
+            path,
+            kind: TypeDescriptionKind::Recursive,
+        });
+    }
+    parents.push(type_id);
+
+    let kind = match &ty.type_def {
+        TypeDef::Composite(c) => {
+            TypeDescriptionKind::Composite(describe_fields(&c.fields, metadata, parents)?)
+        }
+        TypeDef::Variant(v) => TypeDescriptionKind::Variant(
+            v.variants
+                .iter()
+                .map(|var| {
+                    Ok(VariantDescription {
+                        name: var.name.clone(),
+                        index: var.index,
+                        fields: describe_fields(&var.fields, metadata, parents)?,
+                    })
+                })
+                .collect::<Result<_, Error>>()?,
+        ),
+        TypeDef::Sequence(s) => {
+            TypeDescriptionKind::Sequence(describe_boxed(s.type_param.id, metadata, parents)?)
+        }
+        TypeDef::Array(a) => TypeDescriptionKind::Array {
+            len: a.len,
+            item: describe_boxed(a.type_param.id, metadata, parents)?,
+        },
+        TypeDef::Tuple(t) => TypeDescriptionKind::Tuple(
+            t.fields
+                .iter()
+                .map(|f| describe_type_inner(f.id, metadata, parents))
+                .collect::<Result<_, _>>()?,
+        ),
+        TypeDef::Primitive(p) => TypeDescriptionKind::Primitive(p.clone()),
+        TypeDef::Compact(c) => {
+            TypeDescriptionKind::Compact(describe_boxed(c.type_param.id, metadata, parents)?)
+        }
+        TypeDef::BitSequence(_) => TypeDescriptionKind::BitSequence,
+    };
+
+    parents.pop();
+    Ok(TypeDescription {
+        type_id,
+        path,
+        kind,
+    })
+}
+
+fn describe_boxed(
+    type_id: u32,
+    metadata: &Metadata,
+    parents: &mut Vec<u32>,
+) -> Result<Box<TypeDescription>, Error> {
+    describe_type_inner(type_id, metadata, parents).map(Box::new)
+}
+
+fn describe_fields(
+    fields: &[Field<PortableForm>],
+    metadata: &Metadata,
+    parents: &mut Vec<u32>,
+) -> Result<Vec<FieldDescription>, Error> {
+    fields
+        .iter()
+        .map(|f| {
+            Ok(FieldDescription {
+                name: f.name.clone(),
+                type_name: f.type_name.clone(),
+                ty: describe_type_inner(f.ty.id, metadata, parents)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    fn metadata() -> Metadata {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        crate::metadata::decode_from(&bytes[..]).unwrap()
+    }
+
+    #[test]
+    fn describes_runtime_version() {
+        let metadata = metadata();
+        let output_ty = metadata
+            .runtime_api_trait_by_name("Core")
+            .and_then(|t| t.method_by_name("version"))
+            .unwrap()
+            .output_ty();
+
+        let description = describe_type(output_ty, &metadata).unwrap();
+        assert_eq!(
+            description.path.as_deref(),
+            Some("sp_version::RuntimeVersion")
+        );
+
+        let TypeDescriptionKind::Composite(fields) = &description.kind else {
+            panic!("RuntimeVersion should be a composite type");
+        };
+        let names: Vec<_> = fields.iter().map(|f| f.name.as_deref().unwrap()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "spec_name",
+                "impl_name",
+                "authoring_version",
+                "spec_version",
+                "impl_version",
+                "apis",
+                "transaction_version",
+                "state_version"
+            ]
+        );
+        assert_eq!(
+            fields[3].ty.kind,
+            TypeDescriptionKind::Primitive(TypeDefPrimitive::U32)
+        );
+    }
+
+    #[test]
+    fn recursive_types_are_described_once() {
+        let metadata = metadata();
+        // XCM programs contain instructions (eg `SetErrorHandler`) which contain programs.
+        let xcm_ty = metadata
+            .types()
+            .types
+            .iter()
+            .find(|ty| {
+                ty.ty.path.segments.first().map(|s| s.as_str()) == Some("staging_xcm")
+                    && ty.ty.path.segments.last().map(|s| s.as_str()) == Some("Xcm")
+            })
+            .unwrap()
+            .id;
+        let description = describe_type(xcm_ty, &metadata).unwrap();
+
+        fn contains_recursive(d: &TypeDescription, id: u32) -> bool {
+            let fields_contain =
+                |fields: &[FieldDescription]| fields.iter().any(|f| contains_recursive(&f.ty, id));
+            match &d.kind {
+                TypeDescriptionKind::Recursive => d.type_id == id,
+                TypeDescriptionKind::Composite(fields) => fields_contain(fields),
+                TypeDescriptionKind::Variant(vars) => {
+                    vars.iter().any(|v| fields_contain(&v.fields))
+                }
+                TypeDescriptionKind::Sequence(t)
+                | TypeDescriptionKind::Compact(t)
+                | TypeDescriptionKind::Array { item: t, .. } => contains_recursive(t, id),
+                TypeDescriptionKind::Tuple(ts) => ts.iter().any(|t| contains_recursive(t, id)),
+                TypeDescriptionKind::Primitive(_) | TypeDescriptionKind::BitSequence => false,
+            }
+        }
+
+        assert!(contains_recursive(&description, xcm_ty));
+    }
+}
@@ -44,7 +44,7 @@
 pub mod payload;
 
 use crate::error::{Error, MetadataError};
-use crate::metadata::{DecodeWithMetadata, Metadata};
+use crate::metadata::{describe_type, DecodeWithMetadata, Metadata, TypeDescription};
 use alloc::borrow::ToOwned;
 use alloc::format;
 use alloc::string::String;
@@ -100,3 +100,275 @@ pub fn decode_value<P: Payload>(
 
     Ok(val)
 }
+
+/// Decode the value bytes at the location given by the provided runtime API payload, and also
+/// return a description of the type that was decoded. This is particularly useful alongside
+/// [`payload::dynamic()`], where the decoded [`scale_value::Value`] can be rendered along with
+/// the names and types of its fields.
+pub fn decode_value_with_description<P: Payload>(
+    bytes: &mut &[u8],
+    payload: &P,
+    metadata: &Metadata,
+) -> Result<(P::ReturnType, TypeDescription), Error> {
+    let value = decode_value(bytes, payload, metadata)?;
+    let description = output_type_description(payload, metadata)?;
+    Ok((value, description))
+}
+
+/// Return a description of the type returned from the runtime API call given by the payload.
+pub fn output_type_description<P: Payload>(
+    payload: &P,
+    metadata: &Metadata,
+) -> Result<TypeDescription, Error> {
+    let api_method = metadata
+        .runtime_api_trait_by_name_err(payload.trait_name())?
+        .method_by_name(payload.method_name())
+        .ok_or_else(|| MetadataError::RuntimeMethodNotFound(payload.method_name().to_owned()))?;
+    describe_type(api_method.output_ty(), metadata)
+}
+
+/// Runtime APIs often return a `Result` whose error is the runtime's `DispatchError`. If the runtime
+/// API given by the payload returns such a `Result` (or a `Result` whose `Ok` type is such a
+/// `Result`, as with `BlockBuilder_apply_extrinsic`), and the value bytes given represent the
+/// `DispatchError` case, this returns the bytes of the `DispatchError` so that they can be decoded
+/// into something more readable. In any other case, `None` is returned.
+pub fn dispatch_error_bytes<'a, P: Payload>(
+    bytes: &'a [u8],
+    payload: &P,
+    metadata: &Metadata,
+) -> Result<Option<&'a [u8]>, Error> {
+    let Some(dispatch_error_ty) = metadata.dispatch_error_ty() else {
+        return Ok(None);
+    };
+
+    let api_method = metadata
+        .runtime_api_trait_by_name_err(payload.trait_name())?
+        .method_by_name(payload.method_name())
+        .ok_or_else(|| MetadataError::RuntimeMethodNotFound(payload.method_name().to_owned()))?;
+
+    let mut ty_id = api_method.output_ty();
+    let mut bytes = bytes;
+    loop {
+        let Some(result_ty) = ResultType::from_type_id(ty_id, metadata)? else {
+            return Ok(None);
+        };
+        let Some((&variant_index, rest)) = bytes.split_first() else {
+            return Ok(None);
+        };
+
+        if variant_index == result_ty.err_index {
+            return Ok((result_ty.err_ty == dispatch_error_ty).then_some(rest));
+        } else if variant_index == result_ty.ok_index {
+            // Look for a nested DispatchError in the Ok value:
+            ty_id = result_ty.ok_ty;
+            bytes = rest;
+        } else {
+            return Ok(None);
+        }
+    }
+}
+
+/// The variant indexes and type IDs of some `Result` type.
+struct ResultType {
+    ok_index: u8,
+    ok_ty: u32,
+    err_index: u8,
+    err_ty: u32,
+}
+
+impl ResultType {
+    fn from_type_id(ty_id: u32, metadata: &Metadata) -> Result<Option<Self>, Error> {
+        let ty = metadata
+            .types()
+            .resolve(ty_id)
+            .ok_or(MetadataError::TypeNotFound(ty_id))?;
+
+        if ty.path.segments != ["Result"] {
+            return Ok(None);
+        }
+        let scale_info::TypeDef::Variant(variant) = &ty.type_def else {
+            return Ok(None);
+        };
+
+        let find = |name: &str| {
+            variant
+                .variants
+                .iter()
+                .find(|v| v.name == name && v.fields.len() == 1)
+                .map(|v| (v.index, v.fields[0].ty.id))
+        };
+        let (Some((ok_index, ok_ty)), Some((err_index, err_ty))) = (find("Ok"), find("Err")) else {
+            return Ok(None);
+        };
+
+        Ok(Some(ResultType {
+            ok_index,
+            ok_ty,
+            err_index,
+            err_ty,
+        }))
+    }
+}
+
+/// The index of the page being requested from a paginated runtime API. This is handed to
+/// the closure used to construct each payload in [`call_paged`], starting from `0` and
+/// incrementing by one for each subsequent call.
+pub type Cursor = u32;
+
+/// A convenience helper for working with runtime APIs which return results one page at a
+/// time. This is equivalent to making repeated single calls yourself.
+///
+/// `make_payload` is called with an incrementing [`Cursor`] to construct each payload, and
+/// `call` is expected to make the runtime API call and hand back the decoded value. This
+/// continues until a call returns an empty page (ie a value which yields no items when
+/// iterated over), at which point every item seen so far is returned in order. The first
+/// error encountered is returned immediately.
+pub fn call_paged<P, E>(
+    mut make_payload: impl FnMut(Cursor) -> P,
+    mut call: impl FnMut(P) -> Result<P::ReturnType, E>,
+) -> Result<Vec<<P::ReturnType as IntoIterator>::Item>, E>
+where
+    P: Payload,
+    P::ReturnType: IntoIterator,
+{
+    let mut items = Vec::new();
+    let mut cursor: Cursor = 0;
+    loop {
+        let page_len_before = items.len();
+        items.extend(call(make_payload(cursor))?);
+        if items.len() == page_len_before {
+            return Ok(items);
+        }
+        cursor += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+    use payload::DefaultPayload;
+
+    type PagedPayload = DefaultPayload<(u32,), Vec<u64>>;
+
+    fn paged_payload(cursor: Cursor) -> PagedPayload {
+        DefaultPayload::new("Staking", "exposure_page", (cursor,))
+    }
+
+    #[test]
+    fn decode_dynamic_value_with_description() {
+        use crate::metadata::TypeDescriptionKind;
+        use scale_info::TypeDefPrimitive;
+        use scale_value::{Value, ValueDef};
+
+        let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_small.scale");
+        let metadata = crate::metadata::decode_from(&metadata_bytes[..]).unwrap();
+
+        let payload = payload::dynamic("Metadata", "metadata_versions", Vec::<Value>::new());
+        let value_bytes = hex::decode("080e0000000f000000").unwrap();
+        let (value, description) =
+            decode_value_with_description(&mut &*value_bytes, &payload, &metadata).unwrap();
+
+        let value = value.to_value().unwrap();
+        let ValueDef::Composite(items) = &value.value else {
+            panic!("expected a sequence of versions");
+        };
+        let versions: Vec<_> = items.values().map(|v| v.as_u128().unwrap()).collect();
+        assert_eq!(versions, vec![14, 15]);
+
+        let TypeDescriptionKind::Sequence(item) = description.kind else {
+            panic!("expected a sequence type");
+        };
+        assert_eq!(
+            item.kind,
+            TypeDescriptionKind::Primitive(TypeDefPrimitive::U32)
+        );
+    }
+
+    #[test]
+    fn dispatch_errors_are_found_in_nested_results() {
+        let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        let metadata = crate::metadata::decode_from(&metadata_bytes[..]).unwrap();
+
+        // Returns Result<Result<(), DispatchError>, TransactionValidityError>:
+        let payload = payload::dynamic(
+            "BlockBuilder",
+            "apply_extrinsic",
+            Vec::<scale_value::Value>::new(),
+        );
+
+        // Ok(Err(DispatchError::Module { index: 5, error: [1, 0, 0, 0] })):
+        let bytes = [0, 1, 3, 5, 1, 0, 0, 0];
+        assert_eq!(
+            dispatch_error_bytes(&bytes, &payload, &metadata).unwrap(),
+            Some(&bytes[2..])
+        );
+
+        // Ok(Ok(())):
+        let bytes = [0, 0];
+        assert_eq!(
+            dispatch_error_bytes(&bytes, &payload, &metadata).unwrap(),
+            None
+        );
+
+        // Err(TransactionValidityError::Invalid(InvalidTransaction::Payment)) isn't a DispatchError:
+        let bytes = [1, 0, 1];
+        assert_eq!(
+            dispatch_error_bytes(&bytes, &payload, &metadata).unwrap(),
+            None
+        );
+
+        // Values which aren't Results are ignored:
+        let payload = payload::dynamic(
+            "Metadata",
+            "metadata_versions",
+            Vec::<scale_value::Value>::new(),
+        );
+        let bytes = hex::decode("080e0000000f000000").unwrap();
+        assert_eq!(
+            dispatch_error_bytes(&bytes, &payload, &metadata).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn call_paged_collects_every_page_until_empty() {
+        // A mock paginated API, which hands back one of these pages per call:
+        let pages: Vec<Vec<u64>> = vec![vec![1, 2, 3], vec![4, 5], vec![6]];
+        let mut seen_cursors = vec![];
+
+        let items = call_paged(paged_payload, |payload| {
+            let cursor = payload.args_data().0;
+            seen_cursors.push(cursor);
+            Ok::<_, ()>(pages.get(cursor as usize).cloned().unwrap_or_default())
+        })
+        .unwrap();
+
+        assert_eq!(items, vec![1, 2, 3, 4, 5, 6]);
+        // We stop after the first empty page:
+        assert_eq!(seen_cursors, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn call_paged_stops_on_empty_first_page() {
+        let mut calls = 0;
+        let items = call_paged(paged_payload, |_| {
+            calls += 1;
+            Ok::<_, ()>(vec![])
+        })
+        .unwrap();
+
+        assert!(items.is_empty());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn call_paged_returns_first_error() {
+        let res = call_paged(paged_payload, |payload| match payload.args_data().0 {
+            0 => Ok(vec![1]),
+            n => Err(n),
+        });
+
+        assert_eq!(res, Err(1));
+    }
+}
@@ -46,7 +46,11 @@ mod utils;
 
 pub mod address;
 
-use crate::{error::MetadataError, metadata::DecodeWithMetadata, Error, Metadata};
+use crate::{
+    error::{MetadataError, StorageAddressError},
+    metadata::DecodeWithMetadata,
+    Error, Metadata,
+};
 use address::Address;
 use alloc::vec::Vec;
 
@@ -78,6 +82,28 @@ pub fn validate<Addr: Address>(address: &Addr, metadata: &Metadata) -> Result<()
     Ok(())
 }
 
+/// Check that the storage entry pointed to by the given `address` is a map, and so can be iterated
+/// over. Static addresses are only iterable if they point to maps, but dynamic addresses can point
+/// to anything, and so this returns [`StorageAddressError::StorageEntryNotIterable`] if the entry
+/// is a plain value.
+pub fn validate_iterable<Addr: Address>(address: &Addr, metadata: &Metadata) -> Result<(), Error> {
+    let pallet_name = address.pallet_name();
+    let entry_name = address.entry_name();
+
+    let (_, entry_metadata) =
+        utils::lookup_storage_entry_details(pallet_name, entry_name, metadata)?;
+    match entry_metadata.entry_type() {
+        subxt_metadata::StorageEntryType::Map { .. } => Ok(()),
+        subxt_metadata::StorageEntryType::Plain(_) => {
+            Err(StorageAddressError::StorageEntryNotIterable {
+                pallet_name: pallet_name.into(),
+                entry_name: entry_name.into(),
+            }
+            .into())
+        }
+    }
+}
+
 /// Given a storage address and some metadata, this encodes the address into bytes which can be
 /// handed to a node to retrieve the corresponding value.
 pub fn get_address_bytes<Addr: Address>(
@@ -140,3 +166,32 @@ pub fn default_value<Addr: Address>(
     let val = Addr::Target::decode_with_metadata(&mut &*default_bytes, value_ty_id, metadata)?;
     Ok(val)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    fn metadata() -> Metadata {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_small.scale");
+        crate::metadata::decode_from(&bytes[..]).unwrap()
+    }
+
+    #[test]
+    fn plain_entries_are_not_iterable() {
+        let metadata = metadata();
+
+        let plain = address::dynamic("System", "Number", ());
+        let res = validate_iterable(&plain, &metadata);
+        assert_matches!(
+            res,
+            Err(Error::StorageAddress(StorageAddressError::StorageEntryNotIterable {
+                pallet_name,
+                entry_name,
+            })) if pallet_name == "System" && entry_name == "Number"
+        );
+
+        let map = address::dynamic("System", "Account", ());
+        assert!(validate_iterable(&map, &metadata).is_ok());
+    }
+}
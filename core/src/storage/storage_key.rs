@@ -263,8 +263,7 @@ impl StorageKey for Vec<scale_value::Value> {
         for (hasher, ty_id) in hashers.by_ref() {
             match consume_hash_returning_key_bytes(bytes, hasher, ty_id, types)? {
                 Some(value_bytes) => {
-                    let value =
-                        scale_value::scale::decode_as_type(&mut &*value_bytes, ty_id, types)?;
+                    let value = crate::dynamic::decode_value(&mut &*value_bytes, ty_id, types)?;
                     result.push(value.remove_context());
                 }
                 None => {
@@ -9,6 +9,7 @@
 use super::address::Address;
 use crate::error::{Error, MetadataError};
 use crate::metadata::Metadata;
+use crate::utils::hashing;
 use alloc::borrow::ToOwned;
 use alloc::vec::Vec;
 use subxt_metadata::{PalletMetadata, StorageEntryMetadata, StorageHasher};
@@ -16,24 +17,24 @@ use subxt_metadata::{PalletMetadata, StorageEntryMetadata, StorageHasher};
 /// Return the root of a given [`Address`]: hash the pallet name and entry name
 /// and append those bytes to the output.
 pub fn write_storage_address_root_bytes<Addr: Address>(addr: &Addr, out: &mut Vec<u8>) {
-    out.extend(sp_crypto_hashing::twox_128(addr.pallet_name().as_bytes()));
-    out.extend(sp_crypto_hashing::twox_128(addr.entry_name().as_bytes()));
+    out.extend(hashing::twox_128(addr.pallet_name().as_bytes()));
+    out.extend(hashing::twox_128(addr.entry_name().as_bytes()));
 }
 
 /// Take some SCALE encoded bytes and a [`StorageHasher`] and hash the bytes accordingly.
 pub fn hash_bytes(input: &[u8], hasher: StorageHasher, bytes: &mut Vec<u8>) {
     match hasher {
         StorageHasher::Identity => bytes.extend(input),
-        StorageHasher::Blake2_128 => bytes.extend(sp_crypto_hashing::blake2_128(input)),
+        StorageHasher::Blake2_128 => bytes.extend(hashing::blake2_128(input)),
         StorageHasher::Blake2_128Concat => {
-            bytes.extend(sp_crypto_hashing::blake2_128(input));
+            bytes.extend(hashing::blake2_128(input));
             bytes.extend(input);
         }
-        StorageHasher::Blake2_256 => bytes.extend(sp_crypto_hashing::blake2_256(input)),
-        StorageHasher::Twox128 => bytes.extend(sp_crypto_hashing::twox_128(input)),
-        StorageHasher::Twox256 => bytes.extend(sp_crypto_hashing::twox_256(input)),
+        StorageHasher::Blake2_256 => bytes.extend(hashing::blake2_256(input)),
+        StorageHasher::Twox128 => bytes.extend(hashing::twox_128(input)),
+        StorageHasher::Twox256 => bytes.extend(hashing::twox_256(input)),
         StorageHasher::Twox64Concat => {
-            bytes.extend(sp_crypto_hashing::twox_64(input));
+            bytes.extend(hashing::twox_64(input));
             bytes.extend(input);
         }
     }
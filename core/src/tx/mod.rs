@@ -55,21 +55,42 @@
 //! ```
 
 pub mod payload;
+pub mod remark;
 pub mod signer;
 
+mod signer_payload_json;
+mod verify;
+
 use crate::config::{Config, ExtrinsicParams, ExtrinsicParamsEncoder, Hasher};
 use crate::error::{Error, MetadataError};
 use crate::metadata::Metadata;
-use crate::utils::Encoded;
+use crate::utils::hashing::blake2_256;
+use crate::utils::{AccountId32, Encoded};
 use alloc::borrow::{Cow, ToOwned};
 use alloc::vec::Vec;
 use codec::{Compact, Encode};
 use payload::Payload;
 use signer::Signer as SignerT;
-use sp_crypto_hashing::blake2_256;
 
 // Expose these here since we expect them in some calls below.
 pub use crate::client::{ClientState, RuntimeVersion};
+pub use signer_payload_json::SignerPayloadJson;
+pub use verify::{verify_signature, VerifySignature};
+
+/// The extrinsic versions that we know how to construct.
+const SUPPORTED_EXTRINSIC_VERSIONS: [u8; 1] = [4];
+
+/// Return the highest extrinsic version that is supported by the runtime and that we know how to
+/// construct, or an error if there isn't one.
+pub fn extrinsic_version(metadata: &Metadata) -> Result<u8, Error> {
+    let extrinsic = metadata.extrinsic();
+    extrinsic
+        .highest_compatible_version(&SUPPORTED_EXTRINSIC_VERSIONS)
+        .ok_or_else(|| {
+            MetadataError::ExtrinsicVersionNotSupported(extrinsic.supported_versions().to_vec())
+                .into()
+        })
+}
 
 /// Run the validation logic against some extrinsic you'd like to submit. Returns `Ok(())`
 /// if the call is valid (or if it's not possible to check since the call has no validation hash).
@@ -104,12 +125,13 @@ pub fn create_unsigned<T: Config, Call: Payload>(
     // 1. Validate this call against the current node metadata if the call comes
     // with a hash allowing us to do so.
     validate(call, metadata)?;
+    let version = extrinsic_version(metadata)?;
 
     // 2. Encode extrinsic
     let extrinsic = {
         let mut encoded_inner = Vec::new();
-        // transaction protocol version (4) (is not signed, so no 1 bit at the front).
-        4u8.encode_to(&mut encoded_inner);
+        // transaction protocol version (is not signed, so no 1 bit at the front).
+        version.encode_to(&mut encoded_inner);
         // encode call data after this byte.
         call.encode_call_data_to(metadata, &mut encoded_inner)?;
         // now, prefix byte length:
@@ -138,6 +160,7 @@ pub fn create_partial_signed<T: Config, Call: Payload>(
     // 1. Validate this call against the current node metadata if the call comes
     // with a hash allowing us to do so.
     validate(call, &client_state.metadata)?;
+    let version = extrinsic_version(&client_state.metadata)?;
 
     // 2. SCALE encode call data to bytes (pallet u8, call u8, call params).
     let call_data = call_data(call, &client_state.metadata)?;
@@ -148,6 +171,7 @@ pub fn create_partial_signed<T: Config, Call: Payload>(
 
     // Return these details, ready to construct a signed extrinsic from.
     Ok(PartialTransaction {
+        version,
         call_data,
         additional_and_extra_params,
     })
@@ -155,6 +179,12 @@ pub fn create_partial_signed<T: Config, Call: Payload>(
 
 /// Creates a signed extrinsic without submitting it.
 ///
+/// This is the canonical way to sign transactions entirely offline (and in `no_std`
+/// environments); everything needed is given by the metadata, genesis hash and runtime
+/// version in the [`ClientState`], the call, the signer and the params. `subxt`'s
+/// `OfflineClient` uses this under the hood, and so will produce identical bytes given the
+/// same inputs. Use [`Transaction::into_encoded()`] to obtain the bytes to submit.
+///
 /// Note: if not provided, the default account nonce will be set to 0 and the default mortality will be _immortal_.
 /// This is because this method runs offline, and so is unable to fetch the data needed for more appropriate values.
 pub fn create_signed<T, Call, Signer>(
@@ -186,6 +216,7 @@ where
 /// [`PartialTransaction::sign_with_address_and_signature()`] to apply an existing signature and address
 /// to the transaction.
 pub struct PartialTransaction<T: Config> {
+    version: u8,
     call_data: Vec<u8>,
     additional_and_extra_params: T::ExtrinsicParams,
 }
@@ -221,6 +252,31 @@ impl<T: Config> PartialTransaction<T> {
         &self.call_data
     }
 
+    /// Return the signer payload for this extrinsic in the JSON format expected by browser
+    /// extensions such as polkadot-js. This is built from the signed extensions listed in the
+    /// given metadata, which should be the same metadata that this transaction was created with.
+    /// `address` is the account that will sign the payload, and is ss58 encoded using the given
+    /// `ss58_prefix`.
+    ///
+    /// An error is returned if the chain does not make use of the signed extensions needed to
+    /// build this payload (ie `CheckSpecVersion`, `CheckTxVersion`, `CheckGenesis`,
+    /// `CheckMortality` and `CheckNonce`).
+    pub fn to_signer_payload_json(
+        &self,
+        metadata: &Metadata,
+        address: &AccountId32,
+        ss58_prefix: u16,
+    ) -> Result<SignerPayloadJson, Error> {
+        SignerPayloadJson::new(
+            self.version,
+            &self.call_data,
+            &self.additional_and_extra_params,
+            metadata,
+            address,
+            ss58_prefix,
+        )
+    }
+
     /// Convert this [`PartialTransaction`] into a [`Transaction`], ready to submit.
     /// The provided `signer` is responsible for providing the "from" address for the transaction,
     /// as well as providing a signature to attach to it.
@@ -243,11 +299,11 @@ impl<T: Config> PartialTransaction<T> {
         address: &T::Address,
         signature: &T::Signature,
     ) -> Transaction<T> {
-        // Encode the extrinsic (into the format expected by protocol version 4)
+        // Encode the extrinsic (into the format expected by the chosen protocol version)
         let extrinsic = {
             let mut encoded_inner = Vec::new();
-            // "is signed" + transaction protocol version (4)
-            (0b10000000 + 4u8).encode_to(&mut encoded_inner);
+            // "is signed" + transaction protocol version
+            (0b10000000 + self.version).encode_to(&mut encoded_inner);
             // from address for signature
             address.encode_to(&mut encoded_inner);
             // the signature
@@ -0,0 +1,82 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Helpers for anchoring some data on chain using `System.remark_with_event`, which
+//! emits a `System.Remarked` event containing the hash of the data.
+
+use super::payload::{dynamic, DynamicPayload};
+use crate::error::{Error, MetadataError};
+use crate::events::StaticEvent;
+use crate::metadata::Metadata;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use scale_decode::{DecodeAsType, IntoVisitor};
+use scale_value::Value;
+
+/// Construct a `System.remark_with_event` call containing the given data. Once this is
+/// included in a block, a [`Remarked`] event is emitted containing the hash of the data.
+///
+/// Use [`validate_remark_with_event()`] to check that the chain supports this call up front.
+pub fn remark_with_event(data: impl Into<Vec<u8>>) -> DynamicPayload {
+    dynamic(
+        "System",
+        "remark_with_event",
+        vec![Value::from_bytes(data.into())],
+    )
+}
+
+/// Check that the chain described by the given metadata has a `System.remark_with_event` call.
+pub fn validate_remark_with_event(metadata: &Metadata) -> Result<(), Error> {
+    metadata
+        .pallet_by_name_err("System")?
+        .call_variant_by_name("remark_with_event")
+        .ok_or_else(|| MetadataError::CallNameNotFound("remark_with_event".to_string()))?;
+    Ok(())
+}
+
+/// The `System.Remarked` event, which is emitted by `System.remark_with_event` calls and contains
+/// the account that made the remark and the hash of the remark data (which, for the default
+/// Substrate hasher, is the blake2-256 hash of the data).
+#[derive(Debug, Clone, PartialEq, Eq, DecodeAsType)]
+pub struct Remarked<AccountId, Hash> {
+    /// The account that made the remark.
+    pub sender: AccountId,
+    /// The hash of the remark data.
+    pub hash: Hash,
+}
+
+impl<AccountId: IntoVisitor, Hash: IntoVisitor> StaticEvent for Remarked<AccountId, Hash> {
+    const PALLET: &'static str = "System";
+    const EVENT: &'static str = "Remarked";
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn metadata() -> Metadata {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        crate::metadata::decode_from(&bytes[..]).unwrap()
+    }
+
+    #[test]
+    fn remark_with_event_encodes_like_static_call() {
+        let metadata = metadata();
+        validate_remark_with_event(&metadata).unwrap();
+
+        let call = remark_with_event(b"hello".to_vec());
+        let call_data = crate::tx::call_data(&call, &metadata).unwrap();
+
+        // Pallet index, call index, then the compact encoded bytes:
+        let system = metadata.pallet_by_name("System").unwrap();
+        let call_index = system
+            .call_variant_by_name("remark_with_event")
+            .unwrap()
+            .index;
+        let mut expected = vec![system.index(), call_index];
+        codec::Encode::encode_to(&b"hello".to_vec(), &mut expected);
+        assert_eq!(call_data, expected);
+    }
+}
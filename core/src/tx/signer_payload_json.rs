@@ -0,0 +1,260 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::config::ExtrinsicParamsEncoder;
+use crate::error::{Error, ExtrinsicParamsError};
+use crate::metadata::Metadata;
+use crate::utils::{to_hex, AccountId32};
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use codec::{Compact, Decode};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// The signer payload in the JSON format that browser extensions such as polkadot-js or Talisman
+/// expect to be given to sign (known as `SignerPayloadJSON` in polkadot-js). Obtain one from
+/// [`super::PartialTransaction::to_signer_payload_json()`].
+///
+/// Numbers are given as big-endian hex strings, and everything else as hex encoded SCALE bytes.
+///
+/// # Note
+///
+/// The block number that a mortal transaction is checkpointed at cannot be recovered from the
+/// transaction itself, and so `block_number` is set to 0. Set it yourself if you need it to be
+/// accurate. It is not a part of the bytes that are signed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignerPayloadJson {
+    /// The ss58 encoded address of the account that will sign the transaction.
+    pub address: String,
+    /// The hash of the block that the transaction's mortality is checkpointed at.
+    pub block_hash: String,
+    /// The number of the block that the transaction's mortality is checkpointed at.
+    pub block_number: String,
+    /// The SCALE encoded mortality of the transaction.
+    pub era: String,
+    /// The genesis hash of the chain.
+    pub genesis_hash: String,
+    /// The SCALE encoded call data.
+    pub method: String,
+    /// The account nonce.
+    pub nonce: String,
+    /// The identifiers of each of the signed extensions used by the chain, in order.
+    pub signed_extensions: Vec<String>,
+    /// The spec version of the runtime.
+    pub spec_version: String,
+    /// The tip to pay to the block author.
+    pub tip: String,
+    /// The transaction version of the runtime.
+    pub transaction_version: String,
+    /// The extrinsic format version.
+    pub version: u8,
+}
+
+impl SignerPayloadJson {
+    pub(crate) fn new(
+        version: u8,
+        call_data: &[u8],
+        params: &impl ExtrinsicParamsEncoder,
+        metadata: &Metadata,
+        address: &AccountId32,
+        ss58_prefix: u16,
+    ) -> Result<Self, Error> {
+        let mut extra = Vec::new();
+        params.encode_extra_to(&mut extra);
+        let mut additional = Vec::new();
+        params.encode_additional_to(&mut additional);
+
+        let exts = split_by_signed_extension(&extra, &additional, metadata)?;
+        let ext = |name: &str| {
+            exts.get(name)
+                .copied()
+                .ok_or_else(|| ExtrinsicParamsError::SignedExtensionNotFound(name.to_owned()))
+        };
+
+        let spec_version = u32::decode(&mut ext("CheckSpecVersion")?.additional)?;
+        let transaction_version = u32::decode(&mut ext("CheckTxVersion")?.additional)?;
+        let genesis_hash = ext("CheckGenesis")?.additional;
+        let mortality = ext("CheckMortality")?;
+        let Compact(nonce) = Compact::<u64>::decode(&mut ext("CheckNonce")?.extra)?;
+
+        // The tip is the first field of whichever payment extension is in use, if any.
+        let tip = match ext("ChargeTransactionPayment").or_else(|_| ext("ChargeAssetTxPayment")) {
+            Ok(payment) => Compact::<u128>::decode(&mut payment.extra)?.0,
+            Err(_) => 0,
+        };
+
+        Ok(SignerPayloadJson {
+            address: address.to_ss58check_with_prefix(ss58_prefix),
+            block_hash: to_hex(mortality.additional),
+            // Not recoverable from the params for mortal transactions (see the note above).
+            block_number: to_hex(0u32.to_be_bytes()),
+            era: to_hex(mortality.extra),
+            genesis_hash: to_hex(genesis_hash),
+            method: to_hex(call_data),
+            nonce: to_hex(nonce.to_be_bytes()),
+            signed_extensions: metadata
+                .extrinsic()
+                .signed_extensions()
+                .iter()
+                .map(|e| e.identifier().to_owned())
+                .collect(),
+            spec_version: to_hex(spec_version.to_be_bytes()),
+            tip: to_hex(tip.to_be_bytes()),
+            transaction_version: to_hex(transaction_version.to_be_bytes()),
+            version,
+        })
+    }
+}
+
+/// The "extra" and "additional" bytes belonging to a single signed extension.
+#[derive(Clone, Copy)]
+struct SignedExtensionBytes<'a> {
+    extra: &'a [u8],
+    additional: &'a [u8],
+}
+
+/// Split the encoded "extra" and "additional" params into the bytes for each signed extension,
+/// keyed by the signed extension identifier.
+fn split_by_signed_extension<'a>(
+    mut extra: &'a [u8],
+    mut additional: &'a [u8],
+    metadata: &'a Metadata,
+) -> Result<HashMap<&'a str, SignedExtensionBytes<'a>>, Error> {
+    let mut exts = HashMap::new();
+    for ext in metadata.extrinsic().signed_extensions() {
+        let ext_extra = take_type(&mut extra, ext.extra_ty(), metadata)?;
+        let ext_additional = take_type(&mut additional, ext.additional_ty(), metadata)?;
+        exts.insert(
+            ext.identifier(),
+            SignedExtensionBytes {
+                extra: ext_extra,
+                additional: ext_additional,
+            },
+        );
+    }
+    Ok(exts)
+}
+
+/// Return the bytes from the front of `bytes` representing the given type, advancing `bytes` past them.
+fn take_type<'a>(
+    bytes: &mut &'a [u8],
+    type_id: u32,
+    metadata: &Metadata,
+) -> Result<&'a [u8], Error> {
+    let all_bytes = *bytes;
+    scale_decode::visitor::decode_with_visitor(
+        bytes,
+        type_id,
+        metadata.types(),
+        scale_decode::visitor::IgnoreVisitor::new(),
+    )
+    .map_err(|e| Error::Decode(e.into()))?;
+    Ok(&all_bytes[..all_bytes.len() - bytes.len()])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::{DefaultExtrinsicParamsBuilder as Params, PolkadotConfig};
+    use crate::tx::{self, ClientState, RuntimeVersion};
+    use crate::utils::{Era, H256};
+    use scale_value::Value;
+
+    fn client_state() -> ClientState<PolkadotConfig> {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        ClientState {
+            metadata: crate::metadata::decode_from(&bytes[..]).unwrap(),
+            genesis_hash: H256::repeat_byte(0xaa),
+            runtime_version: RuntimeVersion {
+                spec_version: 1_002_000,
+                transaction_version: 26,
+            },
+        }
+    }
+
+    fn alice() -> AccountId32 {
+        AccountId32(sp_keyring::AccountKeyring::Alice.to_raw_public())
+    }
+
+    #[test]
+    fn signer_payload_json_matches_known_good_payload() {
+        let state = client_state();
+        let call = crate::dynamic::tx("System", "remark", vec![Value::from_bytes(b"hi")]);
+        let params = Params::new().tip(1_000).nonce(5).build();
+        let partial_tx = tx::create_partial_signed(&call, &state, params).unwrap();
+
+        let payload = partial_tx
+            .to_signer_payload_json(&state.metadata, &alice(), 0)
+            .unwrap();
+        let payload = serde_json::to_value(&payload).unwrap();
+
+        let genesis_hash = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let expected = serde_json::json!({
+            "address": "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+            "blockHash": genesis_hash,
+            "blockNumber": "0x00000000",
+            "era": "0x00",
+            "genesisHash": genesis_hash,
+            "method": "0x0000086869",
+            "nonce": "0x0000000000000005",
+            "signedExtensions": [
+                "CheckNonZeroSender",
+                "CheckSpecVersion",
+                "CheckTxVersion",
+                "CheckGenesis",
+                "CheckMortality",
+                "CheckNonce",
+                "CheckWeight",
+                "ChargeTransactionPayment",
+                "CheckMetadataHash"
+            ],
+            "specVersion": "0x000f4a10",
+            "tip": "0x000000000000000000000000000003e8",
+            "transactionVersion": "0x0000001a",
+            "version": 4
+        });
+
+        assert_eq!(payload, expected);
+    }
+
+    #[test]
+    fn signer_payload_json_uses_mortality_checkpoint() {
+        let state = client_state();
+        let call = crate::dynamic::tx("System", "remark", vec![Value::from_bytes(b"hi")]);
+        let checkpoint = H256::repeat_byte(0xbb);
+        let params = Params::new().mortal_unchecked(100, checkpoint, 32).build();
+        let partial_tx = tx::create_partial_signed(&call, &state, params).unwrap();
+
+        let payload = partial_tx
+            .to_signer_payload_json(&state.metadata, &alice(), 42)
+            .unwrap();
+
+        assert_eq!(
+            payload.address,
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        assert_eq!(payload.block_hash, to_hex(checkpoint));
+        assert_eq!(
+            payload.era,
+            to_hex(codec::Encode::encode(&Era::mortal(32, 100)))
+        );
+    }
+
+    #[test]
+    fn signer_payload_json_uses_transaction_version() {
+        let state = client_state();
+        let call = crate::dynamic::tx("System", "remark", vec![Value::from_bytes(b"hi")]);
+        let mut partial_tx =
+            tx::create_partial_signed(&call, &state, Params::new().build()).unwrap();
+        partial_tx.version = 5;
+
+        let payload = partial_tx
+            .to_signer_payload_json(&state.metadata, &alice(), 0)
+            .unwrap();
+
+        assert_eq!(payload.version, 5);
+    }
+}
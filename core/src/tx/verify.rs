@@ -0,0 +1,187 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::blocks::Extrinsics;
+use crate::config::Config;
+use crate::error::Error;
+use crate::metadata::Metadata;
+use crate::utils::hashing::blake2_256;
+use alloc::vec;
+use codec::Decode;
+
+/// A signature which can be verified, given the message that was signed and the address
+/// of the account that signed it. Implement this for your [`Config::Signature`] in order to
+/// use [`verify_signature()`] with transactions from your chain.
+///
+/// An implementation for [`crate::utils::MultiSignature`] (supporting sr25519, ed25519 and
+/// ecdsa signatures) is provided when the `substrate-compat` feature is enabled.
+pub trait VerifySignature<Address> {
+    /// Return `true` if this is a valid signature of `message` by the account at `address`.
+    fn verify(&self, message: &[u8], address: &Address) -> bool;
+}
+
+/// Verify the signature of some signed transaction.
+///
+/// The "additional signed" bytes are signed alongside the call data and signed extension
+/// "extra" bytes, but are not a part of the transaction itself, and so they must be provided
+/// here. [`crate::config::AdditionalSignedValues`] can be used to build them.
+///
+/// Returns `Ok(false)` if the transaction is not signed, or if the signature is not valid for
+/// the signer payload rebuilt from the transaction and the given additional signed bytes.
+/// Returns an error if the transaction cannot be decoded.
+pub fn verify_signature<T>(
+    tx_bytes: &[u8],
+    metadata: &Metadata,
+    additional_signed: &[u8],
+) -> Result<bool, Error>
+where
+    T: Config,
+    T::Address: Decode,
+    T::Signature: Decode + VerifySignature<T::Address>,
+{
+    let extrinsics = Extrinsics::<T>::decode_from(vec![tx_bytes.to_vec()], metadata.clone())?;
+    let Some(tx) = extrinsics.iter().next().transpose()? else {
+        return Ok(false);
+    };
+
+    let (Some(mut address), Some(mut signature), Some(extra)) = (
+        tx.address_bytes(),
+        tx.signature_bytes(),
+        tx.signed_extensions_bytes(),
+    ) else {
+        return Ok(false);
+    };
+
+    let address = T::Address::decode(&mut address)?;
+    let signature = T::Signature::decode(&mut signature)?;
+
+    // Rebuild the signer payload in the same way that it's built when signing.
+    let mut payload = tx.call_bytes().to_vec();
+    payload.extend_from_slice(extra);
+    payload.extend_from_slice(additional_signed);
+
+    let is_valid = if payload.len() > 256 {
+        signature.verify(&blake2_256(&payload), &address)
+    } else {
+        signature.verify(&payload, &address)
+    };
+
+    Ok(is_valid)
+}
+
+#[cfg(all(test, feature = "substrate-compat"))]
+mod test {
+    use super::*;
+    use crate::config::{AdditionalSignedValues, PolkadotConfig};
+    use crate::tx::{self, signer::PairSigner, ClientState, RuntimeVersion};
+    use crate::utils::H256;
+    use alloc::vec::Vec;
+    use scale_value::Value;
+    use sp_core::Pair;
+
+    fn client_state() -> ClientState<PolkadotConfig> {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        ClientState {
+            metadata: crate::metadata::decode_from(&bytes[..]).unwrap(),
+            genesis_hash: H256::repeat_byte(0xaa),
+            runtime_version: RuntimeVersion {
+                spec_version: 1_002_000,
+                transaction_version: 26,
+            },
+        }
+    }
+
+    fn additional_signed(state: &ClientState<PolkadotConfig>) -> Vec<u8> {
+        let metadata = &state.metadata;
+        AdditionalSignedValues::from_client_state(state)
+            .encode_for(metadata.extrinsic().signed_extensions(), metadata.types())
+            .into_encoded()
+    }
+
+    /// Sign a remark with the given number of bytes in, returning the transaction bytes.
+    fn sign_remark<P>(state: &ClientState<PolkadotConfig>, pair: P, len: usize) -> Vec<u8>
+    where
+        P: Pair,
+        PairSigner<PolkadotConfig, P>: tx::signer::Signer<PolkadotConfig>,
+        <sp_runtime::MultiSignature as sp_runtime::traits::Verify>::Signer: From<P::Public>,
+    {
+        let signer = PairSigner::<PolkadotConfig, P>::new(pair);
+        let call = crate::dynamic::tx("System", "remark", vec![Value::from_bytes(vec![1; len])]);
+        tx::create_signed(&call, state, &signer, Default::default())
+            .unwrap()
+            .into_encoded()
+    }
+
+    #[test]
+    fn verifies_sr25519_ed25519_and_ecdsa_signatures() {
+        let state = client_state();
+        let additional = additional_signed(&state);
+
+        // Check both short payloads and those long enough to be hashed before signing.
+        for len in [4, 1024] {
+            let txs = [
+                sign_remark(
+                    &state,
+                    sp_core::sr25519::Pair::from_string("//Alice", None).unwrap(),
+                    len,
+                ),
+                sign_remark(
+                    &state,
+                    sp_core::ed25519::Pair::from_string("//Alice", None).unwrap(),
+                    len,
+                ),
+                sign_remark(
+                    &state,
+                    sp_core::ecdsa::Pair::from_string("//Alice", None).unwrap(),
+                    len,
+                ),
+            ];
+
+            for tx_bytes in txs {
+                let is_valid =
+                    verify_signature::<PolkadotConfig>(&tx_bytes, &state.metadata, &additional)
+                        .unwrap();
+                assert!(is_valid);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_signatures_over_different_payloads() {
+        let state = client_state();
+        let tx_bytes = sign_remark(
+            &state,
+            sp_core::sr25519::Pair::from_string("//Alice", None).unwrap(),
+            4,
+        );
+
+        // The transaction was signed for a different genesis hash:
+        let other_state = ClientState {
+            genesis_hash: H256::repeat_byte(0xbb),
+            ..state.clone()
+        };
+        let additional = additional_signed(&other_state);
+
+        let is_valid =
+            verify_signature::<PolkadotConfig>(&tx_bytes, &state.metadata, &additional).unwrap();
+        assert!(!is_valid);
+    }
+
+    #[test]
+    fn unsigned_transactions_are_not_valid() {
+        let state = client_state();
+        let call = crate::dynamic::tx("System", "remark", vec![Value::from_bytes([1, 2, 3])]);
+        let tx_bytes = tx::create_unsigned::<PolkadotConfig, _>(&call, &state.metadata)
+            .unwrap()
+            .into_encoded();
+
+        let is_valid = verify_signature::<PolkadotConfig>(
+            &tx_bytes,
+            &state.metadata,
+            &additional_signed(&state),
+        )
+        .unwrap();
+        assert!(!is_valid);
+    }
+}
@@ -8,6 +8,7 @@
 
 use core::fmt::Display;
 
+use crate::config::Config;
 use alloc::format;
 use alloc::string::String;
 use alloc::vec;
@@ -58,17 +59,21 @@ impl AccountId32 {
         // For serializing to a string to obtain the account nonce, we use the default substrate
         // prefix (since we have no way to otherwise pick one). It doesn't really matter, since when
         // it's deserialized back in system_accountNextIndex, we ignore this (so long as it's valid).
-        const SUBSTRATE_SS58_PREFIX: u8 = 42;
-        // prefix <= 63 just take up one byte at the start:
-        let mut v = vec![SUBSTRATE_SS58_PREFIX];
-        // then push the account ID bytes.
-        v.extend(self.0);
-        // then push a 2 byte checksum of what we have so far.
-        let r = ss58hash(&v);
-        v.extend(&r[0..2]);
-        // then encode to base58.
-        use base58::ToBase58;
-        v.to_base58()
+        const SUBSTRATE_SS58_PREFIX: u16 = 42;
+        self.to_ss58check_with_prefix(SUBSTRATE_SS58_PREFIX)
+    }
+
+    /// Return the ss58-check string for this key, using the given network prefix (for
+    /// instance, 0 for Polkadot or 2 for Kusama). Only the lower 14 bits of the prefix are
+    /// used, since prefixes above 16383 are not valid.
+    pub fn to_ss58check_with_prefix(&self, prefix: u16) -> String {
+        Ss58Formatter::new(prefix).format(self)
+    }
+
+    /// Return the ss58-check string for this key, using the [`Config::SS58_PREFIX`] of the
+    /// given config.
+    pub fn to_ss58check_for<T: Config>(&self) -> String {
+        self.to_ss58check_with_prefix(T::SS58_PREFIX)
     }
 
     // This isn't strictly needed, but to give our AccountId32 a little more usefulness, we also
@@ -105,6 +110,60 @@ impl AccountId32 {
     }
 }
 
+/// Formats [`AccountId32`]s as SS58 strings using some network prefix. The encoded prefix
+/// is computed once up front, making this a little cheaper than calling
+/// [`AccountId32::to_ss58check_with_prefix()`] for each of many accounts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ss58Formatter {
+    prefix_bytes: Vec<u8>,
+}
+
+impl Ss58Formatter {
+    /// Create a new formatter for the given network prefix (for instance, 0 for Polkadot
+    /// or 2 for Kusama). Only the lower 14 bits of the prefix are used, since prefixes
+    /// above 16383 are not valid.
+    pub fn new(prefix: u16) -> Self {
+        let ident = prefix & 0b0011_1111_1111_1111;
+        let prefix_bytes = match ident {
+            // prefix <= 63 just take up one byte at the start:
+            0..=63 => vec![ident as u8],
+            // else it takes up two bytes, with the top two bits of the first set to 01:
+            _ => {
+                let first = ((ident & 0b0000_0000_1111_1100) as u8) >> 2;
+                let second = ((ident >> 8) as u8) | ((ident & 0b0000_0000_0000_0011) as u8) << 6;
+                vec![first | 0b0100_0000, second]
+            }
+        };
+        Self { prefix_bytes }
+    }
+
+    /// Create a new formatter for the [`Config::SS58_PREFIX`] of the given config.
+    pub fn for_config<T: Config>() -> Self {
+        Self::new(T::SS58_PREFIX)
+    }
+
+    /// Return the ss58-check string for the given account.
+    pub fn format(&self, account: &AccountId32) -> String {
+        let mut v = Vec::with_capacity(self.prefix_bytes.len() + 32 + 2);
+        v.extend(&self.prefix_bytes);
+        // then push the account ID bytes.
+        v.extend(account.0);
+        // then push a 2 byte checksum of what we have so far.
+        let r = ss58hash(&v);
+        v.extend(&r[0..2]);
+        // then encode to base58.
+        use base58::ToBase58;
+        v.to_base58()
+    }
+}
+
+/// Return the ss58-check strings for each of the given accounts, using the given network prefix.
+/// See [`Ss58Formatter`].
+pub fn format_accounts_ss58(accounts: &[AccountId32], prefix: u16) -> Vec<String> {
+    let formatter = Ss58Formatter::new(prefix);
+    accounts.iter().map(|a| formatter.format(a)).collect()
+}
+
 /// An error obtained from trying to interpret an SS58 encoded string into an AccountId32
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
 #[allow(missing_docs)]
@@ -158,12 +217,17 @@ impl<'de> Deserialize<'de> for AccountId32 {
     }
 }
 
+/// Display the account as an SS58 address using the generic Substrate network prefix (42).
+/// Use [`AccountId32::to_ss58check_with_prefix()`] to display it for a specific network.
 impl core::fmt::Display for AccountId32 {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{}", self.to_ss58check())
     }
 }
 
+/// Parse an SS58 address for any network into an [`AccountId32`]. The network prefix is
+/// checked to be well formed and the checksum is validated, but the prefix itself is otherwise
+/// ignored, so the same account given for different networks parses to the same bytes.
 impl core::str::FromStr for AccountId32 {
     type Err = FromSs58Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -199,6 +263,7 @@ mod substrate_impls {
 mod test {
     use super::*;
 
+    use alloc::string::ToString;
     use sp_core::crypto::Ss58Codec;
     use sp_keyring::AccountKeyring;
 
@@ -231,4 +296,104 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn ss58_with_prefix_is_compatible_with_substrate_impl() {
+        use sp_core::crypto::Ss58AddressFormat;
+
+        let substrate_account = AccountKeyring::Alice.to_account_id();
+        let local_account = AccountId32(substrate_account.clone().into());
+
+        // Single byte and two byte prefixes:
+        for prefix in [0, 2, 42, 63, 64, 1284, 16383] {
+            let substrate_ss58 =
+                substrate_account.to_ss58check_with_version(Ss58AddressFormat::custom(prefix));
+            assert_eq!(
+                substrate_ss58,
+                local_account.to_ss58check_with_prefix(prefix)
+            );
+        }
+    }
+
+    #[test]
+    fn parses_addresses_for_any_network_to_the_same_account() {
+        let alice = AccountId32(AccountKeyring::Alice.to_account_id().into());
+
+        let generic = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let polkadot = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+        let kusama = "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F";
+
+        for address in [generic, polkadot, kusama] {
+            let parsed: AccountId32 = address.parse().unwrap();
+            assert_eq!(parsed, alice);
+        }
+
+        // Display uses the generic prefix, and specific prefixes can be asked for:
+        assert_eq!(alice.to_string(), generic);
+        assert_eq!(alice.to_ss58check_with_prefix(0), polkadot);
+        assert_eq!(alice.to_ss58check_with_prefix(2), kusama);
+    }
+
+    #[test]
+    fn batch_formatting_matches_individual_formatting() {
+        let accounts: Vec<AccountId32> = [
+            AccountKeyring::Alice,
+            AccountKeyring::Bob,
+            AccountKeyring::Charlie,
+        ]
+        .into_iter()
+        .map(|k| AccountId32(k.to_account_id().into()))
+        .collect();
+
+        for prefix in [0, 2, 42, 64, 16383] {
+            let expected: Vec<String> = accounts
+                .iter()
+                .map(|a| a.to_ss58check_with_prefix(prefix))
+                .collect();
+            assert_eq!(format_accounts_ss58(&accounts, prefix), expected);
+        }
+
+        // Known addresses for Alice, so that we aren't only checking against ourselves:
+        let alice = &accounts[..1];
+        assert_eq!(
+            format_accounts_ss58(alice, 0),
+            vec!["15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"]
+        );
+        assert_eq!(
+            format_accounts_ss58(alice, 42),
+            vec!["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+        );
+    }
+
+    #[test]
+    fn formatting_uses_the_config_prefix() {
+        use crate::config::{PolkadotConfig, SubstrateConfig};
+
+        assert_eq!(PolkadotConfig::SS58_PREFIX, 0);
+        assert_eq!(SubstrateConfig::SS58_PREFIX, 42);
+
+        let alice = AccountId32(AccountKeyring::Alice.to_account_id().into());
+        assert_eq!(
+            alice.to_ss58check_for::<PolkadotConfig>(),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        assert_eq!(
+            alice.to_ss58check_for::<SubstrateConfig>(),
+            alice.to_string()
+        );
+        assert_eq!(
+            Ss58Formatter::for_config::<PolkadotConfig>().format(&alice),
+            alice.to_ss58check_with_prefix(0)
+        );
+    }
+
+    #[test]
+    fn rejects_addresses_with_bad_checksums() {
+        // The last character of Alice's generic address has been changed:
+        let address = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ";
+        assert_eq!(
+            address.parse::<AccountId32>(),
+            Err(FromSs58Error::InvalidChecksum)
+        );
+    }
 }
@@ -0,0 +1,89 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! The hashing functions used by Substrate based chains, for instance to compute storage keys.
+//! The output of each of these is identical to the function of the same name in Substrate.
+
+use blake2::digest::consts::{U16, U32};
+use blake2::{Blake2b, Digest};
+use core::hash::Hasher;
+use twox_hash::XxHash64;
+
+/// Do a XX 64-bit hash and return the result as 8 bytes.
+pub fn twox_64(data: &[u8]) -> [u8; 8] {
+    twox::<8>(data)
+}
+
+/// Do a XX 128-bit hash and return the result as 16 bytes.
+pub use subxt_metadata::twox_128;
+
+/// Do a XX 256-bit hash and return the result as 32 bytes.
+pub fn twox_256(data: &[u8]) -> [u8; 32] {
+    twox::<32>(data)
+}
+
+/// Do a Blake2 128-bit hash and return the result as 16 bytes.
+pub fn blake2_128(data: &[u8]) -> [u8; 16] {
+    Blake2b::<U16>::digest(data).into()
+}
+
+/// Do a Blake2 256-bit hash and return the result as 32 bytes.
+pub fn blake2_256(data: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::digest(data).into()
+}
+
+/// Concatenate the little endian output of `N / 8` XX 64-bit hashes,
+/// seeded with 0, 1, 2 and so on, to build an `N` byte hash.
+fn twox<const N: usize>(data: &[u8]) -> [u8; N] {
+    let mut out = [0u8; N];
+    for (seed, chunk) in out.chunks_exact_mut(8).enumerate() {
+        let mut hasher = XxHash64::with_seed(seed as u64);
+        hasher.write(data);
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUTS: [&[u8]; 5] = [b"", b"System", b"Events", b"Hello world!", &[0xab; 1000]];
+
+    #[test]
+    fn matches_known_substrate_outputs() {
+        assert_eq!(hex::encode(twox_64(b"")), "99e9d85137db46ef");
+        assert_eq!(
+            hex::encode(twox_128(b"")),
+            "99e9d85137db46ef4bbea33613baafd5"
+        );
+        assert_eq!(
+            hex::encode(twox_128(b"System")),
+            "26aa394eea5630e07c48ae0c9558cef7"
+        );
+        assert_eq!(
+            hex::encode(twox_128(b"Events")),
+            "80d41e5e16056765bc8461851072c9d7"
+        );
+        assert_eq!(
+            hex::encode(blake2_128(b"")),
+            "cae66941d9efbd404e4d88758ea67670"
+        );
+        assert_eq!(
+            hex::encode(blake2_256(b"")),
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+    }
+
+    #[test]
+    fn matches_substrate_hashing() {
+        for input in INPUTS {
+            assert_eq!(twox_64(input), sp_crypto_hashing::twox_64(input));
+            assert_eq!(twox_128(input), sp_crypto_hashing::twox_128(input));
+            assert_eq!(twox_256(input), sp_crypto_hashing::twox_256(input));
+            assert_eq!(blake2_128(input), sp_crypto_hashing::blake2_128(input));
+            assert_eq!(blake2_256(input), sp_crypto_hashing::blake2_256(input));
+        }
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Convert decoded [`scale_value::Value`]s into [`serde_json::Value`]s.
+//!
+//! The JSON produced here is intended to be stable and easy to consume from other languages:
+//!
+//! - Composites with named fields become JSON objects, and those with unnamed fields become
+//!   JSON arrays. A composite with exactly one unnamed field (a "newtype", such as
+//!   `AccountId32([u8; 32])`) is replaced with the JSON of that field, and one with no fields
+//!   becomes `null`.
+//! - Sequences and arrays of `u8`s become `0x` prefixed hex strings.
+//! - Variants with no fields become a string containing the variant name, and other variants
+//!   become an object with a single key (the variant name) whose value is the JSON of the
+//!   fields. For instance, `Some(1)` becomes `{ "Some": 1 }` and `None` becomes `"None"`.
+//! - Unsigned and signed integers become JSON numbers if they fit into a `u64` or `i64`
+//!   respectively, and decimal strings otherwise, so that no precision is lost. 256 bit
+//!   unsigned integers are always decimal strings, and 256 bit signed integers are `0x`
+//!   prefixed hex strings of their little endian bytes.
+//! - Bit sequences become JSON arrays of booleans.
+//! - Booleans, strings and chars become JSON booleans and strings.
+
+use super::to_hex;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+use scale_value::{Composite, Primitive, Value, ValueDef};
+use serde_json::{Map, Number, Value as JsonValue};
+
+/// Convert a [`scale_value::Value`] into a [`serde_json::Value`]. The context of each value
+/// is expected to be the ID of its type in the given registry, as is the case for values
+/// obtained from calls like [`crate::events::EventDetails::field_values()`].
+///
+/// See [the module docs](self) for a description of the JSON produced.
+pub fn value_to_json(value: &Value<u32>, types: &PortableRegistry) -> JsonValue {
+    match &value.value {
+        ValueDef::Composite(composite) => {
+            if is_byte_sequence(value.context, types) {
+                if let Some(bytes) = composite_to_bytes(composite) {
+                    return JsonValue::String(to_hex(bytes));
+                }
+            }
+            composite_to_json(composite, types)
+        }
+        ValueDef::Variant(variant) => {
+            if variant.values.is_empty() {
+                JsonValue::String(variant.name.clone())
+            } else {
+                let mut map = Map::new();
+                map.insert(
+                    variant.name.clone(),
+                    composite_to_json(&variant.values, types),
+                );
+                JsonValue::Object(map)
+            }
+        }
+        ValueDef::BitSequence(bits) => JsonValue::Array(bits.iter().map(JsonValue::Bool).collect()),
+        ValueDef::Primitive(primitive) => primitive_to_json(primitive),
+    }
+}
+
+/// Convert a [`scale_value::Composite`] into a [`serde_json::Value`]. See [`value_to_json()`].
+pub fn composite_to_json(composite: &Composite<u32>, types: &PortableRegistry) -> JsonValue {
+    match composite {
+        Composite::Named(fields) if fields.is_empty() => JsonValue::Null,
+        Composite::Unnamed(fields) if fields.is_empty() => JsonValue::Null,
+        Composite::Unnamed(fields) if fields.len() == 1 => value_to_json(&fields[0], types),
+        Composite::Named(fields) => JsonValue::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), value_to_json(value, types)))
+                .collect(),
+        ),
+        Composite::Unnamed(fields) => JsonValue::Array(
+            fields
+                .iter()
+                .map(|value| value_to_json(value, types))
+                .collect(),
+        ),
+    }
+}
+
+fn primitive_to_json(primitive: &Primitive) -> JsonValue {
+    match primitive {
+        Primitive::Bool(b) => JsonValue::Bool(*b),
+        Primitive::Char(c) => JsonValue::String(c.to_string()),
+        Primitive::String(s) => JsonValue::String(s.clone()),
+        Primitive::U128(n) => match u64::try_from(*n) {
+            Ok(n) => JsonValue::Number(Number::from(n)),
+            Err(_) => JsonValue::String(n.to_string()),
+        },
+        Primitive::I128(n) => match i64::try_from(*n) {
+            Ok(n) => JsonValue::Number(Number::from(n)),
+            Err(_) => JsonValue::String(n.to_string()),
+        },
+        Primitive::U256(bytes) => {
+            JsonValue::String(primitive_types::U256::from_little_endian(bytes).to_string())
+        }
+        Primitive::I256(bytes) => JsonValue::String(to_hex(bytes)),
+    }
+}
+
+/// Is the type with the given ID a sequence or array of `u8`s?
+fn is_byte_sequence(type_id: u32, types: &PortableRegistry) -> bool {
+    let Some(ty) = types.resolve(type_id) else {
+        return false;
+    };
+    let inner_id = match &ty.type_def {
+        TypeDef::Sequence(s) => s.type_param.id,
+        TypeDef::Array(a) => a.type_param.id,
+        _ => return false,
+    };
+    matches!(
+        types.resolve(inner_id).map(|ty| &ty.type_def),
+        Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+    )
+}
+
+/// Return the bytes in some composite of `u8` values, or `None` if it contains anything else.
+fn composite_to_bytes(composite: &Composite<u32>) -> Option<Vec<u8>> {
+    composite
+        .values()
+        .map(|value| match &value.value {
+            ValueDef::Primitive(Primitive::U128(n)) => u8::try_from(*n).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scale_info::{meta_type, Registry, TypeInfo};
+    use serde_json::json;
+
+    /// Encode some value and decode it into a [`scale_value::Value`] whose
+    /// context is the ID of each type in the returned registry.
+    fn decoded<T: codec::Encode + TypeInfo + 'static>(value: T) -> (Value<u32>, PortableRegistry) {
+        let mut registry = Registry::new();
+        let id = registry.register_type(&meta_type::<T>()).id;
+        let types: PortableRegistry = registry.into();
+        let bytes = codec::Encode::encode(&value);
+        let value = scale_value::scale::decode_as_type(&mut &*bytes, id, &types).unwrap();
+        (value, types)
+    }
+
+    fn to_json<T: codec::Encode + TypeInfo + 'static>(value: T) -> JsonValue {
+        let (value, types) = decoded(value);
+        value_to_json(&value, &types)
+    }
+
+    #[derive(codec::Encode, TypeInfo)]
+    struct Named {
+        id: [u8; 4],
+        data: Vec<u8>,
+        numbers: Vec<u32>,
+        balance: u128,
+    }
+
+    #[derive(codec::Encode, TypeInfo)]
+    struct Newtype(u64);
+
+    #[allow(dead_code)]
+    #[derive(codec::Encode, TypeInfo)]
+    enum Enum {
+        Unit,
+        Unnamed(u8, bool),
+        Named { a: u8 },
+    }
+
+    #[test]
+    fn composites_to_json() {
+        let value = Named {
+            id: [1, 2, 3, 4],
+            data: vec![255, 0],
+            numbers: vec![1, 2, 3],
+            balance: u128::MAX,
+        };
+        assert_eq!(
+            to_json(value),
+            json!({
+                "id": "0x01020304",
+                "data": "0xff00",
+                "numbers": [1, 2, 3],
+                "balance": "340282366920938463463374607431768211455"
+            })
+        );
+
+        assert_eq!(to_json(Newtype(123)), json!(123));
+        assert_eq!(to_json((1u8, "hi", 'c')), json!([1, "hi", "c"]));
+        assert_eq!(to_json(()), json!(null));
+        assert_eq!(to_json(Vec::<u8>::new()), json!("0x"));
+    }
+
+    #[test]
+    fn variants_to_json() {
+        assert_eq!(to_json(Enum::Unit), json!("Unit"));
+        assert_eq!(to_json(Enum::Unnamed(1, true)), json!({ "Unnamed": [1, true] }));
+        assert_eq!(to_json(Enum::Named { a: 1 }), json!({ "Named": { "a": 1 } }));
+        assert_eq!(to_json(Some(1u8)), json!({ "Some": 1 }));
+        assert_eq!(to_json(None::<u8>), json!("None"));
+    }
+
+    #[test]
+    fn numbers_to_json() {
+        assert_eq!(to_json(u64::MAX), json!(u64::MAX));
+        assert_eq!(to_json(i64::MIN), json!(i64::MIN));
+        assert_eq!(to_json(u64::MAX as u128 + 1), json!("18446744073709551616"));
+        assert_eq!(to_json(i128::MIN), json!(i128::MIN.to_string()));
+    }
+}
@@ -8,27 +8,36 @@ mod account_id;
 mod account_id20;
 pub mod bits;
 mod era;
+pub mod hashing;
 mod multi_address;
 mod multi_signature;
 mod static_type;
 mod unchecked_extrinsic;
+mod weight;
 mod wrapper_opaque;
 
+crate::macros::cfg_feature! {
+    "json",
+    pub mod json;
+}
+
 use alloc::borrow::ToOwned;
 use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
-use codec::{Compact, Decode, Encode};
+use codec::{Decode, Encode};
 use derive_where::derive_where;
 
-pub use account_id::AccountId32;
+pub use account_id::{format_accounts_ss58, AccountId32, Ss58Formatter};
 pub use account_id20::AccountId20;
+pub use codec::Compact;
 pub use era::Era;
 pub use multi_address::MultiAddress;
 pub use multi_signature::MultiSignature;
 pub use primitive_types::{H160, H256, H512};
 pub use static_type::Static;
 pub use unchecked_extrinsic::UncheckedExtrinsic;
+pub use weight::Weight;
 pub use wrapper_opaque::WrapperKeepOpaque;
 
 /// Wraps an already encoded byte vector, prevents being encoded as a raw byte vector as part of
@@ -76,7 +85,73 @@ pub type KeyedVec<K, V> = Vec<(K, V)>;
 /// A unit marker struct.
 pub struct Yes;
 
-/// A quick helper to encode some bytes to hex.
+/// A quick helper to encode some bytes to a `0x` prefixed hex string.
 pub fn to_hex(bytes: impl AsRef<[u8]>) -> String {
     format!("0x{}", hex::encode(bytes.as_ref()))
 }
+
+/// SCALE encode some value and then encode the resulting bytes to a `0x` prefixed hex string.
+pub fn encode_hex<E: Encode + ?Sized>(val: &E) -> String {
+    to_hex(val.encode())
+}
+
+/// Decode a hex string into bytes. The string may or may not be prefixed with `0x`.
+pub fn from_hex(s: &str) -> Result<Vec<u8>, hex::FromHexError> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    hex::decode(s)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn to_hex_is_prefixed() {
+        assert_eq!(to_hex([]), "0x");
+        assert_eq!(to_hex([0u8, 1, 0xab, 0xff]), "0x0001abff");
+    }
+
+    #[test]
+    fn encode_hex_encodes_first() {
+        assert_eq!(encode_hex(&1u32), "0x01000000");
+        assert_eq!(encode_hex(&Compact(1u32)), "0x04");
+        assert_eq!(encode_hex(&alloc::vec![1u8, 2]), "0x080102");
+    }
+
+    #[test]
+    fn from_hex_accepts_with_and_without_prefix() {
+        assert_eq!(
+            from_hex("0x0001abff").unwrap(),
+            alloc::vec![0u8, 1, 0xab, 0xff]
+        );
+        assert_eq!(
+            from_hex("0001abff").unwrap(),
+            alloc::vec![0u8, 1, 0xab, 0xff]
+        );
+        assert_eq!(from_hex("0x").unwrap(), Vec::<u8>::new());
+        assert_eq!(from_hex("").unwrap(), Vec::<u8>::new());
+        assert!(from_hex("0x0").is_err());
+        assert!(from_hex("0xzz").is_err());
+    }
+
+    #[test]
+    fn hex_roundtrips() {
+        let bytes = alloc::vec![0u8, 1, 2, 3, 0xde, 0xad, 0xbe, 0xef];
+        assert_eq!(from_hex(&to_hex(&bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn reversed_version_bytes() {
+        // Some signers expect version numbers as big endian bytes, which
+        // is the reverse of the little endian SCALE encoding of them:
+        let spec_version: u32 = 9430;
+        let mut le_bytes = spec_version.encode();
+        le_bytes.reverse();
+        assert_eq!(to_hex(&le_bytes), to_hex(spec_version.to_be_bytes()));
+        assert_eq!(to_hex(spec_version.to_be_bytes()), "0x000024d6");
+
+        let mut decoded = from_hex("0x000024d6").unwrap();
+        decoded.reverse();
+        assert_eq!(u32::decode(&mut &*decoded).unwrap(), spec_version);
+    }
+}
@@ -38,6 +38,74 @@ pub enum MultiAddress<AccountId, AccountIndex> {
     Address20([u8; 20]),
 }
 
+impl<AccountId, AccountIndex> MultiAddress<AccountId, AccountIndex> {
+    /// Construct an address from an account ID.
+    pub fn id(account_id: AccountId) -> Self {
+        Self::Id(account_id)
+    }
+
+    /// Construct an address from an account index.
+    pub fn index(account_index: AccountIndex) -> Self {
+        Self::Index(account_index)
+    }
+
+    /// Construct an address from some arbitrary raw bytes.
+    pub fn raw(bytes: impl Into<Vec<u8>>) -> Self {
+        Self::Raw(bytes.into())
+    }
+
+    /// Construct an address from a 32 byte representation.
+    pub fn address32(bytes: [u8; 32]) -> Self {
+        Self::Address32(bytes)
+    }
+
+    /// Construct an address from a 20 byte representation, as used by Ethereum
+    /// compatible (eg Frontier based) chains.
+    pub fn address20(bytes: [u8; 20]) -> Self {
+        Self::Address20(bytes)
+    }
+
+    /// Return the account ID, if this is an [`MultiAddress::Id`].
+    pub fn as_id(&self) -> Option<&AccountId> {
+        match self {
+            Self::Id(account_id) => Some(account_id),
+            _ => None,
+        }
+    }
+
+    /// Return the account index, if this is an [`MultiAddress::Index`].
+    pub fn as_index(&self) -> Option<&AccountIndex> {
+        match self {
+            Self::Index(account_index) => Some(account_index),
+            _ => None,
+        }
+    }
+
+    /// Return the raw bytes, if this is an [`MultiAddress::Raw`].
+    pub fn as_raw(&self) -> Option<&[u8]> {
+        match self {
+            Self::Raw(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Return the 32 byte representation, if this is an [`MultiAddress::Address32`].
+    pub fn as_address32(&self) -> Option<&[u8; 32]> {
+        match self {
+            Self::Address32(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Return the 20 byte representation, if this is an [`MultiAddress::Address20`].
+    pub fn as_address20(&self) -> Option<&[u8; 20]> {
+        match self {
+            Self::Address20(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+}
+
 impl<AccountId, AccountIndex> From<AccountId> for MultiAddress<AccountId, AccountIndex> {
     fn from(a: AccountId) -> Self {
         Self::Id(a)
@@ -71,3 +139,73 @@ mod substrate_impls {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::AccountId32;
+
+    type Address = MultiAddress<AccountId32, u32>;
+    type SpAddress = sp_runtime::MultiAddress<sp_runtime::AccountId32, u32>;
+
+    fn bytes(prefix: &[u8], rest: &[u8]) -> Vec<u8> {
+        prefix.iter().chain(rest).copied().collect()
+    }
+
+    #[test]
+    fn encoding_matches_substrate() {
+        let cases: Vec<(Address, SpAddress, Vec<u8>)> = vec![
+            (
+                Address::id(AccountId32([1; 32])),
+                SpAddress::Id(sp_runtime::AccountId32::new([1; 32])),
+                bytes(&[0], &[1; 32]),
+            ),
+            (
+                Address::index(5),
+                SpAddress::Index(5),
+                // Indexes are compact encoded:
+                vec![1, 5 << 2],
+            ),
+            (
+                Address::raw([1, 2, 3]),
+                SpAddress::Raw(vec![1, 2, 3]),
+                vec![2, 3 << 2, 1, 2, 3],
+            ),
+            (
+                Address::address32([3; 32]),
+                SpAddress::Address32([3; 32]),
+                bytes(&[3], &[3; 32]),
+            ),
+            (
+                Address::address20([4; 20]),
+                SpAddress::Address20([4; 20]),
+                bytes(&[4], &[4; 20]),
+            ),
+        ];
+
+        for (address, sp_address, expected) in cases {
+            assert_eq!(address.encode(), expected);
+            assert_eq!(sp_address.encode(), expected);
+            assert_eq!(Address::decode(&mut &*expected).unwrap(), address);
+        }
+    }
+
+    #[test]
+    fn accessors_return_the_matching_variant() {
+        let address = Address::address20([4; 20]);
+        assert_eq!(address.as_address20(), Some(&[4; 20]));
+        assert_eq!(address.as_address32(), None);
+        assert_eq!(address.as_id(), None);
+
+        let address = Address::index(10);
+        assert_eq!(address.as_index(), Some(&10));
+        assert_eq!(address.as_raw(), None);
+
+        let address = Address::raw(vec![1, 2]);
+        assert_eq!(address.as_raw(), Some(&[1u8, 2][..]));
+
+        let address = Address::from(AccountId32([1; 32]));
+        assert_eq!(address.as_id(), Some(&AccountId32([1; 32])));
+        assert_eq!(address.as_address32(), None);
+    }
+}
@@ -24,6 +24,8 @@ pub enum MultiSignature {
 #[cfg(feature = "substrate-compat")]
 mod substrate_impls {
     use super::*;
+    use crate::tx::VerifySignature;
+    use crate::utils::{AccountId32, MultiAddress};
 
     impl From<sp_runtime::MultiSignature> for MultiSignature {
         fn from(value: sp_runtime::MultiSignature) -> Self {
@@ -55,4 +57,33 @@ mod substrate_impls {
             sig.into()
         }
     }
+
+    impl<N> VerifySignature<MultiAddress<AccountId32, N>> for MultiSignature {
+        fn verify(&self, message: &[u8], address: &MultiAddress<AccountId32, N>) -> bool {
+            use sp_core::{ecdsa, ed25519, sr25519, Pair};
+
+            let account = match address {
+                MultiAddress::Id(id) => id.0,
+                MultiAddress::Address32(bytes) => *bytes,
+                _ => return false,
+            };
+
+            match self {
+                MultiSignature::Ed25519(sig) => ed25519::Pair::verify(
+                    &ed25519::Signature::from_raw(*sig),
+                    message,
+                    &ed25519::Public::from_raw(account),
+                ),
+                MultiSignature::Sr25519(sig) => sr25519::Pair::verify(
+                    &sr25519::Signature::from_raw(*sig),
+                    message,
+                    &sr25519::Public::from_raw(account),
+                ),
+                // ECDSA accounts are the blake2_256 hash of the compressed public key.
+                MultiSignature::Ecdsa(sig) => ecdsa::Signature::from_raw(*sig)
+                    .recover(message)
+                    .is_some_and(|public| super::hashing::blake2_256(public.as_ref()) == account),
+            }
+        }
+    }
 }
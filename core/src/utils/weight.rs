@@ -0,0 +1,141 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! A two dimensional `Weight`, compatible with `sp_weights::Weight`.
+
+use codec::{Decode, Encode};
+
+/// A weight, made up of the computation time and the size of the storage proof used by
+/// (or available to) something. This is SCALE compatible with `sp_weights::Weight`, and
+/// is used in place of it in generated code. On chains whose weights only track
+/// computation time, the proof size is always 0.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Encode,
+    Decode,
+    scale_encode::EncodeAsType,
+    scale_decode::DecodeAsType,
+    scale_info::TypeInfo,
+)]
+pub struct Weight {
+    /// The computation time used.
+    #[codec(compact)]
+    pub ref_time: u64,
+    /// The size of the storage proof used.
+    #[codec(compact)]
+    pub proof_size: u64,
+}
+
+impl Weight {
+    /// A weight of zero.
+    pub const fn zero() -> Self {
+        Weight::from_parts(0, 0)
+    }
+
+    /// Construct a weight from its computation time and proof size.
+    pub const fn from_parts(ref_time: u64, proof_size: u64) -> Self {
+        Weight {
+            ref_time,
+            proof_size,
+        }
+    }
+
+    /// The computation time used.
+    pub const fn ref_time(&self) -> u64 {
+        self.ref_time
+    }
+
+    /// The size of the storage proof used.
+    pub const fn proof_size(&self) -> u64 {
+        self.proof_size
+    }
+
+    /// Add two weights together, saturating at the numeric bounds.
+    pub const fn saturating_add(self, other: Weight) -> Self {
+        Weight {
+            ref_time: self.ref_time.saturating_add(other.ref_time),
+            proof_size: self.proof_size.saturating_add(other.proof_size),
+        }
+    }
+
+    /// Subtract one weight from another, saturating at the numeric bounds.
+    pub const fn saturating_sub(self, other: Weight) -> Self {
+        Weight {
+            ref_time: self.ref_time.saturating_sub(other.ref_time),
+            proof_size: self.proof_size.saturating_sub(other.proof_size),
+        }
+    }
+
+    /// Return true if both the computation time and proof size of this weight are
+    /// less than or equal to those of the other weight.
+    pub const fn all_lte(&self, other: &Weight) -> bool {
+        self.ref_time <= other.ref_time && self.proof_size <= other.proof_size
+    }
+}
+
+impl From<(u64, u64)> for Weight {
+    fn from((ref_time, proof_size): (u64, u64)) -> Self {
+        Weight::from_parts(ref_time, proof_size)
+    }
+}
+
+impl From<Weight> for (u64, u64) {
+    fn from(weight: Weight) -> Self {
+        (weight.ref_time, weight.proof_size)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Metadata;
+    use codec::Compact;
+    use scale_decode::DecodeAsType;
+    use scale_encode::EncodeAsType;
+
+    fn metadata() -> Metadata {
+        let bytes = include_bytes!("../../../artifacts/polkadot_metadata_small.scale");
+        crate::metadata::decode_from(&bytes[..]).unwrap()
+    }
+
+    #[test]
+    fn weight_v2_decodes_from_metadata_type() {
+        let metadata = metadata();
+        let weight_ty = metadata
+            .types()
+            .types
+            .iter()
+            .find(|ty| ty.ty.path.segments == ["sp_weights", "weight_v2", "Weight"])
+            .expect("metadata should contain a weight type")
+            .id;
+
+        let bytes = (Compact(1_000_000u64), Compact(2048u64)).encode();
+
+        let weight = Weight::decode_as_type(&mut &*bytes, weight_ty, metadata.types()).unwrap();
+        assert_eq!(weight.ref_time(), 1_000_000);
+        assert_eq!(weight.proof_size(), 2048);
+        assert_eq!(weight, Weight::decode(&mut &*bytes).unwrap());
+
+        let encoded = weight.encode_as_type(weight_ty, metadata.types()).unwrap();
+        assert_eq!(encoded, bytes);
+    }
+
+    #[test]
+    fn weight_arithmetic_saturates() {
+        let a = Weight::from_parts(u64::MAX - 1, 10);
+        let b = Weight::from_parts(5, 20);
+
+        assert_eq!(a.saturating_add(b), Weight::from_parts(u64::MAX, 30));
+        assert_eq!(b.saturating_sub(a), Weight::from_parts(0, 10));
+        assert!(Weight::zero().all_lte(&b));
+        assert!(!a.all_lte(&b));
+        assert_eq!(<(u64, u64)>::from(b), (5, 20));
+    }
+}
@@ -9,7 +9,7 @@ use subxt::tx::Payload as _;
 use subxt::tx::SubmittableExtrinsic;
 use subxt::utils::{AccountId32, MultiSignature};
 
-use crate::services::{extension_signature_for_extrinsic, get_accounts, polkadot, Account};
+use crate::services::{extension_signature_for_partial_extrinsic, get_accounts, polkadot, Account};
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
@@ -142,14 +142,18 @@ impl Component for SigningExamplesComponent {
                             return Message::Error(anyhow!("Fetching account nonce failed"));
                         };
 
-                        let Ok(call_data) = api.tx().call_data(&remark_call) else {
-                            return Message::Error(anyhow!("could not encode call data"));
+                        let params = DefaultExtrinsicParamsBuilder::new()
+                            .nonce(account_nonce)
+                            .build();
+                        let Ok(partial_signed) =
+                            api.tx().create_partial_signed_offline(&remark_call, params)
+                        else {
+                            return Message::Error(anyhow!("PartialExtrinsic creation failed"));
                         };
 
-                        let Ok(signature) = extension_signature_for_extrinsic(
-                            &call_data,
-                            &api,
-                            account_nonce,
+                        let Ok(signature) = extension_signature_for_partial_extrinsic(
+                            &partial_signed,
+                            &account_id,
                             account_source,
                             account_address,
                         )
@@ -163,15 +167,6 @@ impl Component for SigningExamplesComponent {
                             return Message::Error(anyhow!("MultiSignature Decoding"));
                         };
 
-                        let params = DefaultExtrinsicParamsBuilder::new()
-                            .nonce(account_nonce)
-                            .build();
-                        let Ok(partial_signed) =
-                            api.tx().create_partial_signed_offline(&remark_call, params)
-                        else {
-                            return Message::Error(anyhow!("PartialExtrinsic creation failed"));
-                        };
-
                         // Apply the signature
                         let signed_extrinsic = partial_signed
                             .sign_with_address_and_signature(&account_id.into(), &multi_signature);
@@ -189,8 +184,7 @@ impl Component for SigningExamplesComponent {
             }
             Message::ReceivedSignature(signature, signed_extrinsic) => {
                 if let SigningStage::Signing(account) = &self.stage {
-                    let signed_extrinsic_hex =
-                        format!("0x{}", hex::encode(signed_extrinsic.encoded()));
+                    let signed_extrinsic_hex = subxt::utils::to_hex(signed_extrinsic.encoded());
                     self.stage = SigningStage::SigningSuccess {
                         signer_account: account.clone(),
                         signature,
@@ -248,7 +242,7 @@ impl Component for SigningExamplesComponent {
             html!(
                 <div class="mb">
                     <b>{"Hex representation of \"remark\" call in \"System\" pallet:"}</b> <br/>
-                    {format!("0x{}", hex::encode(&self.remark_call_bytes))}
+                    {subxt::utils::to_hex(&self.remark_call_bytes)}
                 </div>
             )
         };
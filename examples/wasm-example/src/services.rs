@@ -1,9 +1,9 @@
 use anyhow::anyhow;
 use js_sys::Promise;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 use std::fmt::Write;
-use subxt::ext::codec::{Compact, Encode};
+use subxt::tx::PartialExtrinsic;
+use subxt::utils::{from_hex, to_hex, AccountId32};
 use subxt::{self, OnlineClient, PolkadotConfig};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
@@ -52,7 +52,7 @@ pub(crate) async fn subscribe_to_finalized_blocks(
             let ext = ext?;
             let idx = ext.index();
             let events = ext.events().await?;
-            let bytes_hex = format!("0x{}", hex::encode(ext.bytes()));
+            let bytes_hex = to_hex(ext.bytes());
 
             // See the API docs for more ways to decode extrinsics:
             let decoded_ext = ext.as_root_extrinsic::<polkadot::Call>();
@@ -110,65 +110,24 @@ pub async fn get_accounts() -> Result<Vec<Account>, anyhow::Error> {
     Ok(accounts)
 }
 
-fn to_hex(bytes: impl AsRef<[u8]>) -> String {
-    format!("0x{}", hex::encode(bytes.as_ref()))
-}
-
-fn encode_then_hex<E: Encode>(input: &E) -> String {
-    format!("0x{}", hex::encode(input.encode()))
-}
-
 /// communicates with JavaScript to obtain a signature for the `partial_extrinsic` via a browser extension (e.g. polkadot-js or Talisman)
-///
-/// Some parameters are hard-coded here and not taken from the partial_extrinsic itself (mortality_checkpoint, era, tip).
-pub async fn extension_signature_for_extrinsic(
-    call_data: &[u8],
-    api: &OnlineClient<PolkadotConfig>,
-    account_nonce: u64,
+pub async fn extension_signature_for_partial_extrinsic(
+    partial_extrinsic: &PartialExtrinsic<PolkadotConfig, OnlineClient<PolkadotConfig>>,
+    account_id: &AccountId32,
     account_source: String,
     account_address: String,
 ) -> Result<Vec<u8>, anyhow::Error> {
-    let genesis_hash = encode_then_hex(&api.genesis_hash());
-    // These numbers aren't SCALE encoded; their bytes are just converted to hex:
-    let spec_version = to_hex(&api.runtime_version().spec_version.to_be_bytes());
-    let transaction_version = to_hex(&api.runtime_version().transaction_version.to_be_bytes());
-    let nonce = to_hex(&account_nonce.to_be_bytes());
-    // If you construct a mortal transaction, then this block hash needs to correspond
-    // to the block number passed to `Era::mortal()`.
-    let mortality_checkpoint = encode_then_hex(&api.genesis_hash());
-    let era = encode_then_hex(&subxt::utils::Era::Immortal);
-    let method = to_hex(call_data);
-    let signed_extensions: Vec<String> = api
-        .metadata()
-        .extrinsic()
-        .signed_extensions()
-        .iter()
-        .map(|e| e.identifier().to_string())
-        .collect();
-    let tip = encode_then_hex(&Compact(0u128));
-
-    let payload = json!({
-        "specVersion": spec_version,
-        "transactionVersion": transaction_version,
-        "address": account_address,
-        "blockHash": mortality_checkpoint,
-        "blockNumber": "0x00000000",
-        "era": era,
-        "genesisHash": genesis_hash,
-        "method": method,
-        "nonce": nonce,
-        "signedExtensions": signed_extensions,
-        "tip": tip,
-        "version": 4,
-    });
+    let mut payload = partial_extrinsic.to_signer_payload_json(account_id, 42)?;
+    // Hand back the address in the same format that the extension gave it to us in:
+    payload.address = account_address.clone();
 
-    let payload = payload.to_string();
+    let payload = serde_json::to_string(&payload)?;
     let result = JsFuture::from(js_sign_payload(payload, account_source, account_address))
         .await
         .map_err(|js_err| anyhow!("{js_err:?}"))?;
     let signature = result
         .as_string()
         .ok_or(anyhow!("Error converting JsValue into String"))?;
-    let signature = hex::decode(&signature[2..])?;
+    let signature = from_hex(&signature)?;
     Ok(signature)
 }
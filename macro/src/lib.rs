@@ -40,6 +40,8 @@ struct RuntimeMetadataArgs {
     #[darling(default)]
     runtime_metadata_path: Option<String>,
     #[darling(default)]
+    runtime_metadata_env: Option<String>,
+    #[darling(default)]
     runtime_metadata_insecure_url: Option<String>,
     #[darling(default)]
     derive_for_all_types: Option<Punctuated<syn::Path, syn::Token![,]>>,
@@ -58,10 +60,20 @@ struct RuntimeMetadataArgs {
     #[darling(default)]
     runtime_types_only: bool,
     #[darling(default)]
+    root_enums_only: bool,
+    #[darling(default)]
+    no_std: bool,
+    #[darling(default)]
     no_default_derives: bool,
     #[darling(default)]
+    no_codec_derives: bool,
+    #[darling(default)]
     no_default_substitutions: bool,
     #[darling(default)]
+    generate_value_conversions: bool,
+    #[darling(default)]
+    precompute_storage_prefixes: bool,
+    #[darling(default)]
     unstable_metadata: darling::util::Flag,
     #[cfg(feature = "runtime-path")]
     #[darling(default)]
@@ -125,12 +137,27 @@ fn subxt_inner(args: TokenStream, item_mod: syn::ItemMod) -> Result<TokenStream,
     if args.runtime_types_only {
         codegen.runtime_types_only();
     }
+    if args.root_enums_only {
+        codegen.root_enums_only();
+    }
+    if args.no_std {
+        codegen.no_std(true);
+    }
     if args.no_default_derives {
         codegen.disable_default_derives();
     }
+    if args.no_codec_derives {
+        codegen.without_codec_derives();
+    }
     if args.no_default_substitutions {
         codegen.disable_default_substitutes();
     }
+    if args.generate_value_conversions {
+        codegen.generate_value_conversions();
+    }
+    if args.precompute_storage_prefixes {
+        codegen.precompute_storage_prefixes();
+    }
     if !args.generate_docs.is_present() {
         codegen.no_docs()
     }
@@ -166,10 +193,21 @@ fn subxt_inner(args: TokenStream, item_mod: syn::ItemMod) -> Result<TokenStream,
         codegen.set_type_substitute(sub.path, sub.with);
     }
 
-    let code = codegen
+    let mut code = codegen
         .generate(metadata)
         .map_err(|e| e.into_compile_error())?;
 
+    // Have rustc track the environment variable and the file that it points to, so that
+    // changing either of them triggers a rebuild.
+    if let Some(var) = &args.runtime_metadata_env {
+        let path = std::env::var(var).unwrap_or_default();
+        let path = metadata_file_path(&path).to_string_lossy().into_owned();
+        code.extend(quote::quote! {
+            const _: ::core::option::Option<&str> = ::core::option_env!(#var);
+            const _: &[u8] = ::core::include_bytes!(#path);
+        });
+    }
+
     Ok(code.into())
 }
 
@@ -208,14 +246,37 @@ fn validate_type_path(path: &syn::Path, metadata: &Metadata) {
     }
 }
 
+/// The path to a metadata file given relative to the crate that the macro is used in.
+fn metadata_file_path(rest_of_path: &str) -> std::path::PathBuf {
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
+    std::path::Path::new(&root).join(rest_of_path)
+}
+
 /// Fetches metadata in a blocking manner, from a url or file path.
 fn fetch_metadata(args: &RuntimeMetadataArgs) -> Result<subxt_codegen::Metadata, TokenStream> {
     // Do we want to fetch unstable metadata? This only works if fetching from a URL.
     let unstable_metadata = args.unstable_metadata.is_present();
 
+    // The metadata path can be given directly, or via an environment variable.
+    let runtime_metadata_path = match (&args.runtime_metadata_path, &args.runtime_metadata_env) {
+        (Some(_), Some(_)) => {
+            abort_call_site!(
+                "Only one of 'runtime_metadata_path' or 'runtime_metadata_env' can be provided"
+            )
+        }
+        (None, Some(var)) => match std::env::var(var) {
+            Ok(path) => Some(path),
+            Err(_) => abort_call_site!(
+                "The environment variable '{}' given in 'runtime_metadata_env' is not set, or is not valid unicode",
+                var
+            ),
+        },
+        (path, None) => path.clone(),
+    };
+
     #[cfg(feature = "runtime-path")]
     if let Some(path) = &args.runtime_path {
-        if args.runtime_metadata_insecure_url.is_some() || args.runtime_metadata_path.is_some() {
+        if args.runtime_metadata_insecure_url.is_some() || runtime_metadata_path.is_some() {
             abort_call_site!(
                 "Only one of 'runtime_metadata_path', 'runtime_metadata_insecure_url' or `runtime_path` must be provided"
             );
@@ -228,10 +289,7 @@ fn fetch_metadata(args: &RuntimeMetadataArgs) -> Result<subxt_codegen::Metadata,
         return Ok(metadata);
     };
 
-    let metadata = match (
-        &args.runtime_metadata_path,
-        &args.runtime_metadata_insecure_url,
-    ) {
+    let metadata = match (&runtime_metadata_path, &args.runtime_metadata_insecure_url) {
         (Some(rest_of_path), None) => {
             if unstable_metadata {
                 abort_call_site!(
@@ -239,9 +297,7 @@ fn fetch_metadata(args: &RuntimeMetadataArgs) -> Result<subxt_codegen::Metadata,
                 )
             }
 
-            let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
-            let root_path = std::path::Path::new(&root);
-            let path = root_path.join(rest_of_path);
+            let path = metadata_file_path(rest_of_path);
             fetch_metadata_from_file_blocking(&path)
                 .and_then(|b| subxt_codegen::Metadata::decode(&mut &*b).map_err(Into::into))
                 .map_err(|e| CodegenError::from(e).into_compile_error())?
@@ -4,6 +4,7 @@
 
 use super::TryFromError;
 
+use crate::utils::validation::twox_128;
 use crate::utils::variant_index::VariantIndex;
 use crate::{
     utils::ordered_map::OrderedMap, ArcStr, ConstantMetadata, ExtrinsicMetadata, Metadata,
@@ -12,6 +13,7 @@ use crate::{
     StorageEntryModifier, StorageEntryType, StorageHasher, StorageMetadata,
 };
 use alloc::borrow::ToOwned;
+use alloc::vec;
 use frame_metadata::v15;
 use hashbrown::HashMap;
 use scale_info::form::PortableForm;
@@ -25,10 +27,12 @@ mod from_v15 {
         fn try_from(m: v15::RuntimeMetadataV15) -> Result<Self, TryFromError> {
             let mut pallets = OrderedMap::new();
             let mut pallets_by_index = HashMap::new();
+            let mut pallets_by_storage_prefix_hash = HashMap::new();
             for (pos, p) in m.pallets.into_iter().enumerate() {
                 let name: ArcStr = p.name.into();
 
                 let storage = p.storage.map(|s| StorageMetadata {
+                    prefix_hash: twox_128(s.prefix.as_bytes()),
                     prefix: s.prefix,
                     entries: s
                         .entries
@@ -52,6 +56,9 @@ mod from_v15 {
                     VariantIndex::build(p.event.as_ref().map(|e| e.ty.id), &m.types);
 
                 pallets_by_index.insert(p.index, pos);
+                if let Some(storage) = &storage {
+                    pallets_by_storage_prefix_hash.insert(storage.prefix_hash, pos);
+                }
                 pallets.push_insert(
                     name.clone(),
                     PalletMetadataInner {
@@ -86,6 +93,7 @@ mod from_v15 {
                 types: m.types,
                 pallets,
                 pallets_by_index,
+                pallets_by_storage_prefix_hash,
                 extrinsic: from_extrinsic_metadata(m.extrinsic),
                 runtime_ty: m.ty.id,
                 dispatch_error_ty,
@@ -112,7 +120,7 @@ mod from_v15 {
 
     fn from_extrinsic_metadata(value: v15::ExtrinsicMetadata<PortableForm>) -> ExtrinsicMetadata {
         ExtrinsicMetadata {
-            version: value.version,
+            supported_versions: vec![value.version],
             signed_extensions: value
                 .signed_extensions
                 .into_iter()
@@ -332,7 +340,7 @@ mod into_v15 {
 
     fn from_extrinsic_metadata(e: ExtrinsicMetadata) -> v15::ExtrinsicMetadata<PortableForm> {
         v15::ExtrinsicMetadata {
-            version: e.version,
+            version: e.version(),
             signed_extensions: e
                 .signed_extensions
                 .into_iter()
@@ -13,6 +13,9 @@
 //!    from a node (this uses [`codec::Decode`]).
 //! 2. Obtaining [`frame_metadata::RuntimeMetadataPrefixed`], and then
 //!    using `.try_into()` to convert it into [`Metadata`].
+//!
+//! This crate is `no_std` compatible; disable the default `std` feature to
+//! use it in environments without the standard library (it only relies on `alloc`).
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
@@ -34,7 +37,9 @@ type ArcStr = Arc<str>;
 
 use crate::utils::validation::{get_custom_value_hash, HASH_LEN};
 pub use from_into::TryFromError;
-pub use utils::validation::MetadataHasher;
+pub use utils::diff::{ChangedConstant, Changes, MetadataDiff, PalletDiff, RuntimeApiDiff};
+pub use utils::type_graph::{TypeGraph, TypeGraphEdge, TypeGraphNode};
+pub use utils::validation::{twox_128, MetadataHasher};
 
 /// Node metadata. This can be constructed by providing some compatible [`frame_metadata`]
 /// which is then decoded into this. We aim to preserve all of the existing information in
@@ -47,6 +52,8 @@ pub struct Metadata {
     pallets: OrderedMap<ArcStr, PalletMetadataInner>,
     /// Find the location in the pallet Vec by pallet index.
     pallets_by_index: HashMap<u8, usize>,
+    /// Find the location in the pallet Vec by the `twox_128` hash of the pallet's storage prefix.
+    pallets_by_storage_prefix_hash: HashMap<[u8; 16], usize>,
     /// Metadata of the extrinsic.
     extrinsic: ExtrinsicMetadata,
     /// The type ID of the `Runtime` type.
@@ -123,6 +130,52 @@ impl Metadata {
         })
     }
 
+    /// Access a pallet given the prefix used by its storage entries (see [`StorageMetadata::prefix()`]).
+    /// This is usually, but not necessarily, the same as the pallet name.
+    pub fn pallet_by_storage_prefix(&self, prefix: &str) -> Option<PalletMetadata<'_>> {
+        self.pallets()
+            .find(|p| p.storage().is_some_and(|s| s.prefix() == prefix))
+    }
+
+    /// Access a pallet given the `twox_128` hash of the prefix used by its storage entries (see
+    /// [`StorageMetadata::prefix_hash()`]). These are the first 16 bytes of every storage key in
+    /// the pallet, so this can be used to find out which pallet some raw storage key belongs to.
+    pub fn pallet_by_storage_prefix_hash(&self, hash: &[u8; 16]) -> Option<PalletMetadata<'_>> {
+        let inner = self
+            .pallets_by_storage_prefix_hash
+            .get(hash)
+            .and_then(|i| self.pallets.get_by_index(*i))?;
+
+        Some(PalletMetadata {
+            inner,
+            types: self.types(),
+        })
+    }
+
+    /// An iterator over the name and encoded variant index of each pallet. This is a
+    /// lighter weight alternative to [`Metadata::pallets()`] when only these are needed.
+    pub fn pallet_indices(&self) -> impl ExactSizeIterator<Item = (&str, u8)> {
+        self.pallets
+            .values()
+            .iter()
+            .map(|inner| (&*inner.name, inner.index))
+    }
+
+    /// Return the name of the pallet with the given encoded variant index, if one exists.
+    pub fn pallet_name_by_index(&self, variant_index: u8) -> Option<&str> {
+        self.pallets_by_index
+            .get(&variant_index)
+            .and_then(|i| self.pallets.get_by_index(*i))
+            .map(|inner| &*inner.name)
+    }
+
+    /// Return the encoded variant index of the pallet with the given name, if one exists.
+    pub fn pallet_index_by_name(&self, pallet_name: &str) -> Option<u8> {
+        self.pallets
+            .get_by_key(pallet_name)
+            .map(|inner| inner.index)
+    }
+
     /// An iterator over all of the runtime APIs.
     pub fn runtime_api_traits(&self) -> impl ExactSizeIterator<Item = RuntimeApiMetadata<'_>> {
         self.apis.values().iter().map(|inner| RuntimeApiMetadata {
@@ -140,6 +193,17 @@ impl Metadata {
         })
     }
 
+    /// Find every runtime API trait which has a method with the given name, returning
+    /// the name of each such trait alongside the method metadata.
+    pub fn find_runtime_api_methods(
+        &self,
+        method_name: &str,
+    ) -> Vec<(&str, &RuntimeApiMethodMetadata)> {
+        self.runtime_api_traits()
+            .filter_map(|api| Some((api.name(), api.method_by_name(method_name)?)))
+            .collect()
+    }
+
     /// Returns custom user defined types
     pub fn custom(&self) -> CustomMetadata<'_> {
         CustomMetadata {
@@ -153,13 +217,52 @@ impl Metadata {
         MetadataHasher::new(self)
     }
 
-    /// Filter out any pallets that we don't want to keep, retaining only those that we do.
+    /// Compare this metadata with some other (newer) metadata, returning the pallets,
+    /// calls, events, storage entries, constants and runtime APIs which were added,
+    /// removed or changed. Items are considered changed if their hashes differ.
+    pub fn diff(&self, other: &Metadata) -> MetadataDiff {
+        utils::diff::diff_metadata(self, other)
+    }
+
+    /// Compare the values of the constants in this metadata with those in some other (newer)
+    /// metadata, returning any constants which exist in both but whose SCALE encoded values
+    /// differ. Unlike [`Metadata::diff()`], which only notices changes to the shape of a
+    /// constant, this notices when eg an `ExistentialDeposit` is given a new value.
+    pub fn changed_constants(&self, other: &Metadata) -> Vec<ChangedConstant> {
+        utils::diff::changed_constants(self, other)
+    }
+
+    /// Build a graph of the types in this metadata, recording which types each type references
+    /// (for instance, the types of the fields in a struct). This can be rendered with
+    /// [`TypeGraph::to_dot()`] in order to visualize how the types relate to each other.
+    pub fn type_graph(&self) -> TypeGraph {
+        utils::type_graph::type_graph(&self.types)
+    }
+
+    /// Filter out any pallets and runtime APIs that we don't want to keep, retaining only those
+    /// that we do.
     pub fn retain<F, G>(&mut self, pallet_filter: F, api_filter: G)
     where
         F: FnMut(&str) -> bool,
         G: FnMut(&str) -> bool,
     {
-        utils::retain::retain_metadata(self, pallet_filter, api_filter);
+        utils::retain::retain_metadata(self, pallet_filter, api_filter, |_, _| true);
+    }
+
+    /// Like [`Metadata::retain()`], but also takes a method filter, which is given the runtime
+    /// API trait name and method name, and can be used to drop individual methods from the
+    /// runtime APIs that are kept.
+    pub fn retain_with_methods<F, G, H>(
+        &mut self,
+        pallet_filter: F,
+        api_filter: G,
+        method_filter: H,
+    ) where
+        F: FnMut(&str) -> bool,
+        G: FnMut(&str) -> bool,
+        H: FnMut(&str, &str) -> bool,
+    {
+        utils::retain::retain_metadata(self, pallet_filter, api_filter, method_filter);
     }
 
     /// Get type hash for a type in the registry
@@ -288,6 +391,11 @@ impl<'a> PalletMetadata<'a> {
         crate::utils::validation::get_call_hash(self, call_name)
     }
 
+    /// Return a hash for the event, or None if it was not found.
+    pub fn event_hash(&self, event_name: &str) -> Option<[u8; HASH_LEN]> {
+        crate::utils::validation::get_event_hash(self, event_name)
+    }
+
     /// Return a hash for the entire pallet.
     pub fn hash(&self) -> [u8; HASH_LEN] {
         crate::utils::validation::get_pallet_hash(*self, &OuterEnumHashes::empty())
@@ -325,6 +433,8 @@ struct PalletMetadataInner {
 pub struct StorageMetadata {
     /// The common prefix used by all storage entries.
     prefix: String,
+    /// The `twox_128` hash of the prefix, which all storage keys in the pallet begin with.
+    prefix_hash: [u8; 16],
     /// Map from storage entry name to details.
     entries: OrderedMap<ArcStr, StorageEntryMetadata>,
 }
@@ -335,6 +445,12 @@ impl StorageMetadata {
         &self.prefix
     }
 
+    /// The `twox_128` hash of [`StorageMetadata::prefix()`]. The keys of every storage
+    /// entry in the pallet begin with these bytes.
+    pub fn prefix_hash(&self) -> &[u8; 16] {
+        &self.prefix_hash
+    }
+
     /// An iterator over the storage entries.
     pub fn entries(&self) -> &[StorageEntryMetadata] {
         self.entries.values()
@@ -378,6 +494,40 @@ impl StorageEntryMetadata {
     pub fn default_bytes(&self) -> &[u8] {
         &self.default
     }
+    /// Decode the default value for this entry into a [`scale_value::Value`], using the
+    /// given type registry. For [`StorageEntryModifier::Optional`] entries the default is
+    /// an `Option` of the value type, and so decodes to a `None` or `Some` variant.
+    pub fn decode_default(
+        &self,
+        types: &PortableRegistry,
+    ) -> Result<scale_value::Value<u32>, DecodeDefaultError> {
+        let value_ty = self.entry_type.value_ty();
+        let bytes = &mut &*self.default;
+
+        let value = match self.modifier {
+            StorageEntryModifier::Default => {
+                scale_value::scale::decode_as_type(bytes, value_ty, types)?
+            }
+            StorageEntryModifier::Optional => match bytes.split_first() {
+                Some((0, rest)) => {
+                    *bytes = rest;
+                    scale_value::Value::variant("None", scale_value::Composite::unnamed([]))
+                }
+                Some((1, rest)) => {
+                    *bytes = rest;
+                    let inner = scale_value::scale::decode_as_type(bytes, value_ty, types)?;
+                    scale_value::Value::variant("Some", scale_value::Composite::unnamed([inner]))
+                }
+                Some((b, _)) => return Err(DecodeDefaultError::InvalidOptionByte(*b)),
+                None => return Err(DecodeDefaultError::NotEnoughBytes),
+            },
+        };
+
+        if !bytes.is_empty() {
+            return Err(DecodeDefaultError::LeftoverBytes(bytes.len()));
+        }
+        Ok(value)
+    }
     /// Storage entry documentation.
     pub fn docs(&self) -> &[String] {
         &self.docs
@@ -474,6 +624,50 @@ pub enum StorageEntryModifier {
     Default,
 }
 
+/// An error returned from [`StorageEntryMetadata::decode_default()`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DecodeDefaultError {
+    /// The default bytes could not be decoded into the value type.
+    Decode(scale_value::scale::DecodeError),
+    /// The default of an optional entry did not begin with a valid `Option` byte.
+    InvalidOptionByte(u8),
+    /// There were no default bytes to decode.
+    NotEnoughBytes,
+    /// Some bytes were left over after decoding the default value.
+    LeftoverBytes(usize),
+}
+
+impl From<scale_value::scale::DecodeError> for DecodeDefaultError {
+    fn from(e: scale_value::scale::DecodeError) -> Self {
+        DecodeDefaultError::Decode(e)
+    }
+}
+
+impl core::fmt::Display for DecodeDefaultError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeDefaultError::Decode(e) => {
+                write!(f, "Cannot decode the storage entry default: {e}")
+            }
+            DecodeDefaultError::InvalidOptionByte(b) => write!(
+                f,
+                "Optional storage entry default should begin with 0 or 1, but begins with {b}"
+            ),
+            DecodeDefaultError::NotEnoughBytes => {
+                write!(f, "The storage entry default contains no bytes")
+            }
+            DecodeDefaultError::LeftoverBytes(n) => write!(
+                f,
+                "{n} bytes were left over after decoding the storage entry default"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeDefaultError {}
+
 /// Metadata for a single constant.
 #[derive(Debug, Clone)]
 pub struct ConstantMetadata {
@@ -517,8 +711,9 @@ pub struct ExtrinsicMetadata {
     signature_ty: u32,
     /// The type of the outermost Extra enum.
     extra_ty: u32,
-    /// Extrinsic version.
-    version: u8,
+    /// The extrinsic versions supported by the runtime, in ascending order.
+    /// There is always at least one version.
+    supported_versions: Vec<u8>,
     /// The signed extensions in the order they appear in the extrinsic.
     signed_extensions: Vec<SignedExtensionMetadata>,
 }
@@ -542,9 +737,28 @@ impl ExtrinsicMetadata {
         self.extra_ty
     }
 
-    /// Extrinsic version.
+    /// The latest extrinsic version supported by the runtime.
     pub fn version(&self) -> u8 {
-        self.version
+        *self
+            .supported_versions
+            .last()
+            .expect("at least one extrinsic version is supported; qed")
+    }
+
+    /// Every extrinsic version supported by the runtime, in ascending order. Metadata
+    /// from current runtimes only advertises a single version.
+    pub fn supported_versions(&self) -> &[u8] {
+        &self.supported_versions
+    }
+
+    /// The highest extrinsic version supported by the runtime which is also in the
+    /// given list of versions, or `None` if there are no versions in common.
+    pub fn highest_compatible_version(&self, understood_versions: &[u8]) -> Option<u8> {
+        self.supported_versions
+            .iter()
+            .rev()
+            .find(|v| understood_versions.contains(v))
+            .copied()
     }
 
     /// The extra/additional information associated with the extrinsic.
@@ -820,4 +1034,155 @@ mod test {
         // The bytes should be identical:
         assert_eq!(bytes, new_bytes);
     }
+
+    #[test]
+    fn single_extrinsic_version() {
+        let metadata = Metadata::decode(&mut &*load_metadata()).unwrap();
+        let extrinsic = metadata.extrinsic();
+
+        assert_eq!(extrinsic.supported_versions(), &[4]);
+        assert_eq!(extrinsic.version(), 4);
+        assert_eq!(extrinsic.highest_compatible_version(&[4]), Some(4));
+        assert_eq!(extrinsic.highest_compatible_version(&[5]), None);
+    }
+
+    #[test]
+    fn multiple_extrinsic_versions() {
+        let mut metadata = Metadata::decode(&mut &*load_metadata()).unwrap();
+        let hash_before = metadata.hasher().hash();
+
+        metadata.extrinsic.supported_versions = vec![4, 5];
+        let extrinsic = metadata.extrinsic();
+
+        assert_eq!(extrinsic.version(), 5);
+        assert_eq!(extrinsic.highest_compatible_version(&[4]), Some(4));
+        assert_eq!(extrinsic.highest_compatible_version(&[4, 5]), Some(5));
+        assert_eq!(extrinsic.highest_compatible_version(&[3]), None);
+
+        // Supporting more versions changes the metadata hash.
+        assert_ne!(metadata.hasher().hash(), hash_before);
+    }
+
+    #[test]
+    fn pallets_can_be_looked_up_by_storage_prefix() {
+        let metadata = Metadata::decode(&mut &*load_metadata()).unwrap();
+
+        let system = metadata.pallet_by_storage_prefix("System").unwrap();
+        assert_eq!(system.name(), "System");
+
+        // twox_128("System"), which all System storage keys begin with:
+        let system_hash = [
+            0x26, 0xaa, 0x39, 0x4e, 0xea, 0x56, 0x30, 0xe0, 0x7c, 0x48, 0xae, 0x0c, 0x95, 0x58,
+            0xce, 0xf7,
+        ];
+        assert_eq!(system.storage().unwrap().prefix_hash(), &system_hash);
+        let system = metadata
+            .pallet_by_storage_prefix_hash(&system_hash)
+            .unwrap();
+        assert_eq!(system.name(), "System");
+
+        // Every pallet with storage can be found from its prefix hash:
+        for pallet in metadata.pallets() {
+            let Some(storage) = pallet.storage() else {
+                continue;
+            };
+            let found = metadata
+                .pallet_by_storage_prefix_hash(storage.prefix_hash())
+                .unwrap();
+            assert_eq!(found.name(), pallet.name());
+        }
+
+        assert!(metadata.pallet_by_storage_prefix("NotAPallet").is_none());
+        assert!(metadata.pallet_by_storage_prefix_hash(&[0; 16]).is_none());
+    }
+
+    #[test]
+    fn storage_entry_defaults_can_be_decoded() {
+        let metadata = Metadata::decode(&mut &*load_metadata()).unwrap();
+
+        // Every storage entry default decodes against its value type:
+        for pallet in metadata.pallets() {
+            let Some(storage) = pallet.storage() else {
+                continue;
+            };
+            for entry in storage.entries() {
+                let value = entry.decode_default(metadata.types());
+                assert!(
+                    value.is_ok(),
+                    "{}.{}: {:?}",
+                    pallet.name(),
+                    entry.name(),
+                    value
+                );
+            }
+        }
+
+        let account = metadata
+            .pallet_by_name("System")
+            .unwrap()
+            .storage()
+            .unwrap()
+            .entry_by_name("Account")
+            .unwrap();
+        let value = account.decode_default(metadata.types()).unwrap();
+        assert!(matches!(
+            value.value,
+            scale_value::ValueDef::Composite(scale_value::Composite::Named(_))
+        ));
+
+        // Bad default bytes are an error rather than a panic:
+        let mut broken = account.clone();
+        broken.default = vec![1, 2, 3];
+        assert!(broken.decode_default(metadata.types()).is_err());
+        broken.default = account.default.iter().copied().chain([0]).collect();
+        assert!(matches!(
+            broken.decode_default(metadata.types()),
+            Err(DecodeDefaultError::LeftoverBytes(1))
+        ));
+    }
+
+    #[test]
+    fn pallet_names_and_indices_can_be_looked_up() {
+        let metadata = Metadata::decode(&mut &*load_metadata()).unwrap();
+
+        assert_eq!(metadata.pallet_name_by_index(0), Some("System"));
+        assert_eq!(metadata.pallet_index_by_name("System"), Some(0));
+
+        assert_eq!(metadata.pallet_name_by_index(u8::MAX), None);
+        assert_eq!(metadata.pallet_index_by_name("NotAPallet"), None);
+
+        // Each lookup agrees with the full pallet metadata:
+        assert_eq!(metadata.pallet_indices().len(), metadata.pallets().len());
+        for (name, index) in metadata.pallet_indices() {
+            let pallet = metadata.pallet_by_index(index).unwrap();
+            assert_eq!(pallet.name(), name);
+            assert_eq!(metadata.pallet_name_by_index(index), Some(name));
+            assert_eq!(metadata.pallet_index_by_name(name), Some(index));
+        }
+    }
+
+    #[test]
+    fn find_runtime_api_methods_across_traits() {
+        let metadata = Metadata::decode(&mut &*load_metadata()).unwrap();
+
+        // A method which is only found on one trait.
+        let found = metadata.find_runtime_api_methods("account_nonce");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, "AccountNonceApi");
+        assert_eq!(found[0].1.name(), "account_nonce");
+
+        // A method which is found on several traits.
+        let found = metadata.find_runtime_api_methods("generate_key_ownership_proof");
+        let trait_names: Vec<_> = found.iter().map(|(name, _)| *name).collect();
+        assert!(trait_names.contains(&"BabeApi"));
+        assert!(trait_names.contains(&"GrandpaApi"));
+        for (_, method) in found {
+            assert_eq!(method.name(), "generate_key_ownership_proof");
+        }
+
+        // A method which doesn't exist.
+        assert!(metadata
+            .find_runtime_api_methods("not_a_real_method")
+            .is_empty());
+    }
 }
@@ -0,0 +1,594 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Compute the differences between two instances of [`Metadata`].
+
+use crate::utils::validation::Hash;
+use crate::{Metadata, PalletMetadata, RuntimeApiMetadata};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// The differences between two instances of [`Metadata`]. This is obtained by
+/// calling [`Metadata::diff()`].
+///
+/// Items are compared by name, and considered changed if their hashes (as used
+/// to validate statically generated code against some metadata) differ. All
+/// names are sorted alphabetically.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetadataDiff {
+    /// Pallets which exist only in the new metadata.
+    pub added_pallets: Vec<String>,
+    /// Pallets which exist only in the old metadata.
+    pub removed_pallets: Vec<String>,
+    /// Pallets which exist in both, but differ.
+    pub changed_pallets: Vec<PalletDiff>,
+    /// Runtime API traits which exist only in the new metadata.
+    pub added_runtime_apis: Vec<String>,
+    /// Runtime API traits which exist only in the old metadata.
+    pub removed_runtime_apis: Vec<String>,
+    /// Runtime API traits which exist in both, but differ.
+    pub changed_runtime_apis: Vec<RuntimeApiDiff>,
+}
+
+impl MetadataDiff {
+    /// Return true if no differences were found.
+    pub fn is_empty(&self) -> bool {
+        self.added_pallets.is_empty()
+            && self.removed_pallets.is_empty()
+            && self.changed_pallets.is_empty()
+            && self.added_runtime_apis.is_empty()
+            && self.removed_runtime_apis.is_empty()
+            && self.changed_runtime_apis.is_empty()
+    }
+}
+
+/// The differences between two versions of a pallet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PalletDiff {
+    /// The name of the pallet.
+    pub name: String,
+    /// Changes to the pallet calls.
+    pub calls: Changes,
+    /// Changes to the pallet events.
+    pub events: Changes,
+    /// Changes to the pallet storage entries.
+    pub storage: Changes,
+    /// Changes to the pallet constants.
+    pub constants: Changes,
+}
+
+/// The differences between two versions of a runtime API trait.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuntimeApiDiff {
+    /// The name of the runtime API trait.
+    pub name: String,
+    /// Changes to the methods of the runtime API trait.
+    pub methods: Changes,
+}
+
+/// The names of some items which were added, removed or changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Changes {
+    /// Items which exist only in the new metadata.
+    pub added: Vec<String>,
+    /// Items which exist only in the old metadata.
+    pub removed: Vec<String>,
+    /// Items which exist in both, but differ.
+    pub changed: Vec<String>,
+}
+
+impl Changes {
+    /// Return true if no differences were found.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A constant whose value differs between two instances of [`Metadata`]. This is
+/// obtained by calling [`Metadata::changed_constants()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChangedConstant {
+    /// The name of the pallet that the constant is in.
+    pub pallet: String,
+    /// The name of the constant.
+    pub name: String,
+    /// The SCALE encoded value of the constant in the old metadata.
+    pub old_value: Vec<u8>,
+    /// The SCALE encoded value of the constant in the new metadata.
+    pub new_value: Vec<u8>,
+}
+
+/// Find the constants which exist in both the old and new metadata but whose values differ,
+/// sorted by pallet name and then constant name.
+pub fn changed_constants(old: &Metadata, new: &Metadata) -> Vec<ChangedConstant> {
+    let mut changed = Vec::new();
+
+    let mut old_pallets: Vec<_> = old.pallets().collect();
+    old_pallets.sort_by(|a, b| a.name().cmp(b.name()));
+
+    for old_pallet in old_pallets {
+        let Some(new_pallet) = new.pallet_by_name(old_pallet.name()) else {
+            continue;
+        };
+
+        let mut old_constants: Vec<_> = old_pallet.constants().collect();
+        old_constants.sort_by(|a, b| a.name().cmp(b.name()));
+
+        for old_constant in old_constants {
+            let Some(new_constant) = new_pallet.constant_by_name(old_constant.name()) else {
+                continue;
+            };
+            if old_constant.value() != new_constant.value() {
+                changed.push(ChangedConstant {
+                    pallet: old_pallet.name().to_string(),
+                    name: old_constant.name().to_string(),
+                    old_value: old_constant.value().to_vec(),
+                    new_value: new_constant.value().to_vec(),
+                });
+            }
+        }
+    }
+
+    changed
+}
+
+/// Compute the differences between some old and new metadata.
+pub fn diff_metadata(old: &Metadata, new: &Metadata) -> MetadataDiff {
+    let mut metadata_diff = MetadataDiff::default();
+
+    let pallets = diff_by_name(
+        old.pallets(),
+        new.pallets(),
+        |p| p.name().to_string(),
+        PalletMetadata::hash,
+        PalletMetadata::hash,
+    );
+    for (name, entry) in pallets {
+        match entry {
+            Entry::Added(_) => metadata_diff.added_pallets.push(name),
+            Entry::Removed(_) => metadata_diff.removed_pallets.push(name),
+            Entry::Changed(old, new) => metadata_diff
+                .changed_pallets
+                .push(diff_pallet(name, &old, &new)),
+        }
+    }
+
+    let apis = diff_by_name(
+        old.runtime_api_traits(),
+        new.runtime_api_traits(),
+        |r| r.name().to_string(),
+        RuntimeApiMetadata::hash,
+        RuntimeApiMetadata::hash,
+    );
+    for (name, entry) in apis {
+        match entry {
+            Entry::Added(_) => metadata_diff.added_runtime_apis.push(name),
+            Entry::Removed(_) => metadata_diff.removed_runtime_apis.push(name),
+            Entry::Changed(old, new) => metadata_diff
+                .changed_runtime_apis
+                .push(diff_runtime_api(name, &old, &new)),
+        }
+    }
+
+    metadata_diff
+}
+
+fn diff_pallet(name: String, old: &PalletMetadata, new: &PalletMetadata) -> PalletDiff {
+    let calls = diff_by_name(
+        old.call_variants().unwrap_or_default(),
+        new.call_variants().unwrap_or_default(),
+        |v| v.name.clone(),
+        |v| old.call_hash(&v.name).unwrap_or_default(),
+        |v| new.call_hash(&v.name).unwrap_or_default(),
+    );
+    let events = diff_by_name(
+        old.event_variants().unwrap_or_default(),
+        new.event_variants().unwrap_or_default(),
+        |v| v.name.clone(),
+        |v| old.event_hash(&v.name).unwrap_or_default(),
+        |v| new.event_hash(&v.name).unwrap_or_default(),
+    );
+    let storage = diff_by_name(
+        old.storage().map(|s| s.entries()).unwrap_or_default(),
+        new.storage().map(|s| s.entries()).unwrap_or_default(),
+        |e| e.name().to_string(),
+        |e| old.storage_hash(e.name()).unwrap_or_default(),
+        |e| new.storage_hash(e.name()).unwrap_or_default(),
+    );
+    let constants = diff_by_name(
+        old.constants(),
+        new.constants(),
+        |c| c.name().to_string(),
+        |c| old.constant_hash(c.name()).unwrap_or_default(),
+        |c| new.constant_hash(c.name()).unwrap_or_default(),
+    );
+
+    PalletDiff {
+        name,
+        calls: to_changes(calls),
+        events: to_changes(events),
+        storage: to_changes(storage),
+        constants: to_changes(constants),
+    }
+}
+
+fn diff_runtime_api(
+    name: String,
+    old: &RuntimeApiMetadata,
+    new: &RuntimeApiMetadata,
+) -> RuntimeApiDiff {
+    let methods = diff_by_name(
+        old.methods(),
+        new.methods(),
+        |m| m.name().to_string(),
+        |m| old.method_hash(m.name()).unwrap_or_default(),
+        |m| new.method_hash(m.name()).unwrap_or_default(),
+    );
+
+    RuntimeApiDiff {
+        name,
+        methods: to_changes(methods),
+    }
+}
+
+/// An item which differs between the old and new metadata.
+enum Entry<T> {
+    Added(T),
+    Removed(T),
+    Changed(T, T),
+}
+
+/// Compare some old and new items by name, returning only those which differ, sorted by name.
+fn diff_by_name<T>(
+    old_items: impl IntoIterator<Item = T>,
+    new_items: impl IntoIterator<Item = T>,
+    name_fn: impl Fn(&T) -> String,
+    old_hash_fn: impl Fn(&T) -> Hash,
+    new_hash_fn: impl Fn(&T) -> Hash,
+) -> BTreeMap<String, Entry<T>> {
+    let mut entries: BTreeMap<String, Entry<T>> = old_items
+        .into_iter()
+        .map(|item| (name_fn(&item), Entry::Removed(item)))
+        .collect();
+
+    for new_item in new_items {
+        let name = name_fn(&new_item);
+        match entries.remove(&name) {
+            None => {
+                entries.insert(name, Entry::Added(new_item));
+            }
+            Some(Entry::Removed(old_item)) => {
+                // Only keep hold of items whose hashes differ.
+                if old_hash_fn(&old_item) != new_hash_fn(&new_item) {
+                    entries.insert(name, Entry::Changed(old_item, new_item));
+                }
+            }
+            Some(entry) => {
+                // Duplicate names in the new items; keep the first one we saw.
+                entries.insert(name, entry);
+            }
+        }
+    }
+
+    entries
+}
+
+fn to_changes<T>(entries: BTreeMap<String, Entry<T>>) -> Changes {
+    let mut changes = Changes::default();
+    for (name, entry) in entries {
+        match entry {
+            Entry::Added(_) => changes.added.push(name),
+            Entry::Removed(_) => changes.removed.push(name),
+            Entry::Changed(..) => changes.changed.push(name),
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use codec::Encode;
+    use frame_metadata::v15;
+    use scale_info::meta_type;
+
+    #[allow(dead_code)]
+    #[derive(scale_info::TypeInfo)]
+    enum OldCall {
+        #[codec(index = 0)]
+        Unchanged { a: u8 },
+        #[codec(index = 1)]
+        Changed { b: u32 },
+        #[codec(index = 2)]
+        Removed,
+    }
+
+    #[allow(dead_code)]
+    #[derive(scale_info::TypeInfo)]
+    enum NewCall {
+        #[codec(index = 0)]
+        Unchanged { a: u8 },
+        #[codec(index = 1)]
+        Changed { b: u64 },
+        #[codec(index = 3)]
+        Added,
+    }
+
+    #[allow(dead_code)]
+    #[derive(scale_info::TypeInfo)]
+    enum OldEvent {
+        Happened(u8),
+    }
+
+    #[allow(dead_code)]
+    #[derive(scale_info::TypeInfo)]
+    enum NewEvent {
+        Happened(u8),
+        AlsoHappened,
+    }
+
+    fn extrinsic() -> v15::ExtrinsicMetadata {
+        v15::ExtrinsicMetadata {
+            version: 0,
+            signed_extensions: vec![],
+            address_ty: meta_type::<()>(),
+            call_ty: meta_type::<()>(),
+            signature_ty: meta_type::<()>(),
+            extra_ty: meta_type::<()>(),
+        }
+    }
+
+    fn pallet(name: &'static str, index: u8) -> v15::PalletMetadata {
+        v15::PalletMetadata {
+            name,
+            storage: None,
+            calls: None,
+            event: None,
+            constants: vec![],
+            error: None,
+            index,
+            docs: vec![],
+        }
+    }
+
+    fn constant<T: scale_info::TypeInfo + 'static>(
+        name: &'static str,
+    ) -> v15::PalletConstantMetadata {
+        constant_with_value::<T>(name, vec![])
+    }
+
+    fn constant_with_value<T: scale_info::TypeInfo + 'static>(
+        name: &'static str,
+        value: Vec<u8>,
+    ) -> v15::PalletConstantMetadata {
+        v15::PalletConstantMetadata {
+            name,
+            ty: meta_type::<T>(),
+            value,
+            docs: vec![],
+        }
+    }
+
+    fn storage_entry<T: scale_info::TypeInfo + 'static>(
+        name: &'static str,
+    ) -> v15::StorageEntryMetadata {
+        v15::StorageEntryMetadata {
+            name,
+            modifier: v15::StorageEntryModifier::Optional,
+            ty: v15::StorageEntryType::Plain(meta_type::<T>()),
+            default: vec![],
+            docs: vec![],
+        }
+    }
+
+    fn runtime_api<T: scale_info::TypeInfo + 'static>(
+        name: &'static str,
+    ) -> v15::RuntimeApiMetadata {
+        v15::RuntimeApiMetadata {
+            name,
+            methods: vec![
+                v15::RuntimeApiMethodMetadata {
+                    name: "unchanged",
+                    inputs: vec![],
+                    output: meta_type::<bool>(),
+                    docs: vec![],
+                },
+                v15::RuntimeApiMethodMetadata {
+                    name: "version",
+                    inputs: vec![],
+                    output: meta_type::<T>(),
+                    docs: vec![],
+                },
+            ],
+            docs: vec![],
+        }
+    }
+
+    fn to_metadata(
+        pallets: Vec<v15::PalletMetadata>,
+        apis: Vec<v15::RuntimeApiMetadata>,
+    ) -> Metadata {
+        v15::RuntimeMetadataV15::new(
+            pallets,
+            extrinsic(),
+            meta_type::<()>(),
+            apis,
+            v15::OuterEnums {
+                call_enum_ty: meta_type::<()>(),
+                event_enum_ty: meta_type::<()>(),
+                error_enum_ty: meta_type::<()>(),
+            },
+            v15::CustomMetadata {
+                map: Default::default(),
+            },
+        )
+        .try_into()
+        .expect("can build valid metadata")
+    }
+
+    fn old_metadata() -> Metadata {
+        let changed = v15::PalletMetadata {
+            calls: Some(v15::PalletCallMetadata {
+                ty: meta_type::<OldCall>(),
+            }),
+            event: Some(v15::PalletEventMetadata {
+                ty: meta_type::<OldEvent>(),
+            }),
+            constants: vec![constant::<u32>("Max"), constant::<u8>("Min")],
+            storage: Some(v15::PalletStorageMetadata {
+                prefix: "Changed",
+                entries: vec![storage_entry::<u32>("Value"), storage_entry::<u8>("Count")],
+            }),
+            ..pallet("Changed", 0)
+        };
+
+        to_metadata(
+            vec![changed, pallet("Unchanged", 1), pallet("Removed", 2)],
+            vec![runtime_api::<u32>("Core"), runtime_api::<u32>("OldApi")],
+        )
+    }
+
+    fn new_metadata() -> Metadata {
+        let changed = v15::PalletMetadata {
+            calls: Some(v15::PalletCallMetadata {
+                ty: meta_type::<NewCall>(),
+            }),
+            event: Some(v15::PalletEventMetadata {
+                ty: meta_type::<NewEvent>(),
+            }),
+            constants: vec![constant::<u64>("Max")],
+            storage: Some(v15::PalletStorageMetadata {
+                prefix: "Changed",
+                entries: vec![
+                    storage_entry::<u32>("Value"),
+                    storage_entry::<u16>("Count"),
+                    storage_entry::<bool>("Flag"),
+                ],
+            }),
+            ..pallet("Changed", 0)
+        };
+
+        to_metadata(
+            vec![changed, pallet("Unchanged", 1), pallet("Added", 3)],
+            vec![runtime_api::<u64>("Core"), runtime_api::<u32>("NewApi")],
+        )
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn identical_metadata_has_no_diff() {
+        let diff = old_metadata().diff(&old_metadata());
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn pallet_and_runtime_api_changes_are_reported() {
+        let diff = old_metadata().diff(&new_metadata());
+
+        assert_eq!(diff.added_pallets, names(&["Added"]));
+        assert_eq!(diff.removed_pallets, names(&["Removed"]));
+        assert_eq!(diff.added_runtime_apis, names(&["NewApi"]));
+        assert_eq!(diff.removed_runtime_apis, names(&["OldApi"]));
+
+        assert_eq!(
+            diff.changed_runtime_apis,
+            vec![RuntimeApiDiff {
+                name: "Core".into(),
+                methods: Changes {
+                    added: vec![],
+                    removed: vec![],
+                    changed: names(&["version"]),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn pallet_item_changes_are_reported() {
+        let diff = old_metadata().diff(&new_metadata());
+
+        assert_eq!(
+            diff.changed_pallets,
+            vec![PalletDiff {
+                name: "Changed".into(),
+                calls: Changes {
+                    added: names(&["Added"]),
+                    removed: names(&["Removed"]),
+                    changed: names(&["Changed"]),
+                },
+                events: Changes {
+                    added: names(&["AlsoHappened"]),
+                    removed: vec![],
+                    changed: vec![],
+                },
+                storage: Changes {
+                    added: names(&["Flag"]),
+                    removed: vec![],
+                    changed: names(&["Count"]),
+                },
+                constants: Changes {
+                    added: vec![],
+                    removed: names(&["Min"]),
+                    changed: names(&["Max"]),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_is_reversible() {
+        let forwards = old_metadata().diff(&new_metadata());
+        let backwards = new_metadata().diff(&old_metadata());
+
+        assert_eq!(forwards.added_pallets, backwards.removed_pallets);
+        assert_eq!(forwards.removed_pallets, backwards.added_pallets);
+        assert_eq!(
+            forwards.changed_pallets[0].calls.added,
+            backwards.changed_pallets[0].calls.removed
+        );
+    }
+
+    #[test]
+    fn changed_constant_values_are_reported() {
+        let balances = |deposit: u128| {
+            let constants = vec![
+                constant_with_value::<u128>("ExistentialDeposit", deposit.encode()),
+                constant_with_value::<u32>("MaxLocks", 50u32.encode()),
+            ];
+            to_metadata(
+                vec![v15::PalletMetadata {
+                    constants,
+                    ..pallet("Balances", 0)
+                }],
+                vec![],
+            )
+        };
+        let old = balances(500);
+        let new = balances(1000);
+
+        // The shape of the constants is unchanged, so `diff` sees nothing:
+        assert!(old.diff(&new).is_empty());
+        assert!(old.changed_constants(&old).is_empty());
+
+        assert_eq!(
+            old.changed_constants(&new),
+            vec![ChangedConstant {
+                pallet: "Balances".into(),
+                name: "ExistentialDeposit".into(),
+                old_value: 500u128.encode(),
+                new_value: 1000u128.encode(),
+            }]
+        );
+    }
+}
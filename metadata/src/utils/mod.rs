@@ -2,7 +2,9 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
+pub mod diff;
 pub mod ordered_map;
 pub mod retain;
+pub mod type_graph;
 pub mod validation;
 pub mod variant_index;
@@ -190,6 +190,8 @@ where
 
 /// Generate a subset of the metadata that contains only the
 /// types needed to represent the provided pallets and runtime APIs.
+/// The methods filter is given the runtime API trait and method names,
+/// and decides which methods to keep in the runtime APIs that are retained.
 ///
 /// # Note
 ///
@@ -200,13 +202,15 @@ where
 ///
 /// Panics if the [`scale_info::PortableRegistry`] did not retain all needed types,
 /// or the metadata does not contain the "sp_runtime::DispatchError" type.
-pub fn retain_metadata<F, G>(
+pub fn retain_metadata<F, G, H>(
     metadata: &mut Metadata,
     mut pallets_filter: F,
     mut runtime_apis_filter: G,
+    mut runtime_api_methods_filter: H,
 ) where
     F: FnMut(&str) -> bool,
     G: FnMut(&str) -> bool,
+    H: FnMut(&str, &str) -> bool,
 {
     let mut type_ids = HashSet::new();
 
@@ -236,6 +240,13 @@ pub fn retain_metadata<F, G>(
         .enumerate()
         .map(|(pos, p)| (p.index, pos))
         .collect();
+    metadata.pallets_by_storage_prefix_hash = metadata
+        .pallets
+        .values()
+        .iter()
+        .enumerate()
+        .filter_map(|(pos, p)| Some((p.storage.as_ref()?.prefix_hash, pos)))
+        .collect();
 
     // Keep the extrinsic stuff referenced in our metadata.
     collect_extrinsic_types(&metadata.extrinsic, &mut type_ids);
@@ -243,15 +254,16 @@ pub fn retain_metadata<F, G>(
     // Keep the "runtime" type ID, since it's referenced in our metadata.
     type_ids.insert(metadata.runtime_ty);
 
-    // Keep only the runtime API types that the filter allows for. Keep hold of all
-    // type IDs in the runtime apis we're keeping. Retain all, if no filter specified.
-    metadata.apis.retain(|api| {
-        let should_retain = runtime_apis_filter(&api.name);
-        if should_retain {
-            collect_runtime_api_types(api, &mut type_ids);
-        }
-        should_retain
-    });
+    // Keep only the runtime APIs that the filter allows for, and within those, only the
+    // methods that the methods filter allows for. Keep hold of all type IDs in the runtime
+    // API methods we're keeping, so that types used only by dropped methods are removed.
+    metadata.apis.retain(|api| runtime_apis_filter(&api.name));
+    for api in metadata.apis.values_mut() {
+        let api_name = &api.name;
+        api.methods
+            .retain(|method| runtime_api_methods_filter(api_name, &method.name));
+        collect_runtime_api_types(api, &mut type_ids);
+    }
 
     // Additionally, subxt depends on the `DispatchError` type existing; we use the same
     // logic here that is used when building our `Metadata`.
@@ -311,6 +323,7 @@ mod tests {
                 &mut metadata,
                 |pallet_name| pallet_name == pallet.name(),
                 |_| true,
+                |_, _| true,
             );
 
             assert_eq!(metadata.pallets.len(), 1);
@@ -347,6 +360,7 @@ mod tests {
                 &mut metadata,
                 |_| true,
                 |runtime_api_name| runtime_api_name == runtime_api.name(),
+                |_, _| true,
             );
 
             assert_eq!(metadata.apis.len(), 1);
@@ -356,4 +370,37 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn retain_one_runtime_api_method() {
+        let metadata_cache = load_metadata();
+        let types_before = metadata_cache.types.types.len();
+
+        let mut metadata = metadata_cache.clone();
+        retain_metadata(
+            &mut metadata,
+            |_| false,
+            |runtime_api_name| runtime_api_name == "Metadata",
+            |runtime_api_name, method_name| {
+                assert_eq!(runtime_api_name, "Metadata");
+                method_name == "metadata_versions"
+            },
+        );
+
+        let api = metadata.runtime_api_trait_by_name("Metadata").unwrap();
+        let methods: Vec<_> = api.methods().map(|m| m.name()).collect();
+        assert_eq!(methods, ["metadata_versions"]);
+        assert!(api.method_by_name("metadata_at_version").is_none());
+
+        // Types used only by the dropped methods are removed too:
+        let mut metadata_all_methods = metadata_cache.clone();
+        retain_metadata(
+            &mut metadata_all_methods,
+            |_| false,
+            |runtime_api_name| runtime_api_name == "Metadata",
+            |_, _| true,
+        );
+        assert!(metadata.types.types.len() < metadata_all_methods.types.types.len());
+        assert!(metadata_all_methods.types.types.len() < types_before);
+    }
 }
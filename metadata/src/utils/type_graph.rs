@@ -0,0 +1,213 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Build a graph of the types in some [`crate::Metadata`] and the types that they reference.
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use scale_info::{form::PortableForm, PortableRegistry, TypeDef};
+
+/// A graph of the types in some metadata, and the types that each of them references.
+/// This is obtained by calling [`crate::Metadata::type_graph()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeGraph {
+    /// Each of the types in the metadata, in order of type ID.
+    pub nodes: Vec<TypeGraphNode>,
+    /// The references from one type to another, ordered by the referencing type ID
+    /// and then by the referenced type ID. Each reference appears at most once.
+    pub edges: Vec<TypeGraphEdge>,
+}
+
+/// A single type in a [`TypeGraph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeGraphNode {
+    /// The ID of the type.
+    pub id: u32,
+    /// The path of the type, which is empty for things like primitives, tuples and sequences.
+    pub path: Vec<String>,
+}
+
+/// A reference from one type to another in a [`TypeGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeGraphEdge {
+    /// The ID of the type doing the referencing.
+    pub from: u32,
+    /// The ID of the type being referenced.
+    pub to: u32,
+}
+
+impl TypeGraph {
+    /// Iterate over the IDs of the types referenced by the type with the given ID.
+    pub fn references(&self, id: u32) -> impl Iterator<Item = u32> + '_ {
+        self.edges
+            .iter()
+            .filter(move |e| e.from == id)
+            .map(|e| e.to)
+    }
+
+    /// Iterate over the IDs of the types which reference the type with the given ID.
+    pub fn referenced_by(&self, id: u32) -> impl Iterator<Item = u32> + '_ {
+        self.edges
+            .iter()
+            .filter(move |e| e.to == id)
+            .map(|e| e.from)
+    }
+
+    /// Render the graph in the DOT format, so that it can be visualized with tools like Graphviz.
+    /// Types are labelled with their path, or with their ID if they have no path.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph types {\n");
+        for node in &self.nodes {
+            let label = if node.path.is_empty() {
+                format!("#{}", node.id)
+            } else {
+                node.path.join("::")
+            };
+            out.push_str(&format!("    {} [label=\"{}\"];\n", node.id, label));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!("    {} -> {};\n", edge.from, edge.to));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Build a [`TypeGraph`] from the types in the given registry.
+pub fn type_graph(types: &PortableRegistry) -> TypeGraph {
+    let mut graph = TypeGraph::default();
+
+    for ty in types.types.iter() {
+        graph.nodes.push(TypeGraphNode {
+            id: ty.id,
+            path: ty.ty.path.segments.clone(),
+        });
+
+        let references: BTreeSet<u32> = referenced_ids(&ty.ty.type_def).into_iter().collect();
+        graph.edges.extend(
+            references
+                .into_iter()
+                .map(|to| TypeGraphEdge { from: ty.id, to }),
+        );
+    }
+
+    graph
+}
+
+/// The IDs of the types directly referenced by some type definition.
+fn referenced_ids(type_def: &TypeDef<PortableForm>) -> Vec<u32> {
+    match type_def {
+        TypeDef::Composite(c) => c.fields.iter().map(|f| f.ty.id).collect(),
+        TypeDef::Variant(v) => v
+            .variants
+            .iter()
+            .flat_map(|v| v.fields.iter().map(|f| f.ty.id))
+            .collect(),
+        TypeDef::Sequence(s) => Vec::from([s.type_param.id]),
+        TypeDef::Array(a) => Vec::from([a.type_param.id]),
+        TypeDef::Tuple(t) => t.fields.iter().map(|f| f.id).collect(),
+        TypeDef::Primitive(_) => Vec::new(),
+        TypeDef::Compact(c) => Vec::from([c.type_param.id]),
+        TypeDef::BitSequence(b) => Vec::from([b.bit_store_type.id, b.bit_order_type.id]),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scale_info::{meta_type, Registry, TypeInfo};
+
+    #[allow(dead_code)]
+    #[derive(TypeInfo)]
+    struct Foo {
+        a: u8,
+        b: Vec<bool>,
+        c: u8,
+    }
+
+    #[allow(dead_code)]
+    #[derive(TypeInfo)]
+    enum Bar {
+        A(Foo),
+        B { value: (u8, u16) },
+    }
+
+    fn registry_with<T: TypeInfo + 'static>() -> (PortableRegistry, u32) {
+        let mut registry = Registry::new();
+        let id = registry.register_type(&meta_type::<T>()).id;
+        (registry.into(), id)
+    }
+
+    fn id_of<T: TypeInfo + 'static>(types: &PortableRegistry) -> u32 {
+        let path = T::type_info().path.segments;
+        types
+            .types
+            .iter()
+            .find(|ty| ty.ty.path.segments == path && !path.is_empty())
+            .map(|ty| ty.id)
+            .expect("type should be in the registry")
+    }
+
+    #[test]
+    fn edges_point_to_field_types() {
+        let (types, foo_id) = registry_with::<Foo>();
+        let graph = type_graph(&types);
+
+        let TypeDef::Composite(foo) = &types.resolve(foo_id).unwrap().type_def else {
+            panic!("Foo should be a composite type");
+        };
+        let a_id = foo.fields[0].ty.id;
+        let b_id = foo.fields[1].ty.id;
+
+        // Both u8 fields are the same type, so there's only one edge to it:
+        let references: Vec<u32> = graph.references(foo_id).collect();
+        let mut expected = Vec::from([a_id, b_id]);
+        expected.sort();
+        assert_eq!(references, expected);
+
+        // The Vec<bool> references bool:
+        let TypeDef::Sequence(seq) = &types.resolve(b_id).unwrap().type_def else {
+            panic!("Vec<bool> should be a sequence type");
+        };
+        let references: Vec<u32> = graph.references(b_id).collect();
+        assert_eq!(references, Vec::from([seq.type_param.id]));
+
+        // Primitives don't reference anything:
+        assert_eq!(graph.references(a_id).count(), 0);
+        assert_eq!(
+            graph.referenced_by(a_id).collect::<Vec<_>>(),
+            Vec::from([foo_id])
+        );
+    }
+
+    #[test]
+    fn variant_and_tuple_fields_are_referenced() {
+        let (types, bar_id) = registry_with::<Bar>();
+        let graph = type_graph(&types);
+        let foo_id = id_of::<Foo>(&types);
+
+        let references: Vec<u32> = graph.references(bar_id).collect();
+        assert!(references.contains(&foo_id));
+        assert_eq!(references.len(), 2);
+
+        let tuple_id = references.into_iter().find(|id| *id != foo_id).unwrap();
+        assert_eq!(graph.references(tuple_id).count(), 2);
+    }
+
+    #[test]
+    fn graph_can_be_rendered_to_dot() {
+        let (types, foo_id) = registry_with::<Foo>();
+        let dot = type_graph(&types).to_dot();
+
+        assert!(dot.starts_with("digraph types {\n"));
+        assert!(dot.contains(&format!("    {foo_id} [label=\"")));
+        assert!(dot.contains("::Foo\"];\n"));
+        assert!(dot.contains(&format!("    {foo_id} -> ")));
+    }
+}
@@ -35,7 +35,9 @@ enum TypeBeingHashed {
 
 /// Hashing function utilized internally.
 fn hash(data: &[u8]) -> Hash {
-    sp_crypto_hashing::twox_256(data)
+    let mut hasher = Twox256Hasher::new();
+    hasher.update(data);
+    hasher.finalize()
 }
 
 /// XOR two hashes together. Only use this when you don't care about the order
@@ -48,23 +50,51 @@ fn xor(a: Hash, b: Hash) -> Hash {
     out
 }
 
+/// A streaming equivalent of Substrate's `twox_256` hash. This allows us to hash
+/// several inputs one after the other without first copying them into a single buffer.
+struct Twox256Hasher([twox_hash::XxHash64; 4]);
+
+impl Twox256Hasher {
+    fn new() -> Self {
+        Self([0, 1, 2, 3].map(twox_hash::XxHash64::with_seed))
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        for hasher in &mut self.0 {
+            core::hash::Hasher::write(hasher, data);
+        }
+    }
+
+    fn finalize(self) -> Hash {
+        let mut out = [0u8; HASH_LEN];
+        for (chunk, hasher) in out.chunks_exact_mut(8).zip(&self.0) {
+            chunk.copy_from_slice(&core::hash::Hasher::finish(hasher).to_le_bytes());
+        }
+        out
+    }
+}
+
+/// Substrate's `twox_128` hash, which is used to build the prefixes of storage keys.
+pub fn twox_128(data: &[u8]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    for (seed, chunk) in out.chunks_exact_mut(8).enumerate() {
+        let mut hasher = twox_hash::XxHash64::with_seed(seed as u64);
+        core::hash::Hasher::write(&mut hasher, data);
+        chunk.copy_from_slice(&core::hash::Hasher::finish(&hasher).to_le_bytes());
+    }
+    out
+}
+
 // Combine some number of HASH_LEN byte hashes and output a single HASH_LEN
 // byte hash to uniquely represent the inputs.
-macro_rules! count_idents {
-    () => { 0 };
-    ($n:ident $($rest:ident)*) => { 1 + count_idents!($($rest)*) }
-}
 macro_rules! concat_and_hash_n {
     ($name:ident($($arg:ident)+)) => {
         fn $name($($arg: &Hash),+) -> Hash {
-            let mut out = [0u8; HASH_LEN * count_idents!($($arg)+)];
-            let mut start = 0;
+            let mut hasher = Twox256Hasher::new();
             $(
-                out[start..start+HASH_LEN].copy_from_slice(&$arg[..]);
-                #[allow(unused_assignments)]
-                { start += HASH_LEN; }
+                hasher.update(&$arg[..]);
             )+
-            hash(&out)
+            hasher.finalize()
         }
     }
 }
@@ -296,9 +326,15 @@ fn get_extrinsic_hash(
         &address_hash,
         &signature_hash,
         &extra_hash,
-        &[extrinsic.version; 32],
+        &[extrinsic.version(); 32],
     );
 
+    // Any older versions that are supported alongside the latest one are hashed too.
+    let older_versions = &extrinsic.supported_versions[..extrinsic.supported_versions.len() - 1];
+    for version in older_versions {
+        bytes = concat_and_hash2(&bytes, &[*version; 32]);
+    }
+
     for signed_extension in extrinsic.signed_extensions.iter() {
         bytes = concat_and_hash4(
             &bytes,
@@ -473,6 +509,23 @@ pub fn get_call_hash(pallet: &PalletMetadata, call_name: &str) -> Option<Hash> {
     Some(hash)
 }
 
+/// Obtain the hash for a specific event, or an error if it's not found.
+pub fn get_event_hash(pallet: &PalletMetadata, event_name: &str) -> Option<Hash> {
+    let event_variant = pallet
+        .event_variants()?
+        .iter()
+        .find(|v| v.name == event_name)?;
+
+    // hash the specific variant representing the event we are interested in.
+    let hash = get_variant_hash(
+        pallet.types,
+        event_variant,
+        &mut HashMap::new(),
+        &OuterEnumHashes::empty(),
+    );
+    Some(hash)
+}
+
 /// Obtain the hash of a specific runtime API function, or an error if it's not found.
 pub fn get_runtime_api_hash(runtime_apis: &RuntimeApiMetadata, method_name: &str) -> Option<Hash> {
     let trait_name = &*runtime_apis.inner.name;
@@ -540,6 +593,15 @@ pub fn get_pallet_hash(pallet: PalletMetadata, outer_enum_hashes: &OuterEnumHash
     )
 }
 
+/// Which parts of the metadata a [`MetadataHasher`] will hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HashedParts {
+    Everything,
+    PalletsOnly,
+    RuntimeApisOnly,
+    ExtrinsicOnly,
+}
+
 /// Obtain a hash representation of our metadata or some part of it.
 /// This is obtained by calling [`crate::Metadata::hasher()`].
 pub struct MetadataHasher<'a> {
@@ -547,6 +609,7 @@ pub struct MetadataHasher<'a> {
     specific_pallets: Option<Vec<&'a str>>,
     specific_runtime_apis: Option<Vec<&'a str>>,
     include_custom_values: bool,
+    hashed_parts: HashedParts,
 }
 
 impl<'a> MetadataHasher<'a> {
@@ -557,6 +620,7 @@ impl<'a> MetadataHasher<'a> {
             specific_pallets: None,
             specific_runtime_apis: None,
             include_custom_values: true,
+            hashed_parts: HashedParts::Everything,
         }
     }
 
@@ -582,21 +646,64 @@ impl<'a> MetadataHasher<'a> {
         self
     }
 
+    /// Only hash the pallets (and the outer enums derived from them), ignoring the runtime
+    /// APIs, extrinsic details and custom values. This can be combined with
+    /// [`Self::only_these_pallets`].
+    pub fn hash_pallets_only(&mut self) -> &mut Self {
+        self.hashed_parts = HashedParts::PalletsOnly;
+        self
+    }
+
+    /// Only hash the runtime APIs, ignoring the pallets, extrinsic details and custom values.
+    /// This is useful if you only intend to make runtime API calls, and can be combined with
+    /// [`Self::only_these_runtime_apis`].
+    pub fn hash_runtime_apis_only(&mut self) -> &mut Self {
+        self.hashed_parts = HashedParts::RuntimeApisOnly;
+        self
+    }
+
+    /// Only hash the extrinsic details (ie the address, signature and signed extension types),
+    /// ignoring the pallets, runtime APIs and custom values.
+    ///
+    /// If any of these types refer to the outer enums (such as `RuntimeCall`), then only the
+    /// variants belonging to the pallets given in [`Self::only_these_pallets`] are hashed (and
+    /// none are, by default), so that changes to the calls of other pallets don't alter the hash.
+    pub fn hash_extrinsic_only(&mut self) -> &mut Self {
+        self.hashed_parts = HashedParts::ExtrinsicOnly;
+        self
+    }
+
+    /// Should the given part of the metadata be hashed?
+    fn should_hash(&self, part: HashedParts) -> bool {
+        self.hashed_parts == HashedParts::Everything || self.hashed_parts == part
+    }
+
     /// Hash the given metadata.
     pub fn hash(&self) -> Hash {
         let metadata = self.metadata;
 
         // Get the hashes of outer enums, considering only `specific_pallets` (if any are set).
         // If any of the typed that represent outer enums are encountered later, hashes from `top_level_enum_hashes` can be substituted.
-        let outer_enum_hashes = OuterEnumHashes::new(metadata, self.specific_pallets.as_deref());
+        // When only hashing the extrinsic, the outer enums only cover the pallets asked for.
+        let specific_pallets = match self.hashed_parts {
+            HashedParts::ExtrinsicOnly => Some(self.specific_pallets.as_deref().unwrap_or(&[])),
+            _ => self.specific_pallets.as_deref(),
+        };
+        let outer_enum_hashes = OuterEnumHashes::new(metadata, specific_pallets);
+
+        let hash_pallets = self.should_hash(HashedParts::PalletsOnly);
+        let hash_apis = self.should_hash(HashedParts::RuntimeApisOnly);
+        let hash_extrinsic = self.should_hash(HashedParts::ExtrinsicOnly);
+        let hash_everything = self.hashed_parts == HashedParts::Everything;
 
         let pallet_hash = metadata.pallets().fold([0u8; HASH_LEN], |bytes, pallet| {
             // If specific pallets are given, only include this pallet if it is in the specific pallets.
-            let should_hash = self
-                .specific_pallets
-                .as_ref()
-                .map(|specific_pallets| specific_pallets.contains(&pallet.name()))
-                .unwrap_or(true);
+            let should_hash = hash_pallets
+                && self
+                    .specific_pallets
+                    .as_ref()
+                    .map(|specific_pallets| specific_pallets.contains(&pallet.name()))
+                    .unwrap_or(true);
             // We don't care what order the pallets are seen in, so XOR their
             // hashes together to be order independent.
             if should_hash {
@@ -610,11 +717,12 @@ impl<'a> MetadataHasher<'a> {
             .runtime_api_traits()
             .fold([0u8; HASH_LEN], |bytes, api| {
                 // If specific runtime APIs are given, only include this pallet if it is in the specific runtime APIs.
-                let should_hash = self
-                    .specific_runtime_apis
-                    .as_ref()
-                    .map(|specific_runtime_apis| specific_runtime_apis.contains(&api.name()))
-                    .unwrap_or(true);
+                let should_hash = hash_apis
+                    && self
+                        .specific_runtime_apis
+                        .as_ref()
+                        .map(|specific_runtime_apis| specific_runtime_apis.contains(&api.name()))
+                        .unwrap_or(true);
                 // We don't care what order the runtime APIs are seen in, so XOR their
                 // hashes together to be order independent.
                 if should_hash {
@@ -624,12 +732,16 @@ impl<'a> MetadataHasher<'a> {
                 }
             });
 
-        let extrinsic_hash =
-            get_extrinsic_hash(&metadata.types, &metadata.extrinsic, &outer_enum_hashes);
-        let runtime_hash =
-            get_type_hash(&metadata.types, metadata.runtime_ty(), &outer_enum_hashes);
-        let custom_values_hash = self
-            .include_custom_values
+        let extrinsic_hash = hash_extrinsic
+            .then(|| get_extrinsic_hash(&metadata.types, &metadata.extrinsic, &outer_enum_hashes))
+            .unwrap_or_default();
+        let runtime_hash = hash_everything
+            .then(|| get_type_hash(&metadata.types, metadata.runtime_ty(), &outer_enum_hashes))
+            .unwrap_or_default();
+        let outer_enums_hash = hash_pallets
+            .then(|| outer_enum_hashes.combined_hash())
+            .unwrap_or_default();
+        let custom_values_hash = (hash_everything && self.include_custom_values)
             .then(|| get_custom_metadata_hash(&metadata.custom(), &outer_enum_hashes))
             .unwrap_or_default();
 
@@ -638,7 +750,7 @@ impl<'a> MetadataHasher<'a> {
             &apis_hash,
             &extrinsic_hash,
             &runtime_hash,
-            &outer_enum_hashes.combined_hash(),
+            &outer_enums_hash,
             &custom_values_hash,
         )
     }
@@ -1243,4 +1355,271 @@ mod tests {
 
         assert_eq!(hash, hash_trimmed);
     }
+
+    fn build_metadata(
+        pallets: Vec<v15::PalletMetadata>,
+        apis: Vec<v15::RuntimeApiMetadata>,
+        extrinsic: v15::ExtrinsicMetadata,
+    ) -> Metadata {
+        v15::RuntimeMetadataV15::new(
+            pallets,
+            extrinsic,
+            meta_type::<()>(),
+            apis,
+            v15::OuterEnums {
+                call_enum_ty: meta_type::<()>(),
+                event_enum_ty: meta_type::<()>(),
+                error_enum_ty: meta_type::<()>(),
+            },
+            v15::CustomMetadata {
+                map: Default::default(),
+            },
+        )
+        .try_into()
+        .expect("can build valid metadata")
+    }
+
+    fn build_default_apis<Output: scale_info::TypeInfo + 'static>() -> Vec<v15::RuntimeApiMetadata>
+    {
+        vec![v15::RuntimeApiMetadata {
+            name: "Core",
+            methods: vec![v15::RuntimeApiMethodMetadata {
+                name: "version",
+                inputs: vec![],
+                output: meta_type::<Output>(),
+                docs: vec![],
+            }],
+            docs: vec![],
+        }]
+    }
+
+    fn build_extrinsic_with_signed_extension() -> v15::ExtrinsicMetadata {
+        v15::ExtrinsicMetadata {
+            signed_extensions: vec![v15::SignedExtensionMetadata {
+                identifier: "CheckNonce",
+                ty: meta_type::<u32>(),
+                additional_signed: meta_type::<()>(),
+            }],
+            ..build_default_extrinsic()
+        }
+    }
+
+    #[test]
+    fn runtime_apis_only_hash_ignores_pallets() {
+        let metadata = build_metadata(
+            build_default_pallets(),
+            build_default_apis::<u32>(),
+            build_default_extrinsic(),
+        );
+        let metadata_other_pallets = build_metadata(
+            vec![build_default_pallets()[0].clone()],
+            build_default_apis::<u32>(),
+            build_default_extrinsic(),
+        );
+        let metadata_other_apis = build_metadata(
+            build_default_pallets(),
+            build_default_apis::<u64>(),
+            build_default_extrinsic(),
+        );
+
+        let apis_hash = |m: &Metadata| MetadataHasher::new(m).hash_runtime_apis_only().hash();
+
+        // Changing pallets doesn't alter the hash, but changing runtime APIs does.
+        assert_eq!(apis_hash(&metadata), apis_hash(&metadata_other_pallets));
+        assert_ne!(apis_hash(&metadata), apis_hash(&metadata_other_apis));
+
+        // It can be narrowed down to specific runtime APIs, too.
+        let no_apis_hash = MetadataHasher::new(&metadata)
+            .hash_runtime_apis_only()
+            .only_these_runtime_apis::<&str>(&[])
+            .hash();
+        let no_apis_hash_other = MetadataHasher::new(&metadata_other_apis)
+            .hash_runtime_apis_only()
+            .only_these_runtime_apis::<&str>(&[])
+            .hash();
+        assert_eq!(no_apis_hash, no_apis_hash_other);
+    }
+
+    #[test]
+    fn pallets_only_hash_ignores_runtime_apis() {
+        let metadata = build_metadata(
+            build_default_pallets(),
+            build_default_apis::<u32>(),
+            build_default_extrinsic(),
+        );
+        let metadata_other_pallets = build_metadata(
+            vec![build_default_pallets()[0].clone()],
+            build_default_apis::<u32>(),
+            build_default_extrinsic(),
+        );
+        let metadata_other_apis = build_metadata(
+            build_default_pallets(),
+            build_default_apis::<u64>(),
+            build_default_extrinsic(),
+        );
+
+        let pallets_hash = |m: &Metadata| MetadataHasher::new(m).hash_pallets_only().hash();
+
+        // Changing runtime APIs doesn't alter the hash, but changing pallets does.
+        assert_eq!(pallets_hash(&metadata), pallets_hash(&metadata_other_apis));
+        assert_ne!(
+            pallets_hash(&metadata),
+            pallets_hash(&metadata_other_pallets)
+        );
+
+        // Hashing everything notices both changes.
+        let hash = |m: &Metadata| MetadataHasher::new(m).hash();
+        assert_ne!(hash(&metadata), hash(&metadata_other_apis));
+        assert_ne!(hash(&metadata), hash(&metadata_other_pallets));
+    }
+
+    #[test]
+    fn extrinsic_only_hash_ignores_pallets_and_runtime_apis() {
+        let metadata = build_metadata(
+            build_default_pallets(),
+            build_default_apis::<u32>(),
+            build_default_extrinsic(),
+        );
+        let metadata_other_pallets_and_apis = build_metadata(
+            vec![build_default_pallets()[0].clone()],
+            build_default_apis::<u64>(),
+            build_default_extrinsic(),
+        );
+        let metadata_other_extrinsic = build_metadata(
+            build_default_pallets(),
+            build_default_apis::<u32>(),
+            build_extrinsic_with_signed_extension(),
+        );
+
+        let extrinsic_hash = |m: &Metadata| MetadataHasher::new(m).hash_extrinsic_only().hash();
+
+        assert_eq!(
+            extrinsic_hash(&metadata),
+            extrinsic_hash(&metadata_other_pallets_and_apis)
+        );
+        assert_ne!(
+            extrinsic_hash(&metadata),
+            extrinsic_hash(&metadata_other_extrinsic)
+        );
+
+        // Pallet and runtime API hashes don't care about the extrinsic details.
+        let apis_hash = |m: &Metadata| MetadataHasher::new(m).hash_runtime_apis_only().hash();
+        assert_eq!(apis_hash(&metadata), apis_hash(&metadata_other_extrinsic));
+    }
+
+    #[test]
+    fn extrinsic_only_hash_only_includes_calls_of_given_pallets() {
+        mod a {
+            #[allow(dead_code)]
+            #[derive(scale_info::TypeInfo)]
+            pub enum RuntimeCall {
+                First(u8),
+                Second(u8),
+            }
+        }
+        mod b {
+            #[allow(dead_code)]
+            #[derive(scale_info::TypeInfo)]
+            pub enum RuntimeCall {
+                First(u8),
+                Second(u16),
+            }
+        }
+
+        // A signed extension which refers to the outer call enum.
+        fn metadata_with_call<Call: scale_info::TypeInfo + 'static>() -> Metadata {
+            v15::RuntimeMetadataV15::new(
+                build_default_pallets(),
+                v15::ExtrinsicMetadata {
+                    signed_extensions: vec![v15::SignedExtensionMetadata {
+                        identifier: "CheckCall",
+                        ty: meta_type::<Call>(),
+                        additional_signed: meta_type::<()>(),
+                    }],
+                    ..build_default_extrinsic()
+                },
+                meta_type::<()>(),
+                vec![],
+                v15::OuterEnums {
+                    call_enum_ty: meta_type::<Call>(),
+                    event_enum_ty: meta_type::<()>(),
+                    error_enum_ty: meta_type::<()>(),
+                },
+                v15::CustomMetadata {
+                    map: Default::default(),
+                },
+            )
+            .try_into()
+            .expect("can build valid metadata")
+        }
+
+        let metadata_a = metadata_with_call::<a::RuntimeCall>();
+        let metadata_b = metadata_with_call::<b::RuntimeCall>();
+
+        let extrinsic_hash = |m: &Metadata| MetadataHasher::new(m).hash_extrinsic_only().hash();
+        let extrinsic_hash_for = |m: &Metadata, pallets: &[&str]| {
+            MetadataHasher::new(m)
+                .hash_extrinsic_only()
+                .only_these_pallets(pallets)
+                .hash()
+        };
+
+        // By default, no pallet calls are hashed, so a change to `Second` isn't noticed.
+        assert_eq!(extrinsic_hash(&metadata_a), extrinsic_hash(&metadata_b));
+        assert_eq!(
+            extrinsic_hash_for(&metadata_a, &["First"]),
+            extrinsic_hash_for(&metadata_b, &["First"])
+        );
+        assert_ne!(
+            extrinsic_hash_for(&metadata_a, &["Second"]),
+            extrinsic_hash_for(&metadata_b, &["Second"])
+        );
+    }
+
+    #[test]
+    fn streaming_hasher_matches_twox_256() {
+        let inputs: [&[u8]; 4] = [b"", b"a", &[1u8; 31], &[0xffu8; 200]];
+        for input in inputs {
+            let mut hasher = Twox256Hasher::new();
+            hasher.update(input);
+            assert_eq!(hasher.finalize(), hash(input));
+        }
+
+        // Feeding data in pieces is the same as hashing it all at once:
+        let data: Vec<u8> = (0..=255).collect();
+        let mut hasher = Twox256Hasher::new();
+        for piece in data.chunks(7) {
+            hasher.update(piece);
+        }
+        assert_eq!(hasher.finalize(), hash(&data));
+    }
+
+    #[test]
+    fn concat_and_hash_matches_hashing_concatenated_bytes() {
+        let hashes: Vec<Hash> = (0..6u8).map(|n| hash(&[n])).collect();
+        let concat_hash = |hashes: &[Hash]| hash(&hashes.concat());
+
+        assert_eq!(
+            concat_and_hash2(&hashes[0], &hashes[1]),
+            concat_hash(&hashes[..2])
+        );
+        assert_eq!(
+            concat_and_hash3(&hashes[0], &hashes[1], &hashes[2]),
+            concat_hash(&hashes[..3])
+        );
+        assert_eq!(
+            concat_and_hash4(&hashes[0], &hashes[1], &hashes[2], &hashes[3]),
+            concat_hash(&hashes[..4])
+        );
+        assert_eq!(
+            concat_and_hash5(&hashes[0], &hashes[1], &hashes[2], &hashes[3], &hashes[4]),
+            concat_hash(&hashes[..5])
+        );
+        assert_eq!(
+            concat_and_hash6(
+                &hashes[0], &hashes[1], &hashes[2], &hashes[3], &hashes[4], &hashes[5]
+            ),
+            concat_hash(&hashes[..6])
+        );
+    }
 }
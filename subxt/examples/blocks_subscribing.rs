@@ -1,4 +1,5 @@
 #![allow(missing_docs)]
+use subxt::utils::to_hex;
 use subxt::{OnlineClient, PolkadotConfig};
 
 #[subxt::subxt(runtime_metadata_path = "../artifacts/polkadot_metadata_small.scale")]
@@ -29,7 +30,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             let ext = ext?;
             let idx = ext.index();
             let events = ext.events().await?;
-            let bytes_hex = format!("0x{}", hex::encode(ext.bytes()));
+            let bytes_hex = to_hex(ext.bytes());
 
             // See the API docs for more ways to decode extrinsics:
             let decoded_ext = ext.as_root_extrinsic::<polkadot::Call>();
@@ -1,9 +1,7 @@
 #![allow(missing_docs)]
 use polkadot::multisig::events::NewMultisig;
-use polkadot::runtime_types::{
-    frame_system::pallet::Call, rococo_runtime::RuntimeCall, sp_weights::weight_v2::Weight,
-};
-use subxt::utils::AccountId32;
+use polkadot::runtime_types::{frame_system::pallet::Call, rococo_runtime::RuntimeCall};
+use subxt::utils::{AccountId32, Weight};
 use subxt::{OnlineClient, PolkadotConfig};
 use subxt_signer::sr25519::{dev, Keypair};
 
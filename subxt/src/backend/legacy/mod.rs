@@ -8,10 +8,11 @@
 pub mod rpc_methods;
 
 use self::rpc_methods::TransactionStatus as RpcTransactionStatus;
-use crate::backend::utils::{retry, retry_stream};
+use crate::backend::utils::{retry, retry_stream, stream_finalized_storage_value};
 use crate::backend::{
-    rpc::RpcClient, Backend, BlockRef, RuntimeVersion, StorageResponse, StreamOf, StreamOfResults,
-    TransactionStatus,
+    rpc::{self, RpcClient},
+    Backend, BlockRef, Justification, Justifications, RuntimeVersion, StorageResponse, StreamOf,
+    StreamOfResults, TransactionStatus,
 };
 use crate::error::RpcError;
 use crate::{config::Header, Config, Error};
@@ -19,6 +20,8 @@ use async_trait::async_trait;
 use futures::{future, future::Either, stream, Future, FutureExt, Stream, StreamExt};
 use std::collections::VecDeque;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 // Expose the RPC methods.
@@ -27,6 +30,7 @@ pub use rpc_methods::LegacyRpcMethods;
 /// Configure and build an [`LegacyBackend`].
 pub struct LegacyBackendBuilder<T> {
     storage_page_size: u32,
+    max_concurrent_gap_fills: usize,
     _marker: std::marker::PhantomData<T>,
 }
 
@@ -41,6 +45,7 @@ impl<T: Config> LegacyBackendBuilder<T> {
     pub fn new() -> Self {
         Self {
             storage_page_size: 64,
+            max_concurrent_gap_fills: 1,
             _marker: std::marker::PhantomData,
         }
     }
@@ -53,12 +58,23 @@ impl<T: Config> LegacyBackendBuilder<T> {
         self
     }
 
+    /// The legacy RPC methods may not emit every finalized block, and so we fill in
+    /// any gaps by fetching the missing headers. This configures how many of these
+    /// missing headers we'll try to fetch at once (default: 1). Headers are always
+    /// handed back in order.
+    pub fn max_concurrent_gap_fills(mut self, max_concurrent_gap_fills: usize) -> Self {
+        self.max_concurrent_gap_fills = max_concurrent_gap_fills.max(1);
+        self
+    }
+
     /// Given an [`RpcClient`] to use to make requests, this returns a [`LegacyBackend`],
     /// which implements the [`Backend`] trait.
     pub fn build(self, client: impl Into<RpcClient>) -> LegacyBackend<T> {
         LegacyBackend {
             storage_page_size: self.storage_page_size,
+            max_concurrent_gap_fills: self.max_concurrent_gap_fills,
             methods: LegacyRpcMethods::new(client.into()),
+            finalized_number: Arc::new(AtomicU64::new(0)),
         }
     }
 }
@@ -67,14 +83,20 @@ impl<T: Config> LegacyBackendBuilder<T> {
 #[derive(Debug)]
 pub struct LegacyBackend<T> {
     storage_page_size: u32,
+    max_concurrent_gap_fills: usize,
     methods: LegacyRpcMethods<T>,
+    // The highest finalized block number that we've seen so far. Blocks never
+    // become unfinalized, so any number up to this one has a finalized hash.
+    finalized_number: Arc<AtomicU64>,
 }
 
 impl<T> Clone for LegacyBackend<T> {
     fn clone(&self) -> LegacyBackend<T> {
         LegacyBackend {
             storage_page_size: self.storage_page_size,
+            max_concurrent_gap_fills: self.max_concurrent_gap_fills,
             methods: self.methods.clone(),
+            finalized_number: self.finalized_number.clone(),
         }
     }
 }
@@ -105,7 +127,10 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
                 let key = key.clone();
                 async move {
                     let res = methods.state_get_storage(&key, Some(at)).await?;
-                    Ok(res.map(move |value| StorageResponse { key, value }))
+                    Ok(res.map(move |value| StorageResponse {
+                        key,
+                        value: value.0,
+                    }))
                 }
             })
         }
@@ -135,6 +160,7 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         let keys = StorageFetchDescendantKeysStream {
             at,
             key,
+            child_key: None,
             storage_page_size: self.storage_page_size,
             methods: self.methods.clone(),
             done: Default::default(),
@@ -166,6 +192,7 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         let keys_stream = StorageFetchDescendantKeysStream {
             at,
             key,
+            child_key: None,
             storage_page_size: self.storage_page_size,
             methods: self.methods.clone(),
             done: Default::default(),
@@ -180,6 +207,94 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         })))
     }
 
+    async fn child_storage_fetch_values(
+        &self,
+        child_key: Vec<u8>,
+        keys: Vec<Vec<u8>>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        let child_storage_key = prefixed_child_storage_key(&child_key);
+        let methods = self.methods.clone();
+
+        let s = stream::iter(keys)
+            .then(move |key| {
+                let methods = methods.clone();
+                let child_storage_key = child_storage_key.clone();
+                retry(move || {
+                    let methods = methods.clone();
+                    let child_storage_key = child_storage_key.clone();
+                    let key = key.clone();
+                    async move {
+                        let res = methods
+                            .childstate_get_storage(&child_storage_key, &key, Some(at))
+                            .await?;
+                        Ok(res.map(move |value| StorageResponse {
+                            key,
+                            value: value.0,
+                        }))
+                    }
+                })
+            })
+            // Filter out any keys that we didn't find a value at.
+            .filter_map(|r| future::ready(r.transpose()));
+
+        Ok(StreamOf(Box::pin(s)))
+    }
+
+    async fn child_storage_fetch_descendant_keys(
+        &self,
+        child_key: Vec<u8>,
+        key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<Vec<u8>>, Error> {
+        let keys = StorageFetchDescendantKeysStream {
+            at,
+            key,
+            child_key: Some(prefixed_child_storage_key(&child_key)),
+            storage_page_size: self.storage_page_size,
+            methods: self.methods.clone(),
+            done: Default::default(),
+            keys_fut: Default::default(),
+            pagination_start_key: None,
+        };
+
+        let keys = keys.flat_map(|keys| match keys {
+            Err(e) => Either::Left(stream::iter(std::iter::once(Err(e)))),
+            Ok(keys) => Either::Right(stream::iter(keys.into_iter().map(Ok))),
+        });
+
+        Ok(StreamOf(Box::pin(keys)))
+    }
+
+    async fn storage_read_proof(
+        &self,
+        keys: Vec<Vec<u8>>,
+        at: T::Hash,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        retry(|| async {
+            let proof = self
+                .methods
+                .state_get_read_proof(keys.iter().map(|k| &**k), Some(at))
+                .await?;
+            Ok(proof.proof.into_iter().map(|node| node.0).collect())
+        })
+        .await
+    }
+
+    async fn storage_fetch_size(&self, key: Vec<u8>, at: T::Hash) -> Result<Option<u64>, Error> {
+        retry(|| self.methods.state_get_storage_size(&key, Some(at))).await
+    }
+
+    async fn storage_fetch_closest_descendant_merkle_value(
+        &self,
+        _key: Vec<u8>,
+        _at: T::Hash,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        Err(Error::Other(
+            "Closest descendant merkle values are not supported by the legacy RPC methods".into(),
+        ))
+    }
+
     async fn genesis_hash(&self) -> Result<T::Hash, Error> {
         retry(|| self.methods.genesis_hash()).await
     }
@@ -188,6 +303,25 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         retry(|| self.methods.chain_get_header(Some(at))).await
     }
 
+    async fn finalized_block_hash_at_number(&self, number: u64) -> Result<Option<T::Hash>, Error> {
+        // Block numbers up to the latest finalized block all map to finalized hashes. We only
+        // need to ask for the finalized head if we haven't already seen a block this new
+        // finalized, so that looking up a range of blocks costs one request per block.
+        if number > self.finalized_number.load(Ordering::Relaxed) {
+            let finalized_hash = retry(|| self.methods.chain_get_finalized_head()).await?;
+            let Some(finalized_header) = self.block_header(finalized_hash).await? else {
+                return Ok(None);
+            };
+            let finalized_number: u64 = finalized_header.number().into();
+            self.finalized_number
+                .fetch_max(finalized_number, Ordering::Relaxed);
+            if number > finalized_number {
+                return Ok(None);
+            }
+        }
+        retry(|| self.methods.chain_get_block_hash(Some(number.into()))).await
+    }
+
     async fn block_body(&self, at: T::Hash) -> Result<Option<Vec<Vec<u8>>>, Error> {
         retry(|| async {
             let Some(details) = self.methods.chain_get_block(Some(at)).await? else {
@@ -200,6 +334,21 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         .await
     }
 
+    async fn block_justifications(&self, at: T::Hash) -> Result<Option<Justifications>, Error> {
+        retry(|| async {
+            let Some(details) = self.methods.chain_get_block(Some(at)).await? else {
+                return Ok(None);
+            };
+            Ok(details.justifications.map(|justifications| {
+                justifications
+                    .into_iter()
+                    .map(|(engine_id, encoded)| Justification { engine_id, encoded })
+                    .collect()
+            }))
+        })
+        .await
+    }
+
     async fn latest_finalized_block_ref(&self) -> Result<BlockRef<T::Hash>, Error> {
         retry(|| async {
             let hash = self.methods.chain_get_finalized_head().await?;
@@ -208,6 +357,19 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         .await
     }
 
+    async fn latest_best_block_ref(&self) -> Result<BlockRef<T::Hash>, Error> {
+        retry(|| async {
+            // Without a block number, this returns the hash of the best block.
+            let hash = self
+                .methods
+                .chain_get_block_hash(None)
+                .await?
+                .ok_or_else(|| Error::Other("Could not find the best block hash".into()))?;
+            Ok(BlockRef::from_hash(hash))
+        })
+        .await
+    }
+
     async fn current_runtime_version(&self) -> Result<RuntimeVersion, Error> {
         retry(|| async {
             let details = self.methods.state_get_runtime_version(None).await?;
@@ -316,10 +478,11 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
 
                 // Fill in any missing blocks, because the backend may not emit every finalized block; just the latest ones which
                 // are finalized each time.
-                let sub = subscribe_to_block_headers_filling_in_gaps(
+                let sub = subscribe_to_block_headers_filling_in_gaps_with_config(
                     this.methods.clone(),
                     sub,
                     last_finalized_block_num,
+                    FillGapsConfig::new().max_concurrent_fills(this.max_concurrent_gap_fills),
                 );
                 let sub = sub.map(|r| {
                     r.map(|h| {
@@ -336,6 +499,16 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
         Ok(retry_sub)
     }
 
+    async fn stream_storage_value(
+        &self,
+        key: Vec<u8>,
+    ) -> Result<StreamOfResults<(BlockRef<T::Hash>, Option<Vec<u8>>)>, Error> {
+        // `state_subscribeStorage` reports changes as they are seen in new best blocks,
+        // which may never be finalized, so we fetch the value at finalized blocks instead.
+        // See `stream_finalized_storage_value` for how often this is done.
+        stream_finalized_storage_value(self, key).await
+    }
+
     async fn submit_transaction(
         &self,
         extrinsic: &[u8],
@@ -405,21 +578,103 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for LegacyBackend<T> {
     ) -> Result<Vec<u8>, Error> {
         retry(|| self.methods.state_call(method, call_parameters, Some(at))).await
     }
+
+    fn connection_events(&self) -> StreamOf<rpc::ConnectionEvent> {
+        self.methods.client().connection_events()
+    }
+}
+
+/// Configuration for [`subscribe_to_block_headers_filling_in_gaps_with_config`].
+#[derive(Clone)]
+pub struct FillGapsConfig {
+    max_concurrent_fills: usize,
+    on_progress: Option<Arc<dyn Fn(usize) + Send + Sync + 'static>>,
+}
+
+impl Default for FillGapsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for FillGapsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FillGapsConfig")
+            .field("max_concurrent_fills", &self.max_concurrent_fills)
+            .field(
+                "on_progress",
+                &self.on_progress.as_ref().map(|_| "<callback>"),
+            )
+            .finish()
+    }
+}
+
+impl FillGapsConfig {
+    /// Create a new [`FillGapsConfig`]. By default, missing headers are fetched one at a time
+    /// and no progress is reported.
+    pub fn new() -> Self {
+        Self {
+            max_concurrent_fills: 1,
+            on_progress: None,
+        }
+    }
+
+    /// The maximum number of missing headers that we'll try to fetch at once when filling
+    /// in a gap (default: 1). A value of 0 is treated as 1. Headers are always handed back
+    /// in increasing block number order regardless of this value.
+    pub fn max_concurrent_fills(mut self, max_concurrent_fills: usize) -> Self {
+        self.max_concurrent_fills = max_concurrent_fills.max(1);
+        self
+    }
+
+    /// Provide a callback which will be called each time a missing header is filled in, with
+    /// the total number of headers that have been filled in so far.
+    pub fn on_progress(mut self, f: impl Fn(usize) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(Arc::new(f));
+        self
+    }
 }
 
 /// Note: This is exposed for testing but is not considered stable and may change
 /// without notice in a patch release.
 #[doc(hidden)]
 pub fn subscribe_to_block_headers_filling_in_gaps<T, S, E>(
+    methods: LegacyRpcMethods<T>,
+    sub: S,
+    last_block_num: Option<u64>,
+) -> impl Stream<Item = Result<T::Header, Error>> + Send
+where
+    T: Config,
+    S: Stream<Item = Result<T::Header, E>> + Send,
+    E: Into<Error> + Send + 'static,
+{
+    subscribe_to_block_headers_filling_in_gaps_with_config(
+        methods,
+        sub,
+        last_block_num,
+        FillGapsConfig::default(),
+    )
+}
+
+/// Like [`subscribe_to_block_headers_filling_in_gaps`], but allows configuring how
+/// gaps are filled in via [`FillGapsConfig`].
+///
+/// Note: This is exposed for testing but is not considered stable and may change
+/// without notice in a patch release.
+#[doc(hidden)]
+pub fn subscribe_to_block_headers_filling_in_gaps_with_config<T, S, E>(
     methods: LegacyRpcMethods<T>,
     sub: S,
     mut last_block_num: Option<u64>,
+    config: FillGapsConfig,
 ) -> impl Stream<Item = Result<T::Header, Error>> + Send
 where
     T: Config,
     S: Stream<Item = Result<T::Header, E>> + Send,
     E: Into<Error> + Send + 'static,
 {
+    let filled_count = Arc::new(AtomicUsize::new(0));
+
     sub.flat_map(move |s| {
         // Get the header, or return a stream containing just the error.
         let header = match s {
@@ -434,10 +689,14 @@ where
         let start_block_num = last_block_num.map(|n| n + 1).unwrap_or(end_block_num);
 
         // Iterate over all of the previous blocks we need headers for, ignoring the current block
-        // (which we already have the header info for):
+        // (which we already have the header info for). `buffered` runs up to `max_concurrent_fills`
+        // of these at once, but always hands back results in the order that they were started,
+        // so headers are still returned in increasing block number order.
         let methods = methods.clone();
+        let on_progress = config.on_progress.clone();
+        let filled_count = filled_count.clone();
         let previous_headers = stream::iter(start_block_num..end_block_num)
-            .then(move |n| {
+            .map(move |n| {
                 let methods = methods.clone();
                 async move {
                     let hash = methods.chain_get_block_hash(Some(n.into())).await?;
@@ -445,7 +704,14 @@ where
                     Ok::<_, Error>(header)
                 }
             })
-            .filter_map(|h| async { h.transpose() });
+            .buffered(config.max_concurrent_fills)
+            .filter_map(|h| async { h.transpose() })
+            .inspect(move |h| {
+                if let (Ok(_), Some(on_progress)) = (h, &on_progress) {
+                    let n = filled_count.fetch_add(1, Ordering::Relaxed) + 1;
+                    on_progress(n);
+                }
+            });
 
         // On the next iteration, we'll get details starting just after this end block.
         last_block_num = Some(end_block_num);
@@ -461,6 +727,8 @@ where
 pub struct StorageFetchDescendantKeysStream<T: Config> {
     methods: LegacyRpcMethods<T>,
     key: Vec<u8>,
+    // The prefixed key of the child trie to look in, if not the main trie.
+    child_key: Option<Vec<u8>>,
     at: T::Hash,
     // How many entries to ask for each time.
     storage_page_size: u32,
@@ -528,24 +796,50 @@ impl<T: Config> Stream for StorageFetchDescendantKeysStream<T> {
             // Else, we don't have a fut to get keys yet so start one going.
             let methods = this.methods.clone();
             let key = this.key.clone();
+            let child_key = this.child_key.clone();
             let at = this.at;
             let storage_page_size = this.storage_page_size;
             let pagination_start_key = this.pagination_start_key.clone();
             let keys_fut = async move {
-                methods
-                    .state_get_keys_paged(
-                        &key,
-                        storage_page_size,
-                        pagination_start_key.as_deref(),
-                        Some(at),
-                    )
-                    .await
+                let keys = match child_key {
+                    Some(child_key) => {
+                        methods
+                            .childstate_get_keys_paged(
+                                &child_key,
+                                &key,
+                                storage_page_size,
+                                pagination_start_key.as_deref(),
+                                Some(at),
+                            )
+                            .await
+                    }
+                    None => {
+                        methods
+                            .state_get_keys_paged(
+                                &key,
+                                storage_page_size,
+                                pagination_start_key.as_deref(),
+                                Some(at),
+                            )
+                            .await
+                    }
+                };
+                keys.map(|keys| keys.into_iter().map(|k| k.0).collect())
             };
             this.keys_fut = Some(Box::pin(keys_fut));
         }
     }
 }
 
+/// The legacy `childstate_*` RPC methods expect the key of a child trie to be prefixed
+/// with the type of the child trie, which is always a default child trie at present.
+fn prefixed_child_storage_key(child_key: &[u8]) -> Vec<u8> {
+    const DEFAULT_CHILD_STORAGE_KEY_PREFIX: &[u8] = b":child_storage:default:";
+    let mut prefixed = DEFAULT_CHILD_STORAGE_KEY_PREFIX.to_vec();
+    prefixed.extend_from_slice(child_key);
+    prefixed
+}
+
 /// This provides a stream of values given some stream of keys.
 #[allow(clippy::type_complexity)]
 pub struct StorageFetchDescendantValuesStream<T: Config> {
@@ -626,3 +920,59 @@ impl<T: Config> Stream for StorageFetchDescendantValuesStream<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::rpc::mock_rpc_client::MockRpcClient;
+    use crate::config::PolkadotConfig;
+
+    #[tokio::test]
+    async fn finalized_head_is_only_fetched_for_newer_blocks() {
+        let zero_hash = format!("0x{}", "00".repeat(32));
+        let rpc_client = MockRpcClient::new()
+            .with_response("chain_getFinalizedHead", serde_json::json!(zero_hash))
+            .with_response(
+                "chain_getHeader",
+                serde_json::json!({
+                    "parentHash": zero_hash,
+                    "number": "0x5",
+                    "stateRoot": zero_hash,
+                    "extrinsicsRoot": zero_hash,
+                    "digest": { "logs": [] }
+                }),
+            )
+            .with_response("chain_getBlockHash", serde_json::json!(zero_hash));
+        let backend =
+            LegacyBackend::<PolkadotConfig>::builder().build(RpcClient::new(rpc_client.clone()));
+
+        for n in 1..=5 {
+            assert!(backend
+                .finalized_block_hash_at_number(n)
+                .await
+                .unwrap()
+                .is_some());
+        }
+        // Block 6 isn't finalized yet, so we check the finalized head again:
+        assert!(backend
+            .finalized_block_hash_at_number(6)
+            .await
+            .unwrap()
+            .is_none());
+
+        assert_eq!(
+            rpc_client.methods_called(),
+            vec![
+                "chain_getFinalizedHead",
+                "chain_getHeader",
+                "chain_getBlockHash",
+                "chain_getBlockHash",
+                "chain_getBlockHash",
+                "chain_getBlockHash",
+                "chain_getBlockHash",
+                "chain_getFinalizedHead",
+                "chain_getHeader",
+            ]
+        );
+    }
+}
@@ -5,7 +5,9 @@
 //! An interface to call the raw legacy RPC methods.
 
 use crate::backend::rpc::{rpc_params, RpcClient, RpcSubscription};
+use crate::error::RpcError;
 use crate::metadata::Metadata;
+use crate::utils::to_hex;
 use crate::{Config, Error};
 use codec::Decode;
 use derive_where::derive_where;
@@ -30,6 +32,11 @@ impl<T: Config> LegacyRpcMethods<T> {
         }
     }
 
+    /// The [`RpcClient`] that these methods are called on.
+    pub(crate) fn client(&self) -> &RpcClient {
+        &self.client
+    }
+
     /// Fetch the raw bytes for a given storage key
     pub async fn state_get_storage(
         &self,
@@ -37,8 +44,19 @@ impl<T: Config> LegacyRpcMethods<T> {
         hash: Option<T::Hash>,
     ) -> Result<Option<StorageData>, Error> {
         let params = rpc_params![to_hex(key), hash];
-        let data: Option<Bytes> = self.client.request("state_getStorage", params).await?;
-        Ok(data.map(|b| b.0))
+        let data = self.client.request("state_getStorage", params).await?;
+        Ok(data)
+    }
+
+    /// Fetch the size in bytes of the value at a given key, or `None` if no value exists.
+    pub async fn state_get_storage_size(
+        &self,
+        key: &[u8],
+        hash: Option<T::Hash>,
+    ) -> Result<Option<u64>, Error> {
+        let params = rpc_params![to_hex(key), hash];
+        let data = self.client.request("state_getStorageSize", params).await?;
+        Ok(data)
     }
 
     /// Returns the keys with prefix with pagination support.
@@ -53,8 +71,48 @@ impl<T: Config> LegacyRpcMethods<T> {
     ) -> Result<Vec<StorageKey>, Error> {
         let start_key = start_key.map(to_hex);
         let params = rpc_params![to_hex(key), count, start_key, at];
-        let data: Vec<Bytes> = self.client.request("state_getKeysPaged", params).await?;
-        Ok(data.into_iter().map(|b| b.0).collect())
+        let data = self.client.request("state_getKeysPaged", params).await?;
+        Ok(data)
+    }
+
+    /// Fetch the raw bytes for a given key in some child trie. The child storage key is
+    /// the prefixed key of the child trie (ie it begins with `:child_storage:default:`).
+    pub async fn childstate_get_storage(
+        &self,
+        child_storage_key: &[u8],
+        key: &[u8],
+        hash: Option<T::Hash>,
+    ) -> Result<Option<StorageData>, Error> {
+        let params = rpc_params![to_hex(child_storage_key), to_hex(key), hash];
+        let data = self.client.request("childstate_getStorage", params).await?;
+        Ok(data)
+    }
+
+    /// Returns the keys with prefix from some child trie with pagination support.
+    /// Up to `count` keys will be returned. If `start_key` is passed, return next keys
+    /// in storage in lexicographic order. The child storage key is the prefixed key of
+    /// the child trie (ie it begins with `:child_storage:default:`).
+    pub async fn childstate_get_keys_paged(
+        &self,
+        child_storage_key: &[u8],
+        prefix: &[u8],
+        count: u32,
+        start_key: Option<&[u8]>,
+        at: Option<T::Hash>,
+    ) -> Result<Vec<StorageKey>, Error> {
+        let start_key = start_key.map(to_hex);
+        let params = rpc_params![
+            to_hex(child_storage_key),
+            to_hex(prefix),
+            count,
+            start_key,
+            at
+        ];
+        let data = self
+            .client
+            .request("childstate_getKeysPaged", params)
+            .await?;
+        Ok(data)
     }
 
     /// Query historical storage entries in the range from the start block to the end block,
@@ -297,6 +355,25 @@ impl<T: Config> LegacyRpcMethods<T> {
         Ok(subscription)
     }
 
+    /// Subscribe to changes to the values at the given storage keys. The first
+    /// [`StorageChangeSet`] emitted has the current value of each key, and subsequent ones
+    /// only contain values for any keys which have changed in a new best block.
+    pub async fn state_subscribe_storage(
+        &self,
+        keys: impl IntoIterator<Item = &[u8]>,
+    ) -> Result<RpcSubscription<StorageChangeSet<T::Hash>>, Error> {
+        let keys: Vec<String> = keys.into_iter().map(to_hex).collect();
+        let subscription = self
+            .client
+            .subscribe(
+                "state_subscribeStorage",
+                rpc_params![keys],
+                "state_unsubscribeStorage",
+            )
+            .await?;
+        Ok(subscription)
+    }
+
     /// Create and submit an extrinsic and return corresponding Hash if successful
     pub async fn author_submit_extrinsic(&self, extrinsic: &[u8]) -> Result<T::Hash, Error> {
         let params = rpc_params![to_hex(extrinsic)];
@@ -387,22 +464,112 @@ impl<T: Config> LegacyRpcMethods<T> {
     /// Submits the extrinsic to the dry_run RPC, to test if it would succeed.
     ///
     /// Returns a [`DryRunResult`], which is the result of performing the dry run.
+    ///
+    /// # Note
+    ///
+    /// `system_dryRun` is an "unsafe" RPC method, and so is only available on nodes that
+    /// are started with `--rpc-methods unsafe` (or which are only listening on localhost,
+    /// as is the case for `--dev` nodes by default). Public endpoints almost always disable
+    /// it. If the node does not support it, [`RpcError::MethodNotSupported`] is returned.
     pub async fn dry_run(
         &self,
         encoded_signed: &[u8],
         at: Option<T::Hash>,
     ) -> Result<DryRunResultBytes, Error> {
         let params = rpc_params![to_hex(encoded_signed), at];
-        let result_bytes: Bytes = self.client.request("system_dryRun", params).await?;
+        let result_bytes: Bytes = self
+            .client
+            .request("system_dryRun", params)
+            .await
+            .map_err(|e| method_not_supported_err("system_dryRun", e))?;
         Ok(DryRunResultBytes(result_bytes.0))
     }
 }
 
+/// Nodes return a JSON-RPC error with code -32601 ("Method not found") for methods
+/// they don't expose, and a rejection mentioning "unsafe" when the method is disabled
+/// by `--rpc-methods safe`. Turn either of these into a clearer error.
+fn method_not_supported_err(method: &str, err: Error) -> Error {
+    let reason = match &err {
+        Error::Rpc(RpcError::ClientError(e)) => e.to_string(),
+        Error::Rpc(RpcError::RequestRejected(e)) => e.clone(),
+        _ => return err,
+    };
+    let lower = reason.to_lowercase();
+    if lower.contains("method not found") || lower.contains("-32601") || lower.contains("unsafe") {
+        RpcError::MethodNotSupported {
+            method: method.to_owned(),
+            reason,
+        }
+        .into()
+    } else {
+        err
+    }
+}
+
 /// Storage key.
-pub type StorageKey = Vec<u8>;
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize, Hash, PartialOrd, Ord, Debug, Default)]
+pub struct StorageKey(#[serde(with = "impl_serde::serialize")] pub Vec<u8>);
 
 /// Storage data.
-pub type StorageData = Vec<u8>;
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize, Hash, PartialOrd, Ord, Debug, Default)]
+pub struct StorageData(#[serde(with = "impl_serde::serialize")] pub Vec<u8>);
+
+macro_rules! impl_storage_bytes {
+    ($($ty:ident)+) => {
+        $(
+            impl $ty {
+                /// Return a `0x` prefixed hex string representation of these bytes.
+                pub fn as_hex(&self) -> String {
+                    to_hex(&self.0)
+                }
+                /// Attempt to decode some hex string, with or without a `0x` prefix, into this type.
+                pub fn try_from_hex(s: &str) -> Result<Self, hex::FromHexError> {
+                    crate::utils::from_hex(s).map($ty)
+                }
+                /// Consume this, returning the underlying bytes.
+                pub fn into_inner(self) -> Vec<u8> {
+                    self.0
+                }
+            }
+            impl std::ops::Deref for $ty {
+                type Target = [u8];
+                fn deref(&self) -> &[u8] {
+                    &self.0[..]
+                }
+            }
+            impl AsRef<[u8]> for $ty {
+                fn as_ref(&self) -> &[u8] {
+                    &self.0[..]
+                }
+            }
+            impl From<Vec<u8>> for $ty {
+                fn from(b: Vec<u8>) -> Self {
+                    $ty(b)
+                }
+            }
+            impl From<$ty> for Vec<u8> {
+                fn from(b: $ty) -> Self {
+                    b.0
+                }
+            }
+            impl std::fmt::Display for $ty {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(&self.as_hex())
+                }
+            }
+            impl std::fmt::LowerHex for $ty {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    if f.alternate() {
+                        f.write_str("0x")?;
+                    }
+                    f.write_str(&hex::encode(&self.0))
+                }
+            }
+        )+
+    }
+}
+impl_storage_bytes!(StorageKey StorageData);
 
 /// Health struct returned by the RPC
 #[derive(Deserialize, Clone, Debug)]
@@ -566,7 +733,7 @@ pub struct StorageChangeSet<Hash> {
     /// Block hash
     pub block: Hash,
     /// A list of changes; tuples of storage key and optional storage data.
-    pub changes: Vec<(Bytes, Option<Bytes>)>,
+    pub changes: Vec<(StorageKey, Option<StorageData>)>,
 }
 
 /// Statistics of a block returned by the `dev_getBlockStats` RPC.
@@ -662,11 +829,6 @@ impl From<U256> for NumberOrHex {
     }
 }
 
-/// A quick helper to encode some bytes to hex.
-fn to_hex(bytes: impl AsRef<[u8]>) -> String {
-    format!("0x{}", hex::encode(bytes.as_ref()))
-}
-
 /// Hex-serialized shim for `Vec<u8>`.
 #[derive(PartialEq, Eq, Clone, Serialize, Deserialize, Hash, PartialOrd, Ord, Debug)]
 pub struct Bytes(#[serde(with = "impl_serde::serialize")] pub Vec<u8>);
@@ -681,3 +843,61 @@ impl From<Vec<u8>> for Bytes {
         Bytes(s)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn storage_key_and_data_hex_roundtrip() {
+        let key = StorageKey(vec![0x26, 0xaa, 0x39, 0x4e]);
+        assert_eq!(key.as_hex(), "0x26aa394e");
+        assert_eq!(StorageKey::try_from_hex(&key.as_hex()).unwrap(), key);
+        assert_eq!(StorageKey::try_from_hex("26aa394e").unwrap(), key);
+
+        let data = StorageData(vec![1, 2, 3]);
+        assert_eq!(data.as_hex(), "0x010203");
+        assert_eq!(StorageData::try_from_hex(&data.as_hex()).unwrap(), data);
+        assert!(StorageData::try_from_hex("0x0").is_err());
+    }
+
+    #[test]
+    fn storage_key_and_data_display_is_prefixed() {
+        let key = StorageKey(vec![0xde, 0xad]);
+        assert_eq!(key.to_string(), "0xdead");
+        assert_eq!(format!("{key:x}"), "dead");
+        assert_eq!(format!("{key:#x}"), "0xdead");
+
+        let data = StorageData(vec![]);
+        assert_eq!(data.to_string(), "0x");
+        assert_eq!(data.as_ref(), &[] as &[u8]);
+    }
+
+    #[test]
+    fn storage_key_and_data_serde_as_hex() {
+        let key = StorageKey(vec![0xbe, 0xef]);
+        let json = serde_json::to_string(&key).unwrap();
+        assert_eq!(json, "\"0xbeef\"");
+        assert_eq!(serde_json::from_str::<StorageKey>(&json).unwrap(), key);
+    }
+
+    #[test]
+    fn method_not_found_errors_become_method_not_supported() {
+        let err = Error::Rpc(RpcError::ClientError(
+            "Method not found (code: -32601)".into(),
+        ));
+        let err = method_not_supported_err("system_dryRun", err);
+        assert!(err.is_method_not_supported());
+
+        let err = Error::Rpc(RpcError::request_rejected(
+            "RPC call is unsafe to be called externally",
+        ));
+        let err = method_not_supported_err("system_dryRun", err);
+        assert!(err.is_method_not_supported());
+
+        // Other errors are left alone:
+        let err = Error::Rpc(RpcError::request_rejected("bad params"));
+        let err = method_not_supported_err("system_dryRun", err);
+        assert!(err.is_rejected());
+    }
+}
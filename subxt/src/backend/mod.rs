@@ -53,6 +53,45 @@ pub trait Backend<T: Config>: sealed::Sealed + Send + Sync + 'static {
         at: T::Hash,
     ) -> Result<StreamOfResults<StorageResponse>, Error>;
 
+    /// Fetch values from a child trie. The child key is the unprefixed key
+    /// of a default child trie.
+    async fn child_storage_fetch_values(
+        &self,
+        child_key: Vec<u8>,
+        keys: Vec<Vec<u8>>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error>;
+
+    /// Fetch keys underneath the given key from a child trie. The child key
+    /// is the unprefixed key of a default child trie.
+    async fn child_storage_fetch_descendant_keys(
+        &self,
+        child_key: Vec<u8>,
+        key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<Vec<u8>>, Error>;
+
+    /// Fetch a proof of the values at the given storage keys. The proof is made up of
+    /// the encoded trie nodes needed to read the values from the block's state root.
+    async fn storage_read_proof(
+        &self,
+        keys: Vec<Vec<u8>>,
+        at: T::Hash,
+    ) -> Result<Vec<Vec<u8>>, Error>;
+
+    /// Fetch the size in bytes of the value at the given storage key, or `None` if no
+    /// value exists. Backends which can't ask for this directly fetch the value to find it.
+    async fn storage_fetch_size(&self, key: Vec<u8>, at: T::Hash) -> Result<Option<u64>, Error>;
+
+    /// Fetch the merkle value of the closest descendant of the given key in the storage trie
+    /// (which is the key itself if a node exists for it), or `None` if there are no descendants.
+    /// The merkle value is the hash of a trie node, or the node itself if it is small enough.
+    async fn storage_fetch_closest_descendant_merkle_value(
+        &self,
+        key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<Option<Vec<u8>>, Error>;
+
     /// Fetch the genesis hash
     async fn genesis_hash(&self) -> Result<T::Hash, Error>;
 
@@ -64,10 +103,25 @@ pub trait Backend<T: Config>: sealed::Sealed + Send + Sync + 'static {
     /// first bytes in the vector will decode to the compact encoded length of the extrinsic)
     async fn block_body(&self, at: T::Hash) -> Result<Option<Vec<Vec<u8>>>, Error>;
 
+    /// Return the justifications attached to the block, or `Ok(None)` if the block
+    /// isn't known or has no justifications. Backends which can't obtain justifications
+    /// (like the unstable backend) return an error.
+    async fn block_justifications(&self, at: T::Hash) -> Result<Option<Justifications>, Error>;
+
+    /// Get the hash of the finalized block with the given block number. This returns
+    /// `Ok(None)` if no such block is known, or if the backend is unable to look up
+    /// blocks by their number, and returns a [`crate::error::BlockError::NotRetained`] error
+    /// if the block is older than the backend is able to look up.
+    async fn finalized_block_hash_at_number(&self, number: u64) -> Result<Option<T::Hash>, Error>;
+
     /// Get the most recent finalized block hash.
     /// Note: needed only in blocks client for finalized block stream; can prolly be removed.
     async fn latest_finalized_block_ref(&self) -> Result<BlockRef<T::Hash>, Error>;
 
+    /// Get the most recent best block hash. Unlike the latest finalized block, this block
+    /// may not end up being finalized.
+    async fn latest_best_block_ref(&self) -> Result<BlockRef<T::Hash>, Error>;
+
     /// Get information about the current runtime.
     async fn current_runtime_version(&self) -> Result<RuntimeVersion, Error>;
 
@@ -89,6 +143,14 @@ pub trait Backend<T: Config>: sealed::Sealed + Send + Sync + 'static {
         &self,
     ) -> Result<StreamOfResults<(T::Header, BlockRef<T::Hash>)>, Error>;
 
+    /// A stream of the value at some storage key. This emits the current value, and then
+    /// the new value each time that it changes, along with a reference to the finalized block
+    /// that the change was seen in. A value of `None` means that nothing is stored at the key.
+    async fn stream_storage_value(
+        &self,
+        key: Vec<u8>,
+    ) -> Result<StreamOfResults<(BlockRef<T::Hash>, Option<Vec<u8>>)>, Error>;
+
     /// Submit a transaction. This will return a stream of events about it.
     async fn submit_transaction(
         &self,
@@ -102,6 +164,10 @@ pub trait Backend<T: Config>: sealed::Sealed + Send + Sync + 'static {
         call_parameters: Option<&[u8]>,
         at: T::Hash,
     ) -> Result<Vec<u8>, Error>;
+
+    /// A stream of changes to the state of the connection to the node.
+    /// See [`rpc::RpcClient::connection_events()`].
+    fn connection_events(&self) -> StreamOf<rpc::ConnectionEvent>;
 }
 
 /// helpful utility methods derived from those provided on [`Backend`]
@@ -136,17 +202,29 @@ pub trait BackendExt<T: Config>: Backend<T> {
 
     /// Return the metadata at some version.
     async fn metadata_at_version(&self, version: u32, at: T::Hash) -> Result<Metadata, Error> {
+        self.try_metadata_at_version(version, at)
+            .await?
+            .ok_or_else(|| Error::Other("Metadata version not found".into()))
+    }
+
+    /// Return the metadata at some version, or `None` if the runtime does not provide
+    /// metadata at that version.
+    async fn try_metadata_at_version(
+        &self,
+        version: u32,
+        at: T::Hash,
+    ) -> Result<Option<Metadata>, Error> {
         let param = version.encode();
 
         let opaque: Option<frame_metadata::OpaqueMetadata> = self
             .call_decoding("Metadata_metadata_at_version", Some(&param), at)
             .await?;
         let Some(opaque) = opaque else {
-            return Err(Error::Other("Metadata version not found".into()));
+            return Ok(None);
         };
 
         let metadata: Metadata = Decode::decode(&mut &opaque.0[..])?;
-        Ok(metadata)
+        Ok(Some(metadata))
     }
 
     /// Return V14 metadata from the legacy `Metadata_metadata` call.
@@ -215,6 +293,12 @@ impl<H: std::hash::Hash> std::hash::Hash for BlockRef<H> {
 impl<H> BlockRef<H> {
     /// A [`BlockRef`] that doesn't reference a given block, but does have an associated hash.
     /// This is used in the legacy backend, which has no notion of pinning blocks.
+    ///
+    /// The resulting reference is "unpinned": holding onto it does nothing to keep the block
+    /// around, and so a backend which relies on pinning (like the
+    /// [`crate::backend::unstable::UnstableBackend`]) may no longer be able to access the
+    /// block's details by the time that they are asked for. Use [`BlockRef::is_pinned()`]
+    /// to check whether a reference is guaranteed to be accessible.
     pub fn from_hash(hash: H) -> Self {
         Self {
             hash,
@@ -238,6 +322,13 @@ impl<H> BlockRef<H> {
     {
         self.hash
     }
+
+    /// Is this reference keeping the block that it points to pinned? This is `true` for
+    /// references created by the backend via [`BlockRef::new()`], and `false` for those
+    /// created via [`BlockRef::from_hash()`] (or from a hash via [`From`]).
+    pub fn is_pinned(&self) -> bool {
+        self._pointer.is_some()
+    }
 }
 
 /// A trait that a [`Backend`] can implement to know when some block
@@ -277,7 +368,9 @@ impl<T> StreamOf<T> {
     }
 }
 
-/// A stream of [`Result<Item, Error>`].
+/// A stream of [`Result<Item, Error>`]. This implements [`futures::TryStream`], and so
+/// methods like [`futures::TryStreamExt::try_next()`] and [`futures::TryStreamExt::try_collect()`]
+/// can be used with it.
 pub type StreamOfResults<T> = StreamOf<Result<T, Error>>;
 
 /// The status of the transaction.
@@ -333,10 +426,39 @@ pub struct StorageResponse {
     pub value: Vec<u8>,
 }
 
+/// The justifications attached to a block, as returned from [`Backend::block_justifications`].
+pub type Justifications = Vec<Justification>;
+
+/// A justification proving that a block is valid according to some consensus engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Justification {
+    /// The ID of the consensus engine that produced the justification
+    /// (for instance `*b"FRNK"` for GRANDPA).
+    pub engine_id: [u8; 4],
+    /// The SCALE encoded justification, whose format depends on the consensus engine.
+    pub encoded: Vec<u8>,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn block_refs_from_hashes_are_not_pinned() {
+        struct Pinned;
+        impl BlockRefT for Pinned {}
+
+        let unpinned = BlockRef::from_hash([1u8; 32]);
+        assert!(!unpinned.is_pinned());
+        assert!(!BlockRef::from([1u8; 32]).is_pinned());
+
+        let pinned = BlockRef::new([1u8; 32], Pinned);
+        assert!(pinned.is_pinned());
+
+        // Pinning doesn't affect which block is being referred to:
+        assert_eq!(pinned, unpinned);
+    }
+
     mod legacy {
         use super::rpc::{RpcClient, RpcClientT};
         use crate::backend::rpc::RawRpcSubscription;
@@ -461,6 +583,17 @@ mod test {
                             let value = data.request.pop(key.0);
                             value.map(|v| serde_json::value::RawValue::from_string(v).unwrap())
                         }
+                        "childstate_getStorage" => {
+                            let mut data = self.data.lock().await;
+                            let params = params.map(|p| p.get().to_string());
+                            let rpc_params = jsonrpsee::types::Params::new(params.as_deref());
+                            let mut seq = rpc_params.sequence();
+                            let child_key: sp_core::Bytes = seq.next().unwrap();
+                            let key: sp_core::Bytes = seq.next().unwrap();
+                            // Results are looked up by the prefixed child key followed by the key.
+                            let value = data.request.pop([child_key.0, key.0].concat());
+                            value.map(|v| serde_json::value::RawValue::from_string(v).unwrap())
+                        }
                         "chain_getBlockHash" => {
                             let mut data = self.data.lock().await;
                             let value = data.request.pop("chain_getBlockHash".into());
@@ -603,6 +736,91 @@ mod test {
             assert_eq!(expected, response)
         }
 
+        #[tokio::test]
+        async fn storage_fetch_values_try_collect() {
+            use futures::TryStreamExt;
+
+            let mock_data = vec![("ID1", bytes("Data1")), ("ID2", bytes("Data2"))];
+            let rpc_client = build_mock_client(mock_data, vec![]).await;
+            let backend: LegacyBackend<Conf> = LegacyBackend::builder().build(rpc_client);
+
+            let response: Vec<StorageResponse> = backend
+                .storage_fetch_values(
+                    ["ID1".into(), "ID2".into()].into(),
+                    crate::utils::H256::random(),
+                )
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+
+            let expected = vec![
+                storage_response("ID1", "Data1"),
+                storage_response("ID2", "Data2"),
+            ];
+
+            assert_eq!(expected, response)
+        }
+
+        #[tokio::test]
+        async fn subscription_into_stream_try_collect() {
+            use futures::TryStreamExt;
+
+            let subscription_data = vec![Ok(vec![Ok(1u32), Ok(2u32), Ok(3u32)])];
+            let rpc_client =
+                build_mock_client(Vec::<(&str, RpcResult<u32>)>::new(), subscription_data).await;
+
+            let items: Vec<u32> = rpc_client
+                .subscribe::<u32>("sub", super::rpc::RpcParams::new(), "unsub")
+                .await
+                .unwrap()
+                .into_stream()
+                .try_collect()
+                .await
+                .unwrap();
+
+            assert_eq!(items, vec![1, 2, 3]);
+        }
+
+        #[tokio::test]
+        async fn child_storage_fetch_values() {
+            let mock_data = vec![
+                (":child_storage:default:CHILDID1", bytes("Data1")),
+                (
+                    ":child_storage:default:CHILDID2",
+                    Err(RpcError::DisconnectedWillReconnect(
+                        "Reconnecting".to_string(),
+                    )),
+                ),
+                (":child_storage:default:CHILDID2", bytes("Data2")),
+            ];
+            let rpc_client = build_mock_client(mock_data, vec![]).await;
+            let backend: LegacyBackend<Conf> = LegacyBackend::builder().build(rpc_client);
+
+            // Test
+            let response = backend
+                .child_storage_fetch_values(
+                    "CHILD".into(),
+                    ["ID1".into(), "ID2".into()].into(),
+                    crate::utils::H256::random(),
+                )
+                .await
+                .unwrap();
+
+            let response = response
+                .map(|x| x.unwrap())
+                .collect::<Vec<StorageResponse>>()
+                .await;
+
+            let expected = vec![
+                storage_response("ID1", "Data1"),
+                storage_response("ID2", "Data2"),
+            ];
+
+            assert_eq!(expected, response)
+        }
+
         #[tokio::test]
         async fn storage_fetch_value() {
             // Setup
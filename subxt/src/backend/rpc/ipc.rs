@@ -0,0 +1,50 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Connect to a node over a Unix domain socket (on Linux and macOS) or a named pipe (on
+//! Windows) rather than over TCP. The usual WebSocket protocol is then spoken over the
+//! resulting stream.
+//!
+//! - `unix:///path/to/node.sock` connects to the Unix domain socket at the given absolute path.
+//! - `npipe:///node` connects to the named pipe `\\.\pipe\node`.
+
+use jsonrpsee::client_transport::ws::Url;
+use std::io;
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
+
+#[cfg(unix)]
+pub type IpcStream = Compat<tokio::net::UnixStream>;
+#[cfg(windows)]
+pub type IpcStream = Compat<tokio::net::windows::named_pipe::NamedPipeClient>;
+
+/// Should we connect to the given URL using [`connect()`]?
+pub fn is_ipc_url(url: &Url) -> bool {
+    matches!(url.scheme(), "unix" | "npipe")
+}
+
+/// Open a stream to the Unix domain socket or named pipe given by the URL.
+pub async fn connect(url: &Url) -> io::Result<IpcStream> {
+    match url.scheme() {
+        #[cfg(unix)]
+        "unix" => {
+            let stream = tokio::net::UnixStream::connect(url.path()).await?;
+            Ok(stream.compat())
+        }
+        #[cfg(windows)]
+        "npipe" => {
+            let name = format!(r"\\.\pipe\{}", url.path().trim_start_matches('/'));
+            let client = tokio::net::windows::named_pipe::ClientOptions::new().open(name)?;
+            Ok(client.compat())
+        }
+        scheme => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("Connecting to `{scheme}://` URLs is not supported on this platform"),
+        )),
+    }
+}
+
+/// The URL handed to the WebSocket handshake when connecting over a socket or pipe.
+pub fn handshake_url() -> Url {
+    Url::parse("ws://localhost").expect("valid URL; qed")
+}
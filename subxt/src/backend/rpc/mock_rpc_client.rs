@@ -0,0 +1,126 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! A configurable [`RpcClientT`] implementation for use in tests.
+
+use super::{RawRpcFuture, RawRpcSubscription, RawValue, RpcClientT};
+use crate::error::RpcError;
+use futures::StreamExt;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+type RequestHandler = Arc<dyn Fn(&str, Value) -> Result<Value, RpcError> + Send + Sync>;
+type SubscriptionHandler = Arc<dyn Fn(&str, Value) -> Result<Vec<Value>, RpcError> + Send + Sync>;
+
+/// An RPC client which records every call made to it, and answers them using the handlers
+/// it's been given. By default, every request and subscription is rejected with an
+/// [`RpcError::RequestRejected`] error.
+#[derive(Clone)]
+pub struct MockRpcClient {
+    calls: Arc<Mutex<Vec<(String, Value)>>>,
+    on_request: RequestHandler,
+    on_subscribe: SubscriptionHandler,
+}
+
+impl Default for MockRpcClient {
+    fn default() -> Self {
+        Self {
+            calls: Default::default(),
+            on_request: Arc::new(|method, _| Err(RpcError::request_rejected(method))),
+            on_subscribe: Arc::new(|method, _| Err(RpcError::request_rejected(method))),
+        }
+    }
+}
+
+impl MockRpcClient {
+    /// Create a new client which rejects every call.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer requests using the given function, which is handed the method name and
+    /// params (or `null` if there are none).
+    pub fn on_request(
+        mut self,
+        f: impl Fn(&str, Value) -> Result<Value, RpcError> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_request = Arc::new(f);
+        self
+    }
+
+    /// Answer requests to the given method with the given response. Requests to any
+    /// other method are handled as they were before.
+    pub fn with_response(self, method: &'static str, response: Value) -> Self {
+        let prev = self.on_request.clone();
+        self.on_request(move |m, params| {
+            if m == method {
+                Ok(response.clone())
+            } else {
+                prev(m, params)
+            }
+        })
+    }
+
+    /// Answer subscriptions using the given function, which is handed the subscribe method
+    /// name and params, and returns the items that the subscription will emit before ending.
+    pub fn on_subscribe(
+        mut self,
+        f: impl Fn(&str, Value) -> Result<Vec<Value>, RpcError> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_subscribe = Arc::new(f);
+        self
+    }
+
+    /// The method names and params of every request and subscription made so far.
+    pub fn calls(&self) -> Vec<(String, Value)> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// The method names of every request and subscription made so far.
+    pub fn methods_called(&self) -> Vec<String> {
+        self.calls().into_iter().map(|(method, _)| method).collect()
+    }
+
+    fn record(&self, method: &str, params: Option<Box<RawValue>>) -> Value {
+        let params = params
+            .map(|p| serde_json::from_str(p.get()).expect("params should be valid JSON"))
+            .unwrap_or(Value::Null);
+        self.calls
+            .lock()
+            .unwrap()
+            .push((method.to_owned(), params.clone()));
+        params
+    }
+}
+
+fn to_raw_value(value: Value) -> Box<RawValue> {
+    RawValue::from_string(value.to_string()).expect("values serialize to valid JSON")
+}
+
+impl RpcClientT for MockRpcClient {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RawRpcFuture<'a, Box<RawValue>> {
+        let params = self.record(method, params);
+        let res = (self.on_request)(method, params).map(to_raw_value);
+        Box::pin(async move { res })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        params: Option<Box<RawValue>>,
+        _unsub: &'a str,
+    ) -> RawRpcFuture<'a, RawRpcSubscription> {
+        let params = self.record(sub, params);
+        let res = (self.on_subscribe)(sub, params).map(|items| RawRpcSubscription {
+            stream: futures::stream::iter(items.into_iter().map(|item| Ok(to_raw_value(item))))
+                .boxed(),
+            id: Some("mock".to_owned()),
+        });
+        Box::pin(async move { res })
+    }
+}
@@ -14,6 +14,8 @@
 //!   methods.
 //! - [`RpcClient`] is the higher level wrapper around this, offering
 //!   the [`RpcClient::request`] and [`RpcClient::subscribe`] methods.
+//! - [`RestrictedRpcClient`] wraps some [`RpcClientT`] implementation, and only allows
+//!   the RPC methods in a given allow-list to be called.
 //!
 //! # Example
 //!
@@ -64,13 +66,24 @@ crate::macros::cfg_unstable_light_client! {
     mod lightclient_impl;
 }
 
+#[cfg(all(feature = "ipc", any(unix, windows)))]
+mod ipc;
+
 crate::macros::cfg_reconnecting_rpc_client! {
    /// reconnecting rpc client.
    pub mod reconnecting_rpc_client;
 }
 
+#[cfg(test)]
+pub(crate) mod mock_rpc_client;
+mod restricted_rpc_client;
 mod rpc_client;
 mod rpc_client_t;
 
-pub use rpc_client::{rpc_params, RpcClient, RpcParams, RpcSubscription};
+pub use rpc_client::{rpc_params, ConnectionEvent, RpcClient, RpcParams, RpcSubscription};
+
+crate::macros::cfg_jsonrpsee_native! {
+    pub use rpc_client::RpcClientBuilder;
+}
+pub use restricted_rpc_client::RestrictedRpcClient;
 pub use rpc_client_t::{RawRpcFuture, RawRpcSubscription, RawValue, RpcClientT};
@@ -59,6 +59,7 @@ use std::{
     time::Duration,
 };
 
+use super::rpc_client::DEFAULT_MAX_SIZE;
 use super::{RawRpcFuture, RawRpcSubscription, RpcClientT};
 use crate::error::RpcError as SubxtRpcError;
 
@@ -216,8 +217,8 @@ pub struct RpcClientBuilder<P> {
 impl Default for RpcClientBuilder<ExponentialBackoff> {
     fn default() -> Self {
         Self {
-            max_request_size: 10 * 1024 * 1024,
-            max_response_size: 10 * 1024 * 1024,
+            max_request_size: DEFAULT_MAX_SIZE,
+            max_response_size: DEFAULT_MAX_SIZE,
             retry_policy: ExponentialBackoff::from_millis(10).max_delay(Duration::from_secs(60)),
             #[cfg(feature = "native")]
             ping_config: Some(PingConfig::new()),
@@ -0,0 +1,137 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use super::{RawRpcFuture, RawRpcSubscription, RawValue, RpcClientT};
+use crate::error::RpcError;
+use std::collections::HashSet;
+
+/// An [`RpcClientT`] implementation which wraps some other client, and only allows
+/// calls to the RPC methods given in an allow-list to be made. Calls to any other
+/// method fail with an [`RpcError::MethodNotAllowed`] error before they reach the
+/// inner client. This is useful when exposing Subxt to untrusted users.
+///
+/// Subscriptions are allowed if the method used to subscribe is in the allow-list; the
+/// corresponding unsubscribe method doesn't need to be given.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use jsonrpsee::ws_client::WsClientBuilder;
+/// use subxt::backend::rpc::{RestrictedRpcClient, RpcClient};
+///
+/// // Wrap any RPC client implementation, such as a jsonrpsee client:
+/// let inner = WsClientBuilder::default()
+///     .build("ws://localhost:9944")
+///     .await
+///     .unwrap();
+/// let restricted = RestrictedRpcClient::new(
+///     inner,
+///     ["chain_getBlockHash", "chain_subscribeFinalizedHeads"],
+/// );
+///
+/// // Hand this to Subxt as you would any other RPC client:
+/// let rpc_client = RpcClient::new(restricted);
+/// # }
+/// ```
+pub struct RestrictedRpcClient<C> {
+    inner: C,
+    allowed_methods: HashSet<String>,
+}
+
+impl<C: RpcClientT> RestrictedRpcClient<C> {
+    /// Wrap the given client, allowing only the given methods to be called.
+    pub fn new<M: Into<String>>(inner: C, allowed_methods: impl IntoIterator<Item = M>) -> Self {
+        Self {
+            inner,
+            allowed_methods: allowed_methods.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Is the given RPC method allowed to be called?
+    pub fn is_allowed(&self, method: &str) -> bool {
+        self.allowed_methods.contains(method)
+    }
+
+    /// Return the client that is being wrapped.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C: RpcClientT> RpcClientT for RestrictedRpcClient<C> {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RawRpcFuture<'a, Box<RawValue>> {
+        if !self.is_allowed(method) {
+            return Box::pin(async move { Err(RpcError::MethodNotAllowed(method.to_owned())) });
+        }
+        self.inner.request_raw(method, params)
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        params: Option<Box<RawValue>>,
+        unsub: &'a str,
+    ) -> RawRpcFuture<'a, RawRpcSubscription> {
+        if !self.is_allowed(sub) {
+            return Box::pin(async move { Err(RpcError::MethodNotAllowed(sub.to_owned())) });
+        }
+        self.inner.subscribe_raw(sub, params, unsub)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::rpc::mock_rpc_client::MockRpcClient;
+
+    #[tokio::test]
+    async fn only_allowed_methods_reach_the_inner_client() {
+        let inner = MockRpcClient::new()
+            .with_response("chain_getBlockHash", serde_json::json!(1))
+            .on_subscribe(|_, _| Ok(vec![]));
+        let client = RestrictedRpcClient::new(
+            inner.clone(),
+            ["chain_getBlockHash", "chain_subscribeNewHeads"],
+        );
+
+        let res = client
+            .request_raw("chain_getBlockHash", None)
+            .await
+            .unwrap();
+        assert_eq!(res.get(), "1");
+
+        let err = client
+            .request_raw("author_submitExtrinsic", None)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, RpcError::MethodNotAllowed(ref m) if m == "author_submitExtrinsic"),
+            "unexpected error: {err:?}"
+        );
+
+        client
+            .subscribe_raw("chain_subscribeNewHeads", None, "chain_unsubscribeNewHeads")
+            .await
+            .unwrap();
+
+        let err = client
+            .subscribe_raw("chain_subscribeAllHeads", None, "chain_unsubscribeAllHeads")
+            .await
+            .err()
+            .expect("subscription should be rejected");
+        assert!(matches!(err, RpcError::MethodNotAllowed(ref m) if m == "chain_subscribeAllHeads"));
+
+        // Only the allowed calls made it through:
+        assert_eq!(
+            inner.methods_called(),
+            vec!["chain_getBlockHash", "chain_subscribeNewHeads"]
+        );
+    }
+}
@@ -3,11 +3,16 @@
 // see LICENSE for license details.
 
 use super::{RawRpcSubscription, RpcClientT};
-use crate::error::Error;
-use futures::{Stream, StreamExt};
+use crate::backend::{StreamOf, StreamOfResults};
+use crate::error::{Error, RpcError};
+use futures::{channel::mpsc, Stream, StreamExt};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::value::RawValue;
-use std::{pin::Pin, sync::Arc, task::Poll};
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::Poll,
+};
 
 /// A concrete wrapper around an [`RpcClientT`] which provides some higher level helper methods,
 /// is cheaply cloneable, and can be handed to things like [`crate::client::OnlineClient`] to
@@ -15,6 +20,7 @@ use std::{pin::Pin, sync::Arc, task::Poll};
 #[derive(Clone)]
 pub struct RpcClient {
     client: Arc<dyn RpcClientT>,
+    connection_events: ConnectionEvents,
 }
 
 impl RpcClient {
@@ -23,6 +29,10 @@ impl RpcClient {
     /// Create a default RPC client pointed at some URL, currently based on [`jsonrpsee`].
     ///
     /// Errors if an insecure URL is provided. In this case, use [`RpcClient::from_insecure_url`] instead.
+    ///
+    /// With the `ipc` feature enabled, `unix:///path/to/node.sock` URLs connect to a Unix domain
+    /// socket (on Linux and macOS) and `npipe:///name` URLs connect to the named pipe
+    /// `\\.\pipe\name` (on Windows). These are considered secure since they are local.
     pub async fn from_url<U: AsRef<str>>(url: U) -> Result<Self, Error> {
         crate::utils::validate_url_is_secure(url.as_ref())?;
         RpcClient::from_insecure_url(url).await
@@ -39,13 +49,32 @@ impl RpcClient {
         Ok(Self::new(client))
     }
 
+    #[cfg(all(feature = "jsonrpsee", feature = "native"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "jsonrpsee", feature = "native"))))]
+    /// Configure and build a default RPC client, currently based on [`jsonrpsee`].
+    pub fn builder() -> RpcClientBuilder {
+        RpcClientBuilder::new()
+    }
+
     /// Create a new [`RpcClient`] from an arbitrary [`RpcClientT`] implementation.
     pub fn new<R: RpcClientT>(client: R) -> Self {
         RpcClient {
             client: Arc::new(client),
+            connection_events: ConnectionEvents::default(),
         }
     }
 
+    /// A stream of [`ConnectionEvent`]s, which are emitted as the connection to the node
+    /// is lost and (if the underlying [`RpcClientT`] supports it) re-established.
+    ///
+    /// Connection changes are noticed as requests and subscriptions made through this client
+    /// (or any clone of it) succeed or fail. When using the reconnecting RPC client, you'll see
+    /// [`ConnectionEvent::Reconnecting`] followed by [`ConnectionEvent::Connected`] once requests
+    /// succeed again. Without it, only [`ConnectionEvent::Disconnected`] may be emitted.
+    pub fn connection_events(&self) -> StreamOf<ConnectionEvent> {
+        self.connection_events.subscribe()
+    }
+
     /// Make an RPC request, given a method name and some parameters.
     ///
     /// See [`RpcParams`] and the [`rpc_params!`] macro for an example of how to
@@ -55,7 +84,9 @@ impl RpcClient {
         method: &str,
         params: RpcParams,
     ) -> Result<Res, Error> {
-        let res = self.client.request_raw(method, params.build()).await?;
+        let res = self.client.request_raw(method, params.build()).await;
+        self.connection_events.observe(&res);
+        let res = res?;
         let val = serde_json::from_str(res.get())?;
         Ok(val)
     }
@@ -71,12 +102,82 @@ impl RpcClient {
         params: RpcParams,
         unsub: &str,
     ) -> Result<RpcSubscription<Res>, Error> {
-        let sub = self
-            .client
-            .subscribe_raw(sub, params.build(), unsub)
-            .await?;
-        Ok(RpcSubscription::new(sub))
+        let sub = self.client.subscribe_raw(sub, params.build(), unsub).await;
+        self.connection_events.observe(&sub);
+        let mut sub = RpcSubscription::new(sub?);
+        sub.connection_events = Some(self.connection_events.clone());
+        Ok(sub)
+    }
+}
+
+/// A change in the state of the connection to the node. See [`RpcClient::connection_events()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// The connection was re-established after being lost.
+    Connected,
+    /// The connection was lost and will not be re-established.
+    Disconnected,
+    /// The connection was lost and the client is trying to re-establish it.
+    Reconnecting,
+}
+
+/// Shared between clones of an [`RpcClient`], this hands out [`ConnectionEvent`]s
+/// to anybody listening for them based on the results we see.
+#[derive(Clone, Default)]
+struct ConnectionEvents {
+    inner: Arc<Mutex<ConnectionEventsInner>>,
+}
+
+#[derive(Default)]
+struct ConnectionEventsInner {
+    last_event: Option<ConnectionEvent>,
+    subscribers: Vec<mpsc::UnboundedSender<ConnectionEvent>>,
+}
+
+impl ConnectionEvents {
+    fn subscribe(&self) -> StreamOf<ConnectionEvent> {
+        let (tx, rx) = mpsc::unbounded();
+        self.inner
+            .lock()
+            .expect("lock not poisoned; qed")
+            .subscribers
+            .push(tx);
+        StreamOf::new(Box::pin(rx))
     }
+
+    fn observe<T>(&self, res: &Result<T, RpcError>) {
+        let event = match res {
+            Ok(_) => ConnectionEvent::Connected,
+            Err(RpcError::DisconnectedWillReconnect(_)) => ConnectionEvent::Reconnecting,
+            Err(RpcError::ClientError(e)) if is_connection_closed(&**e) => {
+                ConnectionEvent::Disconnected
+            }
+            Err(_) => return,
+        };
+
+        let mut inner = self.inner.lock().expect("lock not poisoned; qed");
+        // We start off connected, so only report a connection once it was lost.
+        if inner.last_event.unwrap_or(ConnectionEvent::Connected) == event {
+            return;
+        }
+        inner.last_event = Some(event);
+        inner
+            .subscribers
+            .retain(|tx| tx.unbounded_send(event).is_ok());
+    }
+}
+
+#[cfg(feature = "jsonrpsee")]
+fn is_connection_closed(err: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    matches!(
+        err.downcast_ref::<jsonrpsee::core::client::Error>(),
+        Some(jsonrpsee::core::client::Error::RestartNeeded(_))
+    )
+}
+
+#[cfg(not(feature = "jsonrpsee"))]
+fn is_connection_closed(_err: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    false
 }
 
 impl<C: RpcClientT> From<C> for RpcClient {
@@ -98,6 +199,79 @@ impl std::ops::Deref for RpcClient {
     }
 }
 
+/// The default maximum size of requests and responses, in bytes. This
+/// is the same as the default used by [`jsonrpsee`].
+#[cfg(any(
+    all(feature = "jsonrpsee", feature = "native"),
+    feature = "unstable-reconnecting-rpc-client"
+))]
+pub(crate) const DEFAULT_MAX_SIZE: u32 = 10 * 1024 * 1024;
+
+/// Configure and build an [`RpcClient`] based on [`jsonrpsee`]. This is obtained via
+/// [`RpcClient::builder()`].
+#[cfg(all(feature = "jsonrpsee", feature = "native"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "jsonrpsee", feature = "native"))))]
+#[derive(Debug, Clone)]
+pub struct RpcClientBuilder {
+    max_request_size: u32,
+    max_response_size: u32,
+}
+
+#[cfg(all(feature = "jsonrpsee", feature = "native"))]
+impl Default for RpcClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(feature = "jsonrpsee", feature = "native"))]
+impl RpcClientBuilder {
+    /// Create a new [`RpcClientBuilder`].
+    pub fn new() -> Self {
+        RpcClientBuilder {
+            max_request_size: DEFAULT_MAX_SIZE,
+            max_response_size: DEFAULT_MAX_SIZE,
+        }
+    }
+
+    /// The maximum size of a request in bytes. Default is 10 MiB.
+    pub fn max_request_size(mut self, max: u32) -> Self {
+        self.max_request_size = max;
+        self
+    }
+
+    /// The maximum size of a response in bytes. Default is 10 MiB. Raise this if
+    /// large responses (for instance when downloading metadata) are being rejected.
+    pub fn max_response_size(mut self, max: u32) -> Self {
+        self.max_response_size = max;
+        self
+    }
+
+    /// Build an [`RpcClient`] pointed at some URL.
+    ///
+    /// Errors if an insecure URL is provided. In this case, use
+    /// [`RpcClientBuilder::build_insecure`] instead.
+    pub async fn build<U: AsRef<str>>(self, url: U) -> Result<RpcClient, Error> {
+        crate::utils::validate_url_is_secure(url.as_ref())?;
+        self.build_insecure(url).await
+    }
+
+    /// Build an [`RpcClient`] pointed at some URL.
+    ///
+    /// Allows insecure URLs without SSL encryption, e.g. (http:// and ws:// URLs).
+    pub async fn build_insecure<U: AsRef<str>>(self, url: U) -> Result<RpcClient, Error> {
+        let transport = jsonrpsee_helpers::WsTransportClientBuilder {
+            max_request_size: self.max_request_size,
+            max_response_size: self.max_response_size,
+            ..Default::default()
+        };
+        let client = jsonrpsee_helpers::client_with_transport(url.as_ref(), transport)
+            .await
+            .map_err(|e| crate::error::RpcError::ClientError(Box::new(e)))?;
+        Ok(RpcClient::new(client))
+    }
+}
+
 /// Create some [`RpcParams`] to pass to our [`RpcClient`]. [`RpcParams`]
 /// simply enforces that parameters handed to our [`RpcClient`] methods
 /// are the correct shape.
@@ -186,6 +360,7 @@ impl RpcParams {
 /// [`StreamExt`] extension trait.
 pub struct RpcSubscription<Res> {
     inner: RawRpcSubscription,
+    connection_events: Option<ConnectionEvents>,
     _marker: std::marker::PhantomData<Res>,
 }
 
@@ -203,6 +378,7 @@ impl<Res> RpcSubscription<Res> {
     pub fn new(inner: RawRpcSubscription) -> Self {
         Self {
             inner,
+            connection_events: None,
             _marker: std::marker::PhantomData,
         }
     }
@@ -221,6 +397,15 @@ impl<Res: DeserializeOwned> RpcSubscription<Res> {
     }
 }
 
+impl<Res: DeserializeOwned + Send + 'static> RpcSubscription<Res> {
+    /// Convert this subscription into a [`StreamOfResults`], which is the type of stream
+    /// handed back from [`crate::backend::Backend`] methods. Like any stream of results,
+    /// this implements [`futures::TryStream`].
+    pub fn into_stream(self) -> StreamOfResults<Res> {
+        StreamOf::new(Box::pin(self))
+    }
+}
+
 impl<Res> std::marker::Unpin for RpcSubscription<Res> {}
 
 impl<Res: DeserializeOwned> Stream for RpcSubscription<Res> {
@@ -232,6 +417,10 @@ impl<Res: DeserializeOwned> Stream for RpcSubscription<Res> {
     ) -> Poll<Option<Self::Item>> {
         let res = futures::ready!(self.inner.stream.poll_next_unpin(cx));
 
+        if let (Some(events), Some(res)) = (&self.connection_events, &res) {
+            events.observe(res);
+        }
+
         // Decode the inner RawValue to the type we're expecting and map
         // any errors to the right shape:
         let res = res.map(|r| {
@@ -257,15 +446,53 @@ mod jsonrpsee_helpers {
 
     /// Build WS RPC client from URL
     pub async fn client(url: &str) -> Result<Client, Error> {
-        let (sender, receiver) = ws_transport(url).await?;
-        Ok(Client::builder()
-            .max_buffer_capacity_per_subscription(4096)
-            .build_with_tokio(sender, receiver))
+        client_with_transport(url, WsTransportClientBuilder::default()).await
     }
 
-    async fn ws_transport(url: &str) -> Result<(Sender, Receiver), Error> {
+    /// Build WS RPC client from URL, using the given transport configuration.
+    pub async fn client_with_transport(
+        url: &str,
+        transport: WsTransportClientBuilder,
+    ) -> Result<Client, Error> {
         let url = Url::parse(url).map_err(|e| Error::Transport(e.into()))?;
-        WsTransportClientBuilder::default()
+        let builder = Client::builder().max_buffer_capacity_per_subscription(4096);
+
+        #[cfg(all(feature = "ipc", any(unix, windows)))]
+        if crate::backend::rpc::ipc::is_ipc_url(&url) {
+            let (sender, receiver) = ipc_transport(&url, transport).await?;
+            return Ok(builder.build_with_tokio(sender, receiver));
+        }
+
+        let (sender, receiver) = ws_transport(url, transport).await?;
+        Ok(builder.build_with_tokio(sender, receiver))
+    }
+
+    #[cfg(all(feature = "ipc", any(unix, windows)))]
+    async fn ipc_transport(
+        url: &Url,
+        transport: WsTransportClientBuilder,
+    ) -> Result<
+        (
+            ws::Sender<crate::backend::rpc::ipc::IpcStream>,
+            ws::Receiver<crate::backend::rpc::ipc::IpcStream>,
+        ),
+        Error,
+    > {
+        use crate::backend::rpc::ipc;
+        let stream = ipc::connect(url)
+            .await
+            .map_err(|e| Error::Transport(e.into()))?;
+        transport
+            .build_with_stream(ipc::handshake_url(), stream)
+            .await
+            .map_err(|e| Error::Transport(e.into()))
+    }
+
+    async fn ws_transport(
+        url: Url,
+        transport: WsTransportClientBuilder,
+    ) -> Result<(Sender, Receiver), Error> {
+        transport
             .build(url)
             .await
             .map_err(|e| Error::Transport(e.into()))
@@ -290,3 +517,80 @@ mod jsonrpsee_helpers {
             .build_with_wasm(sender, receiver))
     }
 }
+
+#[cfg(all(test, feature = "jsonrpsee"))]
+mod test {
+    use super::*;
+    use crate::backend::rpc::mock_rpc_client::MockRpcClient;
+    use std::sync::atomic::{AtomicU8, Ordering};
+
+    const CONNECTED: u8 = 0;
+    const RECONNECTING: u8 = 1;
+    const DROPPED: u8 = 2;
+
+    /// A transport whose connection state can be changed by the test.
+    fn mock_transport(state: Arc<AtomicU8>) -> MockRpcClient {
+        let request_state = state.clone();
+        MockRpcClient::new()
+            .on_request(move |_, _| {
+                connection_result(&request_state).map(|_| serde_json::Value::Null)
+            })
+            .on_subscribe(move |_, _| connection_result(&state).map(|_| vec![]))
+    }
+
+    fn connection_result(state: &AtomicU8) -> Result<(), RpcError> {
+        match state.load(Ordering::SeqCst) {
+            CONNECTED => Ok(()),
+            RECONNECTING => Err(RpcError::DisconnectedWillReconnect(
+                "connection lost".to_string(),
+            )),
+            _ => Err(closed_error()),
+        }
+    }
+
+    fn closed_error() -> RpcError {
+        use jsonrpsee::core::client::Error;
+        let reason = Error::Custom("connection closed".to_string());
+        RpcError::ClientError(Box::new(Error::RestartNeeded(Arc::new(reason))))
+    }
+
+    async fn request(client: &RpcClient) {
+        let _ = client.request::<()>("foo", RpcParams::new()).await;
+    }
+
+    #[tokio::test]
+    async fn disconnected_event_is_emitted_when_connection_drops() {
+        let state = Arc::new(AtomicU8::new(CONNECTED));
+        let client = RpcClient::new(mock_transport(state.clone()));
+        let events = client.connection_events();
+
+        request(&client).await;
+        state.store(DROPPED, Ordering::SeqCst);
+        request(&client).await;
+        request(&client).await;
+        drop(client);
+
+        let events: Vec<_> = events.collect().await;
+        assert_eq!(events, vec![ConnectionEvent::Disconnected]);
+    }
+
+    #[tokio::test]
+    async fn reconnecting_and_connected_events_are_emitted() {
+        let state = Arc::new(AtomicU8::new(CONNECTED));
+        let client = RpcClient::new(mock_transport(state.clone()));
+        let events = client.connection_events();
+
+        state.store(RECONNECTING, Ordering::SeqCst);
+        request(&client).await;
+        let _ = client.subscribe::<()>("foo", RpcParams::new(), "bar").await;
+        state.store(CONNECTED, Ordering::SeqCst);
+        request(&client).await;
+        drop(client);
+
+        let events: Vec<_> = events.collect().await;
+        assert_eq!(
+            events,
+            vec![ConnectionEvent::Reconnecting, ConnectionEvent::Connected]
+        );
+    }
+}
@@ -3,21 +3,23 @@
 // see LICENSE for license details.
 
 use super::rpc_methods::{FollowEvent, UnstableRpcMethods};
+use super::StopPolicy;
 use crate::config::Config;
-use crate::error::Error;
+use crate::error::{Error, RpcError};
 use futures::{FutureExt, Stream, StreamExt};
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 /// A `Stream` whose goal is to remain subscribed to `chainHead_follow`. It will re-subscribe if the subscription
-/// is ended for any reason, and it will return the current `subscription_id` as an event, along with the other
-/// follow events.
+/// is ended for any reason (unless told not to on `stop` events via [`StopPolicy`]), and it will return the current
+/// `subscription_id` as an event, along with the other follow events.
 pub struct FollowStream<Hash> {
     // Using this and not just keeping a copy of the RPC methods
     // around means that we can test this in isolation with dummy streams.
     stream_getter: FollowEventStreamGetter<Hash>,
     stream: InnerStreamState<Hash>,
+    on_stop: StopPolicy,
 }
 
 impl<Hash> std::fmt::Debug for FollowStream<Hash> {
@@ -25,6 +27,7 @@ impl<Hash> std::fmt::Debug for FollowStream<Hash> {
         f.debug_struct("FollowStream")
             .field("stream_getter", &"..")
             .field("stream", &self.stream)
+            .field("on_stop", &self.on_stop)
             .finish()
     }
 }
@@ -72,6 +75,10 @@ enum InnerStreamState<Hash> {
     ReceivingEvents(FollowEventStream<Hash>),
     /// We received a stop event. We'll send one on and restart the stream.
     Stopped,
+    /// We received a stop event. We'll send one on and then fail, without restarting the stream.
+    StoppedForGood,
+    /// The subscription was dropped. Report an error and then finish.
+    Dropped,
     /// The stream is finished and will not restart (likely due to an error).
     Finished,
 }
@@ -84,6 +91,8 @@ impl<Hash> std::fmt::Debug for InnerStreamState<Hash> {
             Self::Ready(_) => write!(f, "Ready(..)"),
             Self::ReceivingEvents(_) => write!(f, "ReceivingEvents(..)"),
             Self::Stopped => write!(f, "Stopped"),
+            Self::StoppedForGood => write!(f, "StoppedForGood"),
+            Self::Dropped => write!(f, "Dropped"),
             Self::Finished => write!(f, "Finished"),
         }
     }
@@ -95,17 +104,33 @@ impl<Hash> FollowStream<Hash> {
         Self {
             stream_getter,
             stream: InnerStreamState::New,
+            on_stop: StopPolicy::Resubscribe,
         }
     }
 
+    /// Configure what to do when a `stop` event is received. By default, we resubscribe.
+    pub fn with_stop_policy(mut self, on_stop: StopPolicy) -> Self {
+        self.on_stop = on_stop;
+        self
+    }
+
     /// Create a new [`FollowStream`] given the RPC methods.
     pub fn from_methods<T: Config>(methods: UnstableRpcMethods<T>) -> FollowStream<T::Hash> {
+        Self::from_methods_with_runtime(methods, true)
+    }
+
+    /// Create a new [`FollowStream`] given the RPC methods, choosing whether `chainHead_follow`
+    /// is asked to report runtime details (`withRuntime`) in the events that it emits.
+    pub fn from_methods_with_runtime<T: Config>(
+        methods: UnstableRpcMethods<T>,
+        with_runtime: bool,
+    ) -> FollowStream<T::Hash> {
         FollowStream {
             stream_getter: Box::new(move || {
                 let methods = methods.clone();
                 Box::pin(async move {
                     // Make the RPC call:
-                    let stream = methods.chainhead_v1_follow(true).await?;
+                    let stream = methods.chainhead_v1_follow(with_runtime).await?;
                     // Extract the subscription ID:
                     let Some(sub_id) = stream.subscription_id().map(ToOwned::to_owned) else {
                         return Err(Error::Other(
@@ -119,6 +144,7 @@ impl<Hash> FollowStream<Hash> {
                 })
             }),
             stream: InnerStreamState::New,
+            on_stop: StopPolicy::Resubscribe,
         }
     }
 }
@@ -180,9 +206,12 @@ impl<Hash> Stream for FollowStream<Hash> {
                         }
                         Poll::Ready(Some(Ok(ev))) => {
                             if let FollowEvent::Stop = ev {
-                                // A stop event means the stream has ended, so start
-                                // over after passing on the stop message.
-                                this.stream = InnerStreamState::Stopped;
+                                // A stop event means the stream has ended, so start over (or
+                                // give up, depending on the policy) after passing on the stop message.
+                                this.stream = match this.on_stop {
+                                    StopPolicy::Resubscribe => InnerStreamState::Stopped,
+                                    StopPolicy::Error => InnerStreamState::StoppedForGood,
+                                };
                                 continue;
                             }
                             return Poll::Ready(Some(Ok(FollowStreamMsg::Event(ev))));
@@ -204,6 +233,14 @@ impl<Hash> Stream for FollowStream<Hash> {
                     this.stream = InnerStreamState::New;
                     return Poll::Ready(Some(Ok(FollowStreamMsg::Event(FollowEvent::Stop))));
                 }
+                InnerStreamState::StoppedForGood => {
+                    this.stream = InnerStreamState::Dropped;
+                    return Poll::Ready(Some(Ok(FollowStreamMsg::Event(FollowEvent::Stop))));
+                }
+                InnerStreamState::Dropped => {
+                    this.stream = InnerStreamState::Finished;
+                    return Poll::Ready(Some(Err(RpcError::SubscriptionDropped.into())));
+                }
                 InnerStreamState::Finished => {
                     return Poll::Ready(None);
                 }
@@ -329,4 +366,43 @@ pub mod test {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn follow_stream_stop_policy() {
+        let events = || {
+            [
+                Ok(ev_initialized(1)),
+                Ok(FollowEvent::Stop),
+                Ok(ev_new_block(1, 2)),
+            ]
+        };
+
+        // By default, we resubscribe and carry on after a stop event:
+        let s =
+            FollowStream::new(test_stream_getter(events)).with_stop_policy(StopPolicy::Resubscribe);
+        let out: Vec<_> = s.filter_map(|e| async move { e.ok() }).collect().await;
+        assert_eq!(
+            out,
+            vec![
+                FollowStreamMsg::Ready("sub_id_0".to_owned()),
+                FollowStreamMsg::Event(ev_initialized(1)),
+                FollowStreamMsg::Event(FollowEvent::Stop),
+                FollowStreamMsg::Ready("sub_id_2".to_owned()),
+                FollowStreamMsg::Event(ev_new_block(1, 2)),
+            ]
+        );
+
+        // Else, we pass the stop event on and then end with an error:
+        let s = FollowStream::new(test_stream_getter(events)).with_stop_policy(StopPolicy::Error);
+        let out: Vec<_> = s.collect().await;
+        assert_eq!(out.len(), 4);
+        assert_eq!(
+            out[2].as_ref().unwrap(),
+            &FollowStreamMsg::Event(FollowEvent::Stop)
+        );
+        assert!(matches!(
+            out[3],
+            Err(Error::Rpc(RpcError::SubscriptionDropped))
+        ));
+    }
 }
@@ -398,6 +398,8 @@ where
     Hash: BlockHash,
     F: Fn(FollowEvent<BlockRef<Hash>>) -> Vec<BlockRef<Hash>>,
 {
+    /// Create a new [`FollowStreamFinalizedHeads`] from a driver subscription. The given
+    /// function picks out the finalized blocks to hand back from each follow event.
     pub fn new(stream: FollowStreamDriverSubscription<Hash>, f: F) -> Self {
         Self {
             stream,
@@ -478,7 +480,7 @@ where
 }
 
 #[cfg(test)]
-mod test_utils {
+pub(super) mod test_utils {
     use super::super::follow_stream_unpin::test_utils::test_unpin_stream_getter;
     use super::*;
 
@@ -18,31 +18,148 @@ mod storage_items;
 
 pub mod rpc_methods;
 
+/// The building blocks used by [`UnstableBackend`] to share a single `chainHead_follow`
+/// subscription, exposed for those wishing to build their own backend on top of it.
+///
+/// # Warning
+///
+/// These are **unstable** internals, and are subject to breaking changes at any time.
+///
+/// # Layers
+///
+/// - [`FollowStream`] wraps the raw `chainHead_follow` subscription, and restarts
+///   it when the node emits a `stop` event (see [`StopPolicy`]).
+/// - [`FollowStreamUnpin`] handles pinning. Every block hash seen in the follow
+///   events is handed out as a [`BlockRef`]. A block stays pinned for as long as
+///   any [`BlockRef`] to it is alive; once the last one is dropped, an unpin call is
+///   queued for it. Blocks that live for more than `max_block_life` finalized blocks are unpinned
+///   regardless, to avoid the node terminating the subscription.
+/// - [`FollowStreamDriver`] shares this stream with any number of subscribers.
+///   It must be polled for any progress to be made. Every subscription obtained from a
+///   [`FollowStreamDriverHandle`] begins with a `Ready` message containing the current
+///   subscription ID, then an `Initialized` event, followed by any blocks seen since, as if it
+///   had started its own `chainHead_follow` subscription.
+///
+/// When the underlying subscription is restarted, the subscription ID changes and any blocks
+/// pinned on the old subscription can no longer be used.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::StreamExt;
+/// use subxt::backend::rpc::RpcClient;
+/// use subxt::backend::unstable::internals::{FollowStream, FollowStreamDriver, FollowStreamUnpin};
+/// use subxt::backend::unstable::rpc_methods::FollowEvent;
+/// use subxt::backend::unstable::UnstableRpcMethods;
+/// use subxt::config::{Config, PolkadotConfig};
+///
+/// type Hash = <PolkadotConfig as Config>::Hash;
+///
+/// let rpc_client = RpcClient::from_url("ws://127.0.0.1:9944").await?;
+/// let methods = UnstableRpcMethods::<PolkadotConfig>::new(rpc_client);
+///
+/// // Build the follow stream up, layer by layer:
+/// let follow_stream = FollowStream::<Hash>::from_methods(methods.clone());
+/// let follow_unpin = FollowStreamUnpin::<Hash>::from_methods(follow_stream, methods, 100);
+/// let mut driver = FollowStreamDriver::new(follow_unpin);
+/// let handle = driver.handle();
+///
+/// // The driver needs polling in order for subscribers to receive anything:
+/// tokio::spawn(async move { while driver.next().await.is_some() {} });
+///
+/// let mut events = handle.subscribe().events();
+/// while let Some(ev) = events.next().await {
+///     if let FollowEvent::NewBlock(new_block) = ev {
+///         let block_ref = new_block.block_hash;
+///         println!("New block: {:?}", block_ref.hash());
+///
+///         // The block is unpinned once the last reference to it is dropped:
+///         drop(block_ref);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`UnstableBackend`]: crate::backend::unstable::UnstableBackend
+/// [`StopPolicy`]: crate::backend::unstable::StopPolicy
+/// [`FollowStream`]: crate::backend::unstable::internals::FollowStream
+/// [`FollowStreamUnpin`]: crate::backend::unstable::internals::FollowStreamUnpin
+/// [`BlockRef`]: crate::backend::unstable::internals::BlockRef
+/// [`FollowStreamDriver`]: crate::backend::unstable::internals::FollowStreamDriver
+/// [`FollowStreamDriverHandle`]: crate::backend::unstable::internals::FollowStreamDriverHandle
+#[cfg(feature = "unstable-internals")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable-internals")))]
+pub mod internals {
+    pub use super::follow_stream::{
+        FollowEventStream, FollowEventStreamFut, FollowEventStreamGetter, FollowStream,
+        FollowStreamMsg,
+    };
+    pub use super::follow_stream_driver::{
+        FollowStreamDriver, FollowStreamDriverHandle, FollowStreamDriverSubscription,
+        FollowStreamFinalizedHeads,
+    };
+    pub use super::follow_stream_unpin::{BlockRef, FollowStreamUnpin, UnpinFut, UnpinMethod};
+}
+
 use self::follow_stream_driver::FollowStreamFinalizedHeads;
 use self::rpc_methods::{
     FollowEvent, MethodResponse, RuntimeEvent, StorageQuery, StorageQueryType, StorageResultType,
 };
 use crate::backend::{
-    rpc::RpcClient, utils::retry, Backend, BlockRef, BlockRefT, RuntimeVersion, StorageResponse,
+    rpc::{self, RpcClient},
+    utils::{retry, stream_finalized_storage_value},
+    Backend, BackendExt, BlockRef, BlockRefT, Justifications, RuntimeVersion, StorageResponse,
     StreamOf, StreamOfResults, TransactionStatus,
 };
-use crate::config::BlockHash;
-use crate::error::{Error, RpcError};
+use crate::config::{BlockHash, Header};
+use crate::error::{BlockError, Error, RpcError};
 use crate::Config;
 use async_trait::async_trait;
 use follow_stream_driver::{FollowStreamDriver, FollowStreamDriverHandle};
 use futures::future::Either;
 use futures::{Stream, StreamExt};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::task::Poll;
 use storage_items::StorageItems;
 
 // Expose the RPC methods.
 pub use rpc_methods::UnstableRpcMethods;
 
+/// Obtain the runtime version from a runtime event, or a [`Error::InvalidRuntime`]
+/// error if the node could not obtain the runtime.
+fn runtime_version_from_event(ev: RuntimeEvent) -> Result<RuntimeVersion, Error> {
+    match ev {
+        RuntimeEvent::Invalid(err) => Err(Error::InvalidRuntime { message: err.error }),
+        RuntimeEvent::Valid(ev) => Ok(RuntimeVersion {
+            spec_version: ev.spec.spec_version,
+            transaction_version: ev.spec.transaction_version,
+        }),
+    }
+}
+
+/// What to do when `chainHead_follow` emits a `stop` event. See
+/// [`UnstableBackendBuilder::on_stop()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StopPolicy {
+    /// Transparently start a new `chainHead_follow` subscription and carry on. Any
+    /// blocks that were pinned are lost, and so [`BlockRef`]s handed out before the
+    /// `stop` event can no longer be used to fetch details about those blocks.
+    #[default]
+    Resubscribe,
+    /// Don't resubscribe, and instead end the backend driver with an
+    /// [`RpcError::SubscriptionDropped`] error.
+    Error,
+}
+
 /// Configure and build an [`UnstableBackend`].
 pub struct UnstableBackendBuilder<T> {
     max_block_life: usize,
+    on_stop: StopPolicy,
+    with_runtime: bool,
     _marker: std::marker::PhantomData<T>,
 }
 
@@ -57,6 +174,8 @@ impl<T: Config> UnstableBackendBuilder<T> {
     pub fn new() -> Self {
         Self {
             max_block_life: usize::MAX,
+            on_stop: StopPolicy::Resubscribe,
+            with_runtime: true,
             _marker: std::marker::PhantomData,
         }
     }
@@ -73,6 +192,25 @@ impl<T: Config> UnstableBackendBuilder<T> {
         self
     }
 
+    /// Decide what happens when the node emits a `stop` event, which it does if it can no longer
+    /// give us a consistent view of the chain (for instance because we are keeping too many blocks
+    /// pinned). By default, we resubscribe ([`StopPolicy::Resubscribe`]). In either case, a `stop`
+    /// event is handed to anything following blocks, so that they know pinned blocks were lost.
+    pub fn on_stop(mut self, policy: StopPolicy) -> Self {
+        self.on_stop = policy;
+        self
+    }
+
+    /// Decide whether `chainHead_follow` should report runtime details as blocks are produced.
+    /// This is enabled by default, and is needed in order to obtain the runtime version. When it's
+    /// disabled, [`Backend::current_runtime_version()`] and [`Backend::stream_runtime_version()`]
+    /// return an error straight away, and so the backend can't be used with an
+    /// [`crate::OnlineClient`] which expects to follow runtime updates.
+    pub fn follow_runtime_updates(mut self, with_runtime: bool) -> Self {
+        self.with_runtime = with_runtime;
+        self
+    }
+
     /// Given an [`RpcClient`] to use to make requests, this returns a tuple of an [`UnstableBackend`],
     /// which implements the [`Backend`] trait, and an [`UnstableBackendDriver`] which must be polled in
     /// order for the backend to make progress.
@@ -82,8 +220,11 @@ impl<T: Config> UnstableBackendBuilder<T> {
     ) -> (UnstableBackend<T>, UnstableBackendDriver<T>) {
         // Construct the underlying follow_stream layers:
         let rpc_methods = UnstableRpcMethods::new(client.into());
-        let follow_stream =
-            follow_stream::FollowStream::<T::Hash>::from_methods(rpc_methods.clone());
+        let follow_stream = follow_stream::FollowStream::<T::Hash>::from_methods_with_runtime(
+            rpc_methods.clone(),
+            self.with_runtime,
+        )
+        .with_stop_policy(self.on_stop);
         let follow_stream_unpin = follow_stream_unpin::FollowStreamUnpin::<T::Hash>::from_methods(
             follow_stream,
             rpc_methods.clone(),
@@ -95,6 +236,8 @@ impl<T: Config> UnstableBackendBuilder<T> {
         let backend = UnstableBackend {
             methods: rpc_methods,
             follow_handle: follow_stream_driver.handle(),
+            with_runtime: self.with_runtime,
+            supports_archive: Default::default(),
         };
         let driver = UnstableBackendDriver {
             driver: follow_stream_driver,
@@ -128,6 +271,10 @@ pub struct UnstableBackend<T: Config> {
     methods: UnstableRpcMethods<T>,
     // A handle to the chainHead_follow subscription:
     follow_handle: FollowStreamDriverHandle<T::Hash>,
+    // Whether chainHead_follow was asked to report runtime details:
+    with_runtime: bool,
+    // Whether the node supports the archive methods, once we've asked:
+    supports_archive: Arc<Mutex<Option<bool>>>,
 }
 
 impl<T: Config> UnstableBackend<T> {
@@ -136,6 +283,21 @@ impl<T: Config> UnstableBackend<T> {
         UnstableBackendBuilder::new()
     }
 
+    /// Does the node support the archive methods? This is only asked once. If the node
+    /// can't tell us which methods it supports, we assume that it doesn't support them.
+    async fn supports_archive(&self) -> bool {
+        if let Some(supports_archive) = *self.supports_archive.lock().unwrap() {
+            return supports_archive;
+        }
+
+        let supports_archive = retry(|| self.methods.rpc_methods())
+            .await
+            .map(|methods| methods.iter().any(|m| m == "archive_unstable_hashByHeight"))
+            .unwrap_or(false);
+        *self.supports_archive.lock().unwrap() = Some(supports_archive);
+        supports_archive
+    }
+
     /// Stream block headers based on the provided filter fn
     async fn stream_headers<F>(
         &self,
@@ -208,9 +370,14 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
                 query_type: StorageQueryType::Value,
             });
 
-            let storage_items =
-                StorageItems::from_methods(queries, at, &self.follow_handle, self.methods.clone())
-                    .await?;
+            let storage_items = StorageItems::from_methods(
+                queries,
+                None,
+                at,
+                &self.follow_handle,
+                self.methods.clone(),
+            )
+            .await?;
 
             let stream = storage_items.filter_map(|val| async move {
                 let val = match val {
@@ -246,6 +413,7 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
 
             let storage_items = StorageItems::from_methods(
                 std::iter::once(query),
+                None,
                 at,
                 &self.follow_handle,
                 self.methods.clone(),
@@ -271,6 +439,7 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
 
             let storage_items = StorageItems::from_methods(
                 std::iter::once(query),
+                None,
                 at,
                 &self.follow_handle,
                 self.methods.clone(),
@@ -297,6 +466,122 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
         .await
     }
 
+    async fn child_storage_fetch_values(
+        &self,
+        child_key: Vec<u8>,
+        keys: Vec<Vec<u8>>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<StorageResponse>, Error> {
+        retry(|| async {
+            let queries = keys.iter().map(|key| StorageQuery {
+                key: &**key,
+                query_type: StorageQueryType::Value,
+            });
+
+            let storage_items = StorageItems::from_methods(
+                queries,
+                Some(&child_key),
+                at,
+                &self.follow_handle,
+                self.methods.clone(),
+            )
+            .await?;
+
+            let stream = storage_items.filter_map(|val| async move {
+                let val = match val {
+                    Ok(val) => val,
+                    Err(e) => return Some(Err(e)),
+                };
+
+                let StorageResultType::Value(result) = val.result else {
+                    return None;
+                };
+                Some(Ok(StorageResponse {
+                    key: val.key.0,
+                    value: result.0,
+                }))
+            });
+
+            Ok(StreamOf(Box::pin(stream)))
+        })
+        .await
+    }
+
+    async fn child_storage_fetch_descendant_keys(
+        &self,
+        child_key: Vec<u8>,
+        key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<StreamOfResults<Vec<u8>>, Error> {
+        retry(|| async {
+            // Ask for hashes, and then just ignore them and return the keys that come back.
+            let query = StorageQuery {
+                key: &*key,
+                query_type: StorageQueryType::DescendantsHashes,
+            };
+
+            let storage_items = StorageItems::from_methods(
+                std::iter::once(query),
+                Some(&child_key),
+                at,
+                &self.follow_handle,
+                self.methods.clone(),
+            )
+            .await?;
+
+            let storage_result_stream = storage_items.map(|val| val.map(|v| v.key.0));
+            Ok(StreamOf(Box::pin(storage_result_stream)))
+        })
+        .await
+    }
+
+    async fn storage_read_proof(
+        &self,
+        _keys: Vec<Vec<u8>>,
+        _at: T::Hash,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        Err(Error::Other(
+            "Storage read proofs are not supported by the chainHead RPC methods".into(),
+        ))
+    }
+
+    async fn storage_fetch_size(&self, key: Vec<u8>, at: T::Hash) -> Result<Option<u64>, Error> {
+        // The chainHead methods have no way to ask for the size of a value,
+        // so we fetch the value and measure it instead.
+        let value = self.storage_fetch_value(key, at).await?;
+        Ok(value.map(|v| v.len() as u64))
+    }
+
+    async fn storage_fetch_closest_descendant_merkle_value(
+        &self,
+        key: Vec<u8>,
+        at: T::Hash,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        retry(|| async {
+            let query = StorageQuery {
+                key: &*key,
+                query_type: StorageQueryType::ClosestDescendantMerkleValue,
+            };
+
+            let mut storage_items = StorageItems::<T>::from_methods(
+                std::iter::once(query),
+                None,
+                at,
+                &self.follow_handle,
+                self.methods.clone(),
+            )
+            .await?;
+
+            while let Some(item) = storage_items.next().await {
+                if let StorageResultType::ClosestDescendantMerkleValue(value) = item?.result {
+                    return Ok(Some(value.0));
+                }
+            }
+            Ok(None)
+        })
+        .await
+    }
+
     async fn genesis_hash(&self) -> Result<T::Hash, Error> {
         retry(|| self.methods.chainspec_v1_genesis_hash()).await
     }
@@ -309,6 +594,50 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
         .await
     }
 
+    async fn finalized_block_hash_at_number(&self, number: u64) -> Result<Option<T::Hash>, Error> {
+        if self.supports_archive().await {
+            // Archive nodes can look up any finalized block by its number.
+            let finalized_height =
+                retry(|| self.methods.archive_unstable_finalized_height()).await?;
+            if number > finalized_height {
+                return Ok(None);
+            }
+            let hashes = retry(|| self.methods.archive_unstable_hash_by_height(number)).await?;
+            return match hashes.first() {
+                Some(hash) => Ok(Some(*hash)),
+                None => Err(block_not_retained(number)),
+            };
+        }
+
+        // Otherwise, the chainHead methods only give us access to the blocks that are
+        // currently pinned, so we walk back through the parents of the latest finalized
+        // block until we find the block or run out of pinned blocks. We hold on to the
+        // latest finalized block ref while we do this to keep it pinned.
+        let finalized_ref = self.latest_finalized_block_ref().await?;
+        let mut hash = finalized_ref.hash();
+        loop {
+            // Once we walk back past the pinned blocks, the node rejects requests for
+            // their headers, which means that the block asked for is no longer retained.
+            let header = match self.block_header(hash).await {
+                Ok(Some(header)) => header,
+                Ok(None) => return Err(block_not_retained(number)),
+                Err(Error::Rpc(_)) if hash != finalized_ref.hash() => {
+                    return Err(block_not_retained(number))
+                }
+                Err(e) => return Err(e),
+            };
+            let header_number: u64 = header.number().into();
+            if header_number == number {
+                return Ok(Some(hash));
+            }
+            if header_number < number {
+                // The block asked for hasn't been finalized yet.
+                return Ok(None);
+            }
+            hash = header.parent_hash();
+        }
+    }
+
     async fn block_body(&self, at: T::Hash) -> Result<Option<Vec<Vec<u8>>>, Error> {
         retry(|| async {
             let sub_id = get_subscription_id(&self.follow_handle).await?;
@@ -340,6 +669,12 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
         .await
     }
 
+    async fn block_justifications(&self, _at: T::Hash) -> Result<Option<Justifications>, Error> {
+        Err(Error::Other(
+            "Block justifications are not supported by the chainHead RPC methods".into(),
+        ))
+    }
+
     async fn latest_finalized_block_ref(&self) -> Result<BlockRef<T::Hash>, Error> {
         let next_ref: Option<BlockRef<T::Hash>> = self
             .follow_handle
@@ -360,6 +695,33 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
         next_ref.ok_or_else(|| RpcError::SubscriptionDropped.into())
     }
 
+    async fn latest_best_block_ref(&self) -> Result<BlockRef<T::Hash>, Error> {
+        let mut events = std::pin::pin!(self.follow_handle.subscribe().events());
+
+        // The latest finalized block is the best block unless we're told otherwise.
+        let mut best_ref: BlockRef<T::Hash> = loop {
+            match events.next().await {
+                Some(FollowEvent::Initialized(init)) => {
+                    if let Some(b) = init.finalized_block_hashes.last() {
+                        break b.clone().into();
+                    }
+                }
+                Some(_) => {}
+                None => return Err(RpcError::SubscriptionDropped.into()),
+            }
+        };
+
+        // New subscriptions are immediately handed the block events seen since the latest
+        // finalized block, so look through any that are ready for the newest best block.
+        while let Some(Some(ev)) = futures::FutureExt::now_or_never(events.next()) {
+            if let FollowEvent::BestBlockChanged(ev) = ev {
+                best_ref = ev.best_block_hash.into();
+            }
+        }
+
+        Ok(best_ref)
+    }
+
     async fn current_runtime_version(&self) -> Result<RuntimeVersion, Error> {
         // Just start a stream of version infos, and return the first value we get from it.
         let runtime_version = self.stream_runtime_version().await?.next().await;
@@ -371,6 +733,11 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
     }
 
     async fn stream_runtime_version(&self) -> Result<StreamOfResults<RuntimeVersion>, Error> {
+        // Without runtime details in the follow events, we'd wait forever for a version.
+        if !self.with_runtime {
+            return Err(runtime_updates_not_followed());
+        }
+
         // Keep track of runtime details announced in new blocks, and then when blocks
         // are finalized, find the latest of these that has runtime details, and clear the rest.
         let mut runtimes = HashMap::new();
@@ -432,18 +799,7 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
                     Some(ev) => ev,
                 };
 
-                let runtime_details = match runtime_event {
-                    RuntimeEvent::Invalid(err) => {
-                        return std::future::ready(Some(Err(Error::Other(err.error))))
-                    }
-                    RuntimeEvent::Valid(ev) => ev,
-                };
-
-                let runtime_version = RuntimeVersion {
-                    spec_version: runtime_details.spec.spec_version,
-                    transaction_version: runtime_details.spec.transaction_version
-                };
-                std::future::ready(Some(Ok(runtime_version)))
+                std::future::ready(Some(runtime_version_from_event(runtime_event)))
             });
 
         Ok(StreamOf::new(Box::pin(runtime_stream)))
@@ -492,6 +848,15 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
         .await
     }
 
+    async fn stream_storage_value(
+        &self,
+        key: Vec<u8>,
+    ) -> Result<StreamOfResults<(BlockRef<T::Hash>, Option<Vec<u8>>)>, Error> {
+        // There is no subscription for storage changes in the new APIs, so we fetch
+        // the value at each new finalized block and only emit it if it has changed.
+        stream_finalized_storage_value(self, key).await
+    }
+
     async fn submit_transaction(
         &self,
         extrinsic: &[u8],
@@ -704,6 +1069,26 @@ impl<T: Config + Send + Sync + 'static> Backend<T> for UnstableBackend<T> {
         })
         .await
     }
+
+    fn connection_events(&self) -> StreamOf<rpc::ConnectionEvent> {
+        self.methods.client().connection_events()
+    }
+}
+
+/// The error returned when asked for the hash of a finalized block that the node no longer
+/// retains (or, without the archive methods, that isn't currently pinned).
+fn block_not_retained(number: u64) -> Error {
+    BlockError::NotRetained { number }.into()
+}
+
+/// The error returned when asked for runtime details if the backend was built without
+/// following runtime updates.
+fn runtime_updates_not_followed() -> Error {
+    Error::Other(
+        "The runtime version is not available; the unstable backend was built with \
+         follow_runtime_updates(false)"
+            .to_owned(),
+    )
 }
 
 /// A helper to obtain a subscription ID.
@@ -716,3 +1101,230 @@ async fn get_subscription_id<Hash: BlockHash>(
 
     Ok(sub_id)
 }
+
+#[cfg(test)]
+mod test {
+    use super::rpc_methods::{ErrorEvent, RuntimeSpec, RuntimeVersionEvent};
+    use super::*;
+
+    #[test]
+    fn invalid_runtime_events_produce_invalid_runtime_errors() {
+        let ev = RuntimeEvent::Invalid(ErrorEvent {
+            error: "Cannot decode runtime".to_string(),
+        });
+
+        match runtime_version_from_event(ev) {
+            Err(Error::InvalidRuntime { message }) => assert_eq!(message, "Cannot decode runtime"),
+            other => panic!("Expected an InvalidRuntime error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn valid_runtime_events_produce_runtime_versions() {
+        let ev = RuntimeEvent::Valid(RuntimeVersionEvent {
+            spec: RuntimeSpec {
+                spec_name: "test".to_string(),
+                impl_name: "test".to_string(),
+                spec_version: 100,
+                impl_version: 1,
+                transaction_version: 5,
+                apis: HashMap::new(),
+            },
+        });
+
+        let version = runtime_version_from_event(ev).unwrap();
+        assert_eq!(version.spec_version, 100);
+        assert_eq!(version.transaction_version, 5);
+    }
+
+    #[tokio::test]
+    async fn latest_best_block_ref_uses_best_block_events_seen_so_far() {
+        use super::follow_stream::test_utils::{ev_best_block, ev_initialized, ev_new_block};
+        use super::follow_stream_driver::test_utils::test_follow_stream_driver_getter;
+        use crate::backend::rpc::mock_rpc_client::MockRpcClient;
+        use sp_core::H256;
+
+        let mut driver = test_follow_stream_driver_getter(
+            || {
+                [
+                    Ok(ev_initialized(0)),
+                    Ok(ev_new_block(0, 1)),
+                    Ok(ev_best_block(1)),
+                    Ok(ev_new_block(1, 2)),
+                    Ok(ev_best_block(2)),
+                    Err(Error::Other("ended".to_owned())),
+                ]
+            },
+            10,
+        );
+        let backend = UnstableBackend::<crate::SubstrateConfig> {
+            methods: UnstableRpcMethods::new(RpcClient::new(MockRpcClient::new())),
+            follow_handle: driver.handle(),
+            with_runtime: false,
+            supports_archive: Default::default(),
+        };
+
+        // Before any best block events, the latest finalized block is the best block:
+        let _r = driver.next().await.unwrap();
+        let _i0 = driver.next().await.unwrap();
+        let best = backend.latest_best_block_ref().await.unwrap();
+        assert_eq!(best.hash(), H256::from_low_u64_le(0));
+
+        // The newest best block seen so far is used, without waiting for more events:
+        let _n1 = driver.next().await.unwrap();
+        let _b1 = driver.next().await.unwrap();
+        let _n2 = driver.next().await.unwrap();
+        let _b2 = driver.next().await.unwrap();
+        let best = backend.latest_best_block_ref().await.unwrap();
+        assert_eq!(best.hash(), H256::from_low_u64_le(2));
+    }
+
+    #[tokio::test]
+    async fn walking_back_past_pinned_blocks_is_not_retained() {
+        use super::follow_stream::test_utils::ev_initialized;
+        use super::follow_stream_driver::test_utils::test_follow_stream_driver_getter;
+        use crate::backend::rpc::mock_rpc_client::MockRpcClient;
+        use crate::config::substrate::{BlakeTwo256, Digest, SubstrateHeader};
+        use codec::Encode;
+        use sp_core::H256;
+
+        let mut driver = test_follow_stream_driver_getter(
+            || [Ok(ev_initialized(5)), Err(Error::Other("ended".to_owned()))],
+            10,
+        );
+
+        // Only the pinned finalized block (5) has a header; asking for its parent is rejected.
+        let header = SubstrateHeader::<u32, BlakeTwo256> {
+            parent_hash: H256::from_low_u64_le(4),
+            number: 5,
+            state_root: H256::zero(),
+            extrinsics_root: H256::zero(),
+            digest: Digest::default(),
+        };
+        let header_hex = format!("0x{}", hex::encode(header.encode()));
+        let rpc_client = MockRpcClient::new().on_request(move |method, params| {
+            if method == "chainHead_v1_header"
+                && params[1] == serde_json::json!(H256::from_low_u64_le(5))
+            {
+                Ok(serde_json::json!(header_hex))
+            } else {
+                Err(RpcError::request_rejected(method))
+            }
+        });
+
+        let backend = UnstableBackend::<crate::SubstrateConfig> {
+            methods: UnstableRpcMethods::new(RpcClient::new(rpc_client)),
+            follow_handle: driver.handle(),
+            with_runtime: false,
+            supports_archive: Arc::new(Mutex::new(Some(false))),
+        };
+
+        let _r = driver.next().await.unwrap();
+        let _i5 = driver.next().await.unwrap();
+
+        assert_eq!(
+            backend.finalized_block_hash_at_number(5).await.unwrap(),
+            Some(H256::from_low_u64_le(5))
+        );
+        assert_eq!(
+            backend.finalized_block_hash_at_number(6).await.unwrap(),
+            None
+        );
+        assert!(matches!(
+            backend.finalized_block_hash_at_number(3).await,
+            Err(Error::Block(BlockError::NotRetained { number: 3 }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn closest_descendant_merkle_value_is_read_from_its_operation_events() {
+        use super::follow_stream::test_utils::ev_initialized;
+        use super::follow_stream_driver::test_utils::test_follow_stream_driver_getter;
+        use super::rpc_methods::{Bytes, OperationId, OperationStorageItems, StorageResult};
+        use crate::backend::rpc::mock_rpc_client::MockRpcClient;
+        use sp_core::H256;
+
+        let ev_items = |operation_id: &str, merkle_value: u8| {
+            FollowEvent::OperationStorageItems(OperationStorageItems {
+                operation_id: operation_id.to_owned(),
+                items: [StorageResult {
+                    key: Bytes(vec![1, 2, 3]),
+                    result: StorageResultType::ClosestDescendantMerkleValue(Bytes(vec![
+                        merkle_value,
+                    ])),
+                }]
+                .into(),
+            })
+        };
+        let mut driver = test_follow_stream_driver_getter(
+            move || {
+                [
+                    Ok(ev_initialized(0)),
+                    // Events for other operations are ignored:
+                    Ok(ev_items("other", 0xff)),
+                    Ok(ev_items("op", 0xab)),
+                    Ok(FollowEvent::OperationStorageDone(OperationId {
+                        operation_id: "op".to_owned(),
+                    })),
+                    Err(Error::Other("ended".to_owned())),
+                ]
+            },
+            10,
+        );
+
+        let rpc_client = MockRpcClient::new().with_response(
+            "chainHead_v1_storage",
+            serde_json::json!({ "result": "started", "operationId": "op" }),
+        );
+        let backend = UnstableBackend::<crate::SubstrateConfig> {
+            methods: UnstableRpcMethods::new(RpcClient::new(rpc_client.clone())),
+            follow_handle: driver.handle(),
+            with_runtime: false,
+            supports_archive: Default::default(),
+        };
+
+        let _r = driver.next().await.unwrap();
+        let _i0 = driver.next().await.unwrap();
+
+        // Start the request, and then hand it the operation events:
+        let fetch = backend
+            .storage_fetch_closest_descendant_merkle_value(vec![1, 2, 3], H256::from_low_u64_le(0));
+        let drive = async {
+            for _ in 0..3 {
+                driver.next().await.unwrap().unwrap();
+            }
+        };
+        let (value, ()) = futures::join!(fetch, drive);
+
+        assert_eq!(value.unwrap(), Some(vec![0xab]));
+
+        let (method, params) = rpc_client.calls().pop().unwrap();
+        assert_eq!(method, "chainHead_v1_storage");
+        assert_eq!(
+            params[2],
+            serde_json::json!([{ "key": "0x010203", "type": "closestDescendantMerkleValue" }])
+        );
+    }
+
+    #[tokio::test]
+    async fn runtime_version_errors_when_runtime_updates_not_followed() {
+        use crate::backend::rpc::mock_rpc_client::MockRpcClient;
+
+        // The driver is deliberately never polled; if we waited on follow events
+        // then these calls would hang.
+        let (backend, _driver) = UnstableBackend::<crate::SubstrateConfig>::builder()
+            .follow_runtime_updates(false)
+            .build(RpcClient::new(MockRpcClient::new()));
+
+        let timeout = std::time::Duration::from_secs(5);
+        let res = tokio::time::timeout(timeout, backend.current_runtime_version())
+            .await
+            .expect("current_runtime_version should not hang");
+        assert!(matches!(res, Err(Error::Other(_))));
+
+        let res = tokio::time::timeout(timeout, backend.stream_runtime_version())
+            .await
+            .expect("stream_runtime_version should not hang");
+        assert!(matches!(res, Err(Error::Other(_))));
+    }
+}
@@ -8,6 +8,7 @@
 
 use crate::backend::rpc::{rpc_params, RpcClient, RpcSubscription};
 use crate::config::BlockHash;
+use crate::utils::to_hex;
 use crate::{Config, Error};
 use derive_where::derive_where;
 use futures::{Stream, StreamExt};
@@ -33,6 +34,11 @@ impl<T: Config> UnstableRpcMethods<T> {
         }
     }
 
+    /// The [`RpcClient`] that these methods are called on.
+    pub(crate) fn client(&self) -> &RpcClient {
+        &self.client
+    }
+
     /// Subscribe to `chainHead_v1_follow` to obtain all reported blocks by the chain.
     ///
     /// The subscription ID can be used to make queries for the
@@ -294,6 +300,90 @@ impl<T: Config> UnstableRpcMethods<T> {
             .request("transaction_v1_stop", rpc_params![operation_id])
             .await
     }
+
+    /// Return the height of the most recent finalized block that the node can serve via
+    /// the `archive_unstable_*` methods.
+    ///
+    /// # Note
+    ///
+    /// The `archive_unstable_*` methods are only exposed by archive nodes, and are unstable;
+    /// they may change or be removed in future versions of the spec.
+    pub async fn archive_unstable_finalized_height(&self) -> Result<u64, Error> {
+        self.client
+            .request("archive_unstable_finalizedHeight", rpc_params![])
+            .await
+    }
+
+    /// Return the hashes of the blocks at the given height. This can return more than one
+    /// hash if the height is above the latest finalized height, and no hashes if the node
+    /// does not know about any blocks at this height.
+    ///
+    /// Unlike the `chainHead_v1_*` methods, this does not require the block to be pinned.
+    ///
+    /// # Note
+    ///
+    /// The `archive_unstable_*` methods are only exposed by archive nodes, and are unstable;
+    /// they may change or be removed in future versions of the spec.
+    pub async fn archive_unstable_hash_by_height(
+        &self,
+        height: u64,
+    ) -> Result<Vec<T::Hash>, Error> {
+        self.client
+            .request("archive_unstable_hashByHeight", rpc_params![height])
+            .await
+    }
+
+    /// Query storage at any block that the node has retained, returning the results directly
+    /// (rather than via a `chainHead_v1_follow` subscription). Items that the node decided not
+    /// to query are reported via [`ArchiveStorageResult::discarded_items`], and should be
+    /// retried in a subsequent call.
+    ///
+    /// # Note
+    ///
+    /// The `archive_unstable_*` methods are only exposed by archive nodes, and are unstable;
+    /// they may change or be removed in future versions of the spec.
+    pub async fn archive_unstable_storage(
+        &self,
+        hash: T::Hash,
+        items: impl IntoIterator<Item = StorageQuery<&[u8]>>,
+        child_key: Option<&[u8]>,
+    ) -> Result<ArchiveStorageResult, Error> {
+        let items: Vec<StorageQuery<String>> = items
+            .into_iter()
+            .map(|item| StorageQuery {
+                key: to_hex(item.key),
+                query_type: item.query_type,
+            })
+            .collect();
+
+        self.client
+            .request(
+                "archive_unstable_storage",
+                rpc_params![hash, items, child_key.map(to_hex)],
+            )
+            .await
+    }
+
+    /// Call a runtime API function at any block that the node has retained, returning the
+    /// result directly (rather than via a `chainHead_v1_follow` subscription).
+    ///
+    /// # Note
+    ///
+    /// The `archive_unstable_*` methods are only exposed by archive nodes, and are unstable;
+    /// they may change or be removed in future versions of the spec.
+    pub async fn archive_unstable_call(
+        &self,
+        hash: T::Hash,
+        function: &str,
+        call_parameters: &[u8],
+    ) -> Result<ArchiveCallResult, Error> {
+        self.client
+            .request(
+                "archive_unstable_call",
+                rpc_params![hash, function, to_hex(call_parameters)],
+            )
+            .await
+    }
 }
 
 /// This represents events generated by the `follow` method.
@@ -619,6 +709,46 @@ pub enum StorageQueryType {
     DescendantsHashes,
 }
 
+/// The result of an `archive_unstable_storage` call.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStorageResult {
+    /// The results of the queries that were made.
+    pub result: Vec<StorageResult>,
+    /// The number of query items, counted from the end of those provided,
+    /// that the node did not process and which should be retried.
+    pub discarded_items: usize,
+}
+
+/// The result of an `archive_unstable_call` call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArchiveCallResult {
+    /// The SCALE encoded output of the runtime API call.
+    Success(Bytes),
+    /// The runtime API call failed, for the reason given.
+    Error(String),
+}
+
+impl<'de> Deserialize<'de> for ArchiveCallResult {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Response {
+            success: bool,
+            value: Option<Bytes>,
+            error: Option<String>,
+        }
+
+        let res = Response::deserialize(deserializer)?;
+        if res.success {
+            res.value
+                .map(ArchiveCallResult::Success)
+                .ok_or_else(|| serde::de::Error::missing_field("value"))
+        } else {
+            Ok(ArchiveCallResult::Error(res.error.unwrap_or_default()))
+        }
+    }
+}
+
 /// A subscription which returns follow events, and ends when a Stop event occurs.
 pub struct FollowSubscription<Hash> {
     sub: RpcSubscription<FollowEvent<Hash>>,
@@ -768,10 +898,6 @@ impl From<Vec<u8>> for Bytes {
     }
 }
 
-fn to_hex(bytes: impl AsRef<[u8]>) -> String {
-    format!("0x{}", hex::encode(bytes.as_ref()))
-}
-
 /// Attempt to deserialize either a string or integer into an integer.
 /// See <https://github.com/paritytech/json-rpc-interface-spec/issues/83>
 pub(crate) mod unsigned_number_as_string {
@@ -885,6 +1011,180 @@ pub(crate) mod hashmap_as_tuple_list {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::backend::rpc::mock_rpc_client::MockRpcClient;
+    use crate::utils::H256;
+
+    fn methods(client: &MockRpcClient) -> UnstableRpcMethods<crate::SubstrateConfig> {
+        UnstableRpcMethods::new(RpcClient::new(client.clone()))
+    }
+
+    #[tokio::test]
+    async fn archive_hash_by_height() {
+        let hash = H256::repeat_byte(1);
+        let client = MockRpcClient::new()
+            .with_response("archive_unstable_hashByHeight", serde_json::json!([hash]));
+
+        let hashes = methods(&client)
+            .archive_unstable_hash_by_height(10)
+            .await
+            .unwrap();
+
+        assert_eq!(hashes, vec![hash]);
+        assert_eq!(
+            client.calls(),
+            vec![(
+                "archive_unstable_hashByHeight".to_string(),
+                serde_json::json!([10])
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn archive_storage() {
+        let hash = H256::repeat_byte(1);
+        let client = MockRpcClient::new().with_response(
+            "archive_unstable_storage",
+            serde_json::json!({
+                "result": [
+                    { "key": "0x0102", "value": "0xaabb" },
+                    { "key": "0x0103", "hash": "0xccdd" },
+                ],
+                "discardedItems": 1
+            }),
+        );
+
+        let items = [
+            StorageQuery {
+                key: &[1u8, 2][..],
+                query_type: StorageQueryType::Value,
+            },
+            StorageQuery {
+                key: &[1u8, 3][..],
+                query_type: StorageQueryType::Hash,
+            },
+        ];
+        let res = methods(&client)
+            .archive_unstable_storage(hash, items, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            res,
+            ArchiveStorageResult {
+                result: vec![
+                    StorageResult {
+                        key: Bytes(vec![1, 2]),
+                        result: StorageResultType::Value(Bytes(vec![0xaa, 0xbb])),
+                    },
+                    StorageResult {
+                        key: Bytes(vec![1, 3]),
+                        result: StorageResultType::Hash(Bytes(vec![0xcc, 0xdd])),
+                    },
+                ],
+                discarded_items: 1,
+            }
+        );
+        assert_eq!(
+            client.calls(),
+            vec![(
+                "archive_unstable_storage".to_string(),
+                serde_json::json!([
+                    hash,
+                    [
+                        { "key": "0x0102", "type": "value" },
+                        { "key": "0x0103", "type": "hash" },
+                    ],
+                    null
+                ])
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn archive_call() {
+        let hash = H256::repeat_byte(1);
+        let client = MockRpcClient::new().with_response(
+            "archive_unstable_call",
+            serde_json::json!({ "success": true, "value": "0x0102" }),
+        );
+
+        let res = methods(&client)
+            .archive_unstable_call(hash, "Core_version", &[3])
+            .await
+            .unwrap();
+
+        assert_eq!(res, ArchiveCallResult::Success(Bytes(vec![1, 2])));
+        assert_eq!(
+            client.calls(),
+            vec![(
+                "archive_unstable_call".to_string(),
+                serde_json::json!([hash, "Core_version", "0x03"])
+            )]
+        );
+
+        let err: ArchiveCallResult =
+            serde_json::from_value(serde_json::json!({ "success": false, "error": "boom" }))
+                .unwrap();
+        assert_eq!(err, ArchiveCallResult::Error("boom".to_string()));
+    }
+
+    #[tokio::test]
+    async fn chainhead_storage_closest_descendant_merkle_value() {
+        let hash = H256::repeat_byte(1);
+        let client = MockRpcClient::new().with_response(
+            "chainHead_v1_storage",
+            serde_json::json!({ "result": "started", "operationId": "op" }),
+        );
+
+        let query = StorageQuery {
+            key: &[1u8, 2][..],
+            query_type: StorageQueryType::ClosestDescendantMerkleValue,
+        };
+        let res = methods(&client)
+            .chainhead_v1_storage("sub", hash, [query], None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            res,
+            MethodResponse::Started(MethodResponseStarted {
+                operation_id: "op".to_string(),
+                discarded_items: None,
+            })
+        );
+        assert_eq!(
+            client.calls(),
+            vec![(
+                "chainHead_v1_storage".to_string(),
+                serde_json::json!([
+                    "sub",
+                    hash,
+                    [{ "key": "0x0102", "type": "closestDescendantMerkleValue" }],
+                    null
+                ])
+            )]
+        );
+
+        // The result is handed back on the follow subscription:
+        let event: FollowEvent<H256> = serde_json::from_value(serde_json::json!({
+            "event": "operationStorageItems",
+            "operationId": "op",
+            "items": [{ "key": "0x0102", "closestDescendantMerkleValue": "0xaabbcc" }]
+        }))
+        .unwrap();
+        assert_eq!(
+            event,
+            FollowEvent::OperationStorageItems(OperationStorageItems {
+                operation_id: "op".to_string(),
+                items: VecDeque::from([StorageResult {
+                    key: Bytes(vec![1, 2]),
+                    result: StorageResultType::ClosestDescendantMerkleValue(Bytes(vec![
+                        0xaa, 0xbb, 0xcc
+                    ])),
+                }]),
+            })
+        );
+    }
 
     #[test]
     fn can_deserialize_apis_from_tuple_or_object() {
@@ -18,6 +18,11 @@ use std::task::{Context, Poll};
 
 /// Obtain a stream of storage items given some query. this handles continuing
 /// and stopping under the hood, and returns a stream of `StorageResult`s.
+///
+/// Continuing is driven by demand: `chainHead_continue` is only called once every
+/// buffered item has been handed out and the stream is polled again. Since the node
+/// won't send more items until it's asked to continue, at most one batch of items is
+/// held in memory at a time, no matter how large the underlying query is.
 pub struct StorageItems<T: Config> {
     done: bool,
     operation_id: Arc<str>,
@@ -29,10 +34,11 @@ pub struct StorageItems<T: Config> {
 
 impl<T: Config> StorageItems<T> {
     // Subscribe to follow events, and return a stream of storage results
-    // given some storage queries. The stream will automatically resume as
-    // needed, and stop when done.
+    // given some storage queries, optionally against some child trie. The
+    // stream will automatically resume as needed, and stop when done.
     pub async fn from_methods(
         queries: impl Iterator<Item = StorageQuery<&[u8]>>,
+        child_key: Option<&[u8]>,
         at: T::Hash,
         follow_handle: &FollowStreamDriverHandle<T::Hash>,
         methods: UnstableRpcMethods<T>,
@@ -42,7 +48,7 @@ impl<T: Config> StorageItems<T> {
         // Subscribe to events and make the initial request to get an operation ID.
         let follow_events = follow_handle.subscribe().events();
         let status = methods
-            .chainhead_v1_storage(&sub_id, at, queries, None)
+            .chainhead_v1_storage(&sub_id, at, queries, child_key)
             .await?;
         let operation_id: Arc<str> = match status {
             MethodResponse::LimitReached => {
@@ -135,7 +141,8 @@ impl<T: Config> Stream for StorageItems<T> {
                 FollowEvent::OperationWaitingForContinue(id)
                     if id.operation_id == *self.operation_id =>
                 {
-                    // Start a call to ask for more events
+                    // We only get here once all buffered items have been consumed and we've
+                    // been polled for more, so start a call to ask for more events.
                     self.continue_fut = Some((self.continue_call)());
                     continue;
                 }
@@ -164,3 +171,81 @@ impl<T: Config> Stream for StorageItems<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::super::rpc_methods::{Bytes, OperationId, OperationStorageItems, StorageResultType};
+    use super::*;
+    use crate::SubstrateConfig;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    type Hash = <SubstrateConfig as Config>::Hash;
+
+    fn ev_items(keys: impl IntoIterator<Item = u8>) -> FollowEvent<BlockRef<Hash>> {
+        FollowEvent::OperationStorageItems(OperationStorageItems {
+            operation_id: "op".to_owned(),
+            items: keys
+                .into_iter()
+                .map(|k| StorageResult {
+                    key: Bytes(vec![k]),
+                    result: StorageResultType::Value(Bytes(vec![k])),
+                })
+                .collect(),
+        })
+    }
+
+    fn ev_waiting_for_continue() -> FollowEvent<BlockRef<Hash>> {
+        FollowEvent::OperationWaitingForContinue(OperationId {
+            operation_id: "op".to_owned(),
+        })
+    }
+
+    fn ev_done() -> FollowEvent<BlockRef<Hash>> {
+        FollowEvent::OperationStorageDone(OperationId {
+            operation_id: "op".to_owned(),
+        })
+    }
+
+    async fn next_key(items: &mut StorageItems<SubstrateConfig>) -> Option<Vec<u8>> {
+        items.next().await.map(|r| r.unwrap().key.0)
+    }
+
+    #[tokio::test]
+    async fn continue_is_only_called_as_items_are_consumed() {
+        let events = futures::stream::iter([
+            ev_items([1, 2]),
+            ev_waiting_for_continue(),
+            ev_items([3]),
+            ev_waiting_for_continue(),
+            ev_items([4]),
+            ev_done(),
+        ]);
+
+        let continue_calls = Arc::new(AtomicUsize::new(0));
+        let continue_call: ContinueFutGetter = {
+            let continue_calls = continue_calls.clone();
+            Box::new(move || {
+                continue_calls.fetch_add(1, Ordering::SeqCst);
+                Box::pin(async { Ok(()) })
+            })
+        };
+
+        let mut items =
+            StorageItems::<SubstrateConfig>::new("op".into(), continue_call, Box::pin(events));
+
+        // Items already handed to us are emitted without asking for more.
+        assert_eq!(next_key(&mut items).await, Some(vec![1]));
+        assert_eq!(continue_calls.load(Ordering::SeqCst), 0);
+        assert_eq!(next_key(&mut items).await, Some(vec![2]));
+        assert_eq!(continue_calls.load(Ordering::SeqCst), 0);
+
+        // Each continue is only issued when we ask for an item beyond those buffered.
+        assert_eq!(next_key(&mut items).await, Some(vec![3]));
+        assert_eq!(continue_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(next_key(&mut items).await, Some(vec![4]));
+        assert_eq!(continue_calls.load(Ordering::SeqCst), 2);
+
+        assert_eq!(next_key(&mut items).await, None);
+        assert_eq!(continue_calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,6 +1,7 @@
 //! RPC utils.
 
-use super::{StreamOf, StreamOfResults};
+use super::{Backend, BackendExt, BlockRef, StreamOf, StreamOfResults};
+use crate::config::Config;
 use crate::error::Error;
 use futures::future::BoxFuture;
 use futures::{FutureExt, Stream, StreamExt};
@@ -176,6 +177,67 @@ where
     })))
 }
 
+/// The most finalized blocks that [`stream_finalized_storage_value`] will skip over at once.
+const MAX_SKIPPED_FINALIZED_BLOCKS: usize = 16;
+
+/// A stream of the value at some storage key. The value is fetched at each new finalized
+/// block, and emitted along with that block whenever it differs from the last value emitted.
+///
+/// Neither RPC API has a subscription which reports storage changes in finalized blocks
+/// (`state_subscribeStorage` reports changes in best blocks, which may never be finalized),
+/// which is why we poll. At most one storage request is made per finalized block, and if
+/// several finalized blocks are ready at once (for instance because the previous request
+/// was slow), we only fetch the value at the newest of them.
+pub(crate) async fn stream_finalized_storage_value<T, B>(
+    backend: &B,
+    key: Vec<u8>,
+) -> Result<StreamOfResults<(BlockRef<T::Hash>, Option<Vec<u8>>)>, Error>
+where
+    T: Config,
+    B: Backend<T> + Clone,
+{
+    let this = backend.clone();
+    let mut last_value = None;
+
+    let stream = backend
+        .stream_finalized_block_headers()
+        .await?
+        .ready_chunks(MAX_SKIPPED_FINALIZED_BLOCKS)
+        .then(move |chunk| {
+            let this = this.clone();
+            let key = key.clone();
+            async move {
+                let mut results = Vec::new();
+                let mut newest_block_ref = None;
+                for res in chunk {
+                    match res {
+                        Ok((_, block_ref)) => newest_block_ref = Some(block_ref),
+                        Err(e) => results.push(Err(e)),
+                    }
+                }
+                if let Some(block_ref) = newest_block_ref {
+                    let value = this.storage_fetch_value(key, block_ref.hash()).await;
+                    results.push(value.map(|value| (block_ref, value)));
+                }
+                futures::stream::iter(results)
+            }
+        })
+        .flatten()
+        .filter_map(move |res| {
+            let res = match res {
+                Ok((_, value)) if last_value.as_ref() == Some(&value) => None,
+                Ok((block_ref, value)) => {
+                    last_value = Some(value.clone());
+                    Some(Ok((block_ref, value)))
+                }
+                Err(e) => Some(Err(e)),
+            };
+            std::future::ready(res)
+        });
+
+    Ok(StreamOf(Box::pin(stream)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -268,4 +330,58 @@ mod tests {
         assert!(matches!(result[1], Err(ref e) if e.is_disconnected_will_reconnect()));
         assert!(matches!(result[2], Err(ref e) if matches!(e, Error::Other(_))));
     }
+
+    #[tokio::test]
+    async fn finalized_storage_value_is_only_fetched_at_the_newest_ready_block() {
+        use crate::backend::legacy::LegacyBackend;
+        use crate::backend::rpc::{mock_rpc_client::MockRpcClient, RpcClient};
+        use crate::config::{Header, PolkadotConfig};
+
+        let zero_hash = format!("0x{}", "00".repeat(32));
+        let header = {
+            let zero_hash = zero_hash.clone();
+            move |n: u64| {
+                serde_json::json!({
+                    "parentHash": zero_hash,
+                    "number": format!("0x{n:x}"),
+                    "stateRoot": zero_hash,
+                    "extrinsicsRoot": zero_hash,
+                    "digest": { "logs": [] }
+                })
+            }
+        };
+
+        // Blocks 1, 2 and 3 are all finalized by the time that we first look at the stream:
+        let rpc_client = MockRpcClient::new()
+            .with_response("chain_getFinalizedHead", serde_json::json!(zero_hash))
+            .with_response("chain_getHeader", header(0))
+            .with_response("state_getStorage", serde_json::json!("0x01"))
+            .on_subscribe({
+                let header = header.clone();
+                move |_, _| Ok(vec![header(1), header(2), header(3)])
+            });
+        let backend =
+            LegacyBackend::<PolkadotConfig>::builder().build(RpcClient::new(rpc_client.clone()));
+
+        let values: Vec<_> =
+            stream_finalized_storage_value::<PolkadotConfig, _>(&backend, vec![1, 2, 3])
+                .await
+                .unwrap()
+                .collect()
+                .await;
+
+        let header3: <PolkadotConfig as Config>::Header =
+            serde_json::from_value(header(3)).unwrap();
+        assert_eq!(values.len(), 1);
+        let (block_ref, value) = values[0].as_ref().unwrap();
+        assert_eq!(block_ref.hash(), header3.hash());
+        assert_eq!(value.as_deref(), Some(&[1u8][..]));
+
+        let storage_calls = rpc_client
+            .methods_called()
+            .into_iter()
+            .filter(|m| m == "state_getStorage")
+            .count();
+        assert_eq!(storage_calls, 1);
+    }
 }
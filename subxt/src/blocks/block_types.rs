@@ -3,8 +3,8 @@
 // see LICENSE for license details.
 
 use crate::{
-    backend::BlockRef,
-    blocks::Extrinsics,
+    backend::{BlockRef, Justifications},
+    blocks::{BlockWeightUsage, Extrinsics},
     client::{OfflineClientT, OnlineClientT},
     config::{Config, Header},
     error::{BlockError, DecodeError, Error},
@@ -61,6 +61,11 @@ where
         self.header().number()
     }
 
+    /// Return the hash of the parent block.
+    pub fn parent_hash(&self) -> T::Hash {
+        self.header.parent_hash()
+    }
+
     /// Return the entire block header.
     pub fn header(&self) -> &T::Header {
         &self.header
@@ -72,6 +77,28 @@ where
     T: Config,
     C: OnlineClientT<T>,
 {
+    /// Fetch the parent of this block, returning `None` if this is the genesis block.
+    ///
+    /// An error is returned if the parent block can't be found. When using the unstable backend,
+    /// this will be the case if the parent block isn't pinned (for instance because it was
+    /// finalized before we started following the chain).
+    pub async fn parent(&self) -> Result<Option<Block<T, C>>, Error> {
+        if self.number().into() == 0 {
+            return Ok(None);
+        }
+
+        let parent_hash = self.parent_hash();
+        let Some(header) = self.client.backend().block_header(parent_hash).await? else {
+            return Err(BlockError::not_found(parent_hash).into());
+        };
+
+        Ok(Some(Block::new(
+            header,
+            BlockRef::from_hash(parent_hash),
+            self.client.clone(),
+        )))
+    }
+
     /// Return the events associated with the block, fetching them from the node if necessary.
     pub async fn events(&self) -> Result<events::Events<T>, Error> {
         get_events(&self.client, self.header.hash(), &self.cached_events).await
@@ -92,6 +119,19 @@ where
         )?)
     }
 
+    /// Fetch the justifications attached to this block, if it has any. The GRANDPA justification
+    /// can be found and decoded from these using
+    /// [`crate::blocks::GrandpaJustification::from_justifications()`].
+    ///
+    /// The chainHead RPC methods don't give access to justifications, so this always returns
+    /// an error when using the [`crate::backend::unstable::UnstableBackend`].
+    pub async fn justifications(&self) -> Result<Option<Justifications>, Error> {
+        self.client
+            .backend()
+            .block_justifications(self.header.hash())
+            .await
+    }
+
     /// Work with storage.
     pub fn storage(&self) -> Storage<T, C> {
         Storage::new(self.client.clone(), self.block_ref.clone())
@@ -102,6 +142,40 @@ where
         Ok(RuntimeApi::new(self.client.clone(), self.block_ref.clone()))
     }
 
+    /// Report how much of the available weight and length this block uses, for each dispatch
+    /// class. This reads the `System.BlockWeight` storage entry at this block and adds up the
+    /// encoded lengths of the extrinsics in the block body, and compares them with the
+    /// `System.BlockWeights` and `System.BlockLength` constants. Both the older single number
+    /// weights and the newer two dimensional weights are supported.
+    pub async fn weight_usage(&self) -> Result<BlockWeightUsage, Error> {
+        let block_weight = self
+            .storage()
+            .fetch_or_default(&crate::dynamic::storage("System", "BlockWeight", ()))
+            .await?
+            .to_value()?;
+
+        // `System.AllExtrinsicsLen` is removed again when the block is finalized, so we
+        // work the length out from the block body instead.
+        let block_hash = self.header.hash();
+        let Some(extrinsics) = self.client.backend().block_body(block_hash).await? else {
+            return Err(BlockError::not_found(block_hash).into());
+        };
+        let length = extrinsics.iter().map(|ext| ext.len()).sum::<usize>();
+        let length = u32::try_from(length).map_err(|_| {
+            Error::Other(format!("The block length {length} does not fit in a u32"))
+        })?;
+
+        let constants = self.client.constants();
+        let block_weights = constants
+            .at(&crate::dynamic::constant("System", "BlockWeights"))?
+            .to_value()?;
+        let block_length = constants
+            .at(&crate::dynamic::constant("System", "BlockLength"))?
+            .to_value()?;
+
+        BlockWeightUsage::from_values(&block_weight, length, &block_weights, &block_length)
+    }
+
     /// Get the account nonce for a given account ID at this block.
     pub async fn account_nonce(&self, account_id: &T::AccountId) -> Result<u64, Error> {
         get_account_nonce(&self.client, account_id, self.hash()).await
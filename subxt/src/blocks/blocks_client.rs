@@ -6,12 +6,14 @@ use super::Block;
 use crate::{
     backend::{BlockRef, StreamOfResults},
     client::OnlineClientT,
-    config::Config,
+    config::{Config, Header},
     error::{BlockError, Error},
     utils::PhantomDataSendSync,
 };
 use derive_where::derive_where;
-use futures::StreamExt;
+use futures::future::{self, Either};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use std::collections::VecDeque;
 use std::future::Future;
 
 type BlockStream<T> = StreamOfResults<T>;
@@ -53,7 +55,7 @@ where
         self.at_or_latest(Some(block_ref.into()))
     }
 
-    /// Obtain block details of the latest block hash.
+    /// Obtain block details of the latest finalized block.
     pub fn at_latest(
         &self,
     ) -> impl Future<Output = Result<Block<T, Client>, Error>> + Send + 'static {
@@ -83,6 +85,118 @@ where
         }
     }
 
+    /// Iterate over the finalized blocks from block number `from` up to and including block
+    /// number `to`, in order. If `to` is `None`, then we'll iterate up to the latest finalized
+    /// block at the time of calling this. An error is returned if `to` is greater than the latest
+    /// finalized block number.
+    ///
+    /// # Memory and latency
+    ///
+    /// If the backend can look up blocks by number (the [`crate::backend::legacy::LegacyBackend`]
+    /// can), then each block is looked up and loaded one after the other as the stream is polled,
+    /// and so memory usage is constant regardless of the size of the range, but each block
+    /// requires a couple of round trips to the node.
+    ///
+    /// Otherwise, we walk backwards over block headers from the latest finalized block to `from`
+    /// before handing anything back, holding the headers from `from` to `to` in memory. This will
+    /// only work if every block in that range is still available to the backend; for the
+    /// [`crate::backend::unstable::UnstableBackend`], this means that they must all be pinned,
+    /// and so an error will be returned for older blocks.
+    pub fn range(
+        &self,
+        from: u64,
+        to: Option<u64>,
+    ) -> impl Stream<Item = Result<Block<T, Client>, Error>> + Send + 'static
+    where
+        Client: Send + Sync + 'static,
+    {
+        let client = self.client.clone();
+        let blocks_fut = async move {
+            let backend = client.backend();
+            let finalized_ref = backend.latest_finalized_block_ref().await?;
+            let finalized_header = match backend.block_header(finalized_ref.hash()).await? {
+                Some(header) => header,
+                None => return Err(BlockError::not_found(finalized_ref.hash()).into()),
+            };
+            let finalized_number: u64 = finalized_header.number().into();
+
+            let to = to.unwrap_or(finalized_number);
+            if to > finalized_number {
+                return Err(BlockError::NotFinalized {
+                    number: to,
+                    finalized: finalized_number,
+                }
+                .into());
+            }
+
+            // Nothing to hand back.
+            if from > to {
+                return Ok(Either::Left(stream::empty()));
+            }
+
+            // If the backend can give us the hash of `from`, assume that it can
+            // give us every other hash too and look them up as we go. If it can't
+            // look up a block this old, we'll try walking back to it instead.
+            let from_hash = match backend.finalized_block_hash_at_number(from).await {
+                Ok(from_hash) => from_hash,
+                Err(Error::Block(BlockError::NotRetained { .. })) => None,
+                Err(e) => return Err(e),
+            };
+            if let Some(from_hash) = from_hash {
+                let blocks = stream::iter(from..=to).then(move |n| {
+                    let client = client.clone();
+                    async move {
+                        let hash = if n == from {
+                            from_hash
+                        } else {
+                            match client.backend().finalized_block_hash_at_number(n).await? {
+                                Some(hash) => hash,
+                                None => {
+                                    return Err(Error::Other(format!(
+                                        "Could not find the hash of block number {n}"
+                                    )))
+                                }
+                            }
+                        };
+                        let header = match client.backend().block_header(hash).await? {
+                            Some(header) => header,
+                            None => return Err(BlockError::not_found(hash).into()),
+                        };
+                        Ok(Block::new(header, BlockRef::from_hash(hash), client))
+                    }
+                });
+                return Ok(Either::Right(Either::Left(blocks)));
+            }
+
+            // Else, walk back from the latest finalized block to `from`,
+            // keeping hold of the headers that we want to hand back.
+            let mut headers = VecDeque::new();
+            let mut header = finalized_header;
+            loop {
+                let number: u64 = header.number().into();
+                let parent_hash = header.parent_hash();
+                if number <= to {
+                    headers.push_front(header);
+                }
+                if number <= from {
+                    break;
+                }
+                header = match backend.block_header(parent_hash).await? {
+                    Some(header) => header,
+                    None => return Err(BlockError::not_found(parent_hash).into()),
+                };
+            }
+
+            let blocks = stream::iter(headers).map(move |header| {
+                let block_ref = BlockRef::from_hash(header.hash());
+                Ok(Block::new(header, block_ref, client.clone()))
+            });
+            Ok(Either::Right(Either::Right(blocks)))
+        };
+
+        stream::once(blocks_fut).try_flatten()
+    }
+
     /// Subscribe to all new blocks imported by the node.
     ///
     /// **Note:** You probably want to use [`Self::subscribe_finalized()`] most of
@@ -130,6 +244,67 @@ where
             BlockStreamRes::Ok(stream)
         })
     }
+
+    /// Subscribe to finalized blocks, starting from block number `from`. The finalized blocks
+    /// from `from` up to the latest finalized block are handed back first (as with
+    /// [`Self::range()`]), followed by newly finalized blocks as they arrive, so that every
+    /// finalized block from `from` onwards is returned exactly once and in order. This is useful
+    /// for resuming some indexing after downtime.
+    ///
+    /// See [`Self::range()`] for the limitations around looking up older blocks.
+    pub fn subscribe_finalized_from(
+        &self,
+        from: u64,
+    ) -> impl Future<Output = Result<BlockStream<Block<T, Client>>, Error>> + Send + 'static
+    where
+        Client: Send + Sync + 'static,
+    {
+        let blocks_client = self.clone();
+        async move {
+            let backfill = blocks_client.range(from, None);
+
+            // Only subscribe once the backfill is complete, so that new blocks aren't left pinned
+            // while it's consumed. Anything finalized in the meantime is filled in below.
+            let live = {
+                let blocks_client = blocks_client.clone();
+                stream::once(async move { blocks_client.subscribe_finalized().await })
+                    .map(|sub| match sub {
+                        Ok(sub) => Either::Left(sub),
+                        Err(e) => Either::Right(stream::iter(vec![Err(e)])),
+                    })
+                    .flatten()
+            };
+
+            // Blocks from the subscription which were already handed back by the backfill are
+            // skipped, and any gap between the two is filled in, so that each block is seen once.
+            let stream = backfill
+                .chain(live)
+                .scan(from, move |next_number, block| {
+                    let blocks = match block {
+                        Err(e) => Either::Left(stream::iter(vec![Err(e)])),
+                        Ok(block) => {
+                            let number: u64 = block.number().into();
+                            if number < *next_number {
+                                Either::Left(stream::iter(vec![]))
+                            } else {
+                                let gap = (number > *next_number)
+                                    .then(|| blocks_client.range(*next_number, Some(number - 1)));
+                                *next_number = number + 1;
+                                let block = stream::iter(vec![Ok(block)]);
+                                match gap {
+                                    Some(gap) => Either::Right(gap.chain(block)),
+                                    None => Either::Left(block),
+                                }
+                            }
+                        }
+                    };
+                    future::ready(Some(blocks))
+                })
+                .flatten();
+
+            BlockStreamRes::Ok(StreamOfResults::new(Box::pin(stream)))
+        }
+    }
 }
 
 /// Take a promise that will return a subscription to some block headers,
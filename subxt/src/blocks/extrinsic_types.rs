@@ -4,19 +4,23 @@
 
 use crate::{
     blocks::block_types::{get_events, CachedEvents},
+    blocks::weight_usage::{weight_at, Weight},
     client::{OfflineClientT, OnlineClientT},
     config::{Config, Hasher},
+    dynamic::{At, DecodedValue},
     error::{BlockError, Error},
     events,
 };
 
 use derive_where::derive_where;
 use scale_decode::DecodeAsType;
+use scale_value::ValueDef;
 use subxt_core::blocks::{ExtrinsicDetails as CoreExtrinsicDetails, Extrinsics as CoreExtrinsics};
 
 // Re-export anything that's directly returned/used in the APIs below.
 pub use subxt_core::blocks::{
-    ExtrinsicMetadataDetails, ExtrinsicSignedExtension, ExtrinsicSignedExtensions, StaticExtrinsic,
+    AnyStaticExtrinsic, ExtrinsicMetadataDetails, ExtrinsicSignedExtension,
+    ExtrinsicSignedExtensions, StaticExtrinsic,
 };
 
 /// The body of a block.
@@ -126,6 +130,28 @@ where
     pub fn has<E: StaticExtrinsic>(&self) -> Result<bool, Error> {
         Ok(self.find::<E>().next().transpose()?.is_some())
     }
+
+    /// Iterate through the extrinsics using metadata to dynamically decode and skip
+    /// them, and return only those which decode to one of the extrinsics represented by `E`.
+    /// Types representing several extrinsics can be generated using [`crate::find_any!`].
+    /// If an error occurs, all subsequent iterations return `None`.
+    pub fn find_any<E: AnyStaticExtrinsic>(
+        &self,
+    ) -> impl Iterator<Item = Result<FoundExtrinsic<T, C, E>, Error>> + '_ {
+        self.inner.find_any::<E>().map(|res| {
+            let ext = res?;
+            let details = ExtrinsicDetails::new(
+                ext.details,
+                self.client.clone(),
+                self.hash,
+                self.cached_events.clone(),
+            );
+            Ok(FoundExtrinsic {
+                details,
+                value: ext.value,
+            })
+        })
+    }
 }
 
 /// A single extrinsic in a block.
@@ -239,6 +265,13 @@ where
         self.inner.field_values().map_err(Into::into)
     }
 
+    /// See [`subxt_core::blocks::ExtrinsicDetails::call_to_json()`].
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn call_to_json(&self) -> Result<serde_json::Value, Error> {
+        self.inner.call_to_json().map_err(Into::into)
+    }
+
     /// See [`subxt_core::blocks::ExtrinsicDetails::as_extrinsic()`].
     pub fn as_extrinsic<E: StaticExtrinsic>(&self) -> Result<Option<E>, Error> {
         self.inner.as_extrinsic::<E>().map_err(Into::into)
@@ -360,4 +393,135 @@ impl<T: Config> ExtrinsicEvents<T> {
     pub fn has<Ev: events::StaticEvent>(&self) -> Result<bool, Error> {
         Ok(self.find::<Ev>().next().transpose()?.is_some())
     }
+
+    /// Find the `System.ExtrinsicSuccess` or `System.ExtrinsicFailed` event associated with this
+    /// transaction and decode the [`DispatchInfo`] that it carries. Returns `None` if neither
+    /// event was found.
+    pub fn dispatch_info(&self) -> Result<Option<DispatchInfo>, Error> {
+        for ev in self.iter() {
+            let ev = ev?;
+            if ev.pallet_name() != "System"
+                || !matches!(ev.variant_name(), "ExtrinsicSuccess" | "ExtrinsicFailed")
+            {
+                continue;
+            }
+
+            let fields = DecodedValue {
+                value: ValueDef::Composite(ev.field_values()?),
+                context: 0,
+            };
+            let info = fields
+                .at("dispatch_info")
+                .ok_or_else(|| unexpected_dispatch_info("dispatch_info"))?;
+            return DispatchInfo::from_value(info).map(Some);
+        }
+        Ok(None)
+    }
+}
+
+/// The class of a dispatch, which determines which portion of the block weight it counts towards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispatchClass {
+    /// A normal dispatch, such as most transactions.
+    Normal,
+    /// An operational dispatch, which can use some of the weight reserved for such things.
+    Operational,
+    /// A mandatory dispatch, such as an inherent, which is always included.
+    Mandatory,
+}
+
+/// Information about a dispatched extrinsic, as reported in the `System.ExtrinsicSuccess`
+/// and `System.ExtrinsicFailed` events. This is obtained via
+/// [`ExtrinsicEvents::dispatch_info()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchInfo {
+    /// The actual weight used by the extrinsic.
+    pub weight: Weight,
+    /// The class of the extrinsic.
+    pub class: DispatchClass,
+    /// Whether the extrinsic paid a fee.
+    pub pays_fee: bool,
+}
+
+impl DispatchInfo {
+    /// Build the dispatch info from the dynamically decoded `dispatch_info` event field.
+    fn from_value(info: &DecodedValue) -> Result<Self, Error> {
+        let weight =
+            weight_at(info.at("weight"))?.ok_or_else(|| unexpected_dispatch_info("weight"))?;
+        let class = match variant_name(info.at("class")) {
+            Some("Normal") => DispatchClass::Normal,
+            Some("Operational") => DispatchClass::Operational,
+            Some("Mandatory") => DispatchClass::Mandatory,
+            _ => return Err(unexpected_dispatch_info("class")),
+        };
+        let pays_fee = match variant_name(info.at("pays_fee")) {
+            Some("Yes") => true,
+            Some("No") => false,
+            _ => return Err(unexpected_dispatch_info("pays_fee")),
+        };
+
+        Ok(DispatchInfo {
+            weight,
+            class,
+            pays_fee,
+        })
+    }
+}
+
+fn variant_name(value: Option<&DecodedValue>) -> Option<&str> {
+    match &value?.value {
+        ValueDef::Variant(v) => Some(&v.name),
+        _ => None,
+    }
+}
+
+fn unexpected_dispatch_info(field: &str) -> Error {
+    Error::Other(format!(
+        "Could not find '{field}' in the dispatch info; the dispatch info on this chain is not supported"
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scale_value::{Composite, Value};
+
+    fn dispatch_info(weight: Value, class: &str, pays_fee: &str) -> DecodedValue {
+        Value::named_composite([
+            ("weight", weight),
+            ("class", Value::variant(class, Composite::unnamed([]))),
+            ("pays_fee", Value::variant(pays_fee, Composite::unnamed([]))),
+        ])
+        .map_context(|_| 0)
+    }
+
+    #[test]
+    fn dispatch_info_is_decoded() {
+        let weight = Value::named_composite([
+            ("ref_time", Value::u128(1_000)),
+            ("proof_size", Value::u128(64)),
+        ]);
+        let info = dispatch_info(weight, "Operational", "No");
+
+        assert_eq!(
+            DispatchInfo::from_value(&info).unwrap(),
+            DispatchInfo {
+                weight: Weight {
+                    ref_time: 1_000,
+                    proof_size: 64
+                },
+                class: DispatchClass::Operational,
+                pays_fee: false,
+            }
+        );
+    }
+
+    #[test]
+    fn unexpected_dispatch_info_is_an_error() {
+        let info = dispatch_info(Value::u128(1), "Unknown", "Yes");
+        assert!(DispatchInfo::from_value(&info).is_err());
+
+        let info = dispatch_info(Value::bool(true), "Normal", "Yes");
+        assert!(DispatchInfo::from_value(&info).is_err());
+    }
 }
@@ -0,0 +1,113 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+/// Generate an enum representing any one of several extrinsic or event types, which can then
+/// be handed to [`crate::blocks::Extrinsics::find_any()`] or [`crate::events::Events::find_any()`]
+/// to look for all of these types in a single pass.
+///
+/// Write `for extrinsics` after the enum name to generate an enum whose variants each wrap a
+/// [`crate::blocks::StaticExtrinsic`], or `for events` to generate one whose variants each wrap
+/// a [`crate::events::StaticEvent`]. When decoding, variants are tried in the order given.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use subxt::{OnlineClient, PolkadotConfig};
+///
+/// #[subxt::subxt(runtime_metadata_path = "../artifacts/polkadot_metadata_small.scale")]
+/// pub mod polkadot {}
+///
+/// use polkadot::balances::calls::types::TransferAllowDeath;
+/// use polkadot::system::calls::types::Remark;
+///
+/// subxt::find_any! {
+///     /// The calls that we're interested in.
+///     pub enum InterestingCall for extrinsics {
+///         Transfer(TransferAllowDeath),
+///         Remark(Remark),
+///     }
+/// }
+///
+/// let api = OnlineClient::<PolkadotConfig>::new().await?;
+/// let block = api.blocks().at_latest().await?;
+/// let extrinsics = block.extrinsics().await?;
+///
+/// for ext in extrinsics.find_any::<InterestingCall>() {
+///     let ext = ext?;
+///     match ext.value {
+///         InterestingCall::Transfer(transfer) => {
+///             println!("Transfer of {} at index {}", transfer.value, ext.details.index())
+///         }
+///         InterestingCall::Remark(remark) => {
+///             println!("Remark of {} bytes at index {}", remark.remark.len(), ext.details.index())
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! find_any {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident for extrinsics {
+            $( $(#[$variant_attr:meta])* $variant:ident($ty:ty) ),+ $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis enum $name {
+            $( $(#[$variant_attr])* $variant($ty), )+
+        }
+
+        impl $crate::blocks::AnyStaticExtrinsic for $name {
+            fn decode_any<T: $crate::config::Config>(
+                details: &$crate::ext::subxt_core::blocks::ExtrinsicDetails<T>,
+            ) -> ::core::result::Result<
+                ::core::option::Option<Self>,
+                $crate::ext::subxt_core::Error,
+            > {
+                $(
+                    if let ::core::option::Option::Some(value) = details.as_extrinsic::<$ty>()? {
+                        return ::core::result::Result::Ok(::core::option::Option::Some(
+                            $name::$variant(value),
+                        ));
+                    }
+                )+
+                ::core::result::Result::Ok(::core::option::Option::None)
+            }
+        }
+    };
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident for events {
+            $( $(#[$variant_attr:meta])* $variant:ident($ty:ty) ),+ $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis enum $name {
+            $( $(#[$variant_attr])* $variant($ty), )+
+        }
+
+        impl $crate::events::AnyStaticEvent for $name {
+            fn decode_any<T: $crate::config::Config>(
+                details: &$crate::events::EventDetails<T>,
+            ) -> ::core::result::Result<
+                ::core::option::Option<Self>,
+                $crate::ext::subxt_core::Error,
+            > {
+                $(
+                    if let ::core::option::Option::Some(value) = details.as_event::<$ty>()? {
+                        return ::core::result::Result::Ok(::core::option::Option::Some(
+                            $name::$variant(value),
+                        ));
+                    }
+                )+
+                ::core::result::Result::Ok(::core::option::Option::None)
+            }
+        }
+    };
+}
+pub use find_any;
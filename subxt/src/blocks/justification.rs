@@ -0,0 +1,432 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::backend::Justification;
+use crate::config::{Config, Header};
+use crate::error::Error;
+use codec::{Decode, DecodeAll, Encode};
+
+/// The consensus engine ID that GRANDPA justifications are tagged with.
+pub const GRANDPA_ENGINE_ID: [u8; 4] = *b"FRNK";
+
+/// A GRANDPA justification, proving that a block has been finalized. This is made up of a
+/// commit signed by enough of the GRANDPA authorities, and the headers needed to show that
+/// each signed precommit is for a descendant of the finalized block.
+///
+/// [`GrandpaJustificationFor`] gives the right generic parameters for some [`Config`].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct GrandpaJustification<Hash, Number, Hdr> {
+    /// The round that the block was finalized in.
+    pub round: u64,
+    /// The commit finalizing the block.
+    pub commit: GrandpaCommit<Hash, Number>,
+    /// The headers of the blocks between the finalized block and the precommit targets.
+    pub votes_ancestries: Vec<Hdr>,
+}
+
+/// The [`GrandpaJustification`] type for some [`Config`].
+pub type GrandpaJustificationFor<T> = GrandpaJustification<
+    <T as Config>::Hash,
+    <<T as Config>::Header as Header>::Number,
+    <T as Config>::Header,
+>;
+
+/// A commit to finalize some block, made up of precommits signed by the GRANDPA authorities.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct GrandpaCommit<Hash, Number> {
+    /// The hash of the block being finalized.
+    pub target_hash: Hash,
+    /// The number of the block being finalized.
+    pub target_number: Number,
+    /// The signed precommits.
+    pub precommits: Vec<GrandpaSignedPrecommit<Hash, Number>>,
+}
+
+/// A precommit for some block, signed by one of the GRANDPA authorities.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct GrandpaSignedPrecommit<Hash, Number> {
+    /// The precommit.
+    pub precommit: GrandpaPrecommit<Hash, Number>,
+    /// The ed25519 signature of the precommit.
+    pub signature: [u8; 64],
+    /// The ed25519 public key of the authority that signed the precommit.
+    pub id: [u8; 32],
+}
+
+/// A vote to finalize some block.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct GrandpaPrecommit<Hash, Number> {
+    /// The hash of the block being voted for.
+    pub target_hash: Hash,
+    /// The number of the block being voted for.
+    pub target_number: Number,
+}
+
+impl<Hash: Decode, Number: Decode, Hdr: Decode> GrandpaJustification<Hash, Number, Hdr> {
+    /// Find the GRANDPA justification in some block justifications (for instance, those
+    /// returned from [`crate::blocks::Block::justifications()`]) and decode it. Returns
+    /// `Ok(None)` if there is no GRANDPA justification.
+    pub fn from_justifications(justifications: &[Justification]) -> Result<Option<Self>, Error> {
+        let Some(justification) = justifications
+            .iter()
+            .find(|j| j.engine_id == GRANDPA_ENGINE_ID)
+        else {
+            return Ok(None);
+        };
+        Ok(Some(Self::decode_all(&mut &*justification.encoded)?))
+    }
+}
+
+crate::macros::cfg_feature! {
+    "grandpa-verify",
+    /// Check that a GRANDPA justification (for instance, one obtained via
+    /// [`GrandpaJustification::from_justifications()`]) finalizes the block with the given
+    /// hash and number, given the GRANDPA authorities and their weights, and the ID of the
+    /// authority set.
+    ///
+    /// This checks that the commit is for the given block, that every precommit is signed by
+    /// one of the authorities and is for the target block or a descendant of it, that every
+    /// header in the votes ancestries is needed to show this, and that authorities with more
+    /// than two thirds of the total weight have signed off on the commit.
+    pub fn verify_grandpa_justification<T: Config>(
+        justification: &GrandpaJustificationFor<T>,
+        block_hash: T::Hash,
+        block_number: u64,
+        set_id: u64,
+        authorities: &[([u8; 32], u64)],
+    ) -> Result<(), Error>
+    where
+        <T::Header as Header>::Number: Encode + Copy,
+    {
+        use sp_core::{ed25519, Pair as _};
+        use std::collections::{HashMap, HashSet};
+
+        // The index of the `Precommit` variant in the GRANDPA message enum that is signed.
+        const PRECOMMIT_MESSAGE_INDEX: u8 = 1;
+
+        let invalid =
+            |reason: &str| Error::Other(format!("Invalid GRANDPA justification: {reason}"));
+        let commit = &justification.commit;
+        let target_number: u64 = commit.target_number.into();
+
+        if commit.target_hash != block_hash || target_number != block_number {
+            return Err(invalid("commit is for a different block"));
+        }
+
+        // The parent hash and number of each header in the ancestry, by its hash.
+        let ancestry: HashMap<T::Hash, (T::Hash, u64)> = justification
+            .votes_ancestries
+            .iter()
+            .map(|header| (header.hash(), (header.parent_hash(), header.number().into())))
+            .collect();
+        let mut used_ancestry = HashSet::new();
+
+        let mut signers = HashSet::new();
+        let mut signed_weight = 0u64;
+        for signed in &commit.precommits {
+            let Some((_, weight)) = authorities.iter().find(|(id, _)| *id == signed.id) else {
+                return Err(invalid("precommit signed by an unknown authority"));
+            };
+
+            let message = (
+                PRECOMMIT_MESSAGE_INDEX,
+                &signed.precommit,
+                justification.round,
+                set_id,
+            )
+                .encode();
+            let signature = ed25519::Signature::from_raw(signed.signature);
+            let public = ed25519::Public::from_raw(signed.id);
+            if !ed25519::Pair::verify(&signature, message, &public) {
+                return Err(invalid("bad precommit signature"));
+            }
+
+            // The precommit target number must match the number of the block it's for.
+            let precommit_hash = signed.precommit.target_hash;
+            let precommit_number: u64 = signed.precommit.target_number.into();
+            let expected_number = if precommit_hash == commit.target_hash {
+                Some(target_number)
+            } else {
+                ancestry.get(&precommit_hash).map(|(_, number)| *number)
+            };
+            if expected_number != Some(precommit_number) {
+                return Err(invalid("precommit target number doesn't match its block"));
+            }
+
+            // Follow the parent hashes back from the precommit target to the commit target.
+            let mut hash = precommit_hash;
+            let mut steps = 0;
+            while hash != commit.target_hash {
+                match ancestry.get(&hash) {
+                    Some((parent_hash, _)) if steps < ancestry.len() => {
+                        used_ancestry.insert(hash);
+                        hash = *parent_hash;
+                        steps += 1;
+                    }
+                    _ => return Err(invalid("precommit is not for a descendant of the target")),
+                }
+            }
+
+            if signers.insert(signed.id) {
+                signed_weight = signed_weight
+                    .checked_add(*weight)
+                    .ok_or_else(|| invalid("authority weights overflow"))?;
+            }
+        }
+
+        if used_ancestry.len() != ancestry.len() {
+            return Err(invalid("votes ancestries contain unused headers"));
+        }
+
+        let total_weight = authorities
+            .iter()
+            .try_fold(0u64, |total, (_, weight)| total.checked_add(*weight))
+            .ok_or_else(|| invalid("authority weights overflow"))?;
+        let threshold = total_weight - total_weight.saturating_sub(1) / 3;
+        if total_weight == 0 || signed_weight < threshold {
+            return Err(invalid("not enough authorities signed the commit"));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::substrate::{BlakeTwo256, Digest, SubstrateHeader, H256};
+    use crate::config::SubstrateConfig;
+
+    type SubstrateJustification = GrandpaJustificationFor<SubstrateConfig>;
+
+    fn header(parent_hash: H256, number: u32) -> SubstrateHeader<u32, BlakeTwo256> {
+        SubstrateHeader {
+            parent_hash,
+            number,
+            state_root: H256::zero(),
+            extrinsics_root: H256::zero(),
+            digest: Digest::default(),
+        }
+    }
+
+    #[test]
+    fn grandpa_justification_blob_can_be_decoded() {
+        let bytes = hex::decode(concat!(
+            // round
+            "2a00000000000000",
+            // commit target hash and number
+            "abababababababababababababababababababababababababababababababab",
+            "07000000",
+            // one signed precommit: target hash, number, signature and id
+            "04",
+            "cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd",
+            "08000000",
+            "0101010101010101010101010101010101010101010101010101010101010101",
+            "0101010101010101010101010101010101010101010101010101010101010101",
+            "0202020202020202020202020202020202020202020202020202020202020202",
+            // one ancestry header: parent hash, compact number, state and
+            // extrinsics roots and an empty digest
+            "04",
+            "abababababababababababababababababababababababababababababababab",
+            "20",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "00",
+        ))
+        .unwrap();
+
+        let justifications = vec![
+            crate::backend::Justification {
+                engine_id: *b"BEEF",
+                encoded: vec![1, 2, 3],
+            },
+            crate::backend::Justification {
+                engine_id: GRANDPA_ENGINE_ID,
+                encoded: bytes,
+            },
+        ];
+
+        let justification = SubstrateJustification::from_justifications(&justifications)
+            .unwrap()
+            .expect("GRANDPA justification should be found");
+
+        assert_eq!(justification.round, 42);
+        assert_eq!(justification.commit.target_hash, H256::repeat_byte(0xab));
+        assert_eq!(justification.commit.target_number, 7);
+        assert_eq!(
+            justification.commit.precommits,
+            vec![GrandpaSignedPrecommit {
+                precommit: GrandpaPrecommit {
+                    target_hash: H256::repeat_byte(0xcd),
+                    target_number: 8,
+                },
+                signature: [1; 64],
+                id: [2; 32],
+            }]
+        );
+        assert_eq!(
+            justification.votes_ancestries,
+            vec![header(H256::repeat_byte(0xab), 8)]
+        );
+
+        // No GRANDPA justification, or trailing bytes in it:
+        assert_eq!(
+            SubstrateJustification::from_justifications(&justifications[..1]).unwrap(),
+            None
+        );
+        let mut too_long = justifications[1].clone();
+        too_long.encoded.push(0);
+        assert!(SubstrateJustification::from_justifications(&[too_long]).is_err());
+    }
+
+    #[cfg(feature = "grandpa-verify")]
+    mod verify {
+        use super::*;
+        use sp_core::{ed25519, Pair};
+
+        const SET_ID: u64 = 3;
+        const ROUND: u64 = 10;
+
+        fn authorities() -> Vec<ed25519::Pair> {
+            (1..=4u8)
+                .map(|n| ed25519::Pair::from_seed(&[n; 32]))
+                .collect()
+        }
+
+        fn weights(pairs: &[ed25519::Pair]) -> Vec<([u8; 32], u64)> {
+            pairs.iter().map(|p| (p.public().0, 1)).collect()
+        }
+
+        /// The block that the justifications are for.
+        fn target() -> SubstrateHeader<u32, BlakeTwo256> {
+            header(H256::repeat_byte(1), 1)
+        }
+
+        /// Build a justification for block 1, where the given authorities
+        /// precommit to its child, block 2, giving it the number provided.
+        fn build_justification_with_number(
+            signers: &[ed25519::Pair],
+            precommit_number: u32,
+        ) -> SubstrateJustification {
+            let target = target();
+            let child = header(target.hash(), 2);
+            let precommit = GrandpaPrecommit {
+                target_hash: child.hash(),
+                target_number: precommit_number,
+            };
+            let message = (1u8, &precommit, ROUND, SET_ID).encode();
+
+            GrandpaJustification {
+                round: ROUND,
+                commit: GrandpaCommit {
+                    target_hash: target.hash(),
+                    target_number: 1,
+                    precommits: signers
+                        .iter()
+                        .map(|pair| GrandpaSignedPrecommit {
+                            precommit: precommit.clone(),
+                            signature: pair.sign(&message).0,
+                            id: pair.public().0,
+                        })
+                        .collect(),
+                },
+                votes_ancestries: vec![child],
+            }
+        }
+
+        fn build_justification(signers: &[ed25519::Pair]) -> SubstrateJustification {
+            build_justification_with_number(signers, 2)
+        }
+
+        fn verify(
+            justification: &SubstrateJustification,
+            set_id: u64,
+            authorities: &[([u8; 32], u64)],
+        ) -> Result<(), Error> {
+            verify_grandpa_justification::<SubstrateConfig>(
+                justification,
+                target().hash(),
+                1,
+                set_id,
+                authorities,
+            )
+        }
+
+        #[test]
+        fn justification_signed_by_enough_authorities_is_valid() {
+            let pairs = authorities();
+            let justification = build_justification(&pairs[..3]);
+
+            verify(&justification, SET_ID, &weights(&pairs)).unwrap();
+
+            // The same justification isn't valid for another authority set:
+            assert!(verify(&justification, SET_ID + 1, &weights(&pairs)).is_err());
+        }
+
+        #[test]
+        fn justifications_for_other_blocks_are_rejected() {
+            let pairs = authorities();
+            let justification = build_justification(&pairs[..3]);
+
+            for (hash, number) in [(H256::repeat_byte(2), 1), (target().hash(), 2)] {
+                assert!(verify_grandpa_justification::<SubstrateConfig>(
+                    &justification,
+                    hash,
+                    number,
+                    SET_ID,
+                    &weights(&pairs),
+                )
+                .is_err());
+            }
+        }
+
+        #[test]
+        fn invalid_justifications_are_rejected() {
+            let pairs = authorities();
+
+            // Two of four authorities isn't enough, and nor is
+            // one authority signing more than once:
+            let justification =
+                build_justification(&[pairs[0].clone(), pairs[1].clone(), pairs[1].clone()]);
+            assert!(verify(&justification, SET_ID, &weights(&pairs)).is_err());
+
+            // Signed by someone that isn't an authority:
+            let justification = build_justification(&pairs[..3]);
+            assert!(verify(&justification, SET_ID, &weights(&pairs[1..])).is_err());
+
+            // Missing the ancestry of the precommit targets:
+            let mut no_ancestry = justification.clone();
+            no_ancestry.votes_ancestries.clear();
+            assert!(verify(&no_ancestry, SET_ID, &weights(&pairs)).is_err());
+
+            // Headers in the ancestry which aren't needed:
+            let mut unused_ancestry = justification.clone();
+            unused_ancestry
+                .votes_ancestries
+                .push(header(H256::repeat_byte(9), 5));
+            assert!(verify(&unused_ancestry, SET_ID, &weights(&pairs)).is_err());
+
+            // Precommits which give the wrong number for their target:
+            let wrong_number = build_justification_with_number(&pairs[..3], 3);
+            assert!(verify(&wrong_number, SET_ID, &weights(&pairs)).is_err());
+
+            // A tampered signature:
+            let mut tampered = justification;
+            tampered.commit.precommits[0].signature[0] ^= 1;
+            assert!(verify(&tampered, SET_ID, &weights(&pairs)).is_err());
+        }
+
+        #[test]
+        fn overflowing_authority_weights_are_rejected() {
+            let pairs = authorities();
+            let justification = build_justification(&pairs[..3]);
+
+            let mut huge_weights = weights(&pairs);
+            for (_, weight) in &mut huge_weights {
+                *weight = u64::MAX / 2;
+            }
+            assert!(verify(&justification, SET_ID, &huge_weights).is_err());
+        }
+    }
+}
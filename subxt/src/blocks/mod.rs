@@ -7,16 +7,33 @@
 mod block_types;
 mod blocks_client;
 mod extrinsic_types;
+mod find_any;
+mod justification;
+mod weight_usage;
 
 /// A reference to a block.
 pub use crate::backend::BlockRef;
 
+pub use crate::backend::{Justification, Justifications};
+
 pub use block_types::Block;
 pub use blocks_client::BlocksClient;
 pub use extrinsic_types::{
-    ExtrinsicDetails, ExtrinsicEvents, ExtrinsicSignedExtension, ExtrinsicSignedExtensions,
-    Extrinsics, FoundExtrinsic, StaticExtrinsic,
+    AnyStaticExtrinsic, DispatchClass, DispatchInfo, ExtrinsicDetails, ExtrinsicEvents,
+    ExtrinsicSignedExtension, ExtrinsicSignedExtensions, Extrinsics, FoundExtrinsic,
+    StaticExtrinsic,
+};
+pub use find_any::find_any;
+pub use justification::{
+    GrandpaCommit, GrandpaJustification, GrandpaJustificationFor, GrandpaPrecommit,
+    GrandpaSignedPrecommit, GRANDPA_ENGINE_ID,
 };
+pub use weight_usage::{BlockWeightUsage, DispatchClassWeightUsage, Weight};
+
+crate::macros::cfg_feature! {
+    "grandpa-verify",
+    pub use justification::verify_grandpa_justification;
+}
 
 // We get account nonce info in tx_client, too, so re-use the logic:
 pub(crate) use block_types::get_account_nonce;
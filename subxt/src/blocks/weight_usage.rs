@@ -0,0 +1,332 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::dynamic::{At, DecodedValue};
+use crate::error::Error;
+use scale_value::{Composite, ValueDef};
+
+pub use crate::utils::Weight;
+
+/// The weight used by a single dispatch class in a block, and the limit for that class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DispatchClassWeightUsage {
+    /// The weight used by this class.
+    pub used: Weight,
+    /// The maximum weight that this class can use, or `None` if it is unlimited.
+    pub max: Option<Weight>,
+}
+
+impl DispatchClassWeightUsage {
+    /// The percentage of the maximum computation time used, or `None` if this class is unlimited.
+    pub fn ref_time_percent(&self) -> Option<f64> {
+        let max = self.max?;
+        Some(percent(self.used.ref_time, max.ref_time))
+    }
+
+    /// The percentage of the maximum proof size used, or `None` if this class is unlimited.
+    pub fn proof_size_percent(&self) -> Option<f64> {
+        let max = self.max?;
+        Some(percent(self.used.proof_size, max.proof_size))
+    }
+}
+
+/// How much of the available weight and length a block uses. This is obtained via
+/// [`crate::blocks::Block::weight_usage()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockWeightUsage {
+    /// Weight used by normal transactions.
+    pub normal: DispatchClassWeightUsage,
+    /// Weight used by operational transactions.
+    pub operational: DispatchClassWeightUsage,
+    /// Weight used by mandatory extrinsics (ie inherents) and hooks.
+    pub mandatory: DispatchClassWeightUsage,
+    /// The maximum weight of the block.
+    pub max_block: Weight,
+    /// The total length in bytes of the extrinsics in the block.
+    pub length: u32,
+    /// The maximum length in bytes of the extrinsics in a block, across all dispatch classes.
+    pub max_length: u32,
+}
+
+impl BlockWeightUsage {
+    /// The total weight used across all dispatch classes. This saturates at `u64::MAX` rather
+    /// than overflowing.
+    pub fn total(&self) -> Weight {
+        let classes = [self.normal, self.operational, self.mandatory];
+        Weight {
+            ref_time: classes
+                .iter()
+                .fold(0u64, |acc, c| acc.saturating_add(c.used.ref_time)),
+            proof_size: classes
+                .iter()
+                .fold(0u64, |acc, c| acc.saturating_add(c.used.proof_size)),
+        }
+    }
+
+    /// The percentage of the maximum block computation time used.
+    pub fn ref_time_percent(&self) -> f64 {
+        percent(self.total().ref_time, self.max_block.ref_time)
+    }
+
+    /// The percentage of the maximum block proof size used.
+    pub fn proof_size_percent(&self) -> f64 {
+        percent(self.total().proof_size, self.max_block.proof_size)
+    }
+
+    /// The percentage of the maximum block length used.
+    pub fn length_percent(&self) -> f64 {
+        percent(self.length as u64, self.max_length as u64)
+    }
+
+    /// Build the usage from the dynamically decoded `System.BlockWeight` storage entry, the
+    /// length of the block body, and the `System.BlockWeights` and `System.BlockLength` constants.
+    pub(crate) fn from_values(
+        block_weight: &DecodedValue,
+        length: u32,
+        block_weights: &DecodedValue,
+        block_length: &DecodedValue,
+    ) -> Result<Self, Error> {
+        let class_usage = |class: &str| -> Result<DispatchClassWeightUsage, Error> {
+            let used = weight_at(block_weight.at(class))?
+                .ok_or_else(|| unexpected_shape("System.BlockWeight", class))?;
+            let max = block_weights
+                .at("per_class")
+                .at(class)
+                .at("max_total")
+                .ok_or_else(|| unexpected_shape("System.BlockWeights", class))?;
+            Ok(DispatchClassWeightUsage {
+                used,
+                max: weight_at(option_value(max))?,
+            })
+        };
+
+        let max_block = weight_at(block_weights.at("max_block"))?
+            .ok_or_else(|| unexpected_shape("System.BlockWeights", "max_block"))?;
+
+        let max_length = ["normal", "operational", "mandatory"]
+            .into_iter()
+            .filter_map(|class| block_length.at("max").at(class).and_then(|v| v.as_u128()))
+            .max()
+            .ok_or_else(|| unexpected_shape("System.BlockLength", "max"))?;
+        let max_length = u32::try_from(max_length).map_err(|_| {
+            Error::Other(format!(
+                "The maximum block length {max_length} does not fit in a u32"
+            ))
+        })?;
+
+        Ok(BlockWeightUsage {
+            normal: class_usage("normal")?,
+            operational: class_usage("operational")?,
+            mandatory: class_usage("mandatory")?,
+            max_block,
+            length,
+            max_length,
+        })
+    }
+}
+
+fn percent(used: u64, max: u64) -> f64 {
+    if max == 0 {
+        0.0
+    } else {
+        used as f64 / max as f64 * 100.0
+    }
+}
+
+fn unexpected_shape(entry: &str, field: &str) -> Error {
+    Error::Other(format!(
+        "Could not find '{field}' in {entry}; the weight types on this chain are not supported"
+    ))
+}
+
+/// Weights are either a single number (weights v1), or a struct containing
+/// a `ref_time` and possibly a `proof_size` (weights v2). They may also be
+/// wrapped in single-field newtypes. Returns `Ok(None)` if the value isn't
+/// shaped like a weight, and an error if it doesn't fit in a [`Weight`].
+pub(crate) fn weight_from_value(value: &DecodedValue) -> Result<Option<Weight>, Error> {
+    if let Some(ref_time) = value.as_u128() {
+        let ref_time = u64::try_from(ref_time)
+            .map_err(|_| Error::Other(format!("The weight {ref_time} does not fit in a u64")))?;
+        return Ok(Some(Weight {
+            ref_time,
+            proof_size: 0,
+        }));
+    }
+    if let Some(ref_time) = value.at("ref_time") {
+        let Some(ref_time) = weight_from_value(ref_time)? else {
+            return Ok(None);
+        };
+        let proof_size = weight_at(value.at("proof_size"))?;
+        return Ok(Some(Weight {
+            ref_time: ref_time.ref_time,
+            proof_size: proof_size.map(|w| w.ref_time).unwrap_or(0),
+        }));
+    }
+    match &value.value {
+        ValueDef::Composite(Composite::Unnamed(vals)) if vals.len() == 1 => {
+            weight_from_value(&vals[0])
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Like [`weight_from_value()`], but for a value which may not be present.
+pub(crate) fn weight_at(value: Option<&DecodedValue>) -> Result<Option<Weight>, Error> {
+    Ok(value.map(weight_from_value).transpose()?.flatten())
+}
+
+/// Return the inner value of an `Option` value, or `None` if it is `None`.
+fn option_value(value: &DecodedValue) -> Option<&DecodedValue> {
+    match &value.value {
+        ValueDef::Variant(v) if v.name == "Some" => v.values.values().next(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scale_value::Value;
+
+    fn weight_v2(ref_time: u64, proof_size: u64) -> Value {
+        Value::named_composite([
+            ("ref_time", Value::u128(ref_time as u128)),
+            ("proof_size", Value::u128(proof_size as u128)),
+        ])
+    }
+
+    fn per_class(normal: Value, operational: Value, mandatory: Value) -> Value {
+        Value::named_composite([
+            ("normal", normal),
+            ("operational", operational),
+            ("mandatory", mandatory),
+        ])
+    }
+
+    fn class_limits(max_total: Option<Value>) -> Value {
+        let max_total = match max_total {
+            Some(w) => Value::variant("Some", Composite::unnamed([w])),
+            None => Value::variant("None", Composite::unnamed([])),
+        };
+        Value::named_composite([("max_total", max_total)])
+    }
+
+    fn decoded(value: Value) -> DecodedValue {
+        value.map_context(|_| 0)
+    }
+
+    #[test]
+    fn decodes_v2_weights_and_computes_percentages() {
+        let block_weight = per_class(weight_v2(100, 10), weight_v2(0, 0), weight_v2(50, 5));
+        let block_weights = Value::named_composite([
+            ("max_block", weight_v2(1000, 100)),
+            (
+                "per_class",
+                per_class(
+                    class_limits(Some(weight_v2(750, 75))),
+                    class_limits(Some(weight_v2(1000, 100))),
+                    class_limits(None),
+                ),
+            ),
+        ]);
+        let block_length = Value::named_composite([(
+            "max",
+            per_class(Value::u128(3_000), Value::u128(4_000), Value::u128(4_000)),
+        )]);
+
+        let usage = BlockWeightUsage::from_values(
+            &decoded(block_weight),
+            256,
+            &decoded(block_weights),
+            &decoded(block_length),
+        )
+        .unwrap();
+
+        assert_eq!(
+            usage.normal.used,
+            Weight {
+                ref_time: 100,
+                proof_size: 10
+            }
+        );
+        assert_eq!(usage.normal.ref_time_percent(), Some(100.0 / 750.0 * 100.0));
+        assert_eq!(usage.mandatory.max, None);
+        assert_eq!(usage.mandatory.ref_time_percent(), None);
+        assert_eq!(
+            usage.total(),
+            Weight {
+                ref_time: 150,
+                proof_size: 15
+            }
+        );
+        assert!((usage.ref_time_percent() - 15.0).abs() < 1e-9);
+        assert_eq!(usage.max_length, 4_000);
+        assert!((usage.length_percent() - 6.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn total_weight_saturates() {
+        let class = |ref_time| DispatchClassWeightUsage {
+            used: Weight {
+                ref_time,
+                proof_size: 1,
+            },
+            max: None,
+        };
+        let usage = BlockWeightUsage {
+            normal: class(u64::MAX),
+            operational: class(1),
+            mandatory: class(1),
+            max_block: Weight {
+                ref_time: u64::MAX,
+                proof_size: u64::MAX,
+            },
+            length: 0,
+            max_length: 0,
+        };
+
+        assert_eq!(
+            usage.total(),
+            Weight {
+                ref_time: u64::MAX,
+                proof_size: 3
+            }
+        );
+    }
+
+    #[test]
+    fn decodes_v1_weights() {
+        let weight = weight_from_value(&decoded(Value::u128(123))).unwrap();
+        assert_eq!(
+            weight,
+            Some(Weight {
+                ref_time: 123,
+                proof_size: 0
+            })
+        );
+
+        // Newtype wrapped weights are fine too:
+        let wrapped = Value::unnamed_composite([Value::u128(456)]);
+        assert_eq!(
+            weight_from_value(&decoded(wrapped)).unwrap(),
+            Some(Weight {
+                ref_time: 456,
+                proof_size: 0
+            })
+        );
+    }
+
+    #[test]
+    fn oversized_weights_are_an_error() {
+        let too_big = Value::u128(u64::MAX as u128 + 1);
+        assert!(weight_from_value(&decoded(too_big)).is_err());
+        assert_eq!(
+            weight_from_value(&decoded(Value::u128(u64::MAX as u128))).unwrap(),
+            Some(Weight {
+                ref_time: u64::MAX,
+                proof_size: 0
+            })
+        );
+    }
+}
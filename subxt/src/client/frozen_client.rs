@@ -0,0 +1,90 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use super::OnlineClient;
+use crate::{
+    blocks::{Block, BlockRef},
+    constants::ConstantsClient,
+    error::Error,
+    events::Events,
+    runtime_api::RuntimeApi,
+    storage::Storage,
+    Config, Metadata,
+};
+use derive_where::derive_where;
+use std::future::Future;
+use subxt_core::client::RuntimeVersion;
+
+/// A snapshot of the chain at a single block, obtained via [`OnlineClient::at()`]
+/// or [`OnlineClient::at_latest()`].
+///
+/// Every storage, event, runtime API and constant lookup made through this handle
+/// uses the same block, and decodes using the metadata that was valid at that block,
+/// so several reads can be made without the results straddling different blocks.
+#[derive_where(Clone, Debug)]
+pub struct FrozenClient<T: Config> {
+    client: OnlineClient<T>,
+    block_ref: BlockRef<T::Hash>,
+}
+
+impl<T: Config> FrozenClient<T> {
+    /// Create a new [`FrozenClient`]. The client is expected to have been
+    /// configured with the metadata and runtime version valid at the block.
+    pub(crate) fn new(client: OnlineClient<T>, block_ref: BlockRef<T::Hash>) -> Self {
+        Self { client, block_ref }
+    }
+
+    /// The reference to the block that this snapshot is pinned to.
+    pub fn block_ref(&self) -> &BlockRef<T::Hash> {
+        &self.block_ref
+    }
+
+    /// The hash of the block that this snapshot is pinned to.
+    pub fn block_hash(&self) -> T::Hash {
+        self.block_ref.hash()
+    }
+
+    /// The [`Metadata`] that was valid at the block.
+    pub fn metadata(&self) -> Metadata {
+        self.client.metadata()
+    }
+
+    /// The [`RuntimeVersion`] that was valid at the block.
+    pub fn runtime_version(&self) -> RuntimeVersion {
+        self.client.runtime_version()
+    }
+
+    /// The underlying client, whose metadata and runtime version are
+    /// those that were valid at the block.
+    pub fn client(&self) -> &OnlineClient<T> {
+        &self.client
+    }
+
+    /// Work with storage at the block.
+    pub fn storage(&self) -> Storage<T, OnlineClient<T>> {
+        self.client.storage().at(self.block_ref.clone())
+    }
+
+    /// Obtain the events emitted at the block.
+    pub fn events(&self) -> impl Future<Output = Result<Events<T>, Error>> + Send + 'static {
+        self.client.events().at(self.block_ref.clone())
+    }
+
+    /// Call runtime APIs at the block.
+    pub fn runtime_api(&self) -> RuntimeApi<T, OnlineClient<T>> {
+        self.client.runtime_api().at(self.block_ref.clone())
+    }
+
+    /// Access the constants that were valid at the block.
+    pub fn constants(&self) -> ConstantsClient<T, OnlineClient<T>> {
+        self.client.constants()
+    }
+
+    /// Obtain the block itself, to work with its header and extrinsics.
+    pub fn block(
+        &self,
+    ) -> impl Future<Output = Result<Block<T, OnlineClient<T>>, Error>> + Send + 'static {
+        self.client.blocks().at(self.block_ref.clone())
+    }
+}
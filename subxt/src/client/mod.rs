@@ -8,11 +8,14 @@
 //! require network access. The [`OnlineClient`] requires network
 //! access.
 
+mod frozen_client;
 mod offline_client;
 mod online_client;
 
+pub use frozen_client::FrozenClient;
 pub use offline_client::{OfflineClient, OfflineClientT};
 pub use online_client::{
-    ClientRuntimeUpdater, OnlineClient, OnlineClientT, RuntimeUpdaterStream, Update, UpgradeError,
+    ClientRuntimeUpdater, OnlineClient, OnlineClientT, RuntimeUpdaterStream, RuntimeUpgrade,
+    Update, UpgradeError,
 };
 pub use subxt_core::client::{ClientState, RuntimeVersion};
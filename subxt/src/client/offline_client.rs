@@ -4,7 +4,7 @@
 
 use crate::custom_values::CustomValuesClient;
 use crate::{
-    blocks::BlocksClient, constants::ConstantsClient, events::EventsClient,
+    blocks::BlocksClient, config::Hasher, constants::ConstantsClient, events::EventsClient,
     runtime_api::RuntimeApiClient, storage::StorageClient, tx::TxClient, Config, Metadata,
 };
 
@@ -110,6 +110,13 @@ impl<T: Config> OfflineClient<T> {
         self.inner.metadata.clone()
     }
 
+    /// Calculate the hash of some signed and SCALE encoded transaction bytes using the
+    /// configured [`Config::Hasher`]. This is the same hash that the transaction will be
+    /// reported with once it's submitted, and so can be used to look it up later.
+    pub fn extrinsic_hash(&self, tx_bytes: &[u8]) -> T::Hash {
+        <T::Hasher as Hasher>::hash(tx_bytes)
+    }
+
     // Just a copy of the most important trait methods so that people
     // don't need to import the trait for most things:
 
@@ -2,21 +2,27 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
-use super::{OfflineClient, OfflineClientT};
+use super::{FrozenClient, OfflineClient, OfflineClientT};
 use crate::custom_values::CustomValuesClient;
 use crate::{
-    backend::{legacy::LegacyBackend, rpc::RpcClient, Backend, BackendExt, StreamOfResults},
+    backend::{
+        legacy::LegacyBackend,
+        rpc::{ConnectionEvent, RpcClient},
+        Backend, BackendExt, StreamOfResults,
+    },
     blocks::{BlockRef, BlocksClient},
+    config::Header,
     constants::ConstantsClient,
-    error::Error,
+    error::{BlockError, Error, RpcError},
     events::EventsClient,
     runtime_api::RuntimeApiClient,
-    storage::StorageClient,
+    storage::{AccountBalance, StorageClient},
     tx::TxClient,
     Config, Metadata,
 };
 use derive_where::derive_where;
-use futures::future;
+use futures::{future, Stream, StreamExt};
+use std::future::Future;
 use std::sync::{Arc, RwLock};
 use subxt_core::client::{ClientState, RuntimeVersion};
 
@@ -140,6 +146,12 @@ impl<T: Config> OnlineClient<T> {
     ///
     /// If you're unsure what you're doing, prefer one of the alternate methods to instantiate
     /// a client.
+    ///
+    /// This can be used to provide metadata which has already been obtained or modified (for
+    /// instance, via [`crate::metadata::types::Metadata::retain`]), rather than fetching it
+    /// again. The metadata must be compatible with the node that the backend is connected to,
+    /// or else encoding and decoding things like storage entries, events and transactions
+    /// will likely fail.
     pub fn from_backend_with<B: Backend<T>>(
         genesis_hash: T::Hash,
         runtime_version: RuntimeVersion,
@@ -246,12 +258,96 @@ impl<T: Config> OnlineClient<T> {
         ClientRuntimeUpdater(self.clone())
     }
 
+    /// Obtain a [`FrozenClient`] snapshot of the chain at the given block. Every storage, event,
+    /// runtime API and constant lookup made through it will use this block, and the metadata
+    /// and runtime version that were valid at it, rather than those currently in use.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use subxt::{ OnlineClient, PolkadotConfig };
+    ///
+    /// let client = OnlineClient::<PolkadotConfig>::new().await.unwrap();
+    ///
+    /// let block_ref = client.backend().latest_finalized_block_ref().await.unwrap();
+    /// let snapshot = client.at(block_ref).await.unwrap();
+    ///
+    /// // Both of these lookups are guaranteed to be made at the same block:
+    /// let storage = snapshot.storage();
+    /// let now = subxt::dynamic::storage("Timestamp", "Now", ());
+    /// let number = subxt::dynamic::storage("System", "Number", ());
+    /// let now = storage.fetch(&now).await.unwrap();
+    /// let number = storage.fetch(&number).await.unwrap();
+    /// # }
+    /// ```
+    pub fn at(
+        &self,
+        block_ref: impl Into<BlockRef<T::Hash>>,
+    ) -> impl Future<Output = Result<FrozenClient<T>, Error>> + Send + 'static {
+        let client = self.clone();
+        let block_ref = block_ref.into();
+        async move {
+            let backend = &*client.backend;
+            let runtime_version: RuntimeVersionAt = backend
+                .call_decoding("Core_version", None, block_ref.hash())
+                .await?;
+            let runtime_version = RuntimeVersion {
+                spec_version: runtime_version.spec_version,
+                transaction_version: runtime_version.transaction_version,
+            };
+
+            // Only fetch the metadata again if the runtime differs from the current one.
+            let metadata = if runtime_version == client.runtime_version() {
+                client.metadata()
+            } else {
+                OnlineClient::fetch_metadata(backend, block_ref.hash()).await?
+            };
+
+            let frozen = OnlineClient {
+                inner: Arc::new(RwLock::new(Inner {
+                    genesis_hash: client.genesis_hash(),
+                    runtime_version,
+                    metadata,
+                })),
+                backend: client.backend.clone(),
+            };
+            Ok(FrozenClient::new(frozen, block_ref))
+        }
+    }
+
+    /// Obtain a [`FrozenClient`] snapshot of the chain at the latest finalized block. This
+    /// resolves the latest finalized block once, so that several storage, event, runtime API
+    /// and constant lookups can then be made at it without looking up the latest block again
+    /// for each one. See [`OnlineClient::at()`] for more.
+    pub fn at_latest(
+        &self,
+    ) -> impl Future<Output = Result<FrozenClient<T>, Error>> + Send + 'static {
+        let client = self.clone();
+        async move {
+            let block_ref = client.backend.latest_finalized_block_ref().await?;
+            client.at(block_ref).await
+        }
+    }
+
     /// Return the [`Metadata`] used in this client.
     pub fn metadata(&self) -> Metadata {
         let inner = self.inner.read().expect("shouldn't be poisoned");
         inner.metadata.clone()
     }
 
+    /// Fetch the metadata at the given version from the latest finalized block, by calling the
+    /// `Metadata_metadata_at_version` runtime API. This returns `None` if the runtime does not
+    /// provide metadata at that version. The metadata used by this client is not changed; see
+    /// [`OnlineClient::set_metadata()`] to do that.
+    pub async fn metadata_at_version(&self, version: u32) -> Result<Option<Metadata>, Error> {
+        let block_ref = self.backend.latest_finalized_block_ref().await?;
+        self.backend
+            .try_metadata_at_version(version, block_ref.hash())
+            .await
+    }
+
     /// Change the [`Metadata`] used in this client.
     ///
     /// # Warning
@@ -302,7 +398,71 @@ impl<T: Config> OnlineClient<T> {
         &*self.backend
     }
 
-    /// Return an offline client with the same configuration as this.
+    /// Subscribe to changes in the state of the connection to the node, so that you can react
+    /// to it being lost or re-established.
+    ///
+    /// [`ConnectionEvent::Reconnecting`] and [`ConnectionEvent::Connected`] are only emitted when
+    /// using the reconnecting RPC client. Without it, only [`ConnectionEvent::Disconnected`] may
+    /// be emitted. See [`RpcClient::connection_events()`] for more.
+    pub fn connection_events(&self) -> impl Stream<Item = ConnectionEvent> + Send + 'static {
+        self.backend.connection_events()
+    }
+
+    /// Subscribe to runtime upgrades. Each item describes a change in the runtime version, along
+    /// with a finalized block that the new runtime is in use at. Unlike
+    /// [`ClientRuntimeUpdater::runtime_updates()`], items are only emitted when the runtime
+    /// version actually changes.
+    ///
+    /// The metadata and runtime version used by this client are updated to match the new runtime
+    /// before each upgrade is handed back, so there is no need to also run
+    /// [`ClientRuntimeUpdater::perform_runtime_updates()`].
+    pub async fn subscribe_runtime_upgrades(
+        &self,
+    ) -> Result<impl Stream<Item = Result<RuntimeUpgrade<T>, Error>> + Send + 'static, Error> {
+        let versions = self.backend.stream_runtime_version().await?;
+        let client = self.clone();
+
+        let upgrades =
+            runtime_version_changes(versions, self.runtime_version()).then(move |change| {
+                let client = client.clone();
+                async move {
+                    let (old_version, new_version) = change?;
+                    let finalized_block_ref =
+                        wait_runtime_upgrade_in_finalized_block(&client, &new_version)
+                            .await
+                            .unwrap_or_else(|| {
+                                Err(Error::Other(
+                                    "Finalized block subscription ended unexpectedly".into(),
+                                ))
+                            })?;
+
+                    let metadata =
+                        OnlineClient::fetch_metadata(client.backend(), finalized_block_ref.hash())
+                            .await?;
+                    client.updater().do_update(Update {
+                        metadata,
+                        runtime_version: new_version,
+                    });
+
+                    Ok(RuntimeUpgrade {
+                        old_version,
+                        new_version,
+                        finalized_block_ref,
+                    })
+                }
+            });
+
+        Ok(upgrades)
+    }
+
+    /// Return an offline client with the same configuration as this. This is a cheap snapshot
+    /// of the metadata, genesis hash and runtime version currently in use by this client, and
+    /// can be used to do things like sign many transactions without making any network calls.
+    ///
+    /// # Note
+    ///
+    /// The offline client does not see any runtime upgrades applied to this client after it
+    /// has been created, and so it may need to be obtained again once one has taken place.
     pub fn offline(&self) -> OfflineClient<T> {
         let inner = self.inner.read().expect("shouldn't be poisoned");
         OfflineClient::new(
@@ -312,6 +472,51 @@ impl<T: Config> OnlineClient<T> {
         )
     }
 
+    /// Wait until a block whose number is at least `number` has been finalized, returning a
+    /// reference to the first such block seen. If a block this high has already been finalized,
+    /// this returns the current finalized block.
+    pub async fn wait_for_finalized_block(&self, number: u64) -> Result<BlockRef<T::Hash>, Error> {
+        let mut block_sub = self.backend.stream_finalized_block_headers().await?;
+        while let Some(block) = block_sub.next().await {
+            let (header, block_ref) = block?;
+            let block_number: u64 = header.number().into();
+            if block_number >= number {
+                return Ok(block_ref);
+            }
+        }
+        Err(RpcError::SubscriptionDropped.into())
+    }
+
+    /// Like [`OnlineClient::wait_for_finalized_block()`], but gives up with a
+    /// [`BlockError::Timeout`] error if the `timeout` future completes first. Subxt doesn't rely
+    /// on any particular async runtime, so the timeout is given as a future, for instance
+    /// `tokio::time::sleep(Duration::from_secs(30))`.
+    pub async fn wait_for_finalized_block_with_timeout(
+        &self,
+        number: u64,
+        timeout: impl Future<Output = ()>,
+    ) -> Result<BlockRef<T::Hash>, Error> {
+        let wait = std::pin::pin!(self.wait_for_finalized_block(number));
+        let timeout = std::pin::pin!(timeout);
+        match future::select(wait, timeout).await {
+            future::Either::Left((res, _)) => res,
+            future::Either::Right(_) => Err(BlockError::Timeout { number }.into()),
+        }
+    }
+
+    /// Fetch the free, reserved and frozen balances of an account at the latest finalized block.
+    /// See [`crate::storage::Storage::account_balance()`] for more details.
+    pub async fn account_balance(
+        &self,
+        account_id: &T::AccountId,
+    ) -> Result<AccountBalance, Error> {
+        self.storage()
+            .at_latest()
+            .await?
+            .account_balance(account_id)
+            .await
+    }
+
     // Just a copy of the most important trait methods so that people
     // don't need to import the trait for most things:
 
@@ -497,6 +702,67 @@ impl Update {
     }
 }
 
+/// A runtime upgrade, as returned from [`OnlineClient::subscribe_runtime_upgrades()`].
+#[derive_where(Clone)]
+pub struct RuntimeUpgrade<T: Config> {
+    old_version: RuntimeVersion,
+    new_version: RuntimeVersion,
+    finalized_block_ref: BlockRef<T::Hash>,
+}
+
+impl<T: Config> RuntimeUpgrade<T> {
+    /// The runtime version prior to the upgrade.
+    pub fn old_version(&self) -> RuntimeVersion {
+        self.old_version
+    }
+
+    /// The runtime version after the upgrade.
+    pub fn new_version(&self) -> RuntimeVersion {
+        self.new_version
+    }
+
+    /// The first block finalized after the upgrade was noticed which uses the new runtime.
+    /// The upgrade was enacted in this block or in one of its ancestors, so this is not
+    /// necessarily the block that the upgrade happened in.
+    pub fn finalized_block_ref(&self) -> &BlockRef<T::Hash> {
+        &self.finalized_block_ref
+    }
+}
+
+/// Given a stream of runtime versions, emit the old and new version each time that
+/// the version differs from the previous one, starting from the `current` version.
+fn runtime_version_changes<S>(
+    versions: S,
+    current: RuntimeVersion,
+) -> impl Stream<Item = Result<(RuntimeVersion, RuntimeVersion), Error>>
+where
+    S: Stream<Item = Result<RuntimeVersion, Error>>,
+{
+    versions
+        .scan(current, |last, version| {
+            let change = match version {
+                Ok(version) if version == *last => None,
+                Ok(version) => Some(Ok((std::mem::replace(last, version), version))),
+                Err(e) => Some(Err(e)),
+            };
+            future::ready(Some(change))
+        })
+        .filter_map(future::ready)
+}
+
+/// The start of the runtime version returned from the `Core_version` runtime API;
+/// the remaining fields are ignored.
+#[derive(codec::Decode)]
+struct RuntimeVersionAt {
+    _spec_name: String,
+    _impl_name: String,
+    _authoring_version: u32,
+    spec_version: u32,
+    _impl_version: u32,
+    _apis: Vec<([u8; 8], u32)>,
+    transaction_version: u32,
+}
+
 /// Helper to wait until the runtime upgrade is applied on at finalized block.
 async fn wait_runtime_upgrade_in_finalized_block<T: Config>(
     client: &OnlineClient<T>,
@@ -552,3 +818,201 @@ async fn wait_runtime_upgrade_in_finalized_block<T: Config>(
 
     Some(Ok(block_ref))
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backend::rpc::mock_rpc_client::MockRpcClient;
+    use crate::config::PolkadotConfig;
+    use crate::utils::to_hex;
+
+    const FINALIZED_HASH: [u8; 32] = [1u8; 32];
+    const BEST_HASH: [u8; 32] = [2u8; 32];
+
+    /// A client which reports different hashes for the latest finalized and best blocks.
+    fn mock_rpc_client() -> MockRpcClient {
+        MockRpcClient::new().on_request(|method, _params| {
+            Ok(match method {
+                "chain_getFinalizedHead" => serde_json::json!(to_hex(FINALIZED_HASH)),
+                "chain_getBlockHash" => serde_json::json!(to_hex(BEST_HASH)),
+                "state_call" => serde_json::json!("0x00"),
+                _ => serde_json::Value::Null,
+            })
+        })
+    }
+
+    fn assert_called_at(rpc_client: &MockRpcClient, method: &str, hash: [u8; 32]) {
+        let (last_method, last_params) = rpc_client.calls().pop().unwrap();
+        assert_eq!(last_method, method);
+        assert!(last_params.to_string().contains(&hex::encode(hash)));
+    }
+
+    #[tokio::test]
+    async fn latest_finalized_and_best_blocks_are_distinguished() {
+        let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        let rpc_client = mock_rpc_client();
+        let client = OnlineClient::<PolkadotConfig>::from_rpc_client_with(
+            [7u8; 32].into(),
+            RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 2,
+            },
+            subxt_core::metadata::decode_from(&metadata_bytes[..]).unwrap(),
+            RpcClient::new(rpc_client.clone()),
+        )
+        .unwrap();
+
+        // Storage:
+        let storage = client.storage().at_latest_finalized().await.unwrap();
+        storage.fetch_raw(vec![1, 2, 3]).await.unwrap();
+        assert_called_at(&rpc_client, "state_getStorage", FINALIZED_HASH);
+
+        let storage = client.storage().at_latest_best().await.unwrap();
+        storage.fetch_raw(vec![1, 2, 3]).await.unwrap();
+        assert_called_at(&rpc_client, "state_getStorage", BEST_HASH);
+
+        // Events:
+        client.events().at_latest_finalized().await.unwrap();
+        assert_called_at(&rpc_client, "state_getStorage", FINALIZED_HASH);
+
+        client.events().at_latest_best().await.unwrap();
+        assert_called_at(&rpc_client, "state_getStorage", BEST_HASH);
+
+        // Runtime APIs:
+        let runtime_api = client.runtime_api().at_latest_finalized().await.unwrap();
+        runtime_api
+            .call_raw::<Vec<u8>>("Core_version", None)
+            .await
+            .unwrap();
+        assert_called_at(&rpc_client, "state_call", FINALIZED_HASH);
+
+        let runtime_api = client.runtime_api().at_latest_best().await.unwrap();
+        runtime_api
+            .call_raw::<Vec<u8>>("Core_version", None)
+            .await
+            .unwrap();
+        assert_called_at(&rpc_client, "state_call", BEST_HASH);
+
+        // `at_latest()` means the latest finalized block:
+        let storage = client.storage().at_latest().await.unwrap();
+        storage.fetch_raw(vec![1, 2, 3]).await.unwrap();
+        assert_called_at(&rpc_client, "state_getStorage", FINALIZED_HASH);
+    }
+
+    #[tokio::test]
+    async fn runtime_upgrades_are_only_emitted_on_change() {
+        let version = |spec_version| RuntimeVersion {
+            spec_version,
+            transaction_version: 1,
+        };
+
+        // The runtime version subscription emits the current version first, and may
+        // emit the same version again, but only an actual change is an upgrade:
+        let versions = futures::stream::iter([
+            Ok(version(1)),
+            Ok(version(1)),
+            Ok(version(2)),
+            Ok(version(2)),
+        ]);
+
+        let changes: Vec<_> = runtime_version_changes(versions, version(1))
+            .collect()
+            .await;
+
+        assert_eq!(changes.len(), 1);
+        let (old, new) = changes[0].as_ref().unwrap();
+        assert_eq!(old.spec_version, 1);
+        assert_eq!(new.spec_version, 2);
+    }
+
+    #[tokio::test]
+    async fn runtime_upgrades_refresh_the_client_metadata() {
+        use codec::{Compact, Encode};
+
+        let small_metadata_bytes =
+            include_bytes!("../../../artifacts/polkadot_metadata_small.scale");
+        let full_metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+
+        let zero_hash = to_hex([0u8; 32]);
+        let header = {
+            let zero_hash = zero_hash.clone();
+            move |n: u64| {
+                serde_json::json!({
+                    "parentHash": zero_hash,
+                    "number": format!("0x{n:x}"),
+                    "stateRoot": zero_hash,
+                    "extrinsicsRoot": zero_hash,
+                    "digest": { "logs": [] }
+                })
+            }
+        };
+        let runtime_version = |spec_version: u32| {
+            serde_json::json!({
+                "specVersion": spec_version,
+                "transactionVersion": 1
+            })
+        };
+
+        // The runtime is upgraded to spec version 2, which is in use at finalized block 1:
+        let last_runtime_upgrade = (Compact(2u32), "polkadot".to_string()).encode();
+        let metadata_at_version = Some(full_metadata_bytes.to_vec()).encode();
+        let rpc_client = MockRpcClient::new()
+            .on_request({
+                let header = header.clone();
+                move |method, _params| {
+                    Ok(match method {
+                        "chain_getFinalizedHead" => serde_json::json!(zero_hash),
+                        "chain_getHeader" => header(0),
+                        "state_getStorage" => serde_json::json!(to_hex(&last_runtime_upgrade)),
+                        "state_call" => serde_json::json!(to_hex(&metadata_at_version)),
+                        _ => serde_json::Value::Null,
+                    })
+                }
+            })
+            .on_subscribe({
+                let header = header.clone();
+                move |method, _params| {
+                    Ok(match method {
+                        "state_subscribeRuntimeVersion" => {
+                            vec![runtime_version(1), runtime_version(2)]
+                        }
+                        "chain_subscribeFinalizedHeads" => vec![header(1)],
+                        _ => vec![],
+                    })
+                }
+            });
+
+        let client = OnlineClient::<PolkadotConfig>::from_rpc_client_with(
+            [7u8; 32].into(),
+            RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 1,
+            },
+            subxt_core::metadata::decode_from(&small_metadata_bytes[..]).unwrap(),
+            RpcClient::new(rpc_client),
+        )
+        .unwrap();
+
+        let mut upgrades = Box::pin(client.subscribe_runtime_upgrades().await.unwrap());
+        let upgrade = upgrades.next().await.unwrap().unwrap();
+
+        let header1: <PolkadotConfig as Config>::Header =
+            serde_json::from_value(header(1)).unwrap();
+        assert_eq!(upgrade.old_version().spec_version, 1);
+        assert_eq!(upgrade.new_version().spec_version, 2);
+        assert_eq!(upgrade.finalized_block_ref().hash(), header1.hash());
+
+        // The client now uses the new runtime version and metadata:
+        let full_metadata = subxt_core::metadata::decode_from(&full_metadata_bytes[..]).unwrap();
+        let small_metadata = subxt_core::metadata::decode_from(&small_metadata_bytes[..]).unwrap();
+        assert_ne!(
+            full_metadata.pallets().count(),
+            small_metadata.pallets().count()
+        );
+        assert_eq!(client.runtime_version().spec_version, 2);
+        assert_eq!(
+            client.metadata().pallets().count(),
+            full_metadata.pallets().count()
+        );
+    }
+}
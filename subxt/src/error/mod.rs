@@ -77,6 +77,13 @@ pub enum Error {
     #[cfg_attr(docsrs, doc(cfg(feature = "unstable-light-client")))]
     #[error("An error occurred but it could not be decoded: {0}")]
     LightClient(#[from] LightClientError),
+    /// The node was unable to obtain the runtime at some block, and so
+    /// we could not find out its version.
+    #[error("The runtime is invalid: {message}")]
+    InvalidRuntime {
+        /// The reason given by the node.
+        message: String,
+    },
     /// Other error.
     #[error("Other error: {0}")]
     Other(String),
@@ -130,6 +137,11 @@ impl Error {
     pub fn is_rejected(&self) -> bool {
         matches!(self, Error::Rpc(RpcError::RequestRejected(_)))
     }
+
+    /// Checks whether the error was caused by the node not supporting an RPC method.
+    pub fn is_method_not_supported(&self) -> bool {
+        matches!(self, Error::Rpc(RpcError::MethodNotSupported { .. }))
+    }
 }
 
 /// An RPC error. Since we are generic over the RPC client that is used,
@@ -155,6 +167,20 @@ pub enum RpcError {
     /// The connection was lost and automatically reconnected.
     #[error("RPC error: the connection was lost `{0}`; reconnect automatically initiated")]
     DisconnectedWillReconnect(String),
+    /// The node does not support the RPC method that was called, either because it
+    /// does not implement it or because it has been disabled (as is common for "unsafe"
+    /// RPC methods on public endpoints).
+    #[error("RPC error: the node does not support the method `{method}`: {reason}")]
+    MethodNotSupported {
+        /// The RPC method that was called.
+        method: String,
+        /// The reason given by the node.
+        reason: String,
+    },
+    /// The RPC method is not in the allow-list of a
+    /// [`crate::backend::rpc::RestrictedRpcClient`], and so it was not called.
+    #[error("RPC error: the method `{0}` is not allowed")]
+    MethodNotAllowed(String),
 }
 
 impl RpcError {
@@ -171,6 +197,27 @@ pub enum BlockError {
     /// An error containing the hash of the block that was not found.
     #[error("Could not find a block with hash {0} (perhaps it was on a non-finalized fork?)")]
     NotFound(String),
+    /// A block with the given number has not been finalized yet.
+    #[error("Block number {number} has not been finalized yet (latest finalized block number is {finalized})")]
+    NotFinalized {
+        /// The block number that was asked for.
+        number: u64,
+        /// The latest finalized block number.
+        finalized: u64,
+    },
+    /// The block with the given number can't be looked up, because the node doesn't
+    /// retain it or it is too old to be found.
+    #[error("Cannot look up block number {number}; the node does not retain it, or it is too old to be found")]
+    NotRetained {
+        /// The block number that was asked for.
+        number: u64,
+    },
+    /// Timed out waiting for a block with the given number to be finalized.
+    #[error("Timed out waiting for block number {number} to be finalized")]
+    Timeout {
+        /// The block number that was waited for.
+        number: u64,
+    },
     /// Extrinsic type ID cannot be resolved with the provided metadata.
     #[error("Extrinsic type ID cannot be resolved with the provided metadata. Make sure this is a valid metadata")]
     MissingType,
@@ -196,8 +243,7 @@ impl From<CoreBlockError> for BlockError {
 impl BlockError {
     /// Produce an error that a block with the given hash cannot be found.
     pub fn not_found(hash: impl AsRef<[u8]>) -> BlockError {
-        let hash = format!("0x{}", hex::encode(hash));
-        BlockError::NotFound(hash)
+        BlockError::NotFound(crate::utils::to_hex(hash))
     }
 }
 
@@ -218,4 +264,12 @@ pub enum TransactionError {
     /// The transaction was dropped.
     #[error("The transaction was dropped: {0}")]
     Dropped(String),
+    /// The encoded transaction is longer than the chain will accept.
+    #[error("The transaction is {len} bytes long, but at most {max_len} bytes are allowed")]
+    TooLarge {
+        /// The length of the encoded transaction in bytes.
+        len: usize,
+        /// The maximum length allowed.
+        max_len: usize,
+    },
 }
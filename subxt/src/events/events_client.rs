@@ -3,7 +3,9 @@
 // see LICENSE for license details.
 
 use crate::backend::{Backend, BackendExt, BlockRef};
-use crate::{client::OnlineClientT, error::Error, events::Events, Config};
+use crate::config::Header;
+use crate::error::BlockError;
+use crate::{client::OnlineClientT, error::Error, events::Events, Config, Metadata};
 use derive_where::derive_where;
 use std::future::Future;
 
@@ -43,11 +45,77 @@ where
         self.at_or_latest(Some(block_ref.into()))
     }
 
-    /// Obtain events for the latest block.
+    /// Obtain events at the finalized block with the given block number.
+    ///
+    /// A [`BlockError::NotFinalized`] error is returned if the block has not been finalized yet,
+    /// and a [`BlockError::NotRetained`] error if the backend is unable to find the hash of a
+    /// block with this number. The
+    /// [`crate::backend::legacy::LegacyBackend`] can look up any finalized block, whereas the
+    /// [`crate::backend::unstable::UnstableBackend`] can only look up blocks that are still
+    /// pinned (or any finalized block, if the node supports the `archive` RPC methods).
+    ///
+    /// # Warning
+    ///
+    /// As with [`Self::at()`], this call only supports blocks produced since the most
+    /// recent runtime upgrade.
+    pub fn at_number(
+        &self,
+        number: u64,
+    ) -> impl Future<Output = Result<Events<T>, Error>> + Send + 'static {
+        let client = self.client.clone();
+        async move {
+            let backend = client.backend();
+            let block_hash = match backend.finalized_block_hash_at_number(number).await? {
+                Some(hash) => hash,
+                None => {
+                    // Distinguish blocks which aren't finalized yet from those we can't find.
+                    let finalized_ref = backend.latest_finalized_block_ref().await?;
+                    let finalized_number: u64 =
+                        match backend.block_header(finalized_ref.hash()).await? {
+                            Some(header) => header.number().into(),
+                            None => return Err(BlockError::not_found(finalized_ref.hash()).into()),
+                        };
+                    if number > finalized_number {
+                        return Err(BlockError::NotFinalized {
+                            number,
+                            finalized: finalized_number,
+                        }
+                        .into());
+                    }
+                    return Err(BlockError::NotRetained { number }.into());
+                }
+            };
+
+            get_events(backend, block_hash, client.metadata()).await
+        }
+    }
+
+    /// Obtain events for the latest finalized block. This is the same as
+    /// [`EventsClient::at_latest_finalized()`]. Use [`crate::OnlineClient::at_latest()`]
+    /// to make several lookups at the same latest block without fetching it each time.
     pub fn at_latest(&self) -> impl Future<Output = Result<Events<T>, Error>> + Send + 'static {
         self.at_or_latest(None)
     }
 
+    /// Obtain events for the latest finalized block.
+    pub fn at_latest_finalized(
+        &self,
+    ) -> impl Future<Output = Result<Events<T>, Error>> + Send + 'static {
+        self.at_or_latest(None)
+    }
+
+    /// Obtain events for the latest best block. Prefer [`EventsClient::at_latest_finalized()`]
+    /// unless you need the most recent events, since the best block may not end up being finalized.
+    pub fn at_latest_best(
+        &self,
+    ) -> impl Future<Output = Result<Events<T>, Error>> + Send + 'static {
+        let client = self.client.clone();
+        async move {
+            let block_ref = client.backend().latest_best_block_ref().await?;
+            get_events(client.backend(), block_ref.hash(), client.metadata()).await
+        }
+    }
+
     /// Obtain events at some block hash.
     fn at_or_latest(
         &self,
@@ -63,24 +131,34 @@ where
                 None => client.backend().latest_finalized_block_ref().await?,
             };
 
-            let event_bytes = get_event_bytes(client.backend(), block_ref.hash()).await?;
-            Ok(Events::decode_from(event_bytes, client.metadata()))
+            get_events(client.backend(), block_ref.hash(), client.metadata()).await
         }
     }
 }
 
 // The storage key needed to access events.
 fn system_events_key() -> [u8; 32] {
-    let a = sp_crypto_hashing::twox_128(b"System");
-    let b = sp_crypto_hashing::twox_128(b"Events");
+    let a = crate::utils::hashing::twox_128(b"System");
+    let b = crate::utils::hashing::twox_128(b"Events");
     let mut res = [0; 32];
     res[0..16].clone_from_slice(&a);
     res[16..32].clone_from_slice(&b);
     res
 }
 
+// Fetch and decode the events at the provided block hash. All of the ways to obtain events
+// go through this, so that events are fetched in the same way regardless of the backend.
+pub(crate) async fn get_events<T: Config>(
+    backend: &dyn Backend<T>,
+    block_hash: T::Hash,
+    metadata: Metadata,
+) -> Result<Events<T>, Error> {
+    let event_bytes = get_event_bytes(backend, block_hash).await?;
+    Ok(Events::decode_from(event_bytes, metadata))
+}
+
 // Get the event bytes from the provided client, at the provided block hash.
-pub(crate) async fn get_event_bytes<T: Config>(
+async fn get_event_bytes<T: Config>(
     backend: &dyn Backend<T>,
     block_hash: T::Hash,
 ) -> Result<Vec<u8>, Error> {
@@ -89,3 +167,54 @@ pub(crate) async fn get_event_bytes<T: Config>(
         .await?
         .unwrap_or_default())
 }
+
+#[cfg(test)]
+mod test {
+    use crate::backend::rpc::{mock_rpc_client::MockRpcClient, RpcClient};
+    use crate::client::OnlineClient;
+    use crate::config::PolkadotConfig;
+    use crate::error::{BlockError, Error};
+
+    #[tokio::test]
+    async fn at_number_errors_if_the_block_hash_is_not_found() {
+        // The latest finalized block is #5, but the node can't find the hash of #3:
+        let zero_hash = format!("0x{}", "00".repeat(32));
+        let rpc_client = MockRpcClient::new()
+            .with_response("chain_getFinalizedHead", serde_json::json!(zero_hash))
+            .with_response(
+                "chain_getHeader",
+                serde_json::json!({
+                    "parentHash": zero_hash,
+                    "number": "0x5",
+                    "stateRoot": zero_hash,
+                    "extrinsicsRoot": zero_hash,
+                    "digest": { "logs": [] }
+                }),
+            )
+            .with_response("chain_getBlockHash", serde_json::Value::Null);
+
+        let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_small.scale");
+        let client = OnlineClient::<PolkadotConfig>::from_rpc_client_with(
+            [7u8; 32].into(),
+            subxt_core::client::RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 2,
+            },
+            subxt_core::metadata::decode_from(&metadata_bytes[..]).unwrap(),
+            RpcClient::new(rpc_client),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            client.events().at_number(3).await,
+            Err(Error::Block(BlockError::NotRetained { number: 3 }))
+        ));
+        assert!(matches!(
+            client.events().at_number(6).await,
+            Err(Error::Block(BlockError::NotFinalized {
+                number: 6,
+                finalized: 5
+            }))
+        ));
+    }
+}
@@ -11,7 +11,9 @@ use subxt_core::{Config, Metadata};
 
 mod events_client;
 pub use events_client::EventsClient;
-pub use subxt_core::events::{EventDetails, Events, Phase, StaticEvent};
+pub use subxt_core::events::{
+    AnyStaticEvent, EventDetails, Events, GroupedEvents, Phase, StaticEvent,
+};
 
 /// Creates a new [`Events`] instance by fetching the corresponding bytes at `block_hash` from the client.
 pub async fn new_events_from_client<T, C>(
@@ -23,6 +25,5 @@ where
     T: Config,
     C: OnlineClientT<T>,
 {
-    let event_bytes = events_client::get_event_bytes(client.backend(), block_hash).await?;
-    Ok(Events::<T>::decode_from(event_bytes, metadata))
+    events_client::get_events(client.backend(), block_hash, metadata).await
 }
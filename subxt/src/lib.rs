@@ -71,7 +71,10 @@ pub mod config {
 
 /// Types representing the metadata obtained from a node.
 pub mod metadata {
-    pub use subxt_core::metadata::{DecodeWithMetadata, EncodeWithMetadata, Metadata};
+    pub use subxt_core::metadata::{
+        describe_type, DecodeWithMetadata, EncodeWithMetadata, FieldDescription, Metadata,
+        TypeDescription, TypeDescriptionKind, VariantDescription,
+    };
     // Expose metadata types under a sub module in case somebody needs to reference them:
     pub use subxt_metadata as types;
 }
@@ -79,7 +82,8 @@ pub mod metadata {
 /// Submit dynamic transactions.
 pub mod dynamic {
     pub use subxt_core::dynamic::{
-        constant, runtime_api_call, storage, tx, At, DecodedValue, DecodedValueThunk, Value,
+        constant, runtime_api_call, storage, tx, value_as_type, At, DecodedValue,
+        DecodedValueThunk, Value, ValueConversion,
     };
 }
 
@@ -271,6 +275,20 @@ pub mod ext {
 /// mod polkadot {}
 /// ```
 ///
+/// ## `runtime_metadata_env = "..."`
+///
+/// This attribute can be used instead of `runtime_metadata_path`, and names an environment variable which holds the path to
+/// the metadata file. The variable is read when the macro is expanded, which is useful when the location of the metadata
+/// differs between build environments. Compilation will fail if the variable is not set. Changing the variable, or the
+/// metadata file that it points to, will cause the code to be regenerated on the next build.
+///
+/// ```rust,ignore
+/// #[subxt::subxt(
+///     runtime_metadata_env = "SUBXT_METADATA_PATH"
+/// )]
+/// mod polkadot {}
+/// ```
+///
 /// ## `generate_docs`
 ///
 /// By default, documentation is not generated via the macro, since IDEs do not typically make use of it. This attribute
@@ -297,6 +315,35 @@ pub mod ext {
 /// mod polkadot {}
 /// ```
 ///
+/// ## `root_enums_only`
+///
+/// This attribute makes the codegen only generate the outer `Call`, `Event` and `Error` enums (and the types they rely on),
+/// along with an `is_codegen_valid_for` function to check them against some metadata. This sits between `runtime_types_only`
+/// and the full interface, and is useful if you only need to decode blocks rather than build transactions or access storage.
+///
+/// ```rust,no_run
+/// #[subxt::subxt(
+///     runtime_metadata_path = "../artifacts/polkadot_metadata_full.scale",
+///     root_enums_only
+/// )]
+/// mod polkadot {}
+/// ```
+///
+/// ## `no_std`
+///
+/// By default, types like `Vec` and `String` are referred to via the `subxt_core` crate path. This attribute makes the
+/// generated code refer to them via `::alloc` instead, so that it can be used in a `#![no_std]` crate (which must then
+/// declare `extern crate alloc`). The generated code only relies on `subxt_core`, which doesn't need `std`; if you depend
+/// on `subxt_core` directly rather than via `subxt`, combine this with a `crate` path pointing to it.
+///
+/// ```rust,ignore
+/// #[subxt::subxt(
+///     runtime_metadata_path = "../artifacts/polkadot_metadata_full.scale",
+///     no_std
+/// )]
+/// mod polkadot {}
+/// ```
+///
 /// ## `no_default_derives`
 ///
 /// By default, the macro will add all derives necessary for the generated code to play nicely with Subxt. Adding this attribute
@@ -317,6 +364,48 @@ pub mod ext {
 /// feature in conjunction with `runtime_types_only` (or manually specify a bunch of defaults to make codegen work properly when
 /// generating the subxt interfaces).
 ///
+/// ## `no_codec_derives`
+///
+/// Omit the `codec::Encode` and `codec::Decode` derives (and the `#[codec(..)]` attributes that go with them) from the
+/// generated types, leaving plain data types which can be used to display values that were decoded elsewhere. The generated
+/// types can no longer be SCALE encoded or decoded via `parity-scale-codec`, so this must be used in conjunction with
+/// `runtime_types_only`.
+///
+/// ```rust,no_run
+/// #[subxt::subxt(
+///     runtime_metadata_path = "../artifacts/polkadot_metadata_full.scale",
+///     runtime_types_only,
+///     no_codec_derives
+/// )]
+/// mod polkadot {}
+/// ```
+///
+/// ## `generate_value_conversions`
+///
+/// Implement [`crate::dynamic::ValueConversion`] for the generated types, so that they can be converted to and from dynamic
+/// [`crate::dynamic::Value`]s. Generic types, and types that have been substituted, do not get an implementation.
+///
+/// ```rust,no_run
+/// #[subxt::subxt(
+///     runtime_metadata_path = "../artifacts/polkadot_metadata_full.scale",
+///     generate_value_conversions
+/// )]
+/// mod polkadot {}
+/// ```
+///
+/// ## `precompute_storage_prefixes`
+///
+/// Generate a `const [u8; 32]` on each pallet's `StorageApi` holding the storage prefix of each storage entry (for example
+/// `StorageApi::ACCOUNT_STORAGE_PREFIX`), so that raw storage keys can be built without hashing the pallet and entry names.
+///
+/// ```rust,no_run
+/// #[subxt::subxt(
+///     runtime_metadata_path = "../artifacts/polkadot_metadata_full.scale",
+///     precompute_storage_prefixes
+/// )]
+/// mod polkadot {}
+/// ```
+///
 /// ## `unstable_metadata`
 ///
 /// This attribute works only in combination with `runtime_metadata_insecure_url`. By default, the macro will fetch the latest stable
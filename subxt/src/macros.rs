@@ -68,6 +68,6 @@ pub(crate) use {
     cfg_unstable_light_client,
 };
 
-// Only used by light-client.
+// Only used in some configurations.
 #[allow(unused)]
 pub(crate) use {cfg_jsonrpsee_native, cfg_jsonrpsee_web};
@@ -0,0 +1,456 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Typed helpers for dry-running contract calls and instantiations via the `ContractsApi`
+//! runtime API provided by chains which use `pallet_contracts`.
+
+use super::RuntimeApi;
+use crate::{
+    client::OnlineClientT,
+    dynamic::{At, DecodedValue, Value},
+    error::{DispatchError, Error, MetadataError},
+    utils::Weight,
+    Config, Metadata,
+};
+use codec::{Decode, Encode};
+use scale_encode::EncodeAsType;
+use scale_value::ValueDef;
+
+/// The name of the runtime API trait exposed by `pallet_contracts`.
+const CONTRACTS_API: &str = "ContractsApi";
+
+/// The arguments to dry-run a contract call with; see [`RuntimeApi::contracts_call()`].
+#[derive(Debug, Clone)]
+pub struct ContractCall<AccountId> {
+    /// The account calling the contract.
+    pub origin: AccountId,
+    /// The contract to call.
+    pub dest: AccountId,
+    /// The balance to transfer to the contract.
+    pub value: u128,
+    /// The maximum weight that the call can use. If `None`, the maximum allowed is used.
+    pub gas_limit: Option<Weight>,
+    /// The maximum balance that can be charged for storage. If `None`, there is no limit.
+    pub storage_deposit_limit: Option<u128>,
+    /// The input data (usually the encoded selector and arguments) to pass to the contract.
+    pub input_data: Vec<u8>,
+}
+
+impl<AccountId> ContractCall<AccountId> {
+    /// Call the contract `dest` from `origin` with the given input data, transferring no
+    /// balance and without any limits on the weight or storage deposit used.
+    pub fn new(origin: AccountId, dest: AccountId, input_data: Vec<u8>) -> Self {
+        ContractCall {
+            origin,
+            dest,
+            value: 0,
+            gas_limit: None,
+            storage_deposit_limit: None,
+            input_data,
+        }
+    }
+}
+
+/// The code to instantiate a contract from.
+#[derive(Debug, Clone)]
+pub enum Code<Hash> {
+    /// Upload the given Wasm code and instantiate a contract from it.
+    Upload(Vec<u8>),
+    /// Instantiate a contract from code which has already been uploaded, given its hash.
+    Existing(Hash),
+}
+
+/// The arguments to dry-run a contract instantiation with; see
+/// [`RuntimeApi::contracts_instantiate()`].
+#[derive(Debug, Clone)]
+pub struct ContractInstantiate<AccountId, Hash> {
+    /// The account instantiating the contract.
+    pub origin: AccountId,
+    /// The balance to transfer to the new contract.
+    pub value: u128,
+    /// The maximum weight that the instantiation can use. If `None`, the maximum allowed is used.
+    pub gas_limit: Option<Weight>,
+    /// The maximum balance that can be charged for storage. If `None`, there is no limit.
+    pub storage_deposit_limit: Option<u128>,
+    /// The code to instantiate the contract from.
+    pub code: Code<Hash>,
+    /// The input data (usually the encoded constructor selector and arguments).
+    pub data: Vec<u8>,
+    /// A salt used to derive the address of the new contract.
+    pub salt: Vec<u8>,
+}
+
+impl<AccountId, Hash> ContractInstantiate<AccountId, Hash> {
+    /// Instantiate a contract from `code` as `origin` with the given input data, transferring
+    /// no balance, with an empty salt and without any limits on the weight or storage deposit used.
+    pub fn new(origin: AccountId, code: Code<Hash>, data: Vec<u8>) -> Self {
+        ContractInstantiate {
+            origin,
+            value: 0,
+            gas_limit: None,
+            storage_deposit_limit: None,
+            code,
+            data,
+            salt: Vec::new(),
+        }
+    }
+}
+
+/// The storage deposit that a dry-run would charge or refund.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageDeposit {
+    /// This much balance would be refunded.
+    Refund(u128),
+    /// This much balance would be charged.
+    Charge(u128),
+}
+
+/// The value returned from a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecReturnValue {
+    /// The flags returned by the contract; see [`ExecReturnValue::did_revert()`].
+    pub flags: u32,
+    /// The data returned by the contract.
+    pub data: Vec<u8>,
+}
+
+impl ExecReturnValue {
+    /// The contract reverted, and so any changes it made would be rolled back.
+    pub fn did_revert(&self) -> bool {
+        self.flags & 1 == 1
+    }
+}
+
+/// The value returned from instantiating a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstantiateReturnValue<AccountId> {
+    /// The value returned from the contract's constructor.
+    pub result: ExecReturnValue,
+    /// The address of the new contract.
+    pub account_id: AccountId,
+}
+
+/// The result of dry-running some contract execution.
+#[derive(Debug)]
+pub struct ContractResult<R> {
+    /// The weight that was used by the execution.
+    pub gas_consumed: Weight,
+    /// The weight required for the execution to succeed. This can be more than the weight
+    /// consumed, and should be used as the gas limit when submitting a transaction.
+    pub gas_required: Weight,
+    /// The storage deposit that would be charged or refunded.
+    pub storage_deposit: StorageDeposit,
+    /// Any debug messages that the contract emitted, if debugging is enabled on the node.
+    pub debug_message: Vec<u8>,
+    /// The outcome of the execution.
+    pub result: Result<R, DispatchError>,
+}
+
+/// The result of dry-running a contract call.
+pub type ContractExecResult = ContractResult<ExecReturnValue>;
+
+/// The result of dry-running a contract instantiation.
+pub type ContractInstantiateResult<AccountId> = ContractResult<InstantiateReturnValue<AccountId>>;
+
+impl<T, Client> RuntimeApi<T, Client>
+where
+    T: Config,
+    Client: OnlineClientT<T>,
+{
+    /// Dry-run a contract call via the `ContractsApi_call` runtime API, and decode the result.
+    /// An error is returned if the chain does not provide the `ContractsApi` runtime API.
+    pub async fn contracts_call(
+        &self,
+        call: ContractCall<T::AccountId>,
+    ) -> Result<ContractExecResult, Error> {
+        let args = vec![
+            account_value(&call.origin),
+            account_value(&call.dest),
+            Value::u128(call.value),
+            option_value(call.gas_limit.map(weight_value)),
+            option_value(call.storage_deposit_limit.map(Value::u128)),
+            Value::from_bytes(call.input_data),
+        ];
+        let value = self.call_contracts_api("call", args).await?;
+        let metadata = self.client.metadata();
+        ContractResult::from_value(&value, &metadata, ExecReturnValue::from_value)
+    }
+
+    /// Dry-run a contract instantiation via the `ContractsApi_instantiate` runtime API, and decode
+    /// the result. An error is returned if the chain does not provide the `ContractsApi` runtime API.
+    pub async fn contracts_instantiate(
+        &self,
+        instantiate: ContractInstantiate<T::AccountId, T::Hash>,
+    ) -> Result<ContractInstantiateResult<T::AccountId>, Error>
+    where
+        T::AccountId: Decode,
+    {
+        let code = match instantiate.code {
+            Code::Upload(code) => Value::unnamed_variant("Upload", [Value::from_bytes(code)]),
+            Code::Existing(hash) => {
+                Value::unnamed_variant("Existing", [Value::from_bytes(hash.encode())])
+            }
+        };
+        let args = vec![
+            account_value(&instantiate.origin),
+            Value::u128(instantiate.value),
+            option_value(instantiate.gas_limit.map(weight_value)),
+            option_value(instantiate.storage_deposit_limit.map(Value::u128)),
+            code,
+            Value::from_bytes(instantiate.data),
+            Value::from_bytes(instantiate.salt),
+        ];
+        let value = self.call_contracts_api("instantiate", args).await?;
+        let metadata = self.client.metadata();
+        ContractResult::from_value(&value, &metadata, |value| {
+            let result = ExecReturnValue::from_value(value.at("result")?)?;
+            let account_id = bytes_at(value, "account_id")?;
+            let account_id = T::AccountId::decode(&mut &*account_id).ok()?;
+            Some(InstantiateReturnValue { result, account_id })
+        })
+    }
+
+    async fn call_contracts_api(
+        &self,
+        method: &str,
+        args: Vec<Value>,
+    ) -> Result<DecodedValue, Error> {
+        // If the chain has no `ContractsApi`, this fails with a metadata error before calling it.
+        let payload = crate::dynamic::runtime_api_call(CONTRACTS_API, method, args);
+        let value = self.call(payload).await?.to_value()?;
+        Ok(value)
+    }
+}
+
+impl<R> ContractResult<R> {
+    fn from_value(
+        value: &DecodedValue,
+        metadata: &Metadata,
+        ok_from_value: impl FnOnce(&DecodedValue) -> Option<R>,
+    ) -> Result<Self, Error> {
+        let result = value
+            .at("result")
+            .ok_or_else(|| unexpected_shape("result"))?;
+        let result = match &result.value {
+            ValueDef::Variant(v) if v.name == "Ok" => {
+                let ok = result.at(0).ok_or_else(|| unexpected_shape("result"))?;
+                Ok(ok_from_value(ok).ok_or_else(|| unexpected_shape("result"))?)
+            }
+            ValueDef::Variant(v) if v.name == "Err" => {
+                // Encode the error back to bytes so that it can be decoded into a DispatchError.
+                let err = result.at(0).ok_or_else(|| unexpected_shape("result"))?;
+                let dispatch_error_ty = metadata
+                    .dispatch_error_ty()
+                    .ok_or(MetadataError::DispatchErrorNotFound)?;
+                let err_bytes = err.encode_as_type(dispatch_error_ty, metadata.types())?;
+                Err(DispatchError::decode_from(err_bytes, metadata.clone())?)
+            }
+            _ => return Err(unexpected_shape("result")),
+        };
+
+        let storage_deposit = value
+            .at("storage_deposit")
+            .and_then(|deposit| {
+                let amount = deposit.at(0)?.as_u128()?;
+                match &deposit.value {
+                    ValueDef::Variant(v) if v.name == "Refund" => {
+                        Some(StorageDeposit::Refund(amount))
+                    }
+                    ValueDef::Variant(v) if v.name == "Charge" => {
+                        Some(StorageDeposit::Charge(amount))
+                    }
+                    _ => None,
+                }
+            })
+            .ok_or_else(|| unexpected_shape("storage_deposit"))?;
+
+        Ok(ContractResult {
+            gas_consumed: weight_at(value, "gas_consumed")?,
+            gas_required: weight_at(value, "gas_required")?,
+            storage_deposit,
+            debug_message: bytes_at(value, "debug_message")
+                .ok_or_else(|| unexpected_shape("debug_message"))?,
+            result,
+        })
+    }
+}
+
+impl ExecReturnValue {
+    fn from_value(value: &DecodedValue) -> Option<Self> {
+        // The flags are a bitflags struct wrapping a u32.
+        let flags = value.at("flags")?;
+        let flags = flags.at("bits").unwrap_or(flags).as_u128()?;
+        Some(ExecReturnValue {
+            flags: u32::try_from(flags).ok()?,
+            data: bytes_at(value, "data")?,
+        })
+    }
+}
+
+fn account_value(account_id: &impl Encode) -> Value {
+    Value::from_bytes(account_id.encode())
+}
+
+fn option_value(value: Option<Value>) -> Value {
+    match value {
+        Some(value) => Value::unnamed_variant("Some", [value]),
+        None => Value::unnamed_variant("None", []),
+    }
+}
+
+fn weight_value(weight: Weight) -> Value {
+    Value::named_composite([
+        ("ref_time", Value::u128(weight.ref_time as u128)),
+        ("proof_size", Value::u128(weight.proof_size as u128)),
+    ])
+}
+
+fn weight_at(value: &DecodedValue, field: &str) -> Result<Weight, Error> {
+    let weight = value.at(field).ok_or_else(|| unexpected_shape(field))?;
+    let part = |name: &str| {
+        weight
+            .at(name)
+            .and_then(|v| v.as_u128())
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or_else(|| unexpected_shape(field))
+    };
+    Ok(Weight::from_parts(part("ref_time")?, part("proof_size")?))
+}
+
+fn bytes_at(value: &DecodedValue, field: &str) -> Option<Vec<u8>> {
+    let ValueDef::Composite(bytes) = &value.at(field)?.value else {
+        return None;
+    };
+    bytes
+        .values()
+        .map(|b| b.as_u128().and_then(|b| u8::try_from(b).ok()))
+        .collect()
+}
+
+fn unexpected_shape(field: &str) -> Error {
+    Error::Other(format!(
+        "Could not find '{field}' in the result of the ContractsApi call; the contracts pallet on this chain is not supported"
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn exec_result(result: Value) -> DecodedValue {
+        let weight = |n| {
+            Value::named_composite([
+                ("ref_time", Value::u128(n)),
+                ("proof_size", Value::u128(n * 2)),
+            ])
+        };
+        Value::named_composite([
+            ("gas_consumed", weight(10)),
+            ("gas_required", weight(20)),
+            (
+                "storage_deposit",
+                Value::unnamed_variant("Charge", [Value::u128(500)]),
+            ),
+            ("debug_message", Value::from_bytes(b"hi")),
+            ("result", result),
+            ("events", Value::unnamed_variant("None", [])),
+        ])
+        .map_context(|_| 0)
+    }
+
+    fn metadata() -> Metadata {
+        let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        subxt_core::metadata::decode_from(&metadata_bytes[..]).unwrap()
+    }
+
+    #[test]
+    fn exec_results_are_decoded() {
+        let ok = Value::named_composite([
+            ("flags", Value::named_composite([("bits", Value::u128(1))])),
+            ("data", Value::from_bytes([1, 2, 3])),
+        ]);
+        let value = exec_result(Value::unnamed_variant("Ok", [ok]));
+
+        let result =
+            ContractResult::from_value(&value, &metadata(), ExecReturnValue::from_value).unwrap();
+        assert_eq!(result.gas_consumed, Weight::from_parts(10, 20));
+        assert_eq!(result.gas_required, Weight::from_parts(20, 40));
+        assert_eq!(result.storage_deposit, StorageDeposit::Charge(500));
+        assert_eq!(result.debug_message, b"hi".to_vec());
+
+        let ret = result.result.unwrap();
+        assert!(ret.did_revert());
+        assert_eq!(ret.data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn exec_errors_are_decoded_into_dispatch_errors() {
+        let err = Value::unnamed_variant("BadOrigin", []);
+        let value = exec_result(Value::unnamed_variant("Err", [err]));
+
+        let result =
+            ContractResult::from_value(&value, &metadata(), ExecReturnValue::from_value).unwrap();
+        assert!(matches!(result.result, Err(DispatchError::BadOrigin)));
+
+        // Module errors are decoded into details about the pallet and error:
+        let err = Value::unnamed_variant(
+            "Module",
+            [Value::named_composite([
+                ("index", Value::u128(5)),
+                ("error", Value::from_bytes([1, 0, 0, 0])),
+            ])],
+        );
+        let value = exec_result(Value::unnamed_variant("Err", [err]));
+
+        let result =
+            ContractResult::from_value(&value, &metadata(), ExecReturnValue::from_value).unwrap();
+        let Err(DispatchError::Module(err)) = result.result else {
+            panic!("expected a module error");
+        };
+        let details = err.details().unwrap();
+        assert_eq!(details.pallet.name(), "Balances");
+    }
+
+    #[tokio::test]
+    async fn chains_without_contracts_are_an_error() {
+        use crate::backend::rpc::mock_rpc_client::MockRpcClient;
+        use crate::backend::BlockRef;
+        use crate::config::PolkadotConfig;
+        use crate::error::MetadataError;
+        use crate::OnlineClient;
+
+        // Polkadot doesn't have the contracts pallet:
+        let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        let rpc_client = MockRpcClient::new();
+        let client = OnlineClient::<PolkadotConfig>::from_rpc_client_with(
+            [7u8; 32].into(),
+            subxt_core::client::RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 2,
+            },
+            subxt_core::metadata::decode_from(&metadata_bytes[..]).unwrap(),
+            crate::backend::rpc::RpcClient::new(rpc_client.clone()),
+        )
+        .unwrap();
+
+        let api = RuntimeApi::new(client, BlockRef::from_hash([0u8; 32].into()));
+        let call = ContractCall::new([1u8; 32].into(), [2u8; 32].into(), vec![]);
+        let err = api.contracts_call(call).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Metadata(MetadataError::RuntimeTraitNotFound(name)) if name == "ContractsApi"
+        ));
+
+        // We find out from the metadata, without calling the node:
+        assert!(rpc_client.calls().is_empty());
+    }
+
+    #[test]
+    fn unexpected_results_are_an_error() {
+        let value = Value::named_composite([("result", Value::u128(1))]).map_context(|_| 0);
+        assert!(
+            ContractResult::from_value(&value, &metadata(), ExecReturnValue::from_value).is_err()
+        );
+    }
+}
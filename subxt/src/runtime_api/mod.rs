@@ -7,8 +7,18 @@
 mod runtime_client;
 mod runtime_types;
 
+crate::macros::cfg_feature! {
+    "contracts",
+    mod contracts;
+    pub use contracts::{
+        Code, ContractCall, ContractExecResult, ContractInstantiate, ContractInstantiateResult,
+        ContractResult, ExecReturnValue, InstantiateReturnValue, StorageDeposit,
+    };
+}
+
 pub use runtime_client::RuntimeApiClient;
 pub use runtime_types::RuntimeApi;
 pub use subxt_core::runtime_api::payload::{
     dynamic, DefaultPayload, DynamicPayload, Payload, StaticPayload,
 };
+pub use subxt_core::runtime_api::Cursor;
@@ -35,9 +35,18 @@ where
         RuntimeApi::new(self.client.clone(), block_ref.into())
     }
 
-    /// Obtain a runtime API interface at the latest block hash.
+    /// Obtain a runtime API interface at the latest finalized block. This is the same as
+    /// [`RuntimeApiClient::at_latest_finalized()`]. Use [`crate::OnlineClient::at_latest()`]
+    /// to make several lookups at the same latest block without fetching it each time.
     pub fn at_latest(
         &self,
+    ) -> impl Future<Output = Result<RuntimeApi<T, Client>, Error>> + Send + 'static {
+        self.at_latest_finalized()
+    }
+
+    /// Obtain a runtime API interface at the latest finalized block.
+    pub fn at_latest_finalized(
+        &self,
     ) -> impl Future<Output = Result<RuntimeApi<T, Client>, Error>> + Send + 'static {
         // Clone and pass the client in like this so that we can explicitly
         // return a Future that's Send + 'static, rather than tied to &self.
@@ -49,4 +58,17 @@ where
             Ok(RuntimeApi::new(client, block_ref))
         }
     }
+
+    /// Obtain a runtime API interface at the latest best block. Prefer
+    /// [`RuntimeApiClient::at_latest_finalized()`] unless you need the most recent state,
+    /// since the best block may not end up being finalized.
+    pub fn at_latest_best(
+        &self,
+    ) -> impl Future<Output = Result<RuntimeApi<T, Client>, Error>> + Send + 'static {
+        let client = self.client.clone();
+        async move {
+            let block_ref = client.backend().latest_best_block_ref().await?;
+            Ok(RuntimeApi::new(client, block_ref))
+        }
+    }
 }
@@ -2,15 +2,18 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
+use super::Cursor;
 use super::Payload;
 use crate::{
     backend::{BackendExt, BlockRef},
     client::OnlineClientT,
-    error::Error,
+    error::{DispatchError, Error},
+    metadata::TypeDescription,
     Config,
 };
 use codec::Decode;
 use derive_where::derive_where;
+use futures::{stream, Stream, StreamExt};
 use std::{future::Future, marker::PhantomData};
 
 /// Execute runtime API calls.
@@ -94,4 +97,102 @@ where
             Ok(value)
         }
     }
+
+    /// Execute a runtime API call, like [`Self::call`]. If the runtime API returns a `Result`
+    /// whose error is the runtime's `DispatchError` (see
+    /// [`subxt_core::runtime_api::dispatch_error_bytes`]) and the call failed with such an error,
+    /// it is decoded and handed back as an [`Error::Runtime`], which can describe the pallet
+    /// and error that it came from. Otherwise, the decoded value is returned as normal.
+    pub fn call_with_dispatch_error<Call: Payload>(
+        &self,
+        payload: Call,
+    ) -> impl Future<Output = Result<Call::ReturnType, Error>> {
+        let client = self.client.clone();
+        let block_hash = self.block_ref.hash();
+        async move {
+            let metadata = client.metadata();
+            subxt_core::runtime_api::validate(&payload, &metadata)?;
+
+            let call_name = subxt_core::runtime_api::call_name(&payload);
+            let call_args = subxt_core::runtime_api::call_args(&payload, &metadata)?;
+            let bytes = client
+                .backend()
+                .call(&call_name, Some(call_args.as_slice()), block_hash)
+                .await?;
+
+            if let Some(err_bytes) =
+                subxt_core::runtime_api::dispatch_error_bytes(&bytes, &payload, &metadata)?
+            {
+                let err = DispatchError::decode_from(err_bytes, metadata)?;
+                return Err(Error::Runtime(err));
+            }
+
+            let value = subxt_core::runtime_api::decode_value(&mut &*bytes, &payload, &metadata)?;
+            Ok(value)
+        }
+    }
+
+    /// Execute a runtime API call, handing back the decoded value along with a description of
+    /// its type. This is useful for rendering the result of dynamic calls (see
+    /// [`super::dynamic()`]) with the names and types of each field.
+    pub fn call_with_description<Call: Payload>(
+        &self,
+        payload: Call,
+    ) -> impl Future<Output = Result<(Call::ReturnType, TypeDescription), Error>> {
+        let client = self.client.clone();
+        let block_hash = self.block_ref.hash();
+        async move {
+            let metadata = client.metadata();
+            subxt_core::runtime_api::validate(&payload, &metadata)?;
+
+            let call_name = subxt_core::runtime_api::call_name(&payload);
+            let call_args = subxt_core::runtime_api::call_args(&payload, &metadata)?;
+            let bytes = client
+                .backend()
+                .call(&call_name, Some(call_args.as_slice()), block_hash)
+                .await?;
+
+            let res = subxt_core::runtime_api::decode_value_with_description(
+                &mut &*bytes,
+                &payload,
+                &metadata,
+            )?;
+            Ok(res)
+        }
+    }
+
+    /// A convenience helper for runtime APIs which return their results one page at a time.
+    /// This is equivalent to making repeated calls to [`Self::call`] yourself.
+    ///
+    /// `make_payload` is called with an incrementing [`Cursor`] (starting from `0`) to construct
+    /// each payload. Items from each page are handed back as soon as that page has been fetched,
+    /// and we stop once a page comes back empty (ie yields no items when iterated over), or when
+    /// an error is encountered.
+    pub fn call_paged<Call, F>(
+        &self,
+        make_payload: F,
+    ) -> impl Stream<Item = Result<<Call::ReturnType as IntoIterator>::Item, Error>>
+    where
+        Call: Payload,
+        Call::ReturnType: IntoIterator,
+        F: FnMut(Cursor) -> Call,
+    {
+        let api = self.clone();
+        let state = Some((api, make_payload, 0 as Cursor));
+        stream::unfold(state, |state| async move {
+            let (api, mut make_payload, cursor) = state?;
+            match api.call(make_payload(cursor)).await {
+                Ok(page) => {
+                    let mut items = page.into_iter().peekable();
+                    // An empty page means that there are no more results.
+                    items.peek()?;
+                    let next_state = Some((api, make_payload, cursor + 1));
+                    let items: Vec<_> = items.map(Ok).collect();
+                    Some((stream::iter(items), next_state))
+                }
+                Err(e) => Some((stream::iter(vec![Err(e)]), None)),
+            }
+        })
+        .flatten()
+    }
 }
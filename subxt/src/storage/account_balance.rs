@@ -0,0 +1,98 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::dynamic::{At, DecodedValue};
+use crate::error::Error;
+
+/// The balances of an account, as read from its `System.Account` storage entry. This is
+/// obtained via [`crate::storage::Storage::account_balance()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountBalance {
+    /// The balance that isn't reserved. Some of this may be frozen.
+    pub free: u128,
+    /// The balance that is reserved (or on hold), and so can't be used.
+    pub reserved: u128,
+    /// The amount of the free balance that can't be transferred away. On chains which track
+    /// `misc_frozen` and `fee_frozen` separately, this is the larger of the two.
+    pub frozen: u128,
+}
+
+impl AccountBalance {
+    /// Build the balance from the dynamically decoded `AccountInfo` stored in `System.Account`.
+    pub(crate) fn from_value(account_info: &DecodedValue) -> Result<Self, Error> {
+        let data = account_info
+            .at("data")
+            .ok_or_else(|| unexpected_shape("data"))?;
+        let balance = |field: &str| data.at(field).and_then(|v| v.as_u128());
+
+        let frozen = match balance("frozen") {
+            Some(frozen) => frozen,
+            None => balance("misc_frozen")
+                .unwrap_or(0)
+                .max(balance("fee_frozen").unwrap_or(0)),
+        };
+
+        Ok(AccountBalance {
+            free: balance("free").ok_or_else(|| unexpected_shape("free"))?,
+            reserved: balance("reserved").ok_or_else(|| unexpected_shape("reserved"))?,
+            frozen,
+        })
+    }
+}
+
+fn unexpected_shape(field: &str) -> Error {
+    Error::Other(format!(
+        "Could not find '{field}' in System.Account; the account data on this chain is not supported"
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use scale_value::Value;
+
+    fn account_info(data: Value) -> DecodedValue {
+        Value::named_composite([("nonce", Value::u128(1)), ("data", data)]).map_context(|_| 0)
+    }
+
+    #[test]
+    fn balances_are_read_from_account_data() {
+        let info = account_info(Value::named_composite([
+            ("free", Value::u128(1_000)),
+            ("reserved", Value::u128(200)),
+            ("frozen", Value::u128(300)),
+            ("flags", Value::u128(0)),
+        ]));
+
+        assert_eq!(
+            AccountBalance::from_value(&info).unwrap(),
+            AccountBalance {
+                free: 1_000,
+                reserved: 200,
+                frozen: 300,
+            }
+        );
+    }
+
+    #[test]
+    fn older_frozen_balances_are_supported() {
+        let info = account_info(Value::named_composite([
+            ("free", Value::u128(1_000)),
+            ("reserved", Value::u128(0)),
+            ("misc_frozen", Value::u128(50)),
+            ("fee_frozen", Value::u128(70)),
+        ]));
+
+        assert_eq!(AccountBalance::from_value(&info).unwrap().frozen, 70);
+    }
+
+    #[test]
+    fn unexpected_account_data_is_an_error() {
+        let info = account_info(Value::named_composite([("free", Value::u128(1))]));
+        assert!(AccountBalance::from_value(&info).is_err());
+
+        let info = Value::u128(1).map_context(|_| 0);
+        assert!(AccountBalance::from_value(&info).is_err());
+    }
+}
@@ -4,9 +4,17 @@
 
 //! Types associated with accessing and working with storage items.
 
+mod account_balance;
 mod storage_client;
 mod storage_type;
 
+crate::macros::cfg_feature! {
+    "read-proof",
+    mod read_proof;
+    pub use read_proof::verify_read_proof;
+}
+
+pub use account_balance::AccountBalance;
 pub use storage_client::StorageClient;
 pub use storage_type::{Storage, StorageKeyValuePair};
 pub use subxt_core::storage::address::{
@@ -0,0 +1,86 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+use crate::{error::Error, utils::H256};
+use sp_core::Blake2Hasher;
+use sp_trie::{LayoutV1, StorageProof};
+
+/// Check a storage read proof (for instance, one obtained via [`super::Storage::read_proof()`])
+/// against a trusted state root, and return the value stored at the given key.
+///
+/// This assumes that the state trie is hashed using Blake2-256, as is the case for Substrate
+/// based chains. Returns `Ok(None)` if the proof shows that no value is stored at the key, and
+/// an error if the proof is not valid for the state root, or does not cover the key.
+pub fn verify_read_proof(
+    proof: &[Vec<u8>],
+    state_root: H256,
+    key: &[u8],
+) -> Result<Option<Vec<u8>>, Error> {
+    let db = StorageProof::new(proof.iter().cloned()).into_memory_db::<Blake2Hasher>();
+    sp_trie::read_trie_value::<LayoutV1<Blake2Hasher>, _>(&db, &state_root, key, None, None)
+        .map_err(|e| Error::Other(format!("Invalid storage read proof: {e}")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sp_trie::{MemoryDB, TrieDBMutBuilder, TrieMut};
+
+    /// Build a trie containing the given entries, returning its root and
+    /// every node in it (which is a valid proof of any key in the trie).
+    fn trie_and_proof(entries: &[(&[u8], &[u8])]) -> (H256, Vec<Vec<u8>>) {
+        let mut db = MemoryDB::<Blake2Hasher>::default();
+        let mut root = H256::default();
+        {
+            let mut trie =
+                TrieDBMutBuilder::<LayoutV1<Blake2Hasher>>::new(&mut db, &mut root).build();
+            for (key, value) in entries {
+                trie.insert(key, value).unwrap();
+            }
+        }
+
+        let proof = db
+            .drain()
+            .into_values()
+            .filter(|(_, rc)| *rc > 0)
+            .map(|(node, _)| node)
+            .collect();
+        (root, proof)
+    }
+
+    #[test]
+    fn verifies_values_in_proof() {
+        let long_value = [7u8; 64];
+        let (root, proof) = trie_and_proof(&[
+            (b"alice", b"short"),
+            (b"bob", &long_value),
+            (b"bobby", b"another"),
+        ]);
+
+        assert_eq!(
+            verify_read_proof(&proof, root, b"alice").unwrap(),
+            Some(b"short".to_vec())
+        );
+        assert_eq!(
+            verify_read_proof(&proof, root, b"bob").unwrap(),
+            Some(long_value.to_vec())
+        );
+        assert_eq!(verify_read_proof(&proof, root, b"charlie").unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_proof_for_different_root() {
+        let (_, proof) = trie_and_proof(&[(b"alice", b"short"), (b"bob", b"value")]);
+        let (other_root, _) = trie_and_proof(&[(b"alice", b"other")]);
+
+        assert!(verify_read_proof(&proof, other_root, b"alice").is_err());
+    }
+
+    #[test]
+    fn rejects_incomplete_proof() {
+        let (root, _) = trie_and_proof(&[(b"alice", b"short"), (b"bob", b"value")]);
+
+        assert!(verify_read_proof(&[], root, b"alice").is_err());
+    }
+}
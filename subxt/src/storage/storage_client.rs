@@ -2,16 +2,19 @@
 // This file is dual-licensed as Apache-2.0 or GPL-3.0.
 // see LICENSE for license details.
 
-use super::storage_type::Storage;
+use super::storage_type::{Storage, StreamOfResults};
 use crate::{
     backend::BlockRef,
     client::{OfflineClientT, OnlineClientT},
     error::Error,
+    metadata::DecodeWithMetadata,
     Config,
 };
 use derive_where::derive_where;
+use futures::StreamExt;
 use std::{future::Future, marker::PhantomData};
 use subxt_core::storage::address::Address;
+use subxt_core::utils::Yes;
 
 /// Query the runtime storage.
 #[derive_where(Clone; Client)]
@@ -69,9 +72,18 @@ where
         Storage::new(self.client.clone(), block_ref.into())
     }
 
-    /// Obtain storage at the latest block hash.
+    /// Obtain storage at the latest finalized block. This is the same as
+    /// [`StorageClient::at_latest_finalized()`]. Use [`crate::OnlineClient::at_latest()`]
+    /// to make several lookups at the same latest block without fetching it each time.
     pub fn at_latest(
         &self,
+    ) -> impl Future<Output = Result<Storage<T, Client>, Error>> + Send + 'static {
+        self.at_latest_finalized()
+    }
+
+    /// Obtain storage at the latest finalized block.
+    pub fn at_latest_finalized(
+        &self,
     ) -> impl Future<Output = Result<Storage<T, Client>, Error>> + Send + 'static {
         // Clone and pass the client in like this so that we can explicitly
         // return a Future that's Send + 'static, rather than tied to &self.
@@ -83,4 +95,95 @@ where
             Ok(Storage::new(client, block_ref))
         }
     }
+
+    /// Obtain storage at the latest best block. Prefer [`StorageClient::at_latest_finalized()`]
+    /// unless you need the most recent state, since the best block may not end up being finalized.
+    pub fn at_latest_best(
+        &self,
+    ) -> impl Future<Output = Result<Storage<T, Client>, Error>> + Send + 'static {
+        let client = self.client.clone();
+        async move {
+            let block_ref = client.backend().latest_best_block_ref().await?;
+            Ok(Storage::new(client, block_ref))
+        }
+    }
+
+    /// Subscribe to the value at some storage address. The returned stream emits the current
+    /// value, and then the new value each time that it changes. `None` is emitted if nothing
+    /// is stored at the address.
+    ///
+    /// The value is fetched at each new finalized block, and emitted when it differs from
+    /// the last value emitted, so changes in blocks which are never finalized aren't seen.
+    /// If several blocks are finalized before the previous value has been fetched, only the
+    /// value at the newest of them is fetched.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use subxt::{ PolkadotConfig, OnlineClient };
+    /// use subxt_signer::sr25519::dev;
+    ///
+    /// #[subxt::subxt(runtime_metadata_path = "../artifacts/polkadot_metadata_full.scale")]
+    /// pub mod polkadot {}
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let api = OnlineClient::<PolkadotConfig>::new().await.unwrap();
+    ///
+    /// // Watch the balance of Alice's account:
+    /// let address = polkadot::storage().system().account(dev::alice().public_key());
+    /// let mut values = api.storage().subscribe(address).await.unwrap();
+    ///
+    /// while let Some(Ok(account)) = values.next().await {
+    ///     if let Some(account) = account {
+    ///         println!("Free balance: {}", account.data.free);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn subscribe<Addr>(
+        &self,
+        address: Addr,
+    ) -> impl Future<Output = Result<StreamOfResults<Option<Addr::Target>>, Error>> + 'static
+    where
+        Addr: Address<IsFetchable = Yes> + 'static,
+    {
+        let client = self.client.clone();
+        async move {
+            let metadata = client.metadata();
+
+            // Metadata validation checks whether the static address given
+            // is likely to actually correspond to a real storage entry or not.
+            subxt_core::storage::validate(&address, &metadata)?;
+
+            // Look up the return type once here rather than for each value.
+            let (_pallet, entry) = subxt_core::storage::lookup_storage_entry_details(
+                address.pallet_name(),
+                address.entry_name(),
+                &metadata,
+            )?;
+            let return_type_id = entry.entry_type().value_ty();
+
+            let address_bytes = subxt_core::storage::get_address_bytes(&address, &metadata)?;
+            let s = client
+                .backend()
+                .stream_storage_value(address_bytes)
+                .await?
+                .map(move |res| {
+                    let (_block_ref, value) = res?;
+                    value
+                        .map(|value| {
+                            Addr::Target::decode_with_metadata(
+                                &mut &*value,
+                                return_type_id,
+                                &metadata,
+                            )
+                        })
+                        .transpose()
+                        .map_err(Into::into)
+                });
+
+            Ok(StreamOfResults::new(Box::pin(s)))
+        }
+    }
 }
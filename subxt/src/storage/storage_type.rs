@@ -5,13 +5,15 @@
 use crate::{
     backend::{BackendExt, BlockRef},
     client::OnlineClientT,
+    dynamic::Value,
     error::{Error, MetadataError, StorageAddressError},
     metadata::DecodeWithMetadata,
+    storage::AccountBalance,
     Config,
 };
-use codec::Decode;
+use codec::{Decode, Encode};
 use derive_where::derive_where;
-use futures::StreamExt;
+use futures::{future, StreamExt};
 use std::{future::Future, marker::PhantomData};
 use subxt_core::storage::address::{Address, StorageHashers, StorageKey};
 use subxt_core::utils::Yes;
@@ -62,6 +64,26 @@ where
         }
     }
 
+    /// Fetch the size in bytes of the raw encoded value at the key given, or `None`
+    /// if no value exists. See [`Self::size`].
+    pub fn fetch_raw_size(
+        &self,
+        key: impl Into<Vec<u8>>,
+    ) -> impl Future<Output = Result<Option<u64>, Error>> + 'static {
+        let client = self.client.clone();
+        let key = key.into();
+        // Keep this alive until the call is complete:
+        let block_ref = self.block_ref.clone();
+        // Manual future so lifetime not tied to api.storage().
+        async move {
+            let size = client
+                .backend()
+                .storage_fetch_size(key, block_ref.hash())
+                .await?;
+            Ok(size)
+        }
+    }
+
     /// Stream all of the raw keys underneath the key given
     pub fn fetch_raw_keys(
         &self,
@@ -80,6 +102,77 @@ where
         }
     }
 
+    /// Fetch the raw encoded value at the key given from a child trie. The child key
+    /// is the unprefixed key identifying a default child trie (ie without the
+    /// `:child_storage:default:` prefix).
+    pub fn fetch_raw_child(
+        &self,
+        child_key: impl Into<Vec<u8>>,
+        key: impl Into<Vec<u8>>,
+    ) -> impl Future<Output = Result<Option<Vec<u8>>, Error>> + 'static {
+        let client = self.client.clone();
+        let child_key = child_key.into();
+        let key = key.into();
+        // Keep this alive until the call is complete:
+        let block_ref = self.block_ref.clone();
+        // Manual future so lifetime not tied to api.storage().
+        async move {
+            let data = client
+                .backend()
+                .child_storage_fetch_values(child_key, vec![key], block_ref.hash())
+                .await?
+                .next()
+                .await
+                .transpose()?
+                .map(|res| res.value);
+            Ok(data)
+        }
+    }
+
+    /// Stream all of the raw keys underneath the key given in a child trie. The child
+    /// key is the unprefixed key identifying a default child trie (ie without the
+    /// `:child_storage:default:` prefix).
+    pub fn fetch_raw_child_keys(
+        &self,
+        child_key: impl Into<Vec<u8>>,
+        key: impl Into<Vec<u8>>,
+    ) -> impl Future<Output = Result<StreamOfResults<Vec<u8>>, Error>> + 'static {
+        let client = self.client.clone();
+        let block_hash = self.block_ref.hash();
+        let child_key = child_key.into();
+        let key = key.into();
+        // Manual future so lifetime not tied to api.storage().
+        async move {
+            let keys = client
+                .backend()
+                .child_storage_fetch_descendant_keys(child_key, key, block_hash)
+                .await?;
+            Ok(keys)
+        }
+    }
+
+    /// Fetch a proof of the values stored at the given raw keys. The proof is a set of
+    /// encoded trie nodes which, given the state root of this block, can be used to check
+    /// the values without trusting the node that provided them (see `verify_read_proof`,
+    /// which is available with the `read-proof` feature).
+    pub fn read_proof<Key: Into<Vec<u8>>>(
+        &self,
+        keys: impl IntoIterator<Item = Key>,
+    ) -> impl Future<Output = Result<Vec<Vec<u8>>, Error>> + 'static {
+        let client = self.client.clone();
+        let keys: Vec<Vec<u8>> = keys.into_iter().map(Into::into).collect();
+        // Keep this alive until the call is complete:
+        let block_ref = self.block_ref.clone();
+        // Manual future so lifetime not tied to api.storage().
+        async move {
+            let proof = client
+                .backend()
+                .storage_read_proof(keys, block_ref.hash())
+                .await?;
+            Ok(proof)
+        }
+    }
+
     /// Fetch a decoded value from storage at a given address.
     ///
     /// # Example
@@ -138,6 +231,86 @@ where
         }
     }
 
+    /// Check whether a value exists at the given storage address, without decoding it. This
+    /// is useful when values may be large and only their existence is of interest.
+    ///
+    /// Note that entries which have a default value (ie [`Self::fetch_or_default`] can be used)
+    /// will return `false` if nothing has been explicitly stored at the address.
+    pub fn contains<'address, Addr>(
+        &self,
+        address: &'address Addr,
+    ) -> impl Future<Output = Result<bool, Error>> + 'address
+    where
+        Addr: Address<IsFetchable = Yes> + 'address,
+    {
+        let client = self.clone();
+        async move {
+            let metadata = client.client.metadata();
+
+            // Metadata validation, as in .fetch():
+            subxt_core::storage::validate(address, &metadata)?;
+
+            let lookup_bytes = subxt_core::storage::get_address_bytes(address, &metadata)?;
+            let data = client.fetch_raw(lookup_bytes).await?;
+            Ok(data.is_some())
+        }
+    }
+
+    /// Fetch the size in bytes of the encoded value at the given storage address, or `None` if
+    /// nothing is stored there. This can be used to decide whether a potentially large value is
+    /// worth fetching. The legacy backend asks the node for the size via `state_getStorageSize`,
+    /// whereas the unstable backend has to fetch the value in order to measure it.
+    pub fn size<'address, Addr>(
+        &self,
+        address: &'address Addr,
+    ) -> impl Future<Output = Result<Option<u64>, Error>> + 'address
+    where
+        Addr: Address<IsFetchable = Yes> + 'address,
+    {
+        let client = self.clone();
+        async move {
+            let metadata = client.client.metadata();
+
+            // Metadata validation, as in .fetch():
+            subxt_core::storage::validate(address, &metadata)?;
+
+            let lookup_bytes = subxt_core::storage::get_address_bytes(address, &metadata)?;
+            client.fetch_raw_size(lookup_bytes).await
+        }
+    }
+
+    /// Fetch a decoded value from storage at a given address, along with a proof of the value
+    /// (see [`Self::read_proof`]). Both are obtained from the same block, and so the proof can be
+    /// checked against this block's state root to verify the value that was returned. If no value
+    /// exists at the address, `None` is returned along with a proof that no value exists.
+    pub fn fetch_with_proof<'address, Addr>(
+        &self,
+        address: &'address Addr,
+    ) -> impl Future<Output = Result<(Option<Addr::Target>, Vec<Vec<u8>>), Error>> + 'address
+    where
+        Addr: Address<IsFetchable = Yes> + 'address,
+    {
+        let client = self.clone();
+        async move {
+            let metadata = client.client.metadata();
+
+            // Metadata validation, as in .fetch():
+            subxt_core::storage::validate(address, &metadata)?;
+
+            let lookup_bytes = subxt_core::storage::get_address_bytes(address, &metadata)?;
+            let (data, proof) = future::try_join(
+                client.fetch_raw(lookup_bytes.clone()),
+                client.read_proof([lookup_bytes]),
+            )
+            .await?;
+
+            let val = data
+                .map(|data| subxt_core::storage::decode_value(&mut &*data, address, &metadata))
+                .transpose()?;
+            Ok((val, proof))
+        }
+    }
+
     /// Fetch a StorageKey that has a default value with an optional block hash.
     pub fn fetch_or_default<'address, Addr>(
         &self,
@@ -214,6 +387,10 @@ where
             // metadata.
             subxt_core::storage::validate(&address, &metadata)?;
 
+            // Dynamic addresses are always "iterable", so check that this one actually
+            // points to a map before we try iterating over it.
+            subxt_core::storage::validate_iterable(&address, &metadata)?;
+
             // Look up the return type for flexible decoding. Do this once here to avoid
             // potentially doing it every iteration if we used `decode_storage_with_metadata`
             // in the iterator.
@@ -273,10 +450,10 @@ where
         // construct the storage key. This is done similarly in `frame_support::traits::metadata::StorageVersion::storage_key()`.
         pub const STORAGE_VERSION_STORAGE_KEY_POSTFIX: &[u8] = b":__STORAGE_VERSION__:";
         let mut key_bytes: Vec<u8> = vec![];
-        key_bytes.extend(&sp_crypto_hashing::twox_128(
+        key_bytes.extend(&crate::utils::hashing::twox_128(
             pallet_name.as_ref().as_bytes(),
         ));
-        key_bytes.extend(&sp_crypto_hashing::twox_128(
+        key_bytes.extend(&crate::utils::hashing::twox_128(
             STORAGE_VERSION_STORAGE_KEY_POSTFIX,
         ));
 
@@ -290,6 +467,38 @@ where
         u16::decode(&mut &storage_version_bytes[..]).map_err(Into::into)
     }
 
+    /// Fetch the free, reserved and frozen balances of an account from the `System.Account`
+    /// storage entry. This works across chains whose balance type is any unsigned integer up
+    /// to 128 bits wide. An account which doesn't exist has zero balances.
+    ///
+    /// An error is returned if the chain has no `System.Account` storage entry, or if the
+    /// account data stored in it doesn't contain the expected balances.
+    pub async fn account_balance(
+        &self,
+        account_id: &T::AccountId,
+    ) -> Result<AccountBalance, Error> {
+        let has_account_entry = self
+            .client
+            .metadata()
+            .pallet_by_name("System")
+            .and_then(|p| p.storage())
+            .and_then(|s| s.entry_by_name("Account"))
+            .is_some();
+        if !has_account_entry {
+            return Err(Error::Other(
+                "This chain has no System.Account storage entry to read balances from".into(),
+            ));
+        }
+
+        let address = crate::dynamic::storage(
+            "System",
+            "Account",
+            vec![Value::from_bytes(account_id.encode())],
+        );
+        let account_info = self.fetch_or_default(&address).await?.to_value()?;
+        AccountBalance::from_value(&account_info)
+    }
+
     /// Fetch the runtime WASM code.
     pub async fn runtime_wasm_code(&self) -> Result<Vec<u8>, Error> {
         // note: this should match the `CODE` constant in `sp_core::storage::well_known_keys`
@@ -21,7 +21,9 @@ cfg_substrate_compat! {
 }
 
 pub use subxt_core::tx::payload::{dynamic, DefaultPayload, DynamicPayload, Payload};
+pub use subxt_core::tx::remark::{self, Remarked};
 pub use subxt_core::tx::signer::{self, Signer};
+pub use subxt_core::tx::{verify_signature, SignerPayloadJson, VerifySignature};
 pub use tx_client::{
     PartialExtrinsic, SubmittableExtrinsic, TransactionInvalid, TransactionUnknown, TxClient,
     ValidationResult,
@@ -6,9 +6,10 @@ use crate::{
     backend::{BackendExt, BlockRef, TransactionStatus},
     client::{OfflineClientT, OnlineClientT},
     config::{Config, ExtrinsicParams, Header, RefineParams, RefineParamsData},
-    error::{BlockError, Error},
-    tx::{Payload, Signer as SignerT, TxProgress},
-    utils::PhantomDataSendSync,
+    dynamic::At,
+    error::{BlockError, Error, MetadataError, TransactionError},
+    tx::{DynamicPayload, Payload, Signer as SignerT, SignerPayloadJson, TxProgress},
+    utils::{AccountId32, PhantomDataSendSync},
 };
 use codec::{Compact, Decode, Encode};
 use derive_where::derive_where;
@@ -42,6 +43,15 @@ impl<T: Config, C: OfflineClientT<T>> TxClient<T, C> {
         subxt_core::tx::validate(call, &self.client.metadata()).map_err(Into::into)
     }
 
+    /// Construct a `System.remark_with_event` call containing the given data, returning an
+    /// error if the chain does not support this call. Once included in a block, the call emits a
+    /// [`crate::tx::Remarked`] event containing the hash of the data, which can be found using
+    /// [`crate::blocks::ExtrinsicEvents::find_first()`].
+    pub fn remark_with_event(&self, data: impl Into<Vec<u8>>) -> Result<DynamicPayload, Error> {
+        subxt_core::tx::remark::validate_remark_with_event(&self.client.metadata())?;
+        Ok(subxt_core::tx::remark::remark_with_event(data))
+    }
+
     /// Return the SCALE encoded bytes representing the call data of the transaction.
     pub fn call_data<Call>(&self, call: &Call) -> Result<Vec<u8>, Error>
     where
@@ -112,9 +122,11 @@ where
     C: OnlineClientT<T>,
 {
     /// Fetch the latest block header and account nonce from the backend and use them to refine [`ExtrinsicParams::Params`].
+    /// If an account nonce is given, that is used rather than fetching the account nonce.
     async fn refine_params(
         &self,
         account_id: &T::AccountId,
+        account_nonce: Option<u64>,
         params: &mut <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
     ) -> Result<(), Error> {
         let block_ref = self.client.backend().latest_finalized_block_ref().await?;
@@ -124,8 +136,12 @@ where
             .block_header(block_ref.hash())
             .await?
             .ok_or_else(|| Error::Block(BlockError::not_found(block_ref.hash())))?;
-        let account_nonce =
-            crate::blocks::get_account_nonce(&self.client, account_id, block_ref.hash()).await?;
+        let account_nonce = match account_nonce {
+            Some(nonce) => nonce,
+            None => {
+                crate::blocks::get_account_nonce(&self.client, account_id, block_ref.hash()).await?
+            }
+        };
 
         params.refine(&RefineParamsData::new(
             account_nonce,
@@ -152,7 +168,7 @@ where
         Call: Payload,
     {
         // Refine the params by adding account nonce and latest block information:
-        self.refine_params(account_id, &mut params).await?;
+        self.refine_params(account_id, None, &mut params).await?;
         // Create the partial extrinsic with the refined params:
         self.create_partial_signed_offline(call, params)
     }
@@ -182,6 +198,96 @@ where
         Ok(partial_signed.sign(signer))
     }
 
+    /// Creates a signed extrinsic using the given account nonce, without submitting it.
+    ///
+    /// This is like [`Self::create_signed`], except that the account nonce is not fetched
+    /// from the chain, saving a round trip for each transaction when submitting many of them.
+    /// The latest finalized block is still fetched in order to make the transaction mortal,
+    /// unless `params` says otherwise. `params` should not set a nonce.
+    ///
+    /// # Note
+    ///
+    /// The caller is responsible for providing the correct nonce. If it's lower than the
+    /// account's current nonce, the transaction will be rejected as outdated, and if it's
+    /// higher, the transaction won't be included in a block until the nonces before it are used.
+    pub async fn create_signed_with_nonce<Call, Signer>(
+        &self,
+        call: &Call,
+        signer: &Signer,
+        account_nonce: u64,
+        mut params: <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
+    ) -> Result<SubmittableExtrinsic<T, C>, Error>
+    where
+        Call: Payload,
+        Signer: SignerT<T>,
+    {
+        self.validate(call)?;
+        self.refine_params(&signer.account_id(), Some(account_nonce), &mut params)
+            .await?;
+        Ok(self
+            .create_partial_signed_offline(call, params)?
+            .sign(signer))
+    }
+
+    /// Creates a signed extrinsic to replace some `original` extrinsic which is stuck in
+    /// the transaction pool, for instance because its tip was too low for it to be included
+    /// in a block. This is typically used to resubmit the same call with a higher tip.
+    ///
+    /// The replacement is given the same nonce as the original, so that at most one of them
+    /// can make it into a block, and so `params` should not set a nonce. The tip to use should
+    /// be set in `params`.
+    ///
+    /// The `call` is not compared against the call in the original extrinsic. Passing the same
+    /// call bumps the tip of the original transaction, while passing a different call replaces
+    /// it outright; for instance, replacing it with a `System.remark` effectively cancels it.
+    ///
+    /// # Note
+    ///
+    /// Nodes only accept a replacement into their transaction pool if it has a higher priority
+    /// than the transaction it replaces. Since priority is derived from the tip, the new tip
+    /// must be high enough to raise the priority past that of the original; a marginally higher
+    /// tip may not be enough, in which case submitting the replacement will fail with a
+    /// "Priority is too low" error.
+    ///
+    /// An error is returned if the original extrinsic is not signed, if the replacement would
+    /// not have the same nonce as the original, or if its tip is not higher than the original tip.
+    pub async fn create_replacement<Call, Signer>(
+        &self,
+        original: &SubmittableExtrinsic<T, C>,
+        call: &Call,
+        signer: &Signer,
+        mut params: <T::ExtrinsicParams as ExtrinsicParams<T>>::Params,
+    ) -> Result<SubmittableExtrinsic<T, C>, Error>
+    where
+        Call: Payload,
+        Signer: SignerT<T>,
+    {
+        let metadata = self.client.metadata();
+        let (original_nonce, original_tip) = nonce_and_tip::<T>(original.encoded(), &metadata)?;
+
+        // Build the replacement using the nonce of the original extrinsic.
+        self.validate(call)?;
+        self.refine_params(&signer.account_id(), Some(original_nonce), &mut params)
+            .await?;
+        let replacement = self
+            .create_partial_signed_offline(call, params)?
+            .sign(signer);
+
+        let (nonce, tip) = nonce_and_tip::<T>(replacement.encoded(), &metadata)?;
+        if nonce != original_nonce {
+            return Err(Error::Other(format!(
+                "The replacement extrinsic has nonce {nonce}, but must have the same nonce as the original ({original_nonce})"
+            )));
+        }
+        if tip <= original_tip {
+            return Err(Error::Other(format!(
+                "The replacement extrinsic has a tip of {tip}, but must have a higher tip than the original ({original_tip})"
+            )));
+        }
+
+        Ok(replacement)
+    }
+
     /// Creates and signs an extrinsic and submits it to the chain. Passes default parameters
     /// to construct the "signed extra" and "additional" payloads needed by the extrinsic.
     ///
@@ -269,6 +375,28 @@ where
     }
 }
 
+/// Decode the nonce and tip (which is 0 if not present) of some signed extrinsic.
+fn nonce_and_tip<T: Config>(
+    tx_bytes: &[u8],
+    metadata: &crate::Metadata,
+) -> Result<(u64, u128), Error> {
+    let extrinsics =
+        subxt_core::blocks::Extrinsics::<T>::decode_from(vec![tx_bytes.to_vec()], metadata.clone())
+            .map_err(BlockError::from)?;
+    let extrinsic = extrinsics
+        .iter()
+        .next()
+        .transpose()?
+        .ok_or_else(|| Error::Other("Expected an extrinsic to decode".into()))?;
+    let signed_extensions = extrinsic
+        .signed_extensions()
+        .ok_or_else(|| Error::Other("The extrinsic to replace is not signed".into()))?;
+    let nonce = signed_extensions
+        .nonce()
+        .ok_or_else(|| Error::Other("The extrinsic to replace has no nonce".into()))?;
+    Ok((nonce, signed_extensions.tip().unwrap_or(0)))
+}
+
 /// This payload contains the information needed to produce an extrinsic.
 pub struct PartialExtrinsic<T: Config, C> {
     client: C,
@@ -292,6 +420,21 @@ where
         self.inner.call_data()
     }
 
+    /// Return the signer payload for this extrinsic in the JSON format expected by browser
+    /// extensions such as polkadot-js (known as `SignerPayloadJSON` there). `address` is the
+    /// account that will sign the payload, and is ss58 encoded using the given `ss58_prefix`.
+    ///
+    /// See [`SignerPayloadJson`] for more details.
+    pub fn to_signer_payload_json(
+        &self,
+        address: &AccountId32,
+        ss58_prefix: u16,
+    ) -> Result<SignerPayloadJson, Error> {
+        self.inner
+            .to_signer_payload_json(&self.client.metadata(), address, ss58_prefix)
+            .map_err(Into::into)
+    }
+
     /// Convert this [`PartialExtrinsic`] into a [`SubmittableExtrinsic`], ready to submit.
     /// The provided `signer` is responsible for providing the "from" address for the transaction,
     /// as well as providing a signature to attach to it.
@@ -363,6 +506,46 @@ where
     pub fn into_encoded(self) -> Vec<u8> {
         self.inner.into_encoded()
     }
+
+    /// Returns the length of the SCALE encoded extrinsic in bytes.
+    pub fn encoded_len(&self) -> usize {
+        self.encoded().len()
+    }
+
+    /// Check the length of the extrinsic against the maximum length allowed for normal
+    /// extrinsics, as given by the `System.BlockLength` constant, returning a
+    /// [`TransactionError::TooLarge`] error if the chain would reject it for being too long.
+    pub fn check_encoded_len(&self) -> Result<(), Error> {
+        let block_length = self
+            .client
+            .constants()
+            .at(&crate::dynamic::constant("System", "BlockLength"))?
+            .to_value()?;
+        let max_len = block_length
+            .at("max")
+            .at("normal")
+            .and_then(|v| v.as_u128())
+            .ok_or_else(|| {
+                Error::Other("Could not find 'max.normal' in System.BlockLength".to_owned())
+            })?;
+        let max_len = usize::try_from(max_len).map_err(|_| {
+            Error::Other(format!(
+                "System.BlockLength 'max.normal' of {max_len} does not fit in a usize"
+            ))
+        })?;
+
+        self.check_encoded_len_against(max_len)
+    }
+
+    /// Check the length of the extrinsic against the given maximum length, returning a
+    /// [`TransactionError::TooLarge`] error if it is longer than this.
+    pub fn check_encoded_len_against(&self, max_len: usize) -> Result<(), Error> {
+        let len = self.encoded_len();
+        if len > max_len {
+            return Err(TransactionError::TooLarge { len, max_len }.into());
+        }
+        Ok(())
+    }
 }
 
 impl<T, C> SubmittableExtrinsic<T, C>
@@ -445,6 +628,19 @@ where
     ) -> Result<ValidationResult, Error> {
         let block_hash = at.into().hash();
 
+        // Metadata from V15 onwards lists the runtime APIs that a node supports, so if we
+        // have that information, give back a clear error if the API we need is missing.
+        let metadata = self.client.metadata();
+        if metadata.runtime_api_traits().len() > 0
+            && metadata
+                .runtime_api_trait_by_name("TaggedTransactionQueue")
+                .is_none()
+        {
+            return Err(
+                MetadataError::RuntimeTraitNotFound("TaggedTransactionQueue".to_owned()).into(),
+            );
+        }
+
         // Approach taken from https://github.com/paritytech/json-rpc-interface-spec/issues/55.
         let mut params = Vec::with_capacity(8 + self.encoded().len() + 8);
         2u8.encode_to(&mut params);
@@ -530,6 +726,16 @@ impl ValidationResult {
     }
 }
 
+impl std::fmt::Display for ValidationResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationResult::Valid(_) => write!(f, "Transaction is valid"),
+            ValidationResult::Invalid(e) => write!(f, "Transaction is invalid: {e}"),
+            ValidationResult::Unknown(e) => write!(f, "Transaction validity is unknown: {e}"),
+        }
+    }
+}
+
 /// Transaction is valid; here is some more information about it.
 #[derive(Decode, Clone, Debug, PartialEq)]
 pub struct TransactionValid {
@@ -564,26 +770,33 @@ pub struct TransactionValid {
 }
 
 /// The runtime was unable to validate the transaction.
-#[derive(Decode, Clone, Debug, PartialEq)]
+#[derive(Decode, Clone, Debug, PartialEq, thiserror::Error)]
 pub enum TransactionUnknown {
     /// Could not lookup some information that is required to validate the transaction.
+    #[error("Could not look up information required to validate the transaction")]
     CannotLookup,
     /// No validator found for the given unsigned transaction.
+    #[error("No validator found for the given unsigned transaction")]
     NoUnsignedValidator,
     /// Any other custom unknown validity that is not covered by this enum.
+    #[error("Unknown validity of the transaction (custom code {0})")]
     Custom(u8),
 }
 
 /// The transaction is invalid.
-#[derive(Decode, Clone, Debug, PartialEq)]
+#[derive(Decode, Clone, Debug, PartialEq, thiserror::Error)]
 pub enum TransactionInvalid {
     /// The call of the transaction is not expected.
+    #[error("The call of the transaction is not expected")]
     Call,
     /// General error to do with the inability to pay some fees (e.g. account balance too low).
+    #[error("Inability to pay some fees (e.g. account balance too low)")]
     Payment,
     /// General error to do with the transaction not yet being valid (e.g. nonce too high).
+    #[error("Transaction is not yet valid (e.g. nonce too high)")]
     Future,
     /// General error to do with the transaction being outdated (e.g. nonce too low).
+    #[error("Transaction is outdated (e.g. nonce too low)")]
     Stale,
     /// General error to do with the transaction's proofs (e.g. signature).
     ///
@@ -594,6 +807,7 @@ pub enum TransactionInvalid {
     /// data will only be used to generate the signature, but will not be part of the transaction
     /// itself. As the verifying side does not know which additional data was used while signing
     /// it will only be able to assume a bad signature and cannot express a more meaningful error.
+    #[error("Invalid transaction proof (e.g. bad signature)")]
     BadProof,
     /// The transaction birth block is ancient.
     ///
@@ -603,22 +817,28 @@ pub enum TransactionInvalid {
     /// - Era::birth block number > BlockHashCount`. (e.g. in Polkadot `BlockHashCount` = 2400, so
     ///   a transaction with birth block number 1337 would be valid up until block number 1337 + 2400,
     ///   after which point the transaction would be considered to have an ancient birth block.)
+    #[error("The transaction birth block is ancient")]
     AncientBirthBlock,
     /// The transaction would exhaust the resources of current block.
     ///
     /// The transaction might be valid, but there are not enough resources
     /// left in the current block.
+    #[error("The transaction would exhaust the resources of the current block")]
     ExhaustsResources,
     /// Any other custom invalid validity that is not covered by this enum.
+    #[error("Invalid transaction (custom code {0})")]
     Custom(u8),
     /// An extrinsic with a Mandatory dispatch resulted in Error. This is indicative of either a
     /// malicious validator or a buggy `provide_inherent`. In any case, it can result in
     /// dangerously overweight blocks and therefore if found, invalidates the block.
+    #[error("A transaction with a mandatory dispatch resulted in an error")]
     BadMandatory,
     /// An extrinsic with a mandatory dispatch tried to be validated.
     /// This is invalid; only inherent extrinsics are allowed to have mandatory dispatches.
+    #[error("A transaction with a mandatory dispatch tried to be validated")]
     MandatoryValidation,
     /// The sending address is disabled or known to be invalid.
+    #[error("The sending address is disabled or known to be invalid")]
     BadSigner,
 }
 
@@ -745,4 +965,159 @@ mod test {
             assert_eq!(decoded, validation_result);
         }
     }
+
+    #[test]
+    fn validation_result_has_readable_description() {
+        assert_eq!(
+            ValidationResult::Invalid(TransactionInvalid::Stale).to_string(),
+            "Transaction is invalid: Transaction is outdated (e.g. nonce too low)"
+        );
+        assert_eq!(
+            ValidationResult::Unknown(TransactionUnknown::Custom(7)).to_string(),
+            "Transaction validity is unknown: Unknown validity of the transaction (custom code 7)"
+        );
+    }
+
+    #[test]
+    fn core_create_signed_matches_offline_client() {
+        use crate::client::OfflineClient;
+        use crate::config::{DefaultExtrinsicParamsBuilder as Params, PolkadotConfig};
+        use crate::dynamic::Value;
+        use subxt_core::client::{ClientState, RuntimeVersion};
+        use subxt_signer::ecdsa::dev;
+
+        let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        let state = ClientState::<PolkadotConfig> {
+            metadata: subxt_core::metadata::decode_from(&metadata_bytes[..]).unwrap(),
+            genesis_hash: [7u8; 32].into(),
+            runtime_version: RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 2,
+            },
+        };
+        let client = OfflineClient::<PolkadotConfig>::new(
+            state.genesis_hash,
+            state.runtime_version,
+            state.metadata.clone(),
+        );
+
+        let call = crate::dynamic::tx(
+            "System",
+            "remark",
+            vec![Value::from_bytes(b"offline signing")],
+        );
+        // ECDSA signatures are deterministic, so both paths should produce identical bytes.
+        let params = || Params::new().nonce(3).tip(1_000).build();
+        let signer = dev::alice();
+
+        let core_tx = subxt_core::tx::create_signed(&call, &state, &signer, params()).unwrap();
+        let client_tx = client
+            .tx()
+            .create_signed_offline(&call, &signer, params())
+            .unwrap();
+
+        assert_eq!(core_tx.encoded(), client_tx.encoded());
+    }
+
+    #[tokio::test]
+    async fn create_signed_with_nonce_does_not_fetch_the_nonce() {
+        use crate::backend::rpc::mock_rpc_client::MockRpcClient;
+        use crate::client::OnlineClient;
+        use crate::config::PolkadotConfig;
+        use crate::dynamic::Value;
+        use subxt_signer::ecdsa::dev;
+
+        // Answer the calls needed to find the latest block:
+        let zero_hash = format!("0x{}", "00".repeat(32));
+        let rpc_client = MockRpcClient::new()
+            .with_response("chain_getFinalizedHead", serde_json::json!(zero_hash))
+            .with_response(
+                "chain_getHeader",
+                serde_json::json!({
+                    "parentHash": zero_hash,
+                    "number": "0x5",
+                    "stateRoot": zero_hash,
+                    "extrinsicsRoot": zero_hash,
+                    "digest": { "logs": [] }
+                }),
+            );
+
+        let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        let client = OnlineClient::<PolkadotConfig>::from_rpc_client_with(
+            [7u8; 32].into(),
+            subxt_core::client::RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 2,
+            },
+            subxt_core::metadata::decode_from(&metadata_bytes[..]).unwrap(),
+            crate::backend::rpc::RpcClient::new(rpc_client.clone()),
+        )
+        .unwrap();
+
+        let call = crate::dynamic::tx("System", "remark", vec![Value::from_bytes(b"nonce")]);
+        let tx = client
+            .tx()
+            .create_signed_with_nonce(&call, &dev::alice(), 42, Default::default())
+            .await
+            .unwrap();
+
+        let (nonce, _) = nonce_and_tip::<PolkadotConfig>(tx.encoded(), &client.metadata()).unwrap();
+        assert_eq!(nonce, 42);
+
+        // Only the latest block was fetched; no runtime API call was made for the nonce:
+        assert_eq!(
+            rpc_client.methods_called(),
+            vec!["chain_getFinalizedHead", "chain_getHeader"]
+        );
+    }
+
+    #[test]
+    fn large_batches_fail_the_length_check() {
+        use crate::client::OfflineClient;
+        use crate::config::{DefaultExtrinsicParamsBuilder as Params, PolkadotConfig};
+        use crate::dynamic::Value;
+        use subxt_signer::ecdsa::dev;
+
+        let metadata_bytes = include_bytes!("../../../artifacts/polkadot_metadata_full.scale");
+        let client = OfflineClient::<PolkadotConfig>::new(
+            [7u8; 32].into(),
+            subxt_core::client::RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 2,
+            },
+            subxt_core::metadata::decode_from(&metadata_bytes[..]).unwrap(),
+        );
+
+        let remarks = (0..100)
+            .map(|_| {
+                Value::unnamed_variant(
+                    "System",
+                    [Value::named_variant(
+                        "remark",
+                        [("remark", Value::from_bytes([0u8; 1024]))],
+                    )],
+                )
+            })
+            .collect::<Vec<_>>();
+        let call = crate::dynamic::tx("Utility", "batch", vec![Value::unnamed_composite(remarks)]);
+        let tx = client
+            .tx()
+            .create_signed_offline(&call, &dev::alice(), Params::new().nonce(0).build())
+            .unwrap();
+
+        let len = tx.encoded_len();
+        assert!(len > 100 * 1024);
+        assert_eq!(len, tx.encoded().len());
+
+        // Fine against the chain's own limit, but not against a small one:
+        tx.check_encoded_len().unwrap();
+        tx.check_encoded_len_against(len).unwrap();
+        match tx.check_encoded_len_against(1024) {
+            Err(Error::Transaction(TransactionError::TooLarge { len: l, max_len })) => {
+                assert_eq!(l, len);
+                assert_eq!(max_len, 1024);
+            }
+            other => panic!("Expected a TooLarge error, got {other:?}"),
+        }
+    }
 }
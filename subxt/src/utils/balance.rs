@@ -0,0 +1,187 @@
+// Copyright 2019-2024 Parity Technologies (UK) Ltd.
+// This file is dual-licensed as Apache-2.0 or GPL-3.0.
+// see LICENSE for license details.
+
+//! Helpers for displaying balances in terms of a chain's token.
+
+use crate::backend::legacy::LegacyRpcMethods;
+use crate::config::Config;
+use crate::error::Error;
+
+/// The number of decimal places that balances are shown with, at most.
+const DISPLAY_DECIMAL_PLACES: u8 = 4;
+
+/// Format a balance given the number of decimals and the symbol of the token, rounding to
+/// at most 4 decimal places. For example, `10_000_000_000` with 10 decimals and the symbol
+/// `DOT` is shown as `1.0000 DOT`. Balances on chains with no decimals are shown as integers.
+pub fn format_balance(value: u128, decimals: u8, symbol: &str) -> String {
+    let places = decimals.min(DISPLAY_DECIMAL_PLACES);
+    let (whole, fraction) = match 10u128.checked_pow(decimals as u32) {
+        Some(unit) => (value / unit, value % unit),
+        // No u128 is as large as 10^decimals, so the whole part is 0:
+        None => (0, value),
+    };
+
+    // Round the fraction to the number of places we're showing, carrying into the whole part
+    // if needed (for example, 1.99999 rounds to 2.0000).
+    let fraction = match 10u128.checked_pow((decimals - places) as u32) {
+        Some(scale) => fraction / scale + u128::from(fraction % scale >= scale.div_ceil(2)),
+        None => 0,
+    };
+    let (whole, fraction) = if fraction == 10u128.pow(places as u32) {
+        (whole + 1, 0)
+    } else {
+        (whole, fraction)
+    };
+
+    let amount = if places == 0 {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction:0width$}", width = places as usize)
+    };
+
+    if symbol.is_empty() {
+        amount
+    } else {
+        format!("{amount} {symbol}")
+    }
+}
+
+/// The decimals and symbol of a chain's native token, used to display balances via
+/// [`BalanceFormat::format()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceFormat {
+    /// The number of decimals that the token has.
+    pub decimals: u8,
+    /// The symbol of the token, for example `DOT`.
+    pub symbol: String,
+}
+
+impl BalanceFormat {
+    /// Create a new [`BalanceFormat`] from the number of decimals and the symbol of the token.
+    pub fn new(decimals: u8, symbol: impl Into<String>) -> Self {
+        BalanceFormat {
+            decimals,
+            symbol: symbol.into(),
+        }
+    }
+
+    /// Read the `tokenDecimals` and `tokenSymbol` of a chain's properties, as returned from
+    /// [`crate::backend::legacy::LegacyRpcMethods::system_properties()`] or
+    /// [`crate::backend::unstable::UnstableRpcMethods::chainspec_v1_properties()`]. Chains with
+    /// several tokens list them all, in which case the first one is used. Returns `None` if the
+    /// properties don't describe a token.
+    pub fn from_chain_properties(
+        properties: &serde_json::Map<String, serde_json::Value>,
+    ) -> Option<Self> {
+        // Each property is either a single value or an array of values, one per token.
+        let first = |key: &str| match properties.get(key)? {
+            serde_json::Value::Array(values) => values.first().cloned(),
+            value => Some(value.clone()),
+        };
+
+        let decimals = first("tokenDecimals")?.as_u64()?.try_into().ok()?;
+        let symbol = first("tokenSymbol")?.as_str()?.to_owned();
+        Some(BalanceFormat { decimals, symbol })
+    }
+
+    /// Fetch the chain's properties using [`LegacyRpcMethods::system_properties()`], and read
+    /// the token decimals and symbol from them as in [`BalanceFormat::from_chain_properties()`].
+    /// Returns `None` if the properties don't describe a token.
+    pub async fn fetch<T: Config>(methods: &LegacyRpcMethods<T>) -> Result<Option<Self>, Error> {
+        let properties = methods.system_properties().await?;
+        Ok(Self::from_chain_properties(&properties))
+    }
+
+    /// Format a balance of this token. See [`format_balance()`].
+    pub fn format(&self, value: u128) -> String {
+        format_balance(value, self.decimals, &self.symbol)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn balances_are_formatted_with_decimals() {
+        assert_eq!(format_balance(10_000_000_000, 10, "DOT"), "1.0000 DOT");
+        assert_eq!(format_balance(12_345_678_900, 10, "DOT"), "1.2346 DOT");
+        assert_eq!(format_balance(1_000_000_000_000, 12, "KSM"), "1.0000 KSM");
+        assert_eq!(format_balance(0, 12, "KSM"), "0.0000 KSM");
+        assert_eq!(
+            format_balance(123_456_789_000_000_000_000, 18, "ETH"),
+            "123456.7890 ETH"
+        );
+    }
+
+    #[test]
+    fn balances_are_rounded() {
+        // Rounds up, carrying into the whole part:
+        assert_eq!(format_balance(19_999_990_000, 10, "DOT"), "2.0000 DOT");
+        // Rounds half up:
+        assert_eq!(format_balance(10_000_500_000, 10, "DOT"), "1.0001 DOT");
+        // Rounds down:
+        assert_eq!(format_balance(10_000_499_999, 10, "DOT"), "1.0000 DOT");
+        // Very small amounts round to zero:
+        assert_eq!(format_balance(1, 10, "DOT"), "0.0000 DOT");
+    }
+
+    #[test]
+    fn balances_with_few_or_no_decimals() {
+        assert_eq!(format_balance(1_000, 0, "UNIT"), "1000 UNIT");
+        assert_eq!(format_balance(150, 2, "USD"), "1.50 USD");
+        assert_eq!(format_balance(5, 1, "X"), "0.5 X");
+        assert_eq!(format_balance(1_000, 0, ""), "1000");
+    }
+
+    #[test]
+    fn extreme_balances_dont_overflow() {
+        assert_eq!(
+            format_balance(u128::MAX, 0, "UNIT"),
+            "340282366920938463463374607431768211455 UNIT"
+        );
+        assert_eq!(format_balance(u128::MAX, 38, "UNIT"), "3.4028 UNIT");
+        assert_eq!(format_balance(u128::MAX, 255, "UNIT"), "0.0000 UNIT");
+    }
+
+    #[test]
+    fn balance_format_from_chain_properties() {
+        let properties = serde_json::json!({
+            "ss58Format": 0,
+            "tokenDecimals": 10,
+            "tokenSymbol": "DOT"
+        });
+        let format = BalanceFormat::from_chain_properties(properties.as_object().unwrap()).unwrap();
+        assert_eq!(format, BalanceFormat::new(10, "DOT"));
+        assert_eq!(format.format(10_000_000_000), "1.0000 DOT");
+
+        // Chains with several tokens use the first:
+        let properties = serde_json::json!({
+            "tokenDecimals": [12, 18],
+            "tokenSymbol": ["ACA", "AUSD"]
+        });
+        let format = BalanceFormat::from_chain_properties(properties.as_object().unwrap()).unwrap();
+        assert_eq!(format, BalanceFormat::new(12, "ACA"));
+
+        // No token information:
+        let properties = serde_json::json!({ "ss58Format": 42 });
+        assert!(BalanceFormat::from_chain_properties(properties.as_object().unwrap()).is_none());
+    }
+
+    #[tokio::test]
+    async fn balance_format_is_fetched_from_system_properties() {
+        use crate::backend::rpc::{mock_rpc_client::MockRpcClient, RpcClient};
+        use crate::config::PolkadotConfig;
+
+        let client = MockRpcClient::new().with_response(
+            "system_properties",
+            serde_json::json!({ "tokenDecimals": 12, "tokenSymbol": "KSM" }),
+        );
+        let methods = LegacyRpcMethods::<PolkadotConfig>::new(RpcClient::new(client.clone()));
+        let format = BalanceFormat::fetch(&methods).await.unwrap();
+
+        assert_eq!(format, Some(BalanceFormat::new(12, "KSM")));
+        assert_eq!(client.methods_called(), ["system_properties"]);
+    }
+}
@@ -4,14 +4,18 @@
 
 //! Miscellaneous utility helpers.
 
+mod balance;
+
 use crate::macros::cfg_jsonrpsee;
 use crate::{error::RpcError, Error};
 use url::Url;
 
+pub use balance::{format_balance, BalanceFormat};
+
 pub use subxt_core::utils::{
-    bits, strip_compact_prefix, to_hex, AccountId32, Encoded, Era, KeyedVec, MultiAddress,
-    MultiSignature, PhantomDataSendSync, Static, UncheckedExtrinsic, WrapperKeepOpaque, Yes, H160,
-    H256, H512,
+    bits, encode_hex, format_accounts_ss58, from_hex, hashing, strip_compact_prefix, to_hex,
+    AccountId32, Encoded, Era, KeyedVec, MultiAddress, MultiSignature, PhantomDataSendSync,
+    Ss58Formatter, Static, UncheckedExtrinsic, Weight, WrapperKeepOpaque, Yes, H160, H256, H512,
 };
 
 cfg_jsonrpsee! {
@@ -19,13 +23,14 @@ cfg_jsonrpsee! {
     pub use fetch_chain_spec::{fetch_chainspec_from_rpc_node, FetchChainspecError};
 }
 
-/// A URL is considered secure if it uses a secure scheme ("https" or "wss") or is referring to localhost.
+/// A URL is considered secure if it uses a secure scheme ("https" or "wss"), is referring to localhost,
+/// or refers to a local Unix domain socket ("unix") or named pipe ("npipe").
 ///
 /// Returns an error if the string could not be parsed into a URL.
 pub fn url_is_secure(url: &str) -> Result<bool, Error> {
     let url = Url::parse(url).map_err(|e| Error::Rpc(RpcError::ClientError(Box::new(e))))?;
 
-    let secure_scheme = url.scheme() == "https" || url.scheme() == "wss";
+    let secure_scheme = matches!(url.scheme(), "https" | "wss" | "unix" | "npipe");
     let is_localhost = url.host().is_some_and(|e| match e {
         url::Host::Domain(e) => e == "localhost",
         url::Host::Ipv4(e) => e.is_loopback(),
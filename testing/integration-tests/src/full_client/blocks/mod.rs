@@ -150,6 +150,147 @@ async fn missing_block_headers_will_be_filled_in() -> Result<(), subxt::Error> {
     Ok(())
 }
 
+#[subxt_test]
+async fn missing_block_headers_will_be_filled_in_concurrently() -> Result<(), subxt::Error> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use subxt::backend::legacy;
+
+    let ctx = test_context().await;
+    let rpc = ctx.legacy_rpc_methods().await;
+
+    // As above; deliberately leave gaps so that we have to fill some in.
+    let some_finalized_blocks = rpc
+        .chain_subscribe_finalized_heads()
+        .await?
+        .enumerate()
+        .take(6)
+        .filter(|(n, _)| {
+            let n = *n;
+            async move { n == 0 || n == 5 }
+        })
+        .map(|(_, r)| r);
+
+    let filled = Arc::new(AtomicUsize::new(0));
+    let config = {
+        let filled = filled.clone();
+        legacy::FillGapsConfig::new()
+            .max_concurrent_fills(4)
+            .on_progress(move |n| filled.store(n, Ordering::Relaxed))
+    };
+
+    let all_finalized_blocks = legacy::subscribe_to_block_headers_filling_in_gaps_with_config(
+        rpc,
+        some_finalized_blocks,
+        None,
+        config,
+    );
+    futures::pin_mut!(all_finalized_blocks);
+
+    // Even though we fetch concurrently, headers must be handed back in order.
+    let mut first_block_number = None;
+    let mut last_block_number = None;
+    while let Some(header) = all_finalized_blocks.next().await {
+        let header = header?;
+
+        use subxt::config::Header;
+        let block_number: u128 = header.number().into();
+
+        if let Some(last) = last_block_number {
+            assert_eq!(last + 1, block_number);
+        }
+        first_block_number.get_or_insert(block_number);
+        last_block_number = Some(block_number);
+    }
+
+    // Everything between the first and last block was filled in (the first and last
+    // come from the subscription itself).
+    let (first, last) = (first_block_number.unwrap(), last_block_number.unwrap());
+    let expected_filled = (last - first).saturating_sub(1) as usize;
+    assert_eq!(filled.load(Ordering::Relaxed), expected_filled);
+    Ok(())
+}
+
+#[subxt_test]
+async fn block_range_is_returned_in_order() -> Result<(), subxt::Error> {
+    use subxt::config::Header;
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    // Wait for a few blocks to be finalized so that we have a range to fetch.
+    let mut sub = api.blocks().subscribe_finalized().await?;
+    let mut finalized_number: u64 = 0;
+    while finalized_number < 3 {
+        finalized_number = sub.next().await.unwrap()?.number().into();
+    }
+
+    let from = finalized_number - 2;
+    let blocks: Vec<_> = api
+        .blocks()
+        .range(from, Some(finalized_number))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<_, _>>()?;
+
+    let numbers: Vec<u64> = blocks.iter().map(|b| b.header().number().into()).collect();
+    assert_eq!(numbers, (from..=finalized_number).collect::<Vec<_>>());
+
+    // Each block should be the child of the one before it.
+    for pair in blocks.windows(2) {
+        assert_eq!(pair[1].header().parent_hash, pair[0].hash());
+    }
+
+    // Asking for blocks which aren't finalized yet is an error.
+    let res = api
+        .blocks()
+        .range(from, Some(u64::MAX))
+        .collect::<Vec<_>>()
+        .await;
+    assert!(matches!(
+        res.as_slice(),
+        [Err(subxt::Error::Block(
+            subxt::error::BlockError::NotFinalized { .. }
+        ))]
+    ));
+
+    Ok(())
+}
+
+#[subxt_test]
+async fn subscribe_finalized_from_resumes_from_a_past_block() -> Result<(), subxt::Error> {
+    use subxt::config::Header;
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    // Wait for a few blocks to be finalized so that there are some to backfill.
+    let mut sub = api.blocks().subscribe_finalized().await?;
+    let mut finalized_number: u64 = 0;
+    while finalized_number < 3 {
+        finalized_number = sub.next().await.unwrap()?.number().into();
+    }
+    drop(sub);
+
+    // Resume from a past block, and keep going until we've seen some new blocks too.
+    let from = finalized_number - 2;
+    let mut sub = api.blocks().subscribe_finalized_from(from).await?;
+    let mut blocks = vec![];
+    while blocks.len() < 6 {
+        blocks.push(sub.next().await.unwrap()?);
+    }
+
+    // Every block is seen once and in order, across the backfill and live blocks.
+    let numbers: Vec<u64> = blocks.iter().map(|b| b.header().number().into()).collect();
+    assert_eq!(numbers, (from..from + 6).collect::<Vec<_>>());
+    for pair in blocks.windows(2) {
+        assert_eq!(pair[1].header().parent_hash, pair[0].hash());
+    }
+
+    Ok(())
+}
+
 // Check that we can subscribe to non-finalized blocks.
 #[subxt_test]
 async fn runtime_api_call() -> Result<(), subxt::Error> {
@@ -262,6 +403,152 @@ async fn fetch_block_and_decode_extrinsic_details() {
     }
 }
 
+#[cfg(fullclient)]
+#[subxt_test]
+async fn events_grouped_by_extrinsic_line_up_with_extrinsics() {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let alice = dev::alice();
+    let bob = dev::bob();
+
+    let tx = node_runtime::tx()
+        .balances()
+        .transfer_allow_death(bob.public_key().into(), 10_000);
+
+    let in_block = api
+        .tx()
+        .sign_and_submit_then_watch_default(&tx, &alice)
+        .await
+        .unwrap()
+        .wait_for_finalized()
+        .await
+        .unwrap();
+
+    let block = api.blocks().at(in_block.block_hash()).await.unwrap();
+    let extrinsics = block.extrinsics().await.unwrap();
+    let events = block.events().await.unwrap();
+    let grouped = events.grouped_by_extrinsic().unwrap();
+
+    let transfer = extrinsics
+        .find_first::<node_runtime::balances::calls::types::TransferAllowDeath>()
+        .unwrap()
+        .expect("transfer should be in the block");
+
+    // The transfer event should be in the bucket for the transfer extrinsic:
+    let transfer_events = grouped.for_extrinsic(transfer.details.index());
+    let transfer_event = transfer_events
+        .iter()
+        .find_map(|ev| {
+            ev.as_event::<node_runtime::balances::events::Transfer>()
+                .unwrap()
+        })
+        .expect("transfer event should be emitted by the transfer extrinsic");
+    assert_eq!(transfer_event.amount, 10_000);
+
+    // And not in the bucket for any other extrinsic:
+    for (idx, evs) in grouped.extrinsics() {
+        if idx == transfer.details.index() {
+            continue;
+        }
+        assert!(evs.iter().all(|ev| ev
+            .as_event::<node_runtime::balances::events::Transfer>()
+            .unwrap()
+            .is_none()));
+    }
+}
+
+#[cfg(fullclient)]
+#[subxt_test]
+async fn initialization_and_finalization_events_are_accessible() {
+    use node_runtime::runtime_types::{
+        frame_system::pallet::Call as SystemCall, kitchensink_runtime::RuntimeCall,
+        pallet_scheduler::pallet::Call as SchedulerCall,
+    };
+    use subxt::events::{EventDetails, Phase};
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    // Scheduled calls are dispatched while initializing the block that they're scheduled
+    // for, so schedule a remark to know of some initialization events to look for.
+    let remark = RuntimeCall::System(SystemCall::remark_with_event {
+        remark: b"initialization".to_vec(),
+    });
+    let call = RuntimeCall::Scheduler(SchedulerCall::schedule_after {
+        after: 1,
+        maybe_periodic: None,
+        priority: 0,
+        call: Box::new(remark),
+    });
+    let scheduled = api
+        .tx()
+        .sign_and_submit_then_watch_default(&node_runtime::tx().sudo().sudo(call), &dev::alice())
+        .await
+        .unwrap()
+        .wait_for_finalized_success()
+        .await
+        .unwrap()
+        .find_first::<node_runtime::scheduler::events::Scheduled>()
+        .unwrap()
+        .expect("the remark should be scheduled");
+
+    // Wait for the block that the remark is dispatched in to be finalized:
+    let mut blocks = api.blocks().subscribe_finalized().await.unwrap();
+    while blocks.next().await.unwrap().unwrap().number() < scheduled.when {}
+    let block_hash = api
+        .backend()
+        .finalized_block_hash_at_number(scheduled.when.into())
+        .await
+        .unwrap()
+        .expect("the block has been finalized");
+
+    let block = api.blocks().at(block_hash).await.unwrap();
+    let events = block.events().await.unwrap();
+
+    let initialization: Vec<_> = events
+        .initialization_events()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let finalization: Vec<_> = events
+        .finalization_events()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert!(initialization
+        .iter()
+        .all(|ev| ev.phase() == Phase::Initialization));
+    let is_event = |ev: &EventDetails<SubstrateConfig>, pallet: &str, variant: &str| {
+        ev.pallet_name() == pallet && ev.variant_name() == variant
+    };
+    assert!(initialization
+        .iter()
+        .any(|ev| is_event(ev, "Scheduler", "Dispatched")));
+    assert!(initialization
+        .iter()
+        .any(|ev| is_event(ev, "System", "Remarked")));
+    assert!(finalization
+        .iter()
+        .all(|ev| ev.phase() == Phase::Finalization));
+
+    // These line up with the buckets that grouping by extrinsic produces:
+    let grouped = events.grouped_by_extrinsic().unwrap();
+    let indexes = |evs: &[EventDetails<subxt::SubstrateConfig>]| -> Vec<u32> {
+        evs.iter().map(|ev| ev.index()).collect()
+    };
+    assert_eq!(indexes(&initialization), indexes(grouped.initialization()));
+    assert_eq!(indexes(&finalization), indexes(grouped.finalization()));
+
+    // Events from the timestamp inherent are emitted while applying it, not while
+    // initializing or finalizing the block:
+    let in_extrinsics: usize = grouped.extrinsics().map(|(_, evs)| evs.len()).sum();
+    assert!(in_extrinsics > 0);
+    assert_eq!(
+        initialization.len() + finalization.len() + in_extrinsics,
+        events.len() as usize
+    );
+}
+
 #[cfg(fullclient)]
 #[subxt_test]
 async fn decode_signed_extensions_from_blocks() {
@@ -368,3 +655,131 @@ async fn decode_signed_extensions_from_blocks() {
         assert_eq!(era, Era::Immortal)
     }
 }
+
+#[subxt_test]
+async fn block_weight_usage_is_within_limits() {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    crate::utils::wait_for_blocks(&api).await;
+
+    let block = api.blocks().at_latest().await.unwrap();
+    let usage = block.weight_usage().await.unwrap();
+
+    // Every block has some mandatory weight, from the inherents and hooks:
+    assert!(usage.mandatory.used.ref_time > 0);
+    assert!(usage.max_block.ref_time > 0);
+    assert!(usage.max_length > 0);
+
+    // Every block contains at least the timestamp inherent:
+    assert!(usage.length > 0);
+
+    for class in [usage.normal, usage.operational] {
+        let max = class
+            .max
+            .expect("normal and operational weights are limited");
+        assert!(class.used.ref_time <= max.ref_time);
+        assert!(class.used.proof_size <= max.proof_size);
+    }
+    assert!(usage.length <= usage.max_length);
+    assert!(usage.length_percent() <= 100.0);
+}
+
+#[cfg(fullclient)]
+#[subxt_test]
+async fn block_parent_can_be_loaded() {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    crate::utils::wait_for_blocks(&api).await;
+
+    let block = api.blocks().at_latest().await.unwrap();
+    let parent = block
+        .parent()
+        .await
+        .unwrap()
+        .expect("latest block isn't the genesis block");
+
+    assert_eq!(block.parent_hash(), parent.hash());
+    assert_eq!(block.number(), parent.number() + 1);
+
+    // The genesis block has no parent (the unstable backend can't load the genesis block,
+    // since it isn't pinned):
+    #[cfg(not(feature = "unstable-backend-client"))]
+    {
+        let genesis_hash = api.backend().genesis_hash().await.unwrap();
+        let genesis = api.blocks().at(genesis_hash).await.unwrap();
+        assert!(genesis.parent().await.unwrap().is_none());
+    }
+}
+
+#[cfg(fullclient)]
+#[subxt_test]
+async fn grandpa_justifications_from_the_node_can_be_verified() {
+    use codec::Decode;
+    use subxt::backend::rpc::rpc_params;
+    use subxt::blocks::{verify_grandpa_justification, GrandpaJustificationFor};
+    use subxt::config::{Config, Header};
+
+    /// The finality proof handed back from `grandpa_proveFinality`.
+    #[derive(Decode)]
+    struct FinalityProof {
+        block: <SubstrateConfig as Config>::Hash,
+        justification: Vec<u8>,
+        _unknown_headers: Vec<<SubstrateConfig as Config>::Header>,
+    }
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    crate::utils::wait_for_blocks(&api).await;
+
+    // Ask the node to prove that the latest finalized block is final. This hands back the
+    // GRANDPA justification that the node has for it (or for a later block).
+    let block = api.blocks().at_latest().await.unwrap();
+    let number: u64 = block.number().into();
+    let proof: Option<String> = ctx
+        .rpc_client()
+        .request("grandpa_proveFinality", rpc_params![number])
+        .await
+        .unwrap();
+    let proof = subxt::utils::from_hex(&proof.expect("a finality proof is expected")).unwrap();
+    let proof = FinalityProof::decode(&mut &*proof).unwrap();
+    let justification =
+        GrandpaJustificationFor::<SubstrateConfig>::decode(&mut &*proof.justification).unwrap();
+
+    let header = api
+        .backend()
+        .block_header(proof.block)
+        .await
+        .unwrap()
+        .expect("the finalized block should be known");
+    let runtime_api = api.runtime_api().at(proof.block);
+    let authorities: Vec<([u8; 32], u64)> = runtime_api
+        .call_raw("GrandpaApi_grandpa_authorities", None)
+        .await
+        .unwrap();
+    let set_id: u64 = runtime_api
+        .call_raw("GrandpaApi_current_set_id", None)
+        .await
+        .unwrap();
+
+    verify_grandpa_justification::<SubstrateConfig>(
+        &justification,
+        proof.block,
+        header.number().into(),
+        set_id,
+        &authorities,
+    )
+    .unwrap();
+
+    // It doesn't prove that the parent block was finalized:
+    assert!(verify_grandpa_justification::<SubstrateConfig>(
+        &justification,
+        header.parent_hash(),
+        u64::from(header.number()) - 1,
+        set_id,
+        &authorities,
+    )
+    .is_err());
+}
@@ -5,7 +5,9 @@
 //! Just sanity checking some of the legacy RPC methods to make
 //! sure they don't error out and can decode their results OK.
 
-use crate::{subxt_test, test_context};
+use crate::{subxt_test, test_context, utils::node_runtime};
+use subxt::backend::legacy::rpc_methods::DryRunResult;
+use subxt_signer::sr25519::dev;
 
 #[subxt_test]
 async fn chain_get_block_hash() {
@@ -125,3 +127,42 @@ async fn system_properties() {
 
     let _ = rpc.system_properties().await.unwrap();
 }
+
+#[subxt_test]
+async fn system_account_next_index() {
+    let ctx = test_context().await;
+    let api = ctx.client();
+    let rpc = ctx.legacy_rpc_methods().await;
+
+    let alice = dev::alice().public_key().into();
+    let next_index = rpc.system_account_next_index(&alice).await.unwrap();
+    let nonce = api.tx().account_nonce(&alice).await.unwrap();
+
+    assert_eq!(next_index, nonce);
+}
+
+#[subxt_test]
+async fn dry_run() {
+    let ctx = test_context().await;
+    let api = ctx.client();
+    let rpc = ctx.legacy_rpc_methods().await;
+
+    let tx = node_runtime::tx()
+        .balances()
+        .transfer_allow_death(dev::bob().public_key().into(), 10_000);
+    let signed_extrinsic = api
+        .tx()
+        .create_signed(&tx, &dev::alice(), Default::default())
+        .await
+        .unwrap();
+
+    // The dev node listens on localhost, and so allows "unsafe" methods like this:
+    let result = rpc
+        .dry_run(signed_extrinsic.encoded(), None)
+        .await
+        .unwrap()
+        .into_dry_run_result(&api.metadata())
+        .unwrap();
+
+    assert_eq!(result, DryRunResult::Success);
+}
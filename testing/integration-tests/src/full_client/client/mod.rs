@@ -113,6 +113,244 @@ async fn storage_child_values_same_across_backends() {
     }
 }
 
+#[cfg(fullclient)]
+#[subxt_test]
+async fn events_same_across_backends() {
+    let ctx = test_context().await;
+
+    let unstable_client = ctx.unstable_client().await;
+    let legacy_client = ctx.legacy_client().await;
+
+    let block_ref = legacy_client
+        .blocks()
+        .at_latest()
+        .await
+        .unwrap()
+        .reference();
+
+    let event_details = |events: subxt::events::Events<_>| -> Vec<_> {
+        events
+            .iter()
+            .map(|ev| {
+                let ev = ev.unwrap();
+                (
+                    ev.pallet_name().to_owned(),
+                    ev.variant_name().to_owned(),
+                    ev.bytes().to_vec(),
+                )
+            })
+            .collect()
+    };
+
+    let a = event_details(
+        unstable_client
+            .events()
+            .at(block_ref.clone())
+            .await
+            .unwrap(),
+    );
+    let b = event_details(legacy_client.events().at(block_ref).await.unwrap());
+
+    assert!(!a.is_empty());
+    assert_eq!(a, b);
+}
+
+#[subxt_test]
+async fn events_at_number_match_events_at_hash() {
+    use subxt::config::Header;
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let block = api.blocks().at_latest().await.unwrap();
+    let number: u64 = block.header().number().into();
+
+    let at_number = api.events().at_number(number).await.unwrap();
+    let at_hash = api.events().at(block.hash()).await.unwrap();
+
+    assert_eq!(at_number.bytes(), at_hash.bytes());
+
+    // Asking for events in a block which isn't finalized yet is an error.
+    let res = api.events().at_number(u64::MAX).await;
+    assert!(matches!(
+        res,
+        Err(Error::Block(subxt::error::BlockError::NotFinalized { .. }))
+    ));
+}
+
+#[subxt_test]
+async fn metadata_at_version() {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let metadata = api
+        .metadata_at_version(15)
+        .await
+        .unwrap()
+        .expect("V15 metadata should be available");
+    assert!(metadata.pallet_by_name("System").is_some());
+    assert_eq!(metadata.pallets().count(), api.metadata().pallets().count());
+
+    // Metadata versions that don't exist are reported as such.
+    let missing = api.metadata_at_version(u32::MAX - 1).await.unwrap();
+    assert!(missing.is_none());
+}
+
+#[cfg(fullclient)]
+#[subxt_test]
+async fn client_from_backend_with_retained_metadata() {
+    use std::sync::Arc;
+    use subxt::{backend::legacy::LegacyBackend, utils::AccountId32, OnlineClient};
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    // Keep only the System pallet around.
+    let mut metadata = (*api.metadata()).clone();
+    metadata.retain(|pallet| pallet == "System", |_| false);
+
+    let backend = LegacyBackend::builder().build(ctx.rpc_client());
+    let retained_api = OnlineClient::<subxt::SubstrateConfig>::from_backend_with(
+        api.genesis_hash(),
+        api.runtime_version(),
+        metadata,
+        Arc::new(backend),
+    )
+    .unwrap();
+
+    assert_eq!(retained_api.metadata().pallets().count(), 1);
+    assert_eq!(retained_api.genesis_hash(), api.genesis_hash());
+
+    // Storage reads work using the supplied metadata.
+    let alice: AccountId32 = dev::alice().public_key().into();
+    let addr = node_runtime::storage().system().account(alice);
+    let block_hash = api.blocks().at_latest().await.unwrap().hash();
+
+    let expected = api
+        .storage()
+        .at(block_hash)
+        .fetch_or_default(&addr)
+        .await
+        .unwrap();
+    let actual = retained_api
+        .storage()
+        .at(block_hash)
+        .fetch_or_default(&addr)
+        .await
+        .unwrap();
+
+    assert_eq!(actual.encode(), expected.encode());
+}
+
+#[subxt_test]
+async fn wait_for_finalized_block_number() {
+    use std::time::Duration;
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let current = api.blocks().at_latest().await.unwrap();
+    let target = u64::from(current.number()) + 2;
+
+    let block_ref = api
+        .wait_for_finalized_block_with_timeout(target, tokio::time::sleep(Duration::from_secs(60)))
+        .await
+        .unwrap();
+    let block = api.blocks().at(block_ref).await.unwrap();
+    assert!(u64::from(block.number()) >= target);
+
+    // Blocks which have already been finalized are returned straight away:
+    let block_ref = api.wait_for_finalized_block(target).await.unwrap();
+    let block = api.blocks().at(block_ref).await.unwrap();
+    assert!(u64::from(block.number()) >= target);
+
+    // Blocks which won't be finalized any time soon time out:
+    let err = api
+        .wait_for_finalized_block_with_timeout(u64::MAX, tokio::time::sleep(Duration::from_secs(1)))
+        .await;
+    assert!(matches!(
+        err,
+        Err(Error::Block(subxt::error::BlockError::Timeout {
+            number: u64::MAX
+        }))
+    ));
+}
+
+#[cfg(fullclient)]
+#[subxt_test]
+async fn finalized_block_hash_at_number_same_across_backends() {
+    let ctx = test_context().await;
+
+    let unstable_client = ctx.unstable_client().await;
+    let legacy_client = ctx.legacy_client().await;
+
+    // Make sure that there are a few finalized blocks to look back through.
+    wait_for_blocks(&unstable_client).await;
+
+    // Look up the parent of the latest finalized block that the unstable backend has
+    // seen. The node has already finalized it, so both backends will know about it
+    // however far they've each got, and the unstable backend has to walk back to it.
+    let block = unstable_client.blocks().at_latest().await.unwrap();
+    let parent = block
+        .parent()
+        .await
+        .unwrap()
+        .expect("the latest finalized block has a parent");
+    let number: u64 = parent.number().into();
+
+    let unstable_hash = unstable_client
+        .backend()
+        .finalized_block_hash_at_number(number)
+        .await
+        .unwrap();
+    let legacy_hash = legacy_client
+        .backend()
+        .finalized_block_hash_at_number(number)
+        .await
+        .unwrap();
+
+    assert_eq!(unstable_hash, Some(parent.hash()));
+    assert_eq!(unstable_hash, legacy_hash);
+
+    // Neither backend knows about blocks that haven't been finalized:
+    for client in [&unstable_client, &legacy_client] {
+        let hash = client
+            .backend()
+            .finalized_block_hash_at_number(u64::MAX)
+            .await
+            .unwrap();
+        assert_eq!(hash, None);
+    }
+}
+
+#[subxt_test]
+async fn offline_extrinsic_hash_matches_submitted_hash() {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let alice = dev::alice();
+    let bob = dev::bob();
+
+    let tx = node_runtime::tx()
+        .balances()
+        .transfer_allow_death(bob.public_key().into(), 10_000);
+
+    let signed_extrinsic = api
+        .tx()
+        .create_signed(&tx, &alice, Default::default())
+        .await
+        .unwrap();
+
+    let offline_hash = api.offline().extrinsic_hash(signed_extrinsic.encoded());
+    assert_eq!(offline_hash, signed_extrinsic.hash());
+
+    let progress = signed_extrinsic
+        .submit_and_watch()
+        .await
+        .expect("tx should be submitted");
+    assert_eq!(offline_hash, progress.extrinsic_hash());
+}
+
 #[subxt_test]
 async fn transaction_validation() {
     let ctx = test_context().await;
@@ -181,6 +419,197 @@ async fn validation_fails() {
     );
 }
 
+#[subxt_test]
+async fn validation_fails_with_bad_nonce() {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let alice = dev::alice();
+    let bob = dev::bob();
+
+    wait_for_blocks(&api).await;
+
+    let tx = node_runtime::tx()
+        .balances()
+        .transfer_allow_death(bob.public_key().into(), 10_000);
+
+    // Submit a transaction so that Alice's nonce is at least 1:
+    api.tx()
+        .sign_and_submit_then_watch_default(&tx, &alice)
+        .await
+        .unwrap()
+        .wait_for_finalized_success()
+        .await
+        .unwrap();
+
+    // A nonce which has already been used is stale. (A nonce ahead of the current one
+    // is valid, but requires the transactions before it to be included first.)
+    let params = subxt::config::DefaultExtrinsicParamsBuilder::new()
+        .nonce(0)
+        .build();
+    let signed_extrinsic = api.tx().create_signed(&tx, &alice, params).await.unwrap();
+
+    let validation_res = signed_extrinsic
+        .validate()
+        .await
+        .expect("validation call failed");
+    assert_eq!(
+        validation_res,
+        ValidationResult::Invalid(TransactionInvalid::Stale)
+    );
+}
+
+#[subxt_test]
+async fn replacement_has_same_nonce_and_higher_tip() {
+    use subxt::config::DefaultExtrinsicParamsBuilder;
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let alice = dev::alice();
+    let bob = dev::bob();
+
+    let tx = node_runtime::tx()
+        .balances()
+        .transfer_allow_death(bob.public_key().into(), 10_000);
+
+    // Use a nonce ahead of the current one, so that we can tell that the replacement
+    // isn't just using the latest account nonce:
+    let nonce = api
+        .tx()
+        .account_nonce(&alice.public_key().into())
+        .await
+        .unwrap()
+        + 5;
+    let original = api
+        .tx()
+        .create_signed(
+            &tx,
+            &alice,
+            DefaultExtrinsicParamsBuilder::new()
+                .nonce(nonce)
+                .tip(1_000)
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    let replacement = api
+        .tx()
+        .create_replacement(
+            &original,
+            &tx,
+            &alice,
+            DefaultExtrinsicParamsBuilder::new().tip(2_000).build(),
+        )
+        .await
+        .unwrap();
+
+    let decode = |bytes: &[u8]| {
+        let metadata = api.metadata();
+        let exts =
+            subxt::ext::subxt_core::blocks::Extrinsics::<subxt::SubstrateConfig>::decode_from(
+                vec![bytes.to_vec()],
+                metadata,
+            )
+            .unwrap();
+        let ext = exts.iter().next().unwrap().unwrap();
+        let signed_extensions = ext.signed_extensions().unwrap();
+        (
+            signed_extensions.nonce(),
+            signed_extensions.tip(),
+            ext.call_bytes().to_vec(),
+        )
+    };
+
+    let (original_nonce, original_tip, original_call) = decode(original.encoded());
+    let (replacement_nonce, replacement_tip, replacement_call) = decode(replacement.encoded());
+    assert_eq!((original_nonce, original_tip), (Some(nonce), Some(1_000)));
+    assert_eq!(
+        (replacement_nonce, replacement_tip),
+        (Some(nonce), Some(2_000))
+    );
+    // Passing the same call bumps the tip of the original:
+    assert_eq!(replacement_call, original_call);
+
+    // A different call can be given too, to replace the original outright:
+    let remark = node_runtime::tx().system().remark(b"cancelled".to_vec());
+    let cancellation = api
+        .tx()
+        .create_replacement(
+            &original,
+            &remark,
+            &alice,
+            DefaultExtrinsicParamsBuilder::new().tip(2_000).build(),
+        )
+        .await
+        .unwrap();
+    let (cancellation_nonce, _, cancellation_call) = decode(cancellation.encoded());
+    assert_eq!(cancellation_nonce, Some(nonce));
+    assert_ne!(cancellation_call, original_call);
+
+    // A replacement without a higher tip is refused:
+    let res = api
+        .tx()
+        .create_replacement(
+            &original,
+            &tx,
+            &alice,
+            DefaultExtrinsicParamsBuilder::new().tip(1_000).build(),
+        )
+        .await;
+    assert!(res.is_err());
+}
+
+#[subxt_test]
+async fn offline_client_signs_the_same_as_online_client() {
+    use subxt::{config::DefaultExtrinsicParamsBuilder, tx::SubmittableExtrinsic};
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+    let offline = api.offline();
+
+    let alice = dev::alice();
+    let bob = dev::bob();
+
+    let tx = node_runtime::tx()
+        .balances()
+        .transfer_allow_death(bob.public_key().into(), 10_000);
+
+    let nonce = api
+        .tx()
+        .account_nonce(&alice.public_key().into())
+        .await
+        .unwrap();
+    let params = || DefaultExtrinsicParamsBuilder::new().nonce(nonce).build();
+
+    // Both clients should produce the same payload to sign:
+    let online_payload = api
+        .tx()
+        .create_partial_signed_offline(&tx, params())
+        .unwrap()
+        .signer_payload();
+    let offline_payload = offline
+        .tx()
+        .create_partial_signed_offline(&tx, params())
+        .unwrap()
+        .signer_payload();
+    assert_eq!(online_payload, offline_payload);
+
+    // And a transaction signed offline should be accepted by the node:
+    let signed = offline
+        .tx()
+        .create_signed_offline(&tx, &alice, params())
+        .unwrap();
+    SubmittableExtrinsic::from_bytes(api.clone(), signed.into_encoded())
+        .submit_and_watch()
+        .await
+        .unwrap()
+        .wait_for_finalized_success()
+        .await
+        .unwrap();
+}
+
 #[subxt_test]
 async fn external_signing() {
     let ctx = test_context().await;
@@ -463,3 +892,123 @@ async fn legacy_and_unstable_block_subscription_reconnect() {
         assert!(intersection >= 3, "intersections size is {}", intersection);
     }
 }
+
+#[subxt_test]
+async fn frozen_client_reads_from_a_single_block() {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    wait_for_blocks(&api).await;
+
+    let block = api.blocks().at_latest().await.unwrap();
+    let snapshot = api.at(block.reference()).await.unwrap();
+    assert_eq!(snapshot.block_hash(), block.hash());
+
+    // Let a few more blocks be produced so that "latest" would no longer line up:
+    wait_for_blocks(&api).await;
+
+    let storage = snapshot.storage();
+    let number = storage
+        .fetch_or_default(&node_runtime::storage().system().number())
+        .await
+        .unwrap();
+    let parent_hash = storage
+        .fetch_or_default(&node_runtime::storage().system().parent_hash())
+        .await
+        .unwrap();
+
+    // Both values come from the snapshot block, and not the latest one:
+    assert_eq!(number, block.header().number);
+    assert_eq!(parent_hash, block.header().parent_hash);
+}
+
+#[subxt_test]
+async fn frozen_client_at_latest_uses_one_block() {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    wait_for_blocks(&api).await;
+
+    let snapshot = api.at_latest().await.unwrap();
+    let hash = snapshot.block_hash();
+
+    // Let "latest" move on, and check that every lookup still uses the snapshot block.
+    wait_for_blocks(&api).await;
+
+    let block = snapshot.block().await.unwrap();
+    assert_eq!(block.hash(), hash);
+
+    let number = snapshot
+        .storage()
+        .fetch_or_default(&node_runtime::storage().system().number())
+        .await
+        .unwrap();
+    assert_eq!(number, block.header().number);
+
+    let events = snapshot.events().await.unwrap();
+    let events_at_hash = api.events().at(hash).await.unwrap();
+    assert_eq!(events.bytes(), events_at_hash.bytes());
+}
+
+#[cfg(unix)]
+#[subxt_test]
+async fn rpc_client_over_unix_socket() {
+    use subxt::backend::{legacy::LegacyRpcMethods, rpc::RpcClient};
+    use tokio::net::{TcpStream, UnixListener};
+
+    let ctx = test_context().await;
+    let node_addr = ctx
+        .ws_url()
+        .trim_start_matches("ws://")
+        .trim_end_matches('/')
+        .to_owned();
+
+    // Nodes don't listen on Unix sockets themselves, so we proxy
+    // connections on a socket through to the node's TCP port.
+    let socket_path = std::env::temp_dir().join(format!("subxt-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&socket_path);
+    let listener = UnixListener::bind(&socket_path).unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut unix_stream, _)) = listener.accept().await {
+            let mut tcp_stream = TcpStream::connect(&node_addr).await.unwrap();
+            tokio::spawn(async move {
+                let _ = tokio::io::copy_bidirectional(&mut unix_stream, &mut tcp_stream).await;
+            });
+        }
+    });
+
+    let rpc_client = RpcClient::from_url(format!("unix://{}", socket_path.display()))
+        .await
+        .unwrap();
+    let rpc = LegacyRpcMethods::<subxt::SubstrateConfig>::new(rpc_client);
+
+    let expected = ctx.legacy_rpc_methods().await.genesis_hash().await.unwrap();
+    assert_eq!(rpc.genesis_hash().await.unwrap(), expected);
+
+    std::fs::remove_file(&socket_path).unwrap();
+}
+
+#[subxt_test]
+async fn rpc_client_respects_max_response_size() {
+    use subxt::backend::{legacy::LegacyRpcMethods, rpc::RpcClient};
+
+    let ctx = test_context().await;
+
+    // Metadata is far larger than 1KiB, so is rejected with a small limit...
+    let rpc_client = RpcClient::builder()
+        .max_response_size(1024)
+        .build_insecure(ctx.ws_url())
+        .await
+        .unwrap();
+    let rpc = LegacyRpcMethods::<subxt::SubstrateConfig>::new(rpc_client);
+    assert!(rpc.state_get_metadata(None).await.is_err());
+
+    // ...but is fine with a larger limit.
+    let rpc_client = RpcClient::builder()
+        .max_response_size(100 * 1024 * 1024)
+        .build_insecure(ctx.ws_url())
+        .await
+        .unwrap();
+    let rpc = LegacyRpcMethods::<subxt::SubstrateConfig>::new(rpc_client);
+    assert!(rpc.state_get_metadata(None).await.is_ok());
+}
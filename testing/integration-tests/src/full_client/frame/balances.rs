@@ -8,6 +8,7 @@ use crate::{
 };
 use codec::Decode;
 use subxt::{
+    blocks::DispatchClass,
     error::{DispatchError, Error, TokenError},
     utils::{AccountId32, MultiAddress},
 };
@@ -420,6 +421,36 @@ async fn transfer_implicit_subscription() {
     );
 }
 
+#[subxt_test]
+async fn transfer_dispatch_info() {
+    let alice = dev::alice();
+    let bob = dev::bob();
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let to_bob_tx = node_runtime::tx()
+        .balances()
+        .transfer_allow_death(bob.public_key().into(), 10_000);
+
+    let events = api
+        .tx()
+        .sign_and_submit_then_watch_default(&to_bob_tx, &alice)
+        .await
+        .unwrap()
+        .wait_for_finalized_success()
+        .await
+        .unwrap();
+
+    let info = events
+        .dispatch_info()
+        .expect("Can decode dispatch info")
+        .expect("Can find dispatch info");
+
+    assert_eq!(info.class, DispatchClass::Normal);
+    assert!(info.pays_fee);
+    assert!(info.weight.ref_time > 0);
+}
+
 #[subxt_test]
 async fn constant_existential_deposit() {
     let ctx = test_context().await;
@@ -4,15 +4,16 @@
 
 use crate::{
     node_runtime::{
-        self,
-        contracts::events,
-        runtime_types::{pallet_contracts::wasm::Determinism, sp_weights::weight_v2::Weight},
-        system,
+        self, contracts::events, runtime_types::pallet_contracts::wasm::Determinism, system,
     },
     subxt_test, test_context, TestClient, TestConfig, TestContext,
 };
 use subxt::ext::futures::StreamExt;
-use subxt::{tx::TxProgress, utils::MultiAddress, Config, Error};
+use subxt::{
+    tx::TxProgress,
+    utils::{MultiAddress, Weight},
+    Config, Error,
+};
 use subxt_signer::sr25519::{self, dev};
 
 struct ContractsTestContext {
@@ -252,3 +253,37 @@ async fn tx_call() {
 
     assert!(executed.is_ok(), "Error calling contract: {executed:?}");
 }
+
+#[subxt_test]
+async fn runtime_api_dry_run_call() {
+    use subxt::runtime_api::{Code, ContractCall, ContractInstantiate};
+
+    let cxt = ContractsTestContext::init().await;
+    let (_, contract) = cxt.instantiate_with_code().await.unwrap();
+    let origin: AccountId = dev::alice().public_key().into();
+
+    let runtime_api = cxt.client().runtime_api().at_latest().await.unwrap();
+
+    // Dry-run calling the contract:
+    let result = runtime_api
+        .contracts_call(ContractCall::new(origin.clone(), contract, vec![]))
+        .await
+        .unwrap();
+    let returned = result.result.expect("call should succeed");
+    assert!(!returned.did_revert());
+    assert!(returned.data.is_empty());
+    assert!(result.gas_consumed.ref_time > 0);
+    assert!(result.gas_required.ref_time >= result.gas_consumed.ref_time);
+
+    // Dry-run instantiating another copy of it:
+    let code = wabt::wat2wasm(CONTRACT).expect("invalid wabt");
+    let mut instantiate = ContractInstantiate::new(origin, Code::Upload(code), vec![]);
+    instantiate.value = 100_000_000_000_000_000;
+    instantiate.salt = vec![1, 2, 3];
+    let result = runtime_api
+        .contracts_instantiate(instantiate)
+        .await
+        .unwrap();
+    let instantiated = result.result.expect("instantiation should succeed");
+    assert!(!instantiated.result.did_revert());
+}
@@ -3,13 +3,10 @@
 // see LICENSE for license details.
 
 use crate::{
-    node_runtime::{
-        self,
-        runtime_types::{self, sp_weights::weight_v2::Weight},
-        sudo,
-    },
+    node_runtime::{self, runtime_types, sudo},
     subxt_test, test_context,
 };
+use subxt::utils::Weight;
 use subxt_signer::sr25519::dev;
 
 type Call = runtime_types::kitchensink_runtime::RuntimeCall;
@@ -57,3 +57,29 @@ async fn tx_remark_with_event() -> Result<(), subxt::Error> {
     assert!(found_event);
     Ok(())
 }
+
+#[subxt_test]
+async fn dynamic_remark_with_event_returns_data_hash() -> Result<(), subxt::Error> {
+    use subxt::tx::Remarked;
+    use subxt::utils::{AccountId32, H256};
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let alice = dev::alice();
+    let data = b"anchor this data".to_vec();
+
+    let tx = api.tx().remark_with_event(data.clone())?;
+    let remarked = api
+        .tx()
+        .sign_and_submit_then_watch_default(&tx, &alice)
+        .await?
+        .wait_for_finalized_success()
+        .await?
+        .find_first::<Remarked<AccountId32, H256>>()?
+        .expect("Remarked event should be emitted");
+
+    assert_eq!(remarked.sender, alice.public_key().to_account_id());
+    assert_eq!(remarked.hash, H256(sp_core::blake2_256(&data)));
+    Ok(())
+}
@@ -109,3 +109,77 @@ async fn unchecked_extrinsic_encoding() -> Result<(), subxt::Error> {
 
     Ok(())
 }
+
+#[subxt_test]
+async fn dynamic_call_with_description() -> Result<(), subxt::Error> {
+    use subxt::dynamic::{At, Value};
+    use subxt::metadata::TypeDescriptionKind;
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let payload = subxt::runtime_api::dynamic("Core", "version", Vec::<Value>::new());
+    let (value, description) = api
+        .runtime_api()
+        .at_latest()
+        .await?
+        .call_with_description(payload)
+        .await?;
+    let value = value.to_value()?;
+
+    // Every named field in the description can be found in the decoded value.
+    let TypeDescriptionKind::Composite(fields) = description.kind else {
+        panic!("RuntimeVersion should be a composite type");
+    };
+    assert!(!fields.is_empty());
+    for field in fields {
+        let name = field.name.expect("RuntimeVersion fields are named");
+        assert!(value.at(&*name).is_some(), "field {name} is missing");
+    }
+
+    Ok(())
+}
+
+#[subxt_test]
+async fn dispatch_errors_from_runtime_apis_are_decoded() -> Result<(), subxt::Error> {
+    use subxt::config::DefaultExtrinsicParamsBuilder as Params;
+    use subxt::error::DispatchError;
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    // Bob isn't the sudo key, so this will fail with a Sudo::RequireSudo error. The
+    // transaction is immortal so that it is valid against the state of any block.
+    let call = node_runtime::runtime_types::kitchensink_runtime::RuntimeCall::System(
+        node_runtime::runtime_types::frame_system::pallet::Call::remark {
+            remark: vec![1, 2, 3],
+        },
+    );
+    let tx = node_runtime::tx().sudo().sudo(call);
+    let signed_extrinsic = api
+        .tx()
+        .create_signed(&tx, &dev::bob(), Params::new().build())
+        .await?;
+
+    // BlockBuilder_apply_extrinsic returns Ok(Err(DispatchError)) in this case:
+    let payload = node_runtime::apis()
+        .block_builder()
+        .apply_extrinsic(signed_extrinsic.into_encoded().into());
+    let err = api
+        .runtime_api()
+        .at_latest()
+        .await?
+        .call_with_dispatch_error(payload)
+        .await
+        .expect_err("sudo call from Bob should fail");
+
+    let subxt::Error::Runtime(DispatchError::Module(module_err)) = err else {
+        panic!("Expected a module error, got {err:?}");
+    };
+    let details = module_err.details()?;
+    assert_eq!(details.pallet.name(), "Sudo");
+    assert_eq!(details.variant.name, "RequireSudo");
+    assert_eq!(module_err.to_string(), "Sudo::RequireSudo");
+
+    Ok(())
+}
@@ -30,6 +30,26 @@ async fn storage_plain_lookup() -> Result<(), subxt::Error> {
     Ok(())
 }
 
+#[subxt_test]
+async fn storage_iter_plain_entry_is_an_error() -> Result<(), subxt::Error> {
+    use subxt::error::{Error, StorageAddressError};
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let addr = subxt::dynamic::storage("Timestamp", "Now", ());
+    let res = api.storage().at_latest().await?.iter(addr).await;
+
+    assert!(matches!(
+        res,
+        Err(Error::StorageAddress(
+            StorageAddressError::StorageEntryNotIterable { .. }
+        ))
+    ));
+
+    Ok(())
+}
+
 #[cfg(fullclient)]
 #[subxt_test]
 async fn storage_map_lookup() -> Result<(), subxt::Error> {
@@ -131,6 +151,91 @@ async fn storage_n_map_storage_lookup() -> Result<(), subxt::Error> {
     Ok(())
 }
 
+#[cfg(fullclient)]
+#[subxt_test]
+async fn storage_contains() -> Result<(), subxt::Error> {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    // Create an asset class with ID 100, so that there is an entry
+    // for it in the `Asset` map, which has no default value.
+    let signer = dev::alice();
+    let alice: AccountId32 = dev::alice().public_key().into();
+    let tx = node_runtime::tx().assets().create(100, alice.into(), 1);
+    api.tx()
+        .sign_and_submit_then_watch_default(&tx, &signer)
+        .await?
+        .wait_for_finalized_success()
+        .await?;
+
+    let storage = api.storage().at_latest().await?;
+
+    let present = node_runtime::storage().assets().asset(100);
+    assert!(storage.contains(&present).await?);
+
+    let absent = node_runtime::storage().assets().asset(101);
+    assert!(!storage.contains(&absent).await?);
+    assert!(storage.fetch(&absent).await?.is_none());
+
+    Ok(())
+}
+
+#[cfg(fullclient)]
+#[subxt_test]
+async fn storage_size() -> Result<(), subxt::Error> {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let storage = api.storage().at_latest().await?;
+
+    // Alice is endowed at genesis, so her account exists:
+    let alice: AccountId32 = dev::alice().public_key().into();
+    let present = node_runtime::storage().system().account(alice);
+    let raw = storage
+        .fetch_raw(api.storage().address_bytes(&present)?)
+        .await?
+        .expect("alice's account should exist");
+    assert_eq!(storage.size(&present).await?, Some(raw.len() as u64));
+
+    let absent = node_runtime::storage().assets().asset(u32::MAX);
+    assert_eq!(storage.size(&absent).await?, None);
+
+    Ok(())
+}
+
+#[cfg(fullclient)]
+#[subxt_test]
+async fn account_balance() -> Result<(), subxt::Error> {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let storage = api.storage().at_latest().await?;
+    let alice: AccountId32 = dev::alice().public_key().into();
+
+    let info = storage
+        .fetch(&node_runtime::storage().system().account(alice.clone()))
+        .await?
+        .expect("alice's account should exist");
+    let balance = storage.account_balance(&alice).await?;
+
+    assert!(balance.free > 0);
+    assert_eq!(balance.free, info.data.free);
+    assert_eq!(balance.reserved, info.data.reserved);
+    assert_eq!(balance.frozen, info.data.frozen);
+
+    // Accounts that don't exist have no balance:
+    let nobody = AccountId32([0xff; 32]);
+    assert_eq!(
+        storage.account_balance(&nobody).await?,
+        subxt::storage::AccountBalance::default()
+    );
+
+    // The client can look up balances at the latest block, too:
+    assert!(api.account_balance(&alice).await?.free > 0);
+
+    Ok(())
+}
+
 #[cfg(fullclient)]
 #[subxt_test]
 async fn storage_partial_lookup() -> Result<(), subxt::Error> {
@@ -236,6 +341,40 @@ async fn storage_pallet_storage_version() -> Result<(), subxt::Error> {
     Ok(())
 }
 
+#[cfg(fullclient)]
+#[subxt_test]
+async fn storage_dynamic_value_as_static_type() -> Result<(), subxt::Error> {
+    use subxt::dynamic::{value_as_type, Value};
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+    let storage = api.storage().at_latest().await?;
+
+    let alice: AccountId32 = dev::alice().public_key().into();
+    let static_addr = node_runtime::storage().system().account(alice.clone());
+    let static_info = storage.fetch(&static_addr).await?.unwrap();
+
+    let dynamic_addr = subxt::dynamic::storage("System", "Account", vec![Value::from_bytes(alice)]);
+    let dynamic_info = storage.fetch(&dynamic_addr).await?.unwrap().to_value()?;
+
+    // Convert the dynamic value into the generated `AccountInfo` type:
+    let metadata = api.metadata();
+    let account_ty = metadata
+        .pallet_by_name("System")
+        .and_then(|p| p.storage())
+        .and_then(|s| s.entry_by_name("Account"))
+        .map(|e| e.entry_type().value_ty())
+        .unwrap();
+    let converted = value_as_type(&dynamic_info, account_ty, &metadata)?;
+
+    fn same_type<T>(_: &T, _: &T) {}
+    same_type(&converted, &static_info);
+    assert_eq!(converted.nonce, static_info.nonce);
+    assert_eq!(converted.data.free, static_info.data.free);
+
+    Ok(())
+}
+
 #[subxt_test]
 async fn storage_iter_decode_keys() -> Result<(), subxt::Error> {
     use futures::StreamExt;
@@ -286,3 +425,113 @@ async fn storage_iter_decode_keys() -> Result<(), subxt::Error> {
 
     Ok(())
 }
+
+#[cfg(fullclient)]
+#[subxt_test]
+async fn storage_subscribe_to_account_changes() -> Result<(), subxt::Error> {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    let alice = dev::alice();
+    let bob = dev::bob();
+
+    let addr = node_runtime::storage()
+        .system()
+        .account(bob.public_key().to_account_id());
+    let mut values = api.storage().subscribe(addr).await?;
+
+    // The current value is emitted first:
+    let initial = values
+        .next()
+        .await
+        .expect("current value expected")?
+        .expect("bob has an account");
+
+    let transfer_tx = node_runtime::tx()
+        .balances()
+        .transfer_allow_death(bob.public_key().to_address(), 10_000);
+    api.tx()
+        .sign_and_submit_then_watch_default(&transfer_tx, &alice)
+        .await?
+        .wait_for_finalized_success()
+        .await?;
+
+    // The next value emitted should reflect the transfer:
+    let updated = values
+        .next()
+        .await
+        .expect("updated value expected")?
+        .expect("bob has an account");
+    assert_eq!(updated.data.free, initial.data.free + 10_000);
+
+    Ok(())
+}
+
+// Read proofs are only available via the legacy RPC methods.
+#[cfg(all(fullclient, not(feature = "unstable-backend-client")))]
+#[subxt_test]
+async fn storage_read_proof_verifies_against_state_root() -> Result<(), subxt::Error> {
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    wait_for_blocks(&api).await;
+
+    let alice: AccountId32 = dev::alice().public_key().into();
+    let addr = node_runtime::storage().system().account(alice);
+    let key = api.storage().address_bytes(&addr)?;
+
+    let block = api.blocks().at_latest().await?;
+    let storage = api.storage().at(block.reference());
+
+    let proof = storage.read_proof([key.clone()]).await?;
+    let value = subxt::storage::verify_read_proof(&proof, block.header().state_root, &key)?;
+
+    assert!(value.is_some());
+    assert_eq!(value, storage.fetch_raw(key).await?);
+
+    // A proof isn't valid against the state root of a different block.
+    let genesis = api.blocks().at(api.genesis_hash()).await?;
+    assert!(subxt::storage::verify_read_proof(&proof, genesis.header().state_root, &key).is_err());
+
+    Ok(())
+}
+
+// Read proofs are only available via the legacy RPC methods.
+#[cfg(all(fullclient, not(feature = "unstable-backend-client")))]
+#[subxt_test]
+async fn storage_fetch_with_proof() -> Result<(), subxt::Error> {
+    use codec::Encode;
+
+    let ctx = test_context().await;
+    let api = ctx.client();
+
+    wait_for_blocks(&api).await;
+
+    let block = api.blocks().at_latest().await?;
+    let state_root = block.header().state_root;
+    let storage = api.storage().at(block.reference());
+
+    // A value which exists is returned along with a proof of it:
+    let alice: AccountId32 = dev::alice().public_key().into();
+    let addr = node_runtime::storage().system().account(alice);
+    let key = api.storage().address_bytes(&addr)?;
+
+    let (value, proof) = storage.fetch_with_proof(&addr).await?;
+    let value = value.expect("alice has an account");
+    let proven = subxt::storage::verify_read_proof(&proof, state_root, &key)?;
+    assert_eq!(proven, Some(value.encode()));
+
+    // A value which doesn't exist is returned along with a proof of its absence:
+    let nobody = AccountId32([0xab; 32]);
+    let addr = node_runtime::storage().system().account(nobody);
+    let key = api.storage().address_bytes(&addr)?;
+
+    let (value, proof) = storage.fetch_with_proof(&addr).await?;
+    assert!(value.is_none());
+    assert_eq!(
+        subxt::storage::verify_read_proof(&proof, state_root, &key)?,
+        None
+    );
+
+    Ok(())
+}
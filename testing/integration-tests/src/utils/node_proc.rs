@@ -44,6 +44,7 @@ pub struct TestNodeProcess<R: Config> {
     unstable_client: RefCell<Option<OnlineClient<R>>>,
     legacy_client: RefCell<Option<OnlineClient<R>>>,
 
+    ws_url: String,
     rpc_client: rpc::RpcClient,
     client: OnlineClient<R>,
 }
@@ -119,6 +120,11 @@ where
     pub fn rpc_client(&self) -> rpc::RpcClient {
         self.rpc_client.clone()
     }
+
+    /// Returns the URL of the node that we're connected to.
+    pub fn ws_url(&self) -> &str {
+        &self.ws_url
+    }
 }
 
 /// Kind of rpc client to use in tests
@@ -219,6 +225,7 @@ impl TestNodeProcessBuilder {
             client,
             legacy_client: RefCell::new(legacy_client),
             unstable_client: RefCell::new(unstable_client),
+            ws_url,
             rpc_client,
         })
     }
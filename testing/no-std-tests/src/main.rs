@@ -40,7 +40,14 @@ fn compile_test() {
     subxt_metadata::Metadata::decode(&mut &bytes[..]).expect_err("invalid byte sequence");
 
     const METADATA: &[u8] = include_bytes!("../../../artifacts/polkadot_metadata_small.scale");
-    subxt_metadata::Metadata::decode(&mut &METADATA[..]).expect("should be valid metadata");
+    let metadata =
+        subxt_metadata::Metadata::decode(&mut &METADATA[..]).expect("should be valid metadata");
+
+    // Subxt Metadata hashing and diffing compiles:
+    let _hash = metadata.hasher().hash();
+    let _pallets_hash = metadata.hasher().hash_pallets_only().hash();
+    let diff = metadata.diff(&metadata);
+    assert!(diff.is_empty());
 
     // Subxt Signer compiles:
     use subxt_signer::sr25519;
@@ -0,0 +1,7 @@
+// The `SUBXT_UI_TESTS_METADATA_PATH` environment variable is set by the test runner.
+#[subxt::subxt(runtime_metadata_env = "SUBXT_UI_TESTS_METADATA_PATH")]
+pub mod polkadot {}
+
+fn main() {
+    let _remark = polkadot::tx().system().remark(vec![1, 2, 3]);
+}
@@ -0,0 +1,13 @@
+#[subxt::subxt(
+    runtime_metadata_path = "../../../../artifacts/polkadot_metadata_small.scale",
+    runtime_types_only,
+    no_codec_derives
+)]
+pub mod polkadot {}
+
+fn assert_debug<T: core::fmt::Debug>() {}
+
+fn main() {
+    // Has `#[codec(compact)]` fields when the codec derives are present:
+    assert_debug::<polkadot::runtime_types::sp_weights::weight_v2::Weight>();
+}
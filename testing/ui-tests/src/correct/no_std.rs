@@ -0,0 +1,17 @@
+#![no_std]
+
+extern crate alloc;
+// We need std to be linked in order to build and run this as a normal binary, but
+// we give it a different name so that any `::std` paths in the generated code fail
+// to resolve.
+extern crate std as _std;
+
+#[subxt::subxt(
+    runtime_metadata_path = "../../../../artifacts/polkadot_metadata_small.scale",
+    no_std
+)]
+pub mod polkadot {}
+
+fn main() {
+    let _remark = polkadot::tx().system().remark(alloc::vec![1, 2, 3]);
+}
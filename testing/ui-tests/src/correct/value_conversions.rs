@@ -0,0 +1,26 @@
+use subxt::dynamic::ValueConversion;
+use subxt::ext::codec::Decode;
+
+#[subxt::subxt(
+    runtime_metadata_path = "../../../../artifacts/polkadot_metadata_small.scale",
+    derive_for_type(path = "sp_runtime::DispatchError", derive = "PartialEq", recursive),
+    generate_value_conversions
+)]
+pub mod polkadot {}
+
+use polkadot::runtime_types::sp_runtime::{DispatchError, ModuleError};
+
+fn main() {
+    let bytes = include_bytes!("../../../../artifacts/polkadot_metadata_small.scale");
+    let metadata = subxt::Metadata::decode(&mut &bytes[..]).unwrap();
+
+    let err = DispatchError::Module(ModuleError {
+        index: 1,
+        error: [2, 0, 0, 0],
+    });
+
+    // Static types can be converted to dynamic values and back again:
+    let value = err.to_value(&metadata).unwrap();
+    let round_tripped = DispatchError::from_value(&value, &metadata).unwrap();
+    assert_eq!(round_tripped, err);
+}
@@ -0,0 +1,10 @@
+#[subxt::subxt(runtime_metadata_env = "SUBXT_UI_TESTS_METADATA_PATH_NOT_SET")]
+pub mod node_runtime {}
+
+#[subxt::subxt(
+    runtime_metadata_path = "../../../../artifacts/polkadot_metadata_tiny.scale",
+    runtime_metadata_env = "SUBXT_UI_TESTS_METADATA_PATH"
+)]
+pub mod node_runtime2 {}
+
+fn main() {}
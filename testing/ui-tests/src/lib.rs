@@ -27,6 +27,11 @@ fn ui_tests() {
     let mut m = MetadataTestRunner::default();
     let t = trybuild::TestCases::new();
 
+    // Used by `src/correct/metadata_path_from_env.rs` to locate its metadata.
+    std::env::set_var(
+        "SUBXT_UI_TESTS_METADATA_PATH",
+        "../../../../artifacts/polkadot_metadata_small.scale",
+    );
     t.pass("src/correct/*.rs");
     // Check that storage maps with no keys are handled properly.
     t.pass(